@@ -0,0 +1,72 @@
+// shipments that split and merge, import -> filter -> generate -> explain -> export
+use std::error::Error;
+
+use chrono::{TimeZone, Utc};
+use pmrs::algo::transformation::ocel::features::object_point::{object_average_event_interaction, object_lifetime};
+use pmrs::objects::ocdg::exporter::{export_ocdg_graphml, ExportOptions};
+use pmrs::objects::ocdg::{generate_ocdg, trace_pair, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::Ocel;
+use strum::IntoEnumIterator;
+
+fn run(export_path: &str) -> Result<(Ocel, Ocdg), Box<dyn Error>> {
+    let log = import_ocel("logs/logistics.jsonocel")?;
+    // everything after the morning shift is out of scope
+    let log = log.prefix(&Utc.ymd(2022, 6, 1).and_hms(13, 0, 0));
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>())?;
+    export_ocdg_graphml(&ocdg, export_path, &ExportOptions { per_relation_keys: true, ..Default::default() })?;
+    Ok((log, ocdg))
+}
+
+// (source, target) labels of the edges holding the relation, sorted
+fn pairs(log: &Ocel, ocdg: &Ocdg, rel: Relations) -> Vec<(String, String)> {
+    let label = |oid: &usize| log.object_map.get_by_right(oid).unwrap().to_owned();
+    let mut pairs: Vec<(String, String)> = ocdg.irels.iter()
+                                                     .flat_map(|(src, targets)| targets.iter().map(move |(tar, rels)| (src, tar, rels)))
+                                                     .filter(|(_, _, rels)| rels.contains_key(&rel.relation_index().into()))
+                                                     .map(|(src, tar, _)| (label(src), label(tar)))
+                                                     .collect();
+    pairs.sort();
+    pairs
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = std::env::temp_dir().join("pmrs-example-logistics.graphml");
+    let (log, ocdg) = run(path.to_str().unwrap())?;
+    println!("{} events before 13:00, ocdg with {} nodes and {} edges", log.events.len(), ocdg.net.node_count(), ocdg.net.edge_count());
+    for rel in [Relations::SPLIT, Relations::MERGE] {
+        println!("{}: {:?}", rel, pairs(&log, &ocdg, rel));
+    }
+    let s1 = log.object_map.get_by_left("s1").unwrap();
+    let s2 = log.object_map.get_by_left("s2").unwrap();
+    println!("s1 split into s2: {}", trace_pair(&log, *s1, *s2, Relations::SPLIT).holds());
+    println!("s1 lives {} minutes and meets {} objects per event", object_lifetime(&log, s1).num_minutes(), object_average_event_interaction(&log, s1));
+    Ok(())
+}
+
+#[test]
+fn logistics() {
+    let path = std::env::temp_dir().join("pmrs-example-logistics-test.graphml");
+    let (log, ocdg) = run(path.to_str().unwrap()).unwrap();
+    let pair = |src: &str, tar: &str| (src.to_string(), tar.to_string());
+
+    // the final delivery of s4 is cut off, the consolidation at 12:30 is kept
+    assert_eq!(log.events.len(), 6);
+    assert_eq!(log.objects.len(), 6);
+    assert_eq!(ocdg.net.node_count(), 6);
+
+    assert_eq!(pairs(&log, &ocdg, Relations::SPLIT), vec![pair("s1", "s2"), pair("s1", "s3")]);
+    // the three shipments left in the morning all end in a different event
+    assert_eq!(pairs(&log, &ocdg, Relations::MERGE), vec![pair("s1", "s2"), pair("s1", "s3"), pair("s2", "s1"), pair("s2", "s3"), pair("s3", "s1"), pair("s3", "s2")]);
+    // t2 descends from s1 over s2, s4 over s3
+    assert_eq!(pairs(&log, &ocdg, Relations::REACHABLE), vec![pair("s1", "s4"), pair("s1", "t2")]);
+    assert_eq!(ocdg.net.edge_count(), 14);
+
+    let s1 = *log.object_map.get_by_left("s1").unwrap();
+    let s2 = *log.object_map.get_by_left("s2").unwrap();
+    assert!(trace_pair(&log, s1, s2, Relations::SPLIT).holds());
+    assert!(!trace_pair(&log, s2, s1, Relations::SPLIT).holds());
+    assert_eq!(object_lifetime(&log, &s1).num_hours(), 2);
+    assert_eq!(object_average_event_interaction(&log, &s1), 1.0);
+    assert!(std::fs::read_to_string(&path).unwrap().contains("rel_SPLIT"));
+}
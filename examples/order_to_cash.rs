@@ -0,0 +1,79 @@
+// import -> filter -> generate -> object features -> export on the order-to-cash fixture
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+
+use polars::prelude::DataFrame;
+use pmrs::algo::transformation::ocel::features::object_point::{object_point_features, LifecycleView, ObjectPoint, ObjectPointConfig};
+use pmrs::objects::ocdg::exporter::export_ocdg;
+use pmrs::objects::ocdg::importer::import_ocdg;
+use pmrs::objects::ocdg::{generate_ocdg, Ocdg, Relations};
+use pmrs::objects::ocel::importer::{import_ocel_with_report, ImportConfig, ImportReport};
+use pmrs::objects::ocel::Ocel;
+use strum::IntoEnumIterator;
+
+struct Pipeline {
+    report: ImportReport,
+    dropped: Vec<String>,
+    log: Ocel,
+    ocdg: Ocdg,
+    features: DataFrame,
+    reimported: Ocdg
+}
+
+fn run(export_path: &str) -> Result<Pipeline, Box<dyn Error>> {
+    let (mut log, report) = import_ocel_with_report("logs/order-to-cash.jsonocel", &ImportConfig::default())?;
+    let dropped = log.drop_events_without_objects();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>())?;
+
+    let params = HashMap::from([(ObjectPoint::ObjectLifetime, None), (ObjectPoint::ObjectEventInteractionOperator, None)]);
+    let features = object_point_features(ObjectPointConfig { ocel: &log, ocdg: &ocdg, params: &params, lifecycle_view: LifecycleView::Full });
+
+    export_ocdg(&ocdg, export_path)?;
+    let reimported = import_ocdg(export_path)?;
+    Ok(Pipeline { report, dropped, log, ocdg, features, reimported })
+}
+
+fn edge_counts(ocdg: &Ocdg) -> BTreeMap<String, usize> {
+    ocdg.metadata().map(|metadata| metadata.edge_counts.clone()).unwrap_or_default()
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = std::env::temp_dir().join("pmrs-example-order-to-cash.gexf");
+    let pipeline = run(path.to_str().unwrap())?;
+    println!("imported {} events and {} objects, dropped {:?}, {} events left", pipeline.report.events, pipeline.report.objects, pipeline.dropped, pipeline.log.events.len());
+    println!("ocdg with {} nodes and {} edges", pipeline.ocdg.net.node_count(), pipeline.ocdg.net.edge_count());
+    for (relation, count) in edge_counts(&pipeline.ocdg) {
+        println!("  {:<12} {}", relation, count);
+    }
+    println!("{}", pipeline.features);
+    println!("reimported {} nodes from {}", pipeline.reimported.net.node_count(), path.display());
+    Ok(())
+}
+
+#[test]
+fn order_to_cash() {
+    let path = std::env::temp_dir().join("pmrs-example-order-to-cash-test.gexf");
+    let pipeline = run(path.to_str().unwrap()).unwrap();
+
+    assert_eq!((pipeline.report.events, pipeline.report.objects, pipeline.report.empty_omaps), (9, 8, 1));
+    assert_eq!(pipeline.dropped, vec!["s1"]);
+    assert_eq!(pipeline.log.events.len(), 8);
+
+    assert_eq!(pipeline.ocdg.net.node_count(), 8);
+    assert_eq!(pipeline.ocdg.net.edge_count(), 20);
+    let expected = [("COBIRTH", 8), ("CODEATH", 6), ("CONSUMES", 4), ("DESCENDANTS", 4), ("INTERACTS", 18), ("MERGE", 2), ("MINION", 2), ("OVERLAPS", 4), ("PEELER", 6), ("REACHABLE", 2)];
+    assert_eq!(edge_counts(&pipeline.ocdg), BTreeMap::from(expected.map(|(relation, count)| (relation.to_string(), count))));
+
+    assert_eq!(pipeline.features.shape(), (8, 3));
+    // i3 meets o2 when ordered and nobody when picked
+    let oids: Vec<Option<&str>> = pipeline.features.column("oids").unwrap().utf8().unwrap().into_iter().collect();
+    let row = |oid: &str| oids.iter().position(|label| *label == Some(oid)).unwrap();
+    let interaction: Vec<Option<f64>> = pipeline.features.column("ObjectEventInteractionOperator").unwrap().f64().unwrap().into_iter().collect();
+    assert_eq!(interaction[row("i3")], Some(0.5));
+    // o1 lives from the order at 08:00 to its invoice at 10:00
+    let lifetimes: Vec<Option<i64>> = pipeline.features.column("ObjectLifetime").unwrap().i64().unwrap().into_iter().collect();
+    assert_eq!(lifetimes[row("o1")], Some(2 * 3600 * 1_000_000_000));
+
+    assert_eq!(pipeline.reimported.net.node_count(), pipeline.ocdg.net.node_count());
+    assert_eq!(pipeline.reimported.net.edge_count(), pipeline.ocdg.net.edge_count());
+}
@@ -0,0 +1,47 @@
+// sliding windows over the order-to-cash fixture, streamed to csv chunk by chunk
+use std::error::Error;
+
+use chrono::Duration;
+use pmrs::objects::exporter::StreamingExporter;
+use pmrs::objects::ocdg::series::{OcdgSeries, SeriesCountsCsv};
+use pmrs::objects::ocdg::{GenerationConfig, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+
+fn run(export_path: &str) -> Result<(OcdgSeries, u64), Box<dyn Error>> {
+    let mut log = import_ocel("logs/order-to-cash.jsonocel")?;
+    log.drop_events_without_objects();
+    let config = GenerationConfig { relations: vec![Relations::INTERACTS, Relations::COBIRTH], ..Default::default() };
+    let series = OcdgSeries::by_window(&log, Duration::hours(2), Duration::hours(1), &config)?;
+    let written = SeriesCountsCsv(series.statistics()).export(export_path)?;
+    Ok((series, written))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = std::env::temp_dir().join("pmrs-example-streaming.csv");
+    let (series, written) = run(path.to_str().unwrap())?;
+    println!("{} windows, {} bytes written to {}", series.len(), written, path.display());
+    for stats in series.statistics() {
+        println!("{} - {}: {} nodes, {} edges, {} events", stats.interval.start, stats.interval.end, stats.nodes, stats.edges, stats.events);
+    }
+    Ok(())
+}
+
+#[test]
+fn streaming() {
+    let path = std::env::temp_dir().join("pmrs-example-streaming-test.csv");
+    let (series, written) = run(path.to_str().unwrap()).unwrap();
+
+    // hourly stride over 08:00 to 12:00, only events that support a relation are counted
+    assert_eq!(series.len(), 5);
+    let events: Vec<usize> = series.statistics().iter().map(|stats| stats.events).collect();
+    assert_eq!(events, vec![2, 2, 2, 1, 1]);
+
+    let csv = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(csv.len() as u64, written);
+    assert!(!pmrs::objects::exporter::partial_path(path.to_str().unwrap()).exists());
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows[0], "start,end,relation,count");
+    assert_eq!(rows.len(), 11);
+    assert_eq!(rows[1], "2022-06-01T08:00:00+00:00,2022-06-01T10:00:00+00:00,COBIRTH,8");
+    assert_eq!(rows[2], "2022-06-01T08:00:00+00:00,2022-06-01T10:00:00+00:00,INTERACTS,8");
+}
@@ -1 +1,5 @@
 pub mod transformation;
+pub mod analysis;
+pub mod simulation;
+pub mod privacy;
+pub mod report;
@@ -0,0 +1,511 @@
+use std::{collections::{BTreeMap, BTreeSet}, error::Error, io::Write};
+use ahash::AHashSet;
+use nohash_hasher::{IntMap, IntSet};
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use polars::prelude::{DataFrame, NamedFromOwned, Series};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use serde_json::{json, Value};
+use strum::{EnumString, Display};
+
+use crate::objects::exporter::{Exporter, StreamingExporter};
+use crate::objects::ocdg::{Ocdg, Relations};
+use crate::objects::ocel::Ocel;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum Granularity {
+    Hour,
+    Day,
+    Week
+}
+
+impl Granularity {
+    pub fn truncate(&self, timestamp: &DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Granularity::Hour => Utc.ymd(timestamp.year(), timestamp.month(), timestamp.day()).and_hms(timestamp.hour(), 0, 0),
+            Granularity::Day => Utc.ymd(timestamp.year(), timestamp.month(), timestamp.day()).and_hms(0, 0, 0),
+            Granularity::Week => {
+                let day = Utc.ymd(timestamp.year(), timestamp.month(), timestamp.day()).and_hms(0, 0, 0);
+                day - Duration::days(timestamp.weekday().num_days_from_monday().into())
+            }
+        }
+    }
+
+    fn step(&self) -> Duration {
+        match self {
+            Granularity::Hour => Duration::hours(1),
+            Granularity::Day => Duration::days(1),
+            Granularity::Week => Duration::weeks(1)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum SeriesCount {
+    Events,
+    EdgesFirstSeen
+}
+
+pub struct RelationSeriesConfig<'a> {
+    pub ocel: &'a Ocel,
+    pub ocdg: &'a Ocdg,
+    pub relation: Relations,
+    pub bucket: Granularity,
+    pub count: SeriesCount,
+    pub object_type: Option<&'a str>
+}
+
+pub fn relation_timeseries(ocel: &Ocel, ocdg: &Ocdg, relation: Relations, bucket: Granularity) -> Vec<(DateTime<Utc>, usize)> {
+    relation_timeseries_config(RelationSeriesConfig { ocel, ocdg, relation, bucket, count: SeriesCount::Events, object_type: None })
+}
+
+pub fn relation_timeseries_config(config: RelationSeriesConfig) -> Vec<(DateTime<Utc>, usize)> {
+    let rel_index: usize = config.relation.relation_index().into();
+    let mut buckets: BTreeMap<DateTime<Utc>, usize> = BTreeMap::new();
+    let mut seen_events: AHashSet<usize> = AHashSet::new();
+    let has_type = |oid: &usize| config.object_type.is_none_or(|otype| config.ocdg.node_attributes.get(oid).is_some_and(|node| node.node_type == otype));
+    // symmetric relations are stored in both directions, a pair is counted from its smaller id unless the type filter drops that side
+    let counted_twice = |src: &usize, tar: &usize| config.relation.is_symmetric() && src > tar && has_type(tar);
+
+    for (src, targets) in &config.ocdg.irels {
+        if !has_type(src) {
+            continue;
+        }
+
+        for (tar, rels) in targets {
+            if let Some(events) = rels.get(&rel_index) {
+                match config.count {
+                    SeriesCount::Events => {
+                        for eid in events {
+                            if seen_events.insert(*eid) {
+                                if let Some(event) = config.ocel.events.get(eid) {
+                                    *buckets.entry(config.bucket.truncate(&event.timestamp)).or_default() += 1;
+                                }
+                            }
+                        }
+                    },
+                    SeriesCount::EdgesFirstSeen if counted_twice(src, tar) => {},
+                    SeriesCount::EdgesFirstSeen => {
+                        let first_seen = events.iter()
+                                               .filter_map(|eid| config.ocel.events.get(eid))
+                                               .map(|event| event.timestamp)
+                                               .min();
+                        if let Some(timestamp) = first_seen {
+                            *buckets.entry(config.bucket.truncate(&timestamp)).or_default() += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // fill the gaps so that dashboards get a contiguous series
+    let mut series: Vec<(DateTime<Utc>, usize)> = vec![];
+    if let (Some(first), Some(last)) = (buckets.keys().next(), buckets.keys().next_back()) {
+        let mut curr = *first;
+        while curr <= *last {
+            series.push((curr, buckets.get(&curr).copied().unwrap_or(0)));
+            curr = curr + config.bucket.step();
+        }
+    }
+    series
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LifecycleNode {
+    pub activity: String,
+    pub support: usize,
+    pub ends: usize,
+    pub children: Vec<usize>
+}
+
+#[derive(Debug, Clone)]
+pub struct LifecycleTree {
+    pub object_type: String,
+    pub nodes: Vec<LifecycleNode>
+}
+
+impl LifecycleTree {
+    pub fn root(&self) -> &LifecycleNode {
+        &self.nodes[0]
+    }
+
+    pub fn support(&self, path: &[&str]) -> Option<usize> {
+        let mut curr = &self.nodes[0];
+        for activity in path {
+            curr = curr.children.iter()
+                                .map(|child| &self.nodes[*child])
+                                .find(|child| child.activity == *activity)?;
+        }
+        Some(curr.support)
+    }
+
+    pub fn drop_off(&self, node: usize) -> usize {
+        let node = &self.nodes[node];
+        node.support - node.children.iter().map(|child| self.nodes[*child].support).sum::<usize>()
+    }
+
+    pub fn to_dot(&self) -> String {
+        let total = self.root().support.max(1) as f64;
+        let mut dot = "digraph lifecycle {\n    rankdir=LR;\n    node [shape=box];\n".to_string();
+        dot.push_str(&format!("    n0 [label=\"{} ({})\", shape=ellipse];\n", escape_dot(&self.object_type), self.root().support));
+        for (index, node) in self.nodes.iter().enumerate().skip(1) {
+            dot.push_str(&format!("    n{} [label=\"{}\\nsupport {}, drop-off {}\"];\n", index, escape_dot(&node.activity), node.support, self.drop_off(index)));
+        }
+        for (index, node) in self.nodes.iter().enumerate() {
+            for child in &node.children {
+                let support = self.nodes[*child].support;
+                dot.push_str(&format!("    n{} -> n{} [label=\"{}\", penwidth={:.2}];\n", index, child, support, 1.0 + 4.0 * support as f64 / total));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn export_dot(&self, file_path: &str) -> Result<bool, Box<dyn Error>> {
+        self.export(file_path)?;
+        Ok(true)
+    }
+}
+
+impl Exporter for LifecycleTree {
+    fn write_content(&self, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        f.write_all(self.to_dot().as_bytes())?;
+        Ok(())
+    }
+}
+
+pub fn lifecycle_tree(ocel: &Ocel, object_type: &str, min_support: usize) -> LifecycleTree {
+    // full trie first, children keyed by activity
+    let mut trie: Vec<(String, usize, usize, BTreeMap<String, usize>)> = vec![(String::new(), 0, 0, BTreeMap::new())];
+    for obj in ocel.objects.values().filter(|obj| obj.obj_type == object_type) {
+        let mut events: Vec<&usize> = obj.events.iter().filter(|eid| ocel.events.contains_key(eid)).collect();
+        events.sort_by_key(|eid| (ocel.events.event(eid).timestamp, **eid));
+
+        let mut curr = 0;
+        trie[curr].1 += 1;
+        for eid in events {
+            let activity = ocel.events.event(eid).activity;
+            curr = match trie[curr].3.get(activity) {
+                Some(child) => *child,
+                None => {
+                    trie.push((activity.to_owned(), 0, 0, BTreeMap::new()));
+                    let child = trie.len() - 1;
+                    trie[curr].3.insert(activity.to_owned(), child);
+                    child
+                }
+            };
+            trie[curr].1 += 1;
+        }
+        trie[curr].2 += 1;
+    }
+
+    // copy the supported part, most frequent flows first
+    let mut tree = LifecycleTree { object_type: object_type.to_owned(), nodes: vec![] };
+    let mut stack: Vec<(usize, Option<usize>)> = vec![(0, None)];
+    while let Some((trie_index, parent)) = stack.pop() {
+        let (activity, support, ends, children) = &trie[trie_index];
+        tree.nodes.push(LifecycleNode { activity: activity.to_owned(), support: *support, ends: *ends, children: vec![] });
+        let index = tree.nodes.len() - 1;
+        if let Some(parent) = parent {
+            tree.nodes[parent].children.push(index);
+        }
+
+        let mut supported: Vec<usize> = children.values().copied().filter(|child| trie[*child].1 >= min_support).collect();
+        supported.sort_by(|a, b| trie[*b].1.cmp(&trie[*a].1).then(trie[*a].0.cmp(&trie[*b].0)));
+        stack.extend(supported.into_iter().rev().map(|child| (child, Some(index))));
+    }
+    tree
+}
+
+fn escape_dot(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[derive(Debug, Clone)]
+pub struct Cohort {
+    pub id: usize,
+    pub size: usize,
+    pub birth_event: usize,
+    pub birth_activity: String,
+    pub birth_timestamp: DateTime<Utc>,
+    pub object_types: Vec<String>,
+    pub members: Vec<usize>
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CohortTable {
+    pub cohorts: Vec<Cohort>,
+    pub object_cohort: IntMap<usize, usize>
+}
+
+impl CohortTable {
+    pub fn cohort_of(&self, oid: &usize) -> Option<&Cohort> {
+        self.object_cohort.get(oid).map(|id| &self.cohorts[*id])
+    }
+
+    pub fn size_series(&self) -> Vec<(DateTime<Utc>, usize)> {
+        self.cohorts.iter().map(|cohort| (cohort.birth_timestamp, cohort.size)).collect()
+    }
+
+    // same "oids" key column as the object point feature table
+    pub fn object_frame(&self, ocel: &Ocel) -> DataFrame {
+        let mut oids: Vec<(&String, u32)> = self.object_cohort.iter()
+                                                .map(|(oid, cohort)| (ocel.object_map.get_by_right(oid).expect("This can't fail"), *cohort as u32))
+                                                .collect();
+        oids.sort();
+        let mut string_oids: Series = oids.iter().map(|(oid, _)| oid.to_string()).collect();
+        string_oids.rename("oids");
+        let cohorts: Series = Series::from_vec("cohort", oids.iter().map(|(_, cohort)| *cohort).collect::<Vec<u32>>());
+        DataFrame::new(vec![string_oids, cohorts]).expect("Columns have the same length")
+    }
+}
+
+pub fn cohorts(ocel: &Ocel, ocdg: &Ocdg) -> CohortTable {
+    let cobirth: usize = Relations::COBIRTH.relation_index().into();
+    let mut visited: IntSet<usize> = IntSet::default();
+    let mut components: Vec<Vec<usize>> = vec![];
+
+    for oid in ocdg.node_attributes.keys() {
+        if !visited.insert(*oid) {
+            continue;
+        }
+        let mut component: Vec<usize> = vec![];
+        let mut stack: Vec<usize> = vec![*oid];
+        while let Some(curr) = stack.pop() {
+            component.push(curr);
+            if let Some(targets) = ocdg.irels.get(&curr) {
+                for (tar, rels) in targets {
+                    if rels.contains_key(&cobirth) && visited.insert(*tar) {
+                        stack.push(*tar);
+                    }
+                }
+            }
+        }
+        components.push(component);
+    }
+
+    let mut table = CohortTable::default();
+    let mut born: Vec<(DateTime<Utc>, usize, Vec<usize>)> = components.into_iter()
+                                                                      .filter_map(|members| {
+                                                                          let birth = members.iter()
+                                                                                             .filter_map(|oid| ocel.objects.get(oid).and_then(|obj| obj.events.first()))
+                                                                                             .min_by_key(|eid| (ocel.events.event(eid).timestamp, **eid))?;
+                                                                          Some((ocel.events.event(birth).timestamp, *birth, members))
+                                                                      })
+                                                                      .collect();
+    born.sort_by_key(|(timestamp, birth, _)| (*timestamp, *birth));
+
+    for (id, (birth_timestamp, birth_event, mut members)) in born.into_iter().enumerate() {
+        members.sort();
+        let object_types: BTreeSet<String> = members.iter().map(|oid| ocel.objects[oid].obj_type.to_owned()).collect();
+        for oid in &members {
+            table.object_cohort.insert(*oid, id);
+        }
+        table.cohorts.push(Cohort { id,
+                                    size: members.len(),
+                                    birth_event,
+                                    birth_activity: ocel.events.event(&birth_event).activity.to_owned(),
+                                    birth_timestamp,
+                                    object_types: object_types.into_iter().collect(),
+                                    members });
+    }
+    table
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceWorkload {
+    pub resource: String,
+    pub events: usize,
+    pub cases: usize
+}
+
+// arc a -> b counts the cases in which an event of a is directly followed by an event of b
+pub fn resource_network(ocel: &Ocel, resource_type: &str, case_type: &str) -> DiGraph<String, usize> {
+    let mut resources: Vec<(&str, usize)> = ocel.objects.iter()
+                                                .filter(|(_, obj)| obj.obj_type == resource_type)
+                                                .map(|(oid, _)| (ocel.object_map.get_by_right(oid).map_or("?", |label| label.as_str()), *oid))
+                                                .collect();
+    resources.sort();
+
+    let mut net: DiGraph<String, usize> = DiGraph::new();
+    let inodes: IntMap<usize, NodeIndex> = resources.iter().map(|(label, oid)| (*oid, net.add_node(label.to_string()))).collect();
+
+    let mut handovers: BTreeMap<(NodeIndex, NodeIndex), usize> = BTreeMap::new();
+    for obj in ocel.objects.values().filter(|obj| obj.obj_type == case_type) {
+        let mut events: Vec<&usize> = obj.events.iter().filter(|eid| ocel.events.contains_key(eid)).collect();
+        events.sort_by_key(|eid| (ocel.events.event(eid).timestamp, **eid));
+
+        let mut case_handovers: BTreeSet<(NodeIndex, NodeIndex)> = BTreeSet::new();
+        for pair in events.windows(2) {
+            let from: Vec<NodeIndex> = ocel.events.event(pair[0]).omap.iter().filter_map(|oid| inodes.get(oid)).copied().collect();
+            let to: Vec<NodeIndex> = ocel.events.event(pair[1]).omap.iter().filter_map(|oid| inodes.get(oid)).copied().collect();
+            for a in &from {
+                for b in to.iter().filter(|b| *b != a) {
+                    case_handovers.insert((*a, *b));
+                }
+            }
+        }
+        for handover in case_handovers {
+            *handovers.entry(handover).or_default() += 1;
+        }
+    }
+
+    for ((a, b), cases) in handovers {
+        net.add_edge(a, b, cases);
+    }
+    net
+}
+
+pub fn resource_workload(ocel: &Ocel, resource_type: &str, case_type: &str) -> Vec<ResourceWorkload> {
+    let mut workload: Vec<ResourceWorkload> = ocel.objects.iter()
+                                                  .filter(|(_, obj)| obj.obj_type == resource_type)
+                                                  .map(|(oid, obj)| {
+                                                      let cases: IntSet<usize> = obj.events.iter()
+                                                                                    .filter_map(|eid| ocel.events.get(eid))
+                                                                                    .flat_map(|event| event.omap.iter())
+                                                                                    .filter(|case| ocel.objects.get(case).is_some_and(|case| case.obj_type == case_type))
+                                                                                    .copied()
+                                                                                    .collect();
+                                                      ResourceWorkload { resource: ocel.object_map.get_by_right(oid).map_or("?", |label| label.as_str()).to_owned(),
+                                                                         events: obj.events.len(),
+                                                                         cases: cases.len() }
+                                                  })
+                                                  .collect();
+    workload.sort_by(|a, b| a.resource.cmp(&b.resource));
+    workload
+}
+
+pub struct ResourceNetworkDot<'a>(pub &'a DiGraph<String, usize>);
+
+impl<'a> ResourceNetworkDot<'a> {
+    pub fn to_dot(&self) -> String {
+        let mut dot = "digraph handover {\n    node [shape=ellipse];\n".to_string();
+        for node in self.0.node_indices() {
+            dot.push_str(&format!("    n{} [label=\"{}\"];\n", node.index(), escape_dot(&self.0[node])));
+        }
+        for edge in self.0.edge_references() {
+            dot.push_str(&format!("    n{} -> n{} [label=\"{}\"];\n", edge.source().index(), edge.target().index(), edge.weight()));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn export_dot(&self, file_path: &str) -> Result<bool, Box<dyn Error>> {
+        self.export(file_path)?;
+        Ok(true)
+    }
+}
+
+impl<'a> Exporter for ResourceNetworkDot<'a> {
+    fn write_content(&self, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        f.write_all(self.to_dot().as_bytes())?;
+        Ok(())
+    }
+}
+
+pub struct TimeseriesCsv<'a>(pub &'a [(DateTime<Utc>, usize)]);
+
+// one chunk for the header and one per bucket
+impl<'a> StreamingExporter for TimeseriesCsv<'a> {
+    fn chunk_count(&self) -> usize {
+        self.0.len() + 1
+    }
+
+    fn write_chunk(&self, index: usize, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        match index {
+            0 => writeln!(f, "bucket,count")?,
+            _ => {
+                let (bucket, count) = &self.0[index - 1];
+                writeln!(f, "{},{}", bucket.to_rfc3339(), count)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn timeseries_to_csv(series: &[(DateTime<Utc>, usize)]) -> String {
+    let csv = TimeseriesCsv(series);
+    let mut out: Vec<u8> = vec![];
+    for index in 0..csv.chunk_count() {
+        csv.write_chunk(index, &mut out).expect("Writing to memory can't fail");
+    }
+    String::from_utf8(out).expect("The csv is built from valid strings")
+}
+
+pub fn timeseries_to_json(series: &[(DateTime<Utc>, usize)]) -> Value {
+    Value::Array(series.iter()
+                       .map(|(bucket, count)| json!({"bucket": bucket.to_rfc3339(), "count": count}))
+                       .collect())
+}
+
+pub fn export_timeseries_csv(series: &[(DateTime<Utc>, usize)], file_path: &str) -> Result<bool, Box<dyn Error>> {
+    TimeseriesCsv(series).export(file_path)?;
+    Ok(true)
+}
+
+pub fn export_timeseries_json(series: &[(DateTime<Utc>, usize)], file_path: &str) -> Result<bool, Box<dyn Error>> {
+    timeseries_to_json(series).export(file_path)?;
+    Ok(true)
+}
+
+// pairs of types seen together in fewer events are dropped by type_lift, their lift is too noisy to read
+pub const LIFT_MIN_COOCCURRENCES: usize = 5;
+
+pub fn type_lift(ocel: &Ocel) -> Vec<(String, String, f64)> {
+    type_lift_with(ocel, LIFT_MIN_COOCCURRENCES)
+}
+
+// lift = P(both types in an event) / (P(first type in an event) * P(second type in an event)) over all events, events
+// without objects included. 1 means independent, above 1 the types meet more often than chance. each unordered pair of
+// different types appears once with the names sorted, the pairs are sorted by descending lift
+pub fn type_lift_with(ocel: &Ocel, min_cooccurrences: usize) -> Vec<(String, String, f64)> {
+    let mut singles: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut pairs: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for event in ocel.events.values() {
+        let types: BTreeSet<&str> = event.omap.iter().filter_map(|oid| ocel.objects.get(oid)).map(|obj| obj.obj_type.as_str()).collect();
+        for (i, first) in types.iter().enumerate() {
+            *singles.entry(first).or_default() += 1;
+            for second in types.iter().skip(i + 1) {
+                *pairs.entry((first, second)).or_default() += 1;
+            }
+        }
+    }
+
+    let events = ocel.events.len() as f64;
+    let mut lifts: Vec<(String, String, f64)> = pairs.into_iter()
+                                                     .filter(|(_, count)| *count >= min_cooccurrences.max(1))
+                                                     .map(|((first, second), count)| {
+                                                         let lift = (count as f64 * events) / (singles[first] as f64 * singles[second] as f64);
+                                                         (first.to_owned(), second.to_owned(), lift)
+                                                     })
+                                                     .collect();
+    lifts.sort_by(|a, b| b.2.total_cmp(&a.2).then_with(|| (&a.0, &a.1).cmp(&(&b.0, &b.1))));
+    lifts
+}
+
+pub struct TypeLiftCsv<'a>(pub &'a [(String, String, f64)]);
+
+// one chunk for the header and one per pair
+impl<'a> StreamingExporter for TypeLiftCsv<'a> {
+    fn chunk_count(&self) -> usize {
+        self.0.len() + 1
+    }
+
+    fn write_chunk(&self, index: usize, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        let quote = |name: &str| if name.contains([',', '"', '\n']) {format!("\"{}\"", name.replace('"', "\"\""))} else {name.to_owned()};
+        match index {
+            0 => writeln!(f, "first_type,second_type,lift")?,
+            _ => {
+                let (first, second, lift) = &self.0[index - 1];
+                writeln!(f, "{},{},{}", quote(first), quote(second), lift)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn export_type_lift_csv(lifts: &[(String, String, f64)], file_path: &str) -> Result<bool, Box<dyn Error>> {
+    TypeLiftCsv(lifts).export(file_path)?;
+    Ok(true)
+}
@@ -0,0 +1,105 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone)]
+pub struct PrivacyConfig {
+    // counts below k are redacted
+    pub k: u64,
+    // laplace noise with scale 1 / epsilon on the remaining counts, counts that drop below k are redacted as well
+    pub epsilon: Option<f64>,
+    // object keys whose integers are not counts, e.g. ids or years
+    pub exempt: Vec<String>,
+    // fixed seed for reproducible noise, taken from the clock otherwise
+    pub seed: Option<u64>
+}
+
+impl Default for PrivacyConfig {
+    fn default() -> Self {
+        Self { k: 5, epsilon: None, exempt: vec![], seed: None }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SuppressionReport {
+    // json pointers of the touched fields
+    pub redacted: Vec<String>,
+    pub noised: Vec<String>
+}
+
+// every unsigned integer of the report is treated as a count, redacted counts become the string "<k"
+pub fn suppress(report: &Value, k: u64) -> Value {
+    suppress_with(report, &PrivacyConfig { k, ..Default::default() }).0
+}
+
+pub fn suppress_with(report: &Value, config: &PrivacyConfig) -> (Value, SuppressionReport) {
+    let seed = config.seed.unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos() as u64));
+    let mut walker = Walker { config, rng: SplitMix(seed), report: SuppressionReport::default() };
+    let value = walker.walk(report, "");
+    (value, walker.report)
+}
+
+struct Walker<'a> {
+    config: &'a PrivacyConfig,
+    rng: SplitMix,
+    report: SuppressionReport
+}
+
+impl<'a> Walker<'a> {
+    fn walk(&mut self, value: &Value, pointer: &str) -> Value {
+        match value {
+            Value::Object(fields) => {
+                let mut out = Map::new();
+                for (key, field) in fields {
+                    let path = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+                    let field = if self.config.exempt.contains(key) {field.clone()} else {self.walk(field, &path)};
+                    out.insert(key.to_owned(), field);
+                }
+                Value::Object(out)
+            },
+            Value::Array(items) => Value::Array(items.iter().enumerate().map(|(index, item)| self.walk(item, &format!("{}/{}", pointer, index))).collect()),
+            Value::Number(number) => match number.as_u64() {
+                Some(count) => self.count(count, pointer),
+                None => value.clone()
+            },
+            other => other.clone()
+        }
+    }
+
+    fn count(&mut self, count: u64, pointer: &str) -> Value {
+        let count = match self.config.epsilon {
+            Some(epsilon) if count >= self.config.k => {
+                self.report.noised.push(pointer.to_owned());
+                (count as f64 + self.rng.laplace(1.0 / epsilon)).round().max(0.0) as u64
+            },
+            _ => count
+        };
+        if count < self.config.k {
+            self.report.redacted.push(pointer.to_owned());
+            return Value::String(format!("<{}", self.config.k));
+        }
+        Value::from(count)
+    }
+}
+
+// small deterministic generator, good enough for noise and shuffles and free of extra dependencies
+pub(crate) struct SplitMix(pub(crate) u64);
+
+impl SplitMix {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    // uniform in (-0.5, 0.5)
+    fn uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64 - 0.5
+    }
+
+    fn laplace(&mut self, scale: f64) -> f64 {
+        let u = self.uniform();
+        -scale * u.signum() * (1.0 - 2.0 * u.abs()).ln()
+    }
+}
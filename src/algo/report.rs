@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+use nohash_hasher::IntSet;
+
+use crate::objects::ocdg::Ocdg;
+use crate::objects::ocel::Ocel;
+
+// markdown narrative of one object, relations per step need an ocdg with full evidence
+pub fn object_journey(ocel: &Ocel, ocdg: &Ocdg, oid: usize) -> Option<String> {
+    let obj = ocel.objects.get(&oid)?;
+    let label = |oid: &usize| ocel.object_map.get_by_right(oid).map_or_else(|| oid.to_string(), |label| label.to_owned());
+    let describe = |oid: &usize| format!("{} ({})", label(oid), ocel.objects.get(oid).map_or("?", |obj| obj.obj_type.as_str()));
+    let event_label = |eid: &usize| ocel.event_map.get_by_right(eid).map_or_else(|| eid.to_string(), |label| label.to_owned());
+    let listing = |oids: &mut Vec<usize>| {
+        oids.sort_by_key(|oid| label(oid));
+        oids.iter().map(describe).collect::<Vec<String>>().join(", ")
+    };
+    // objects that share the first or last event and have it as their own first or last event
+    let sharing = |eid: usize, pick: fn(&[usize]) -> Option<&usize>| -> Vec<usize> {
+        ocel.events.event(&eid).omap.iter()
+                             .copied()
+                             .filter(|other| *other != oid && ocel.objects.get(other).and_then(|other| pick(&other.events)) == Some(&eid))
+                             .collect()
+    };
+
+    let mut out = format!("# Journey of {}\n\n", describe(&oid));
+    let (first, last) = match (obj.events.first(), obj.events.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => {
+            out.push_str("The object has no events.\n");
+            return Some(out);
+        }
+    };
+
+    let born_with = listing(&mut sharing(first, |events| events.first()));
+    out.push_str(&format!("**Born** at {} `{}` ({}){}.\n\n",
+                          event_label(&first), ocel.events.event(&first).activity, ocel.events.event(&first).timestamp.to_rfc3339(),
+                          if born_with.is_empty() {String::new()} else {format!(" together with {}", born_with)}));
+
+    out.push_str("## Lifecycle\n\n");
+    let mut seen: IntSet<usize> = IntSet::default();
+    for (step, eid) in obj.events.iter().enumerate() {
+        let event = &ocel.events.event(eid);
+        out.push_str(&format!("{}. **{}** `{}` at {}\n", step + 1, event_label(eid), event.activity, event.timestamp.to_rfc3339()));
+        let mut partners: Vec<usize> = event.omap.iter().copied().filter(|other| *other != oid && seen.insert(*other)).collect();
+        if !partners.is_empty() {
+            out.push_str(&format!("   - meets {}\n", listing(&mut partners)));
+        }
+        // relation -> partner label -> (outgoing, incoming)
+        let mut formed: BTreeMap<String, BTreeMap<String, (bool, bool)>> = BTreeMap::new();
+        for (src, tar, rel) in ocdg.relations_of_event(*eid).unwrap_or_default() {
+            if src == oid {
+                formed.entry(rel.to_string()).or_default().entry(label(&tar)).or_default().0 = true;
+            } else if tar == oid {
+                formed.entry(rel.to_string()).or_default().entry(label(&src)).or_default().1 = true;
+            }
+        }
+        for (rel, partners) in formed {
+            let partners: Vec<String> = partners.into_iter()
+                                                .map(|(partner, direction)| match direction {
+                                                    (true, true) => format!("↔ {}", partner),
+                                                    (true, false) => format!("→ {}", partner),
+                                                    _ => format!("← {}", partner)
+                                                })
+                                                .collect();
+            out.push_str(&format!("   - {} {}\n", rel, partners.join(", ")));
+        }
+    }
+
+    let died_with = listing(&mut sharing(last, |events| events.last()));
+    out.push_str(&format!("\n**Died** at {} `{}` ({}){}.\n",
+                          event_label(&last), ocel.events.event(&last).activity, ocel.events.event(&last).timestamp.to_rfc3339(),
+                          if died_with.is_empty() {String::new()} else {format!(" together with {}", died_with)}));
+    Some(out)
+}
@@ -0,0 +1,68 @@
+use std::{sync::atomic::{AtomicBool, Ordering}, thread, time::{Duration, Instant}};
+
+use crate::objects::ocel::{EventRef, Ocel};
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayEvent<'a> {
+    pub eid: usize,
+    pub event_id: &'a str,
+    pub event: EventRef<'a>
+}
+
+pub struct ReplayIter<'a> {
+    ocel: &'a Ocel,
+    order: Vec<usize>,
+    position: usize,
+    speed: f64
+}
+
+impl<'a> Iterator for ReplayIter<'a> {
+    type Item = (Duration, ReplayEvent<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let eid = *self.order.get(self.position)?;
+        let event = self.ocel.events.event(&eid);
+        let delay = match self.position.checked_sub(1).map(|prev| self.ocel.events.event(&self.order[prev])) {
+            Some(prev) if self.speed > 0.0 => {
+                let gap = (event.timestamp - prev.timestamp).to_std().unwrap_or(Duration::ZERO);
+                gap.div_f64(self.speed)
+            },
+            _ => Duration::ZERO
+        };
+        self.position += 1;
+        let event_id = self.ocel.event_map.get_by_right(&eid).map_or("", |id| id.as_str());
+        Some((delay, ReplayEvent { eid, event_id, event }))
+    }
+}
+
+// events with identical timestamps keep their id order and arrive without delay
+pub fn replay_iter(ocel: &Ocel, speed: f64) -> ReplayIter<'_> {
+    let mut order: Vec<usize> = ocel.events.keys().copied().collect();
+    order.sort_by_key(|eid| (ocel.events.event(eid).timestamp, *eid));
+    ReplayIter { ocel, order, position: 0, speed }
+}
+
+pub fn replay<F>(ocel: &Ocel, speed: f64, sink: F) -> usize where F: FnMut(ReplayEvent) {
+    replay_cancellable(ocel, speed, &AtomicBool::new(false), sink)
+}
+
+pub fn replay_cancellable<F>(ocel: &Ocel, speed: f64, cancel: &AtomicBool, mut sink: F) -> usize where F: FnMut(ReplayEvent) {
+    let mut emitted: usize = 0;
+    let mut due = Instant::now();
+    for (delay, event) in replay_iter(ocel, speed) {
+        due += delay;
+        // sleep in small slices so that a cancel does not wait for long gaps
+        while let Some(remaining) = due.checked_duration_since(Instant::now()) {
+            if cancel.load(Ordering::Relaxed) {
+                return emitted;
+            }
+            thread::sleep(remaining.min(Duration::from_millis(10)));
+        }
+        if cancel.load(Ordering::Relaxed) {
+            return emitted;
+        }
+        sink(event);
+        emitted += 1;
+    }
+    emitted
+}
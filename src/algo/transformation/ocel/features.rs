@@ -1,5 +1,11 @@
 pub mod object_point;
+pub mod aggregate;
+pub mod prefix;
+pub mod cv;
+pub mod survival;
 pub(crate) mod object_group;
 pub(crate) mod event_point;
 pub(crate) mod event_group;
 pub(crate) mod operator;
+
+pub use self::prefix::as_of;
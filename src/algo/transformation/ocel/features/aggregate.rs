@@ -0,0 +1,134 @@
+use std::error::Error;
+use std::fmt;
+use polars::prelude::{DataFrame, NamedFromOwned, Series};
+use serde_json::Value;
+use strum::{Display, EnumString};
+
+use crate::objects::ocel::importer::timestamp::TimestampParser;
+use crate::objects::ocel::{Ocel, OcelAttributeValue};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+pub enum Aggregation {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    Count,
+    // the value of the latest event
+    Last
+}
+
+// what an event without the attribute (or with a json null) contributes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, EnumString, Display)]
+pub enum NullPolicy {
+    // left out, an aggregation over nothing but nulls is NaN (Sum and Count are 0)
+    #[default]
+    Skip,
+    // counted as 0
+    ZeroFill,
+    // any null makes the aggregate NaN
+    Nan
+}
+
+// the events an aggregate runs over, an object stands for the events of its lifecycle
+#[derive(Debug, Clone, Copy)]
+pub enum AggregateTarget<'a> {
+    Object(usize),
+    Events(&'a [usize])
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AggregateError {
+    UnknownObject(usize),
+    UnknownEvent(usize),
+    // only numbers are aggregated, Count accepts values of any type
+    NotNumeric { event: String, attribute: String, value: OcelAttributeValue }
+}
+
+impl fmt::Display for AggregateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AggregateError::UnknownObject(oid) => write!(f, "The object {} is not part of the log", oid),
+            AggregateError::UnknownEvent(eid) => write!(f, "The event {} is not part of the log", eid),
+            AggregateError::NotNumeric { event, attribute, value } => write!(f, "Event {} holds the non numeric value {:?} for attribute {}", event, value, attribute)
+        }
+    }
+}
+
+impl Error for AggregateError {}
+
+pub fn attribute(ocel: &Ocel, target: AggregateTarget, attr: &str, agg: Aggregation, policy: NullPolicy) -> Result<f64, AggregateError> {
+    let eids: &[usize] = match target {
+        AggregateTarget::Object(oid) => &ocel.objects.get(&oid).ok_or(AggregateError::UnknownObject(oid))?.events,
+        AggregateTarget::Events(eids) => eids
+    };
+    // events are read in timestamp order so that Last does not depend on the order of the ids
+    let mut events = Vec::with_capacity(eids.len());
+    for eid in eids {
+        events.push((eid, ocel.events.get(eid).ok_or(AggregateError::UnknownEvent(*eid))?));
+    }
+    events.sort_by_key(|(eid, event)| (event.timestamp, **eid));
+
+    let timestamps = TimestampParser::default();
+    let mut values: Vec<f64> = Vec::with_capacity(events.len());
+    let mut present = 0;
+    for (eid, event) in events {
+        let value = match event.vmap.get(attr) {
+            None | Some(Value::Null) => match policy {
+                NullPolicy::Skip => continue,
+                NullPolicy::ZeroFill => {
+                    values.push(0.0);
+                    continue;
+                },
+                NullPolicy::Nan => return Ok(f64::NAN)
+            },
+            Some(value) => value
+        };
+        present += 1;
+        if agg == Aggregation::Count {
+            continue;
+        }
+        // untyped attributes keep the type of their json value, numbers written as text are not numbers
+        match OcelAttributeValue::parse(value, None, &timestamps).expect("Undeclared attributes always parse") {
+            OcelAttributeValue::Number(number) => values.push(number.as_f64().unwrap_or(f64::NAN)),
+            other => return Err(AggregateError::NotNumeric { event: event_label(ocel, eid), attribute: attr.to_owned(), value: other })
+        }
+    }
+
+    Ok(match agg {
+        Aggregation::Count if policy == NullPolicy::ZeroFill => values.len() as f64 + present as f64,
+        Aggregation::Count => present as f64,
+        Aggregation::Sum => values.iter().sum(),
+        Aggregation::Mean if values.is_empty() => f64::NAN,
+        Aggregation::Mean => values.iter().sum::<f64>() / values.len() as f64,
+        Aggregation::Min => values.iter().copied().reduce(f64::min).unwrap_or(f64::NAN),
+        Aggregation::Max => values.iter().copied().reduce(f64::max).unwrap_or(f64::NAN),
+        Aggregation::Last => values.last().copied().unwrap_or(f64::NAN)
+    })
+}
+
+// one aggregate per object, in the order of the oids
+pub fn attribute_batch(ocel: &Ocel, oids: &[usize], attr: &str, agg: Aggregation, policy: NullPolicy) -> Result<Vec<f64>, AggregateError> {
+    oids.iter().map(|oid| attribute(ocel, AggregateTarget::Object(*oid), attr, agg, policy)).collect()
+}
+
+// an object feature table with an "oids" column and one "<aggregation>:<attribute>" column per aggregate,
+// rows are sorted by object label so that it can be joined with the other object tables
+pub fn object_attribute_features(ocel: &Ocel, aggregates: &[(&str, Aggregation)], policy: NullPolicy) -> Result<DataFrame, AggregateError> {
+    let mut labels: Vec<(&String, &usize)> = ocel.object_map.iter().filter(|(_, oid)| ocel.objects.contains_key(oid)).collect();
+    labels.sort();
+    let oids: Vec<usize> = labels.iter().map(|(_, oid)| **oid).collect();
+
+    let mut string_oids: Series = labels.iter().map(|(label, _)| label.to_string()).collect();
+    string_oids.rename("oids");
+    let mut series_vec: Vec<Series> = vec![string_oids];
+    for (attr, agg) in aggregates {
+        let values = attribute_batch(ocel, &oids, attr, *agg, policy)?;
+        series_vec.push(Series::from_vec(&format!("{}:{}", agg, attr), values));
+    }
+    Ok(DataFrame::new(series_vec).expect("Every column has a row per object"))
+}
+
+fn event_label(ocel: &Ocel, eid: &usize) -> String {
+    ocel.event_map.get_by_right(eid).cloned().unwrap_or_else(|| eid.to_string())
+}
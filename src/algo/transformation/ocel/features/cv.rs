@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use nohash_hasher::{IntMap, IntSet};
+use polars::prelude::{AnyValue, DataFrame};
+
+use crate::algo::privacy::SplitMix;
+use crate::objects::ocel::Ocel;
+
+pub type TrainIdx = Vec<usize>;
+pub type TestIdx = Vec<usize>;
+
+// k folds over the rows of a feature table where all rows of a group land in the same test fold.
+// rows of event tables ("eids" column) are grouped by process execution, the connected component of objects sharing events,
+// rows of object tables ("oids" column) by object. rows with unknown ids form their own group.
+pub fn grouped_folds(table: &DataFrame, ocel: &Ocel, k: usize, seed: u64) -> Vec<(TrainIdx, TestIdx)> {
+    if k < 2 {
+        return vec![];
+    }
+    let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (row, key) in row_groups(table, ocel).into_iter().enumerate() {
+        groups.entry(key).or_default().push(row);
+    }
+
+    // shuffled for the seed, every group then goes to the currently smallest fold
+    let mut groups: Vec<Vec<usize>> = groups.into_values().collect();
+    let mut rng = SplitMix(seed);
+    for i in (1..groups.len()).rev() {
+        groups.swap(i, (rng.next_u64() % (i as u64 + 1)) as usize);
+    }
+
+    let mut tests: Vec<TestIdx> = vec![vec![]; k];
+    for rows in groups {
+        let fold = (0..k).min_by_key(|fold| (tests[*fold].len(), *fold)).expect("k is at least 2");
+        tests[fold].extend(rows);
+    }
+    tests.into_iter()
+         .map(|mut test| {
+             test.sort_unstable();
+             let held_out: IntSet<usize> = test.iter().copied().collect();
+             let train: TrainIdx = (0..table.height()).filter(|row| !held_out.contains(row)).collect();
+             (train, test)
+         })
+         .collect()
+}
+
+// label counts of the test rows of every fold
+pub fn fold_label_distribution(table: &DataFrame, label: &str, folds: &[(TrainIdx, TestIdx)]) -> Option<Vec<BTreeMap<String, usize>>> {
+    let labels = table.column(label).ok()?;
+    Some(folds.iter()
+              .map(|(_, test)| {
+                  let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+                  for row in test {
+                      *counts.entry(any_to_string(labels.get(*row))).or_default() += 1;
+                  }
+                  counts
+              })
+              .collect())
+}
+
+fn row_groups(table: &DataFrame, ocel: &Ocel) -> Vec<String> {
+    let ids = |column: &str| -> Option<Vec<String>> {
+        let series = table.column(column).ok()?;
+        Some((0..series.len()).map(|row| any_to_string(series.get(row))).collect())
+    };
+    if let Some(eids) = ids("eids") {
+        let executions = executions(ocel);
+        return eids.into_iter()
+                   .map(|label| match ocel.event_map.get_by_left(&label).and_then(|eid| ocel.events.event(eid).omap.iter().next()).and_then(|oid| executions.get(oid)) {
+                       Some(execution) => format!("execution:{}", execution),
+                       None => format!("event:{}", label)
+                   })
+                   .collect();
+    }
+    match ids("oids") {
+        Some(oids) => oids.into_iter().map(|label| format!("object:{}", label)).collect(),
+        None => (0..table.height()).map(|row| format!("row:{}", row)).collect()
+    }
+}
+
+// object id -> execution id, executions are numbered by their smallest object label
+fn executions(ocel: &Ocel) -> IntMap<usize, usize> {
+    let mut neighbours: IntMap<usize, IntSet<usize>> = IntMap::default();
+    for event in ocel.events.values() {
+        for oid in &event.omap {
+            neighbours.entry(*oid).or_default().extend(&event.omap);
+        }
+    }
+    let mut labels: Vec<(&String, &usize)> = ocel.object_map.iter().collect();
+    labels.sort();
+
+    let mut execution: IntMap<usize, usize> = IntMap::default();
+    let mut next = 0;
+    for (_, oid) in labels {
+        if execution.contains_key(oid) {
+            continue;
+        }
+        let mut stack: Vec<usize> = vec![*oid];
+        execution.insert(*oid, next);
+        while let Some(curr) = stack.pop() {
+            for neighbour in neighbours.get(&curr).into_iter().flatten() {
+                if !execution.contains_key(neighbour) {
+                    execution.insert(*neighbour, next);
+                    stack.push(*neighbour);
+                }
+            }
+        }
+        next += 1;
+    }
+    execution
+}
+
+fn any_to_string(value: AnyValue) -> String {
+    match value {
+        AnyValue::Utf8(value) => value.to_owned(),
+        other => other.to_string()
+    }
+}
@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 use ahash::{AHashSet, AHashMap};
 use chrono::Duration;
 use petgraph::EdgeDirection::Outgoing;
@@ -36,11 +37,60 @@ pub enum ObjectPoint {
 pub struct ObjectPointConfig<'a> {
     pub ocel: &'a Ocel,
     pub ocdg: &'a Ocdg,
-    pub params: &'a HashMap<ObjectPoint, Option<Value>>
+    pub params: &'a HashMap<ObjectPoint, Option<Value>>,
+    pub lifecycle_view: LifecycleView
+}
+
+// the events of a lifecycle the features look at, bounds the work per object for very long lifecycles
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LifecycleView {
+    #[default]
+    Full,
+    // the first and the last k events
+    FirstLastK(usize),
+    // every n-th event, the first and the last event are always kept
+    Stride(usize)
+}
+
+impl LifecycleView {
+    pub fn events<'a>(&self, lifecycle: &'a [usize]) -> Cow<'a, [usize]> {
+        match *self {
+            LifecycleView::FirstLastK(k) if lifecycle.len() > 2 * k.max(1) => {
+                let k = k.max(1);
+                Cow::Owned(lifecycle[..k].iter().chain(&lifecycle[lifecycle.len() - k..]).copied().collect())
+            },
+            LifecycleView::Stride(n) if n > 1 && lifecycle.len() > 2 => {
+                let mut events: Vec<usize> = lifecycle.iter().step_by(n).copied().collect();
+                if !(lifecycle.len() - 1).is_multiple_of(n) {
+                    events.push(lifecycle[lifecycle.len() - 1]);
+                }
+                Cow::Owned(events)
+            },
+            _ => Cow::Borrowed(lifecycle)
+        }
+    }
+}
 
+// what a feature column holds when the lifecycles are sampled.
+// activity existence and counts only see the sampled events and become lower bounds, the unit set ratio and the event
+// interaction are averages over the sampled events. the neighbour count and the lifetime (first and last event are kept) stay exact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnSemantics {
+    Exact,
+    LowerBound,
+    Estimate
 }
 
 pub fn object_point_features(config: ObjectPointConfig) -> DataFrame {
+    object_point_features_with_metadata(config).0
+}
+
+// also returns the semantics of every feature column
+pub fn object_point_features_with_metadata(config: ObjectPointConfig) -> (DataFrame, BTreeMap<String, ColumnSemantics>) {
+    let sampled = config.lifecycle_view != LifecycleView::Full;
+    let degraded = |semantics: ColumnSemantics| if sampled {semantics} else {ColumnSemantics::Exact};
+    let mut metadata: BTreeMap<String, ColumnSemantics> = BTreeMap::new();
+    let lifecycle = |oid: &usize| config.lifecycle_view.events(&config.ocel.objects[oid].events);
     let object_linker = link_objects(&config.ocel.object_map, &config.ocdg.object_map);
     let entity_order: Vec<usize> = object_linker.left_values().map(|v| **v).collect();
     let mut string_oids: Series = entity_order.iter().map(|oid| config.ocel.object_map.get_by_right(oid).unwrap().to_owned()).collect();
@@ -61,13 +111,13 @@ pub fn object_point_features(config: ObjectPointConfig) -> DataFrame {
                 let mut feature_vector: Vec<u32> = Vec::with_capacity(feature_values.len());
                 feature_values.iter().for_each(|(index, value)| feature_vector.insert(*index, (*value).try_into().unwrap()));
                 series_vec.push(Series::from_vec(feature.into(), feature_vector));
-                
+                metadata.insert(feature.to_string(), ColumnSemantics::Exact);
             },
             ObjectPoint::ActivityExistence => {
                 let feature_values: Vec<(usize, usize, u8)> = entity_order.par_iter()
                                                             .enumerate()
                                                             .map(|(index, log_oid)| {
-                                                                activity_existence(config.ocel, *log_oid).iter().enumerate().map(|(actid, res)| (index, actid, *res)).collect::<Vec<(usize, usize, u8)>>()
+                                                                activities_present(config.ocel, &lifecycle(log_oid)).iter().enumerate().map(|(actid, res)| (index, actid, *res)).collect::<Vec<(usize, usize, u8)>>()
                                                             })
                                                             .flatten()
                                                             .collect();
@@ -77,6 +127,7 @@ pub fn object_point_features(config: ObjectPointConfig) -> DataFrame {
 
                 config.ocel.activities.iter().enumerate().for_each(|(index, act_name)| {
                                                                         series_vec.push(Series::from_vec(format!("{}:{}", feature, act_name).as_str(), feature_vector[index].clone()));
+                                                                        metadata.insert(format!("{}:{}", feature, act_name), degraded(ColumnSemantics::LowerBound));
                                                                     });
 
             },
@@ -84,7 +135,7 @@ pub fn object_point_features(config: ObjectPointConfig) -> DataFrame {
                 let feature_values: Vec<(usize, usize, usize)> = entity_order.par_iter()
                                                             .enumerate()
                                                             .map(|(index, log_oid)| {
-                                                                activity_existence_count(config.ocel, *log_oid).iter().enumerate().map(|(actid, res)| (index, actid, *res)).collect::<Vec<(usize, usize, usize)>>()
+                                                                activity_counts(config.ocel, &lifecycle(log_oid)).iter().enumerate().map(|(actid, res)| (index, actid, *res)).collect::<Vec<(usize, usize, usize)>>()
                                                             })
                                                             .flatten()
                                                             .collect();
@@ -93,7 +144,8 @@ pub fn object_point_features(config: ObjectPointConfig) -> DataFrame {
                 feature_values.iter().for_each(|(oid, act_id, value)| feature_vector.get_mut(*act_id).expect("This can't fail").insert(*oid, (*value).try_into().unwrap()));
 
                 config.ocel.activities.iter().enumerate().for_each(|(index, act_name)| {
-                                                                        series_vec.push(Series::from_vec(format!("{}:{}", feature, act_name).as_str(), feature_vector[index].clone()))
+                                                                        series_vec.push(Series::from_vec(format!("{}:{}", feature, act_name).as_str(), feature_vector[index].clone()));
+                                                                        metadata.insert(format!("{}:{}", feature, act_name), degraded(ColumnSemantics::LowerBound));
                                                                     });
 
             },
@@ -108,35 +160,38 @@ pub fn object_point_features(config: ObjectPointConfig) -> DataFrame {
                 let mut feature_vector: Vec<i64> = Vec::with_capacity(feature_values.len());
                 feature_values.iter().for_each(|(index, value)| feature_vector.insert(*index, match value.num_nanoseconds() {Some(v) => {v}, None => {0}}));
                 series_vec.push(Series::from_vec(feature.into(), feature_vector));
+                metadata.insert(feature.to_string(), ColumnSemantics::Exact);
             },
             ObjectPoint::ObjectUnitSetRatio => {
                 let feature_values: Vec<(usize, f64)> = entity_order.par_iter()
                                                             .enumerate()
                                                             .map(|(index, log_oid)| {
-                                                                (index, object_unit_set_ratio(config.ocel, log_oid))
+                                                                (index, unit_set_ratio(config.ocel, log_oid, &lifecycle(log_oid)))
                                                             })
                                                             .collect();
 
                 let mut feature_vector: Vec<f64> = Vec::with_capacity(feature_values.len());
                 feature_values.iter().for_each(|(index, value)| feature_vector.insert(*index, *value));
                 series_vec.push(Series::from_vec(feature.into(), feature_vector));
+                metadata.insert(feature.to_string(), degraded(ColumnSemantics::Estimate));
             },
             ObjectPoint::ObjectEventInteractionOperator => {
                 let feature_values: Vec<(usize, f64)> = entity_order.par_iter()
                                                             .enumerate()
                                                             .map(|(index, log_oid)| {
-                                                                (index, object_average_event_interaction(config.ocel, log_oid))
+                                                                (index, average_event_interaction(config.ocel, &lifecycle(log_oid)))
                                                             })
                                                             .collect();
 
                 let mut feature_vector: Vec<f64> = Vec::with_capacity(feature_values.len());
                 feature_values.iter().for_each(|(index, value)| feature_vector.insert(*index, *value));
                 series_vec.push(Series::from_vec(feature.into(), feature_vector));
+                metadata.insert(feature.to_string(), degraded(ColumnSemantics::Estimate));
             }
             _ => {}
         }
     }
-    DataFrame::new(series_vec).unwrap()
+    (DataFrame::new(series_vec).unwrap(), metadata)
 }
 
 pub fn unique_neighbor_count(ocdg: &Ocdg, oid: &usize) -> usize {
@@ -147,20 +202,28 @@ pub fn unique_neighbor_count(ocdg: &Ocdg, oid: &usize) -> usize {
 }
 
 pub fn activity_existence(log: &Ocel, oid: usize) -> Vec<u8> {
-    let oe_activities: AHashSet<&String> = AHashSet::from_iter(log.objects[&oid].events.iter()
-                                            .map(|oe| &log.events[&oe].activity));
+    activities_present(log, &log.objects[&oid].events)
+}
+
+fn activities_present(log: &Ocel, events: &[usize]) -> Vec<u8> {
+    let oe_activities: AHashSet<&str> = AHashSet::from_iter(events.iter()
+                                            .map(|oe| log.events.event(oe).activity));
     log.activities.iter()
-                   .map(|act| {if oe_activities.contains(act) {1} else {0}})
+                   .map(|act| {if oe_activities.contains(act.as_str()) {1} else {0}})
                    .collect_vec()
 }
 
 
 pub fn activity_existence_count(log: &Ocel, oid: usize) -> Vec<usize> {
-    let oe_activities: HashMap<&String, usize> = log.objects[&oid].events.iter()
-                                                                         .map(|oe| &log.events[&oe].activity)
-                                                                         .counts();
+    activity_counts(log, &log.objects[&oid].events)
+}
+
+fn activity_counts(log: &Ocel, events: &[usize]) -> Vec<usize> {
+    let oe_activities: HashMap<&str, usize> = events.iter()
+                                                                      .map(|oe| log.events.event(oe).activity)
+                                                                      .counts();
     log.activities.iter()
-              .map(|act| {match oe_activities.get(act) {
+              .map(|act| {match oe_activities.get(act.as_str()) {
                             Some(v) => *v,
                             None => 0
                         }})
@@ -169,8 +232,8 @@ pub fn activity_existence_count(log: &Ocel, oid: usize) -> Vec<usize> {
 
 pub fn activity_value_operator(log: &Ocel, oid: usize, attr: String, op: Operator) -> f64 {
     op.execute(log.objects[&oid].events.iter()
-                            .filter(|oe| !log.events[&oe].vmap.contains_key(&attr))
-                            .map(|oe| match &log.events[&oe].vmap[&attr] {
+                            .filter(|oe| !log.events.event(&oe).vmap.contains_key(&attr))
+                            .map(|oe| match &log.events.event(&oe).vmap[&attr] {
                                         Value::Number(v) => v.as_f64().unwrap(),
                                         _ => 0.0
                                     })).unwrap()
@@ -185,18 +248,22 @@ pub fn object_lifetime(log: &Ocel, oid: &usize) -> Duration {
         let end = node.events.last().unwrap();
 
         if log.events.contains_key(&initial) && log.events.contains_key(&end) {
-            return log.events[&end].timestamp - log.events[&initial].timestamp;
+            return log.events.event(&end).timestamp - log.events.event(&initial).timestamp;
         }
     }
     Duration::zero()
 }
 
 pub fn object_unit_set_ratio(log: &Ocel, oid: &usize) -> f64 {
-    if let Some(node) = log.objects.get(oid) {
-        let unitset = node.events.iter()
+    log.objects.get(oid).map_or(0.0, |node| unit_set_ratio(log, oid, &node.events))
+}
+
+fn unit_set_ratio(log: &Ocel, oid: &usize, events: &[usize]) -> f64 {
+    if !events.is_empty() {
+        let unitset = events.iter()
                           .map(|ev| {
                               if log.events.contains_key(ev) {
-                                  for oid2 in &log.events[ev].omap {
+                                  for oid2 in &log.events.event(ev).omap {
                                         if oid != oid2 && log.objects[oid].obj_type == log.objects[oid2].obj_type {
                                             return 0;
                                         }
@@ -207,22 +274,27 @@ pub fn object_unit_set_ratio(log: &Ocel, oid: &usize) -> f64 {
                               1
                           }).fold(0, |accum, item| accum + item);
 
-        return unitset as f64 / node.events.len() as f64
+        return unitset as f64 / events.len() as f64
     }
     0.0
 }
 
 pub fn object_average_event_interaction(log: &Ocel, oid: &usize) -> f64 {
-    if let Some(node) = log.objects.get(oid) {
-        let interaction = node.events.iter()
+    log.objects.get(oid).map_or(0.0, |node| average_event_interaction(log, &node.events))
+}
+
+fn average_event_interaction(log: &Ocel, events: &[usize]) -> f64 {
+    if !events.is_empty() {
+        let interaction = events.iter()
                                      .map(|ev| {
                                         if log.events.contains_key(ev) {
-                                            return log.events[ev].omap.len() - 1;
+                                            // an omap without the object itself counts as no interaction
+                                            return log.events.event(ev).omap.len().saturating_sub(1);
                                         }
                                         0})
                                      .fold(0, |accum, item| accum + item);
 
-        return interaction as f64 / node.events.len() as f64
+        return interaction as f64 / events.len() as f64
 
     }
     0.0
@@ -245,8 +317,8 @@ pub fn object_events_directly_follows(log: &Ocel, oid: &usize) -> AHashMap<Strin
     if let Some(obj) = log.objects.get(oid) {
         (0..obj.events.len() - 1).into_iter()
                                  .for_each(|i| {
-                                    let src = &log.events[&obj.events[i]].activity;
-                                    let tar = &log.events[&obj.events[i+1]].activity;
+                                    let src = log.events.event(&obj.events[i]).activity;
+                                    let tar = log.events.event(&obj.events[i+1]).activity;
                                     let df_srctar = df.entry(src.to_owned())
                                       .or_insert(AHashMap::default())
                                       .entry(tar.to_owned())
@@ -274,7 +346,7 @@ pub fn object_wait_time(log: &Ocel, oid: &usize, act1: &str, act2: &str) -> Dura
                         ev1 = *item;
                     }
                 } else if time_diff == Duration::zero() {
-                    time_diff = log.events[&ev2].timestamp - log.events[&ev1].timestamp;
+                    time_diff = log.events.event(&ev2).timestamp - log.events.event(&ev1).timestamp;
                 }
             }
         });
@@ -285,11 +357,11 @@ pub fn object_wait_time(log: &Ocel, oid: &usize, act1: &str, act2: &str) -> Dura
 pub fn object_oe_root(log: &Ocel, oid: &usize) -> bool {
     if let Some(obj) = log.objects.get(oid) {
         if let Some(root_ev) = obj.events.first() {
-            let root1 = &log.events[root_ev];
+            let root1 = log.events.event(root_ev);
             for oid2 in root1.omap.iter() {
                 if let Some(other) = log.objects.get(&oid2) {
                     if let Some(root_ev2) = other.events.first() {
-                        let root2 = &log.events[root_ev2];
+                        let root2 = log.events.event(root_ev2);
                         if root1.timestamp > root2.timestamp {
                             return false;
                         }
@@ -311,11 +383,11 @@ pub fn object_oe_root(log: &Ocel, oid: &usize) -> bool {
 pub fn object_oe_leaf(log: &Ocel, oid: &usize) -> bool {
     if let Some(obj) = log.objects.get(oid) {
         if let Some(leaf_ev) = obj.events.last() {
-            let leaf1 = &log.events[leaf_ev];
+            let leaf1 = log.events.event(leaf_ev);
             for oid2 in leaf1.omap.iter() {
                 if let Some(other) = log.objects.get(&oid2) {
                     if let Some(leaf_ev2) = other.events.last() {
-                        let leaf2 = &log.events[leaf_ev2];
+                        let leaf2 = log.events.event(leaf_ev2);
                         if leaf1.timestamp < leaf2.timestamp {
                             return false;
                         }
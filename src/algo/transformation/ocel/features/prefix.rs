@@ -0,0 +1,16 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use polars::prelude::DataFrame;
+use serde_json::Value;
+
+use crate::objects::ocdg::{generate_ocdg_with, Evidence, OcdgError, Relations};
+use crate::objects::ocel::Ocel;
+use super::object_point::{object_point_features, LifecycleView, ObjectPoint, ObjectPointConfig};
+
+// object features as they could have been computed at the cutoff, the graph is generated from the prefix log
+// without event sets since none of the object features read them
+pub fn as_of(ocel: &Ocel, relations: &[Relations], cutoff: &DateTime<Utc>, params: &HashMap<ObjectPoint, Option<Value>>) -> Result<DataFrame, OcdgError> {
+    let prefix = ocel.prefix(cutoff);
+    let ocdg = generate_ocdg_with(&prefix, relations, Evidence::None)?;
+    Ok(object_point_features(ObjectPointConfig { ocel: &prefix, ocdg: &ocdg, params, lifecycle_view: LifecycleView::Full }))
+}
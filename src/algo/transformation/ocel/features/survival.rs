@@ -0,0 +1,118 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+use chrono::{DateTime, Utc};
+use petgraph::Direction;
+use polars::prelude::{DataFrame, NamedFromOwned, Series};
+use serde_json::Value;
+
+use crate::objects::ocdg::{Evidence, Ocdg, Relations};
+use crate::objects::ocel::Ocel;
+
+// one object of the source type, at risk from its first event until it forms the relation or the observation ends
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurvivalRow {
+    pub oid: usize,
+    pub object: String,
+    pub birth: DateTime<Utc>,
+    // seconds from the birth to the first event of the relation, or to the end of the observation when censored
+    pub duration: f64,
+    // false for censored objects, the relation was not formed by the end of the observation
+    pub observed: bool,
+    // in the order of SurvivalTable::covariates
+    pub covariates: Vec<f64>
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurvivalTable {
+    // birth_objects: the objects of the first event besides the object itself, then the numeric object attributes by name
+    pub covariates: Vec<String>,
+    // the censor time or the last timestamp of the log, whichever comes first
+    pub observation_end: DateTime<Utc>,
+    // sorted by object label
+    pub rows: Vec<SurvivalRow>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SurvivalError {
+    // the times of the relations are read from their supporting events
+    MissingEvidence(Evidence),
+    EmptyLog
+}
+
+impl fmt::Display for SurvivalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SurvivalError::MissingEvidence(evidence) => write!(f, "The graph keeps {:?} evidence, the relation times need the events of Evidence::Full", evidence),
+            SurvivalError::EmptyLog => write!(f, "The log has no events to end the observation at")
+        }
+    }
+}
+
+impl Error for SurvivalError {}
+
+// the time until every object of the source type first points at another object with the relation.
+// the observation ends at the censor time or at the end of the log if it comes earlier, relations formed later are censored
+// like objects never forming it, and objects born after the end are left out as they were never at risk
+pub fn build(ocel: &Ocel, ocdg: &Ocdg, source_type: &str, relation: Relations, censor_time: Option<DateTime<Utc>>) -> Result<SurvivalTable, SurvivalError> {
+    if ocdg.evidence != Evidence::Full {
+        return Err(SurvivalError::MissingEvidence(ocdg.evidence));
+    }
+    let log_end = ocel.events.values().map(|event| event.timestamp).max().ok_or(SurvivalError::EmptyLog)?;
+    let observation_end = censor_time.map_or(log_end, |censor| censor.min(log_end));
+
+    let mut sources: Vec<(&String, usize)> = ocel.object_map.iter()
+                                                 .filter(|(_, oid)| ocel.objects.get(oid).is_some_and(|obj| obj.obj_type == source_type))
+                                                 .map(|(label, oid)| (label, *oid))
+                                                 .collect();
+    sources.sort();
+    // only the attributes of the sources, numbers written as text are not numeric
+    let attributes: BTreeSet<&String> = sources.iter()
+                                               .flat_map(|(_, oid)| ocel.objects[oid].ovmap.iter())
+                                               .filter(|(_, value)| value.is_number())
+                                               .map(|(name, _)| name)
+                                               .collect();
+    let mut covariates: Vec<String> = vec!["birth_objects".to_owned()];
+    covariates.extend(attributes.iter().map(|name| name.to_string()));
+
+    let mut rows: Vec<SurvivalRow> = vec![];
+    for (label, oid) in sources {
+        let obj = &ocel.objects[&oid];
+        let Some(first) = obj.events.iter().filter_map(|eid| ocel.events.get(eid)).min_by_key(|event| event.timestamp) else { continue };
+        let birth = first.timestamp;
+        if birth > observation_end {
+            continue;
+        }
+
+        let formed = ocdg.neighbors_with_relation_iter(oid, relation, Direction::Outgoing)
+                         .filter_map(|tar| ocdg.relation_events(oid, tar, relation))
+                         .flatten()
+                         .filter_map(|eid| ocel.events.get(eid))
+                         .map(|event| event.timestamp)
+                         .min();
+        let (end, observed) = match formed {
+            Some(formed) if formed <= observation_end => (formed.max(birth), true),
+            _ => (observation_end, false)
+        };
+
+        let mut row_covariates = vec![first.omap.len().saturating_sub(1) as f64];
+        row_covariates.extend(attributes.iter().map(|name| obj.ovmap.get(*name).and_then(Value::as_f64).unwrap_or(f64::NAN)));
+        rows.push(SurvivalRow { oid, object: label.to_owned(), birth, duration: (end - birth).num_milliseconds() as f64 / 1000.0, observed, covariates: row_covariates });
+    }
+    Ok(SurvivalTable { covariates, observation_end, rows })
+}
+
+impl SurvivalTable {
+    // an "oids" column of object labels, "duration" in seconds, "observed" as 1 or 0 and one column per covariate
+    pub fn to_dataframe(&self) -> DataFrame {
+        let mut oids: Series = self.rows.iter().map(|row| row.object.to_owned()).collect();
+        oids.rename("oids");
+        let mut series_vec: Vec<Series> = vec![oids,
+                                               Series::from_vec("duration", self.rows.iter().map(|row| row.duration).collect::<Vec<f64>>()),
+                                               Series::from_vec("observed", self.rows.iter().map(|row| u8::from(row.observed)).collect::<Vec<u8>>())];
+        for (index, name) in self.covariates.iter().enumerate() {
+            series_vec.push(Series::from_vec(name, self.rows.iter().map(|row| row.covariates[index]).collect::<Vec<f64>>()));
+        }
+        DataFrame::new(series_vec).expect("Every column has a row per object")
+    }
+}
@@ -1,2 +1,39 @@
 pub mod objects;
 pub mod algo;
+pub mod pipeline;
+#[cfg(feature = "service")]
+pub mod service;
+/// The types and entry points most programs need, `use pmrs::prelude::*;` replaces the deep module paths.
+///
+/// Import a log, generate its graph with one of the relation presets and analyse it:
+///
+/// ```
+/// use pmrs::prelude::*;
+///
+/// let log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+/// let config = GenerationConfig { relations: RelationSet::preset(Preset::Lineage).into_vec(), ..Default::default() };
+/// let ocdg: Ocdg = generate_ocdg_config(&log, &config).unwrap();
+/// assert_eq!(ocdg.net.node_count(), log.objects.len());
+///
+/// let i2 = *log.object_map.get_by_left("i2").unwrap();
+/// let i1 = *log.object_map.get_by_left("i1").unwrap();
+/// assert!(trace_pair(&log, i2, i1, Relations::INHERITANCE).holds());
+/// ```
+///
+/// The graph and the log are written with the exporters of the prelude:
+///
+/// ```
+/// use pmrs::prelude::*;
+///
+/// let log: Ocel = import_ocel("logs/three-days.jsonocel").unwrap();
+/// let ocdg: Ocdg = generate_ocdg(&log, &[Relations::INTERACTS, Relations::COBIRTH]).unwrap();
+/// let mut graphml: Vec<u8> = vec![];
+/// export_ocdg_graphml_to(&ocdg, &mut graphml, &ExportOptions::default()).unwrap();
+/// assert!(String::from_utf8(graphml).unwrap().contains("<graphml"));
+/// assert!(!canonical_edges(&ocdg).is_empty());
+/// ```
+pub mod prelude;
+
+// stable paths for the analyses and the feature extraction
+pub use crate::algo::analysis;
+pub use crate::algo::transformation::ocel::features;
@@ -1,6 +1,7 @@
 use clap::load_yaml;
 use clap::App;
 use pmrs::objects::ocel::validator::{validate_ocel, validate_ocel_verbose};
+use pmrs::pipeline;
 
 
 fn main() {
@@ -34,4 +35,32 @@ fn main() {
             println!("Error: {} file format is not supported.", input_file);
         }
     }
+
+    if let Some(run_matches) = matches.subcommand_matches("run") {
+        let config_file = run_matches.value_of("CONFIG").unwrap();
+        let (config, base) = match pipeline::load_with_preset(config_file, run_matches.value_of("preset")) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                println!("There was an Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+        if run_matches.is_present("estimate") {
+            match pipeline::estimate_config(&config, base) {
+                Ok((report, estimate)) => print!("{}{}", report, estimate),
+                Err(e) => {
+                    println!("There was an Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        match pipeline::run_config(&config, base) {
+            Ok(report) => print!("{}", report),
+            Err(e) => {
+                println!("There was an Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
 }
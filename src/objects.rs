@@ -1,3 +1,4 @@
 pub mod ocel;
 pub mod ocdg;
 pub mod linker;
+pub mod exporter;
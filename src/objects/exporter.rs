@@ -0,0 +1,168 @@
+pub mod metrics;
+
+pub use self::metrics::export_metrics;
+
+use std::{error::Error, fmt, fs::{self, File, OpenOptions}, io::{self, BufWriter, Write}, path::{Path, PathBuf}};
+
+#[derive(Debug)]
+pub struct ExportError {
+    pub file_path: String,
+    pub bytes_written: u64,
+    pub source: Box<dyn Error>
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Export to {} failed after {} bytes: {}", self.file_path, self.bytes_written, self.source)
+    }
+}
+
+impl Error for ExportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+pub trait Exporter {
+    fn write_content(&self, f: &mut dyn Write) -> Result<(), Box<dyn Error>>;
+
+    // writes next to the target and only renames once everything is on disk
+    fn export(&self, file_path: &str) -> Result<u64, ExportError> {
+        let partial = partial_path(file_path);
+        let mut writer = match File::create(&partial) {
+            Ok(file) => CountingWriter::new(BufWriter::new(file)),
+            Err(e) => return Err(export_error(file_path, 0, e.into()))
+        };
+
+        let written = self.write_content(&mut writer).and_then(|_| finish(writer.inner, &partial, file_path).map_err(|e| e.into()));
+        match written {
+            Ok(_) => Ok(writer.count),
+            Err(e) => {
+                let _ = fs::remove_file(&partial);
+                Err(export_error(file_path, writer.count, e))
+            }
+        }
+    }
+
+    // the same content into a writer the caller owns, the error names the writer instead of a file
+    fn write_to(&self, f: &mut dyn Write) -> Result<u64, ExportError> {
+        let mut writer = CountingWriter::new(f);
+        match self.write_content(&mut writer).and_then(|_| writer.flush().map_err(|e| e.into())) {
+            Ok(_) => Ok(writer.count),
+            Err(e) => Err(export_error("the writer", writer.count, e))
+        }
+    }
+}
+
+pub trait StreamingExporter {
+    fn chunk_count(&self) -> usize;
+    fn write_chunk(&self, index: usize, f: &mut dyn Write) -> Result<(), Box<dyn Error>>;
+
+    fn export(&self, file_path: &str) -> Result<u64, ExportError> {
+        let _ = fs::remove_file(manifest_path(file_path));
+        self.export_resume(file_path)
+    }
+
+    // keeps the partial file and a manifest on failure, a later call continues after the last complete chunk
+    fn export_resume(&self, file_path: &str) -> Result<u64, ExportError> {
+        let partial = partial_path(file_path);
+        let manifest = manifest_path(file_path);
+        let (mut next_chunk, offset) = match read_manifest(&manifest) {
+            Some(state) if partial.exists() => state,
+            _ => (0, 0)
+        };
+
+        let file = OpenOptions::new().create(true).write(true).truncate(false).open(&partial)
+                                     .and_then(|file| file.set_len(offset).map(|_| file))
+                                     .and_then(|mut file| io::Seek::seek(&mut file, io::SeekFrom::Start(offset)).map(|_| file));
+        let mut writer = match file {
+            Ok(file) => CountingWriter::new(BufWriter::new(file)),
+            Err(e) => return Err(export_error(file_path, offset, e.into()))
+        };
+        writer.count = offset;
+
+        while next_chunk < self.chunk_count() {
+            let written = self.write_chunk(next_chunk, &mut writer)
+                              .and_then(|_| writer.flush().map_err(|e| e.into()))
+                              .and_then(|_| write_manifest(&manifest, next_chunk + 1, writer.count).map_err(|e| e.into()));
+            if let Err(e) = written {
+                return Err(export_error(file_path, writer.count, e));
+            }
+            next_chunk += 1;
+        }
+
+        match finish(writer.inner, &partial, file_path) {
+            Ok(_) => {
+                let _ = fs::remove_file(&manifest);
+                Ok(writer.count)
+            },
+            Err(e) => Err(export_error(file_path, writer.count, e.into()))
+        }
+    }
+}
+
+impl Exporter for serde_json::Value {
+    fn write_content(&self, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer(f, self)?;
+        Ok(())
+    }
+}
+
+pub fn partial_path(file_path: &str) -> PathBuf {
+    PathBuf::from(format!("{}.partial", file_path))
+}
+
+pub fn manifest_path(file_path: &str) -> PathBuf {
+    PathBuf::from(format!("{}.partial.manifest", file_path))
+}
+
+struct CountingWriter<W: Write> {
+    inner: W,
+    count: u64
+}
+
+impl<W: Write> CountingWriter<W> {
+    fn new(inner: W) -> Self {
+        Self { inner, count: 0 }
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn finish(writer: BufWriter<File>, partial: &Path, file_path: &str) -> io::Result<()> {
+    let file = writer.into_inner().map_err(|e| e.into_error())?;
+    file.sync_all()?;
+    fs::rename(partial, file_path)
+}
+
+fn read_manifest(manifest: &Path) -> Option<(usize, u64)> {
+    let content = fs::read_to_string(manifest).ok()?;
+    let mut chunks = None;
+    let mut bytes = None;
+    for line in content.lines() {
+        match line.split_once('=') {
+            Some(("chunks", value)) => chunks = value.parse().ok(),
+            Some(("bytes", value)) => bytes = value.parse().ok(),
+            _ => {}
+        }
+    }
+    Some((chunks?, bytes?))
+}
+
+fn write_manifest(manifest: &Path, chunks: usize, bytes: u64) -> io::Result<()> {
+    fs::write(manifest, format!("chunks={}\nbytes={}\n", chunks, bytes))
+}
+
+fn export_error(file_path: &str, bytes_written: u64, source: Box<dyn Error>) -> ExportError {
+    ExportError { file_path: file_path.to_owned(), bytes_written, source }
+}
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::Write;
+use strum::IntoEnumIterator;
+
+use crate::objects::exporter::Exporter;
+use crate::objects::ocdg::{Ocdg, Relations};
+use crate::objects::ocel::Ocel;
+
+const QUANTILES: [f64; 3] = [0.5, 0.9, 0.99];
+
+pub struct OcelMetrics<'a> {
+    pub ocel: &'a Ocel,
+    pub ocdg: &'a Ocdg
+}
+
+impl<'a> Exporter for OcelMetrics<'a> {
+    fn write_content(&self, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        export_metrics(self.ocel, self.ocdg, f)
+    }
+}
+
+// OpenMetrics text exposition, families and label values are sorted so that scrapes diff cleanly
+pub fn export_metrics(ocel: &Ocel, ocdg: &Ocdg, writer: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    let mut events: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut omap_sizes: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+    for event in ocel.events.values() {
+        *events.entry(event.activity).or_default() += 1;
+        omap_sizes.entry(event.activity).or_default().push(event.omap.len() as f64);
+    }
+    let mut objects: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut lifetimes: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+    for obj in ocel.objects.values() {
+        *objects.entry(&obj.obj_type).or_default() += 1;
+        let timestamps: Vec<_> = obj.events.iter().filter_map(|eid| ocel.events.get(eid)).map(|event| event.timestamp).collect();
+        if let (Some(first), Some(last)) = (timestamps.iter().min(), timestamps.iter().max()) {
+            lifetimes.entry(&obj.obj_type).or_default().push((*last - *first).num_milliseconds() as f64 / 1000.0);
+        }
+    }
+    let edge_counts = ocdg.relation_instance_counts();
+
+    writeln!(writer, "# TYPE pmrs_events counter")?;
+    writeln!(writer, "# HELP pmrs_events Events of the log per activity.")?;
+    for (activity, count) in &events {
+        writeln!(writer, "pmrs_events_total{{activity=\"{}\"}} {}", escape_label(activity), count)?;
+    }
+    writeln!(writer, "# TYPE pmrs_objects counter")?;
+    writeln!(writer, "# HELP pmrs_objects Objects of the log per type.")?;
+    for (object_type, count) in &objects {
+        writeln!(writer, "pmrs_objects_total{{type=\"{}\"}} {}", escape_label(object_type), count)?;
+    }
+    writeln!(writer, "# TYPE pmrs_relation_edges gauge")?;
+    writeln!(writer, "# HELP pmrs_relation_edges Relation instances of the graph per relation.")?;
+    for relation in Relations::iter() {
+        writeln!(writer, "pmrs_relation_edges{{relation=\"{}\"}} {}", relation, edge_counts.get(&relation.to_string()).copied().unwrap_or(0))?;
+    }
+    writeln!(writer, "# TYPE pmrs_object_lifetime_seconds summary")?;
+    writeln!(writer, "# UNIT pmrs_object_lifetime_seconds seconds")?;
+    writeln!(writer, "# HELP pmrs_object_lifetime_seconds Time between the first and last event of the objects per type.")?;
+    write_summary(writer, "pmrs_object_lifetime_seconds", "type", &mut lifetimes)?;
+    writeln!(writer, "# TYPE pmrs_event_objects summary")?;
+    writeln!(writer, "# HELP pmrs_event_objects Objects per event per activity.")?;
+    write_summary(writer, "pmrs_event_objects", "activity", &mut omap_sizes)?;
+    writeln!(writer, "# EOF")?;
+    Ok(())
+}
+
+// nearest rank quantiles
+fn write_summary(writer: &mut dyn Write, name: &str, label: &str, values: &mut BTreeMap<&str, Vec<f64>>) -> Result<(), Box<dyn Error>> {
+    for (key, samples) in values.iter_mut() {
+        samples.sort_by(|a, b| a.partial_cmp(b).expect("Samples are never NaN"));
+        let key = escape_label(key);
+        for quantile in QUANTILES {
+            let rank = ((samples.len() as f64 * quantile).ceil() as usize).max(1);
+            writeln!(writer, "{}{{{}=\"{}\",quantile=\"{}\"}} {}", name, label, key, quantile, samples[rank - 1])?;
+        }
+        writeln!(writer, "{}_sum{{{}=\"{}\"}} {}", name, label, key, samples.iter().sum::<f64>())?;
+        writeln!(writer, "{}_count{{{}=\"{}\"}} {}", name, label, key, samples.len())?;
+    }
+    Ok(())
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -2,21 +2,49 @@ pub(crate) mod variants;
 pub mod importer;
 pub mod exporter;
 pub(crate) mod generation;
+pub mod density;
+pub mod contraction;
+pub mod components;
+pub(crate) mod metadata;
+pub mod similarity;
+pub mod layers;
+pub mod coverage;
+pub mod series;
+pub mod metrics;
+pub mod stream;
+pub mod estimate;
+pub mod skeleton;
+pub mod presets;
+pub(crate) mod json;
+pub mod query;
+pub mod fingerprint;
+pub(crate) mod birth;
 
-use std::{collections::hash_map::Entry, vec, fmt};
-use ahash::AHashSet;
+use std::{cmp::Ordering, collections::{hash_map::Entry, BTreeMap, BTreeSet, VecDeque}, error::Error, vec, fmt, sync::OnceLock, time::Instant};
+use ahash::{AHashMap, AHashSet};
 use bimap::BiMap;
+use itertools::Either;
+use petgraph::Direction;
 use petgraph::graph::{DiGraph, NodeIndex, EdgeIndex};
 use nohash_hasher::{IntSet, IntMap};
 use array_tool::vec::Intersect;
 use rayon::prelude::*;
 use num_enum::{TryFromPrimitive, IntoPrimitive};
 use strum::{EnumIter, EnumString};
+use chrono::{DateTime, Duration, Utc};
+use static_assertions::assert_impl_all;
+use serde::{Serialize, Deserialize};
 
 use super::ocel::Ocel;
+use super::ocel::hierarchy::{TypeHierarchy, TypeMatching};
+use super::ocel::dense::{DenseEvents, EventBitSet};
 
+// the items of the internal modules that belong to the graph api
+pub use self::birth::{attribute_timestamp, birth_quality, Birth, BirthQuality, EarlyEvents};
+pub use self::metadata::{log_fingerprint, GenerationMetadata, GenerationPhase, GenerationReport};
 
-#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, TryFromPrimitive, IntoPrimitive, EnumIter, EnumString)]
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, TryFromPrimitive, IntoPrimitive, EnumIter, EnumString, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum Relations {
     INTERACTS = 0,
@@ -30,7 +58,9 @@ pub enum Relations {
     MERGE = 8,
     MINION = 9,
     PEELER = 10,
-    ENGAGES = 11
+    ENGAGES = 11,
+    REACHABLE = 12,
+    OVERLAPS = 13
 }
 
 impl fmt::Display for Relations {
@@ -43,6 +73,8 @@ impl Relations {
     fn relation_type(&self) -> u8 {
         match self {
             Relations::SPLIT => 1,
+            // computed from the DESCENDANTS edges once all objects are evaluated
+            Relations::REACHABLE => 4,
             _ => {2}
             
         }
@@ -61,169 +93,491 @@ impl Relations {
             Relations::MERGE => 8,
             Relations::MINION => 9,
             Relations::PEELER => 10,
-            Relations:: ENGAGES => 11
+            Relations:: ENGAGES => 11,
+            Relations::REACHABLE => 12,
+            Relations::OVERLAPS => 13
         }
     }
 
+    pub fn from_index(index: usize) -> Option<Relations> {
+        u8::try_from(index).ok().and_then(|i| Relations::try_from(i).ok())
+    }
+
     
-    fn execute_whole(&self, log: &Ocel, ocdg: &Ocdg, neighs: &IntMap<usize, IntSet<usize>>, oid1: usize) -> Vec<(usize, usize, EventAdd, Relations)> {
+    fn execute_whole(&self, log: &Ocel, config: &GenerationConfig, oid1: usize) -> Vec<(usize, usize, EventAdd, Relations)> {
         let mut to_add: Vec<(usize, usize, EventAdd, Relations)> = Vec::new();
-        let src_oe = &log.objects.get(&oid1).unwrap().events;
-        let src_type = &ocdg.node_attributes.get(&oid1).unwrap().node_type;
-            match self {
-                Relations::SPLIT => {
-                    let mut conforming_oid: IntSet<usize> = IntSet::default();
-                    let src_e = src_oe.last().unwrap();
-                    for oid2 in neighs.get(&oid1).unwrap() {
-                        let neigh_oe = &log.objects.get(&oid2).unwrap().events;
-                        let neigh_type = &ocdg.node_attributes.get(&oid2).unwrap().node_type;
-                        if src_type == neigh_type && src_e == neigh_oe.first().unwrap() {
-                            conforming_oid.insert(*oid2);
-                        }
-                        
-                    }
-                    if conforming_oid.len() > 1 {
-                        for oid2 in &conforming_oid {
-                            to_add.push((oid1, *oid2, EventAdd::SINGLE(*src_e), Relations::SPLIT));
+        if *self == Relations::SPLIT {
+            let (src_e, conforming_oid) = split_conforming(log, config, oid1);
+            if conforming_oid.len() > 1 {
+                for oid2 in conforming_oid.iter().filter(|oid2| **oid2 != oid1) {
+                    to_add.push((oid1, *oid2, EventAdd::SINGLE(src_e), Relations::SPLIT));
+                }
+            }
+        }
+        to_add
+    }
 
-                        }
-                    }
-                },
-                _ => {},
+
+    // follows the DESCENDANTS chains (source -> [(target, event)]) from the object. every object reached over two or more
+    // DESCENDANTS edges gets an edge holding the events of all chains that reach it, direct descendants get none
+    fn execute_closure(&self, chains: &IntMap<usize, Vec<(usize, usize)>>, oid1: usize) -> Vec<(usize, usize, EventAdd, Relations)> {
+        let mut to_add: Vec<(usize, usize, EventAdd, Relations)> = Vec::new();
+        if *self != Relations::REACHABLE {
+            return to_add;
+        }
+        let direct: IntSet<usize> = chains.get(&oid1).into_iter().flatten().map(|(tar, _)| *tar).collect();
+        // object -> the events of the chains from the source to it. an object is visited again only when its events grew,
+        // so the propagation ends on cycles as well. a DESCENDANTS chain never returns to an earlier object in a time ordered log
+        let mut reached: IntMap<usize, IntSet<usize>> = IntMap::default();
+        let mut queued: IntSet<usize> = IntSet::from_iter([oid1]);
+        let mut worklist: VecDeque<usize> = VecDeque::from([oid1]);
+        while let Some(oid) = worklist.pop_front() {
+            queued.remove(&oid);
+            let events = reached.get(&oid).cloned().unwrap_or_default();
+            for (tar, eid) in chains.get(&oid).into_iter().flatten().filter(|(tar, _)| *tar != oid1) {
+                let tar_events = reached.entry(*tar).or_default();
+                let known = tar_events.len();
+                tar_events.extend(&events);
+                tar_events.insert(*eid);
+                if tar_events.len() > known && queued.insert(*tar) {
+                    worklist.push_back(*tar);
+                }
             }
-            to_add
         }
+        let mut targets: Vec<usize> = reached.keys().copied().filter(|tar| !direct.contains(tar)).collect();
+        targets.sort_unstable();
+        for tar in targets {
+            let events = reached.remove(&tar).expect("Every target was reached");
+            to_add.push((oid1, tar, EventAdd::MULTI(events), Relations::REACHABLE));
+        }
+        to_add
+    }
 
+    fn uses_shared_events(&self) -> bool {
+        matches!(self, Relations::INTERACTS | Relations::MINION | Relations::PEELER | Relations::ENGAGES | Relations::OVERLAPS)
+    }
+
+    pub(crate) fn is_symmetric(&self) -> bool {
+        matches!(self, Relations::INTERACTS | Relations::COLIFE | Relations::COBIRTH | Relations::CODEATH | Relations::PEELER | Relations::ENGAGES | Relations::OVERLAPS)
+    }
 
-    fn execute(&self, log: &Ocel, ocdg: &Ocdg, oid1: usize, oid2: usize) -> Vec<(usize, usize, EventAdd, Relations)> {
+    fn execute(&self, log: &Ocel, config: &GenerationConfig, dense: &DenseLifecycles, oid1: usize, oid2: usize) -> Vec<(usize, usize, EventAdd, Relations)> {
         let mut to_add: Vec<(usize, usize, EventAdd, Relations)> = Vec::new();
+        // symmetric relations are only evaluated once per pair
+        if self.is_symmetric() && oid1 > oid2 {
+            return to_add;
+        }
+
+        if let Some(eids) = self.evidence(log, config, Some(dense), oid1, oid2, &mut Tracer::silent()) {
+            if self.is_symmetric() {
+                to_add.push((oid1, oid2, eids.clone(), *self));
+                to_add.push((oid2, oid1, eids, *self));
+            } else {
+                to_add.push((oid1, oid2, eids, *self));
+            }
+        }
+        to_add
+    }
+
+    // the dense lifecycles are only built during generation, tracing works on the log directly
+    fn evidence(&self, log: &Ocel, config: &GenerationConfig, dense: Option<&DenseLifecycles>, oid1: usize, oid2: usize, tracer: &mut Tracer) -> Option<EventAdd> {
         let src_oe = &log.objects.get(&oid1).unwrap().events;
         let tar_oe = &log.objects.get(&oid2).unwrap().events;
-        let src_type = &ocdg.node_attributes.get(&oid1).unwrap().node_type;
-        let tar_type = &ocdg.node_attributes.get(&oid2).unwrap().node_type;
-        
+        let src_type = &log.objects.get(&oid1).unwrap().obj_type;
+        let tar_type = &log.objects.get(&oid2).unwrap().obj_type;
+        let ev = |eid: &usize| event_label(log, eid);
+
+        let overlap: (Vec<usize>, Vec<usize>);
+        let (src_oe, tar_oe, dense) = if config.scope == RelationScope::OverlapOnly && self.uses_shared_events() {
+            overlap = overlap_lifecycles(log, src_oe, tar_oe);
+            if !tracer.check(!overlap.0.is_empty() && !overlap.1.is_empty(), || format!("source has {} and target has {} events in the overlap window", overlap.0.len(), overlap.1.len())) {
+                return None;
+            }
+            (&overlap.0, &overlap.1, None)
+        } else {
+            (src_oe, tar_oe, dense)
+        };
+
         match self {
             Relations::INTERACTS => {
-                if oid1 < oid2 {
-                    let e_set = intersection_count_sorted_vec(src_oe, tar_oe);
-                    to_add.push((oid1, oid2, EventAdd::MULTI(e_set.to_owned()), Relations::INTERACTS));
-                    to_add.push((oid2, oid1, EventAdd::MULTI(e_set), Relations::INTERACTS));
+                let e_set = match dense {
+                    Some(dense) => dense.shared(oid1, oid2),
+                    // lifecycles are not necessarily sorted by id outside of generation
+                    None => IntSet::from_iter(src_oe.intersect(tar_oe.to_vec()))
+                };
+                if tracer.check(!e_set.is_empty(), || format!("the objects share {} events", e_set.len())) {
+                    return Some(EventAdd::MULTI(e_set));
                 }
             },
             Relations::DESCENDANTS => {
-                if (src_oe[0] < tar_oe[0]) && src_oe.contains(&tar_oe[0]) {
-                    to_add.push((oid1, oid2, EventAdd::SINGLE(tar_oe[0]), Relations::DESCENDANTS));
-                }
-            },
-            Relations::COLIFE => { // one time
-                if oid1 < oid2 && src_oe == tar_oe {
-                    let e_set: IntSet<usize> = IntSet::from_iter(src_oe.to_owned());
-                    to_add.push((oid1, oid2, EventAdd::MULTI(e_set.to_owned()), Relations::COLIFE));
-                    to_add.push((oid2, oid1, EventAdd::MULTI(e_set), Relations::COLIFE));
+                let (src_birth, tar_birth) = (config.birth_source.birth(log, oid1), config.birth_source.birth(log, oid2));
+                // the first events are compared by id as they always were, declared births by their time
+                let born_before = match config.birth_source {
+                    BirthSource::FirstEvent => src_birth.event < tar_birth.event,
+                    _ => (src_birth.timestamp, src_birth.event) < (tar_birth.timestamp, tar_birth.event)
+                };
+                if tracer.check(born_before, || format!("source first event {} {} before the target's first event ({})", ev(&src_birth.event), is(born_before), ev(&tar_birth.event))) &&
+                   tracer.check(src_oe.contains(&tar_birth.event), || format!("target first event {} {} part of the source lifecycle", ev(&tar_birth.event), is(src_oe.contains(&tar_birth.event)))) {
+                    return Some(match config.descendants_evidence {
+                        DescendantsEvidence::BirthOnly => EventAdd::SINGLE(tar_birth.event),
+                        // the target's lifecycle starts at the birth, so every shared event is the birth or later
+                        DescendantsEvidence::AllSharedEvents => EventAdd::MULTI(match dense {
+                            Some(dense) => dense.shared(oid1, oid2),
+                            None => IntSet::from_iter(src_oe.intersect(tar_oe.to_vec()))
+                        })
+                    });
                 }
             },
-            Relations::COBIRTH => { // one time
-                if oid1 < oid2 {
-                    let src_e = src_oe.first().unwrap();
-                    if src_e == tar_oe.first().unwrap() {
-                        to_add.push((oid1, oid2, EventAdd::SINGLE(*src_e), Relations::COBIRTH));
-                        to_add.push((oid2, oid1, EventAdd::SINGLE(*src_e), Relations::COBIRTH));
-                    }
+            Relations::COLIFE => {
+                if tracer.check(src_oe == tar_oe, || format!("source lifecycle ({} events) {} identical to the target lifecycle ({} events)", src_oe.len(), is(src_oe == tar_oe), tar_oe.len())) {
+                    return Some(EventAdd::MULTI(IntSet::from_iter(src_oe.to_owned())));
                 }
             },
-            Relations::CODEATH => { // one time
-                if oid1 < oid2 {
-                    let src_e = src_oe.last().unwrap();
-                    if src_e == tar_oe.last().unwrap() {
-                        to_add.push((oid1, oid2, EventAdd::SINGLE(*src_e), Relations::CODEATH));
-                        to_add.push((oid2, oid1, EventAdd::SINGLE(*src_e), Relations::CODEATH));
-                    }
+            Relations::COBIRTH => {
+                let src_e = &config.birth_source.birth(log, oid1).event;
+                let tar_e = &config.birth_source.birth(log, oid2).event;
+                if tracer.check(src_e == tar_e, || format!("source first event {} {} the target's first event ({})", ev(src_e), is(src_e == tar_e), ev(tar_e))) {
+                    return Some(EventAdd::SINGLE(*src_e));
                 }
             },
-            Relations::INHERITANCE => {
+            Relations::CODEATH => {
                 let src_e = src_oe.last().unwrap();
-                if src_type == tar_type &&
-                   src_e == tar_oe.first().unwrap() {
-                    to_add.push((oid1, oid2, EventAdd::SINGLE(*src_e), Relations::INHERITANCE));
+                let tar_e = tar_oe.last().unwrap();
+                if tracer.check(src_e == tar_e, || format!("source last event {} {} the target's last event ({})", ev(src_e), is(src_e == tar_e), ev(tar_e))) {
+                    return Some(EventAdd::SINGLE(*src_e));
                 }
             },
-            Relations::CONSUMES => {
+            Relations::INHERITANCE | Relations::CONSUMES => {
                 let src_e = src_oe.last().unwrap();
-                if src_type != tar_type &&
-                   src_e == tar_oe.first().unwrap() {
-                    to_add.push((oid1, oid2, EventAdd::SINGLE(*src_e), Relations::CONSUMES));
+                let tar_e = &config.birth_source.birth(log, oid2).event;
+                let same_type = *self == Relations::INHERITANCE;
+                let types_match = config.same_type(src_type, tar_type);
+                if !tracer.check(types_match == same_type, || format!("source type {} {} the target type ({})", src_type, is(types_match), tar_type)) {
+                    return None;
+                }
+                match config.max_gap(*self) {
+                    Some(max_gap) if src_e != tar_e => {
+                        let gap = log.events.event(tar_e).timestamp - log.events.event(src_e).timestamp;
+                        let within = Duration::zero() <= gap && gap <= max_gap;
+                        let witnessed = config.witnessed(log, *src_e, *tar_e);
+                        if tracer.check(within, || format!("target first event {} {} within {}s after the source's last event ({})", ev(tar_e), is(within), max_gap.num_seconds(), ev(src_e))) &&
+                           tracer.check(witnessed, || format!("source last event {} {} linked to the target's first event ({}) by {:?}", ev(src_e), is(witnessed), ev(tar_e), config.handover_witness)) {
+                            return Some(EventAdd::MULTI(IntSet::from_iter([*src_e, *tar_e])));
+                        }
+                    },
+                    _ => {
+                        if tracer.check(src_e == tar_e, || format!("source last event {} {} the target's first event ({})", ev(src_e), is(src_e == tar_e), ev(tar_e))) {
+                            return Some(EventAdd::SINGLE(*src_e));
+                        }
+                    }
                 }
             },
             Relations::MERGE => {
                 let src_e = src_oe.last().unwrap();
-                if src_type == tar_type && 
-                   src_oe.last().unwrap() != tar_oe.last().unwrap() {
-                    to_add.push((oid1, oid2, EventAdd::SINGLE(*src_e), Relations::MERGE));
+                let tar_e = tar_oe.last().unwrap();
+                let types_match = config.same_type(src_type, tar_type);
+                if tracer.check(types_match, || format!("source type {} {} the target type ({})", src_type, is(types_match), tar_type)) &&
+                   tracer.check(src_e != tar_e, || format!("source last event {} {} the target's last event ({})", ev(src_e), is(src_e == tar_e), ev(tar_e))) {
+                    return Some(EventAdd::SINGLE(*src_e));
                 }
             },
             Relations::MINION => {
-                   if src_oe.len() > tar_oe.len() {
-                       let common_events: Vec<_> = src_oe.intersect(tar_oe.to_vec()); 
-                       if common_events.len() == tar_oe.len() {
-                            to_add.push((oid1, oid2, EventAdd::MULTI(IntSet::<usize>::from_iter(common_events)), Relations::MINION));
-                       }
-                   }
+                if tracer.check(src_oe.len() > tar_oe.len(), || format!("source lifecycle ({} events) {} longer than the target lifecycle ({} events)", src_oe.len(), is(src_oe.len() > tar_oe.len()), tar_oe.len())) {
+                    let common_events: IntSet<usize> = match dense {
+                        Some(dense) => dense.shared(oid1, oid2),
+                        None => IntSet::from_iter(src_oe.intersect(tar_oe.to_vec()))
+                    };
+                    if tracer.check(common_events.len() == tar_oe.len(), || format!("{} of {} target events {} shared with the source", common_events.len(), tar_oe.len(), if common_events.len() == tar_oe.len() {"are"} else {"are not all"})) {
+                        return Some(EventAdd::MULTI(common_events));
+                    }
+                }
             },
             Relations::PEELER => {
-                if oid1 < oid2  {
-                    let shorter_oe = if src_oe.len() > tar_oe.len() {tar_oe} else {src_oe};
-                    let mut shared_events: IntSet<usize> = IntSet::default();
-                    let mut failed: bool = false;
-                    for event in shorter_oe.iter() {
-                        let omap = &log.events.get(&*event).unwrap().omap;    
-                        if omap.len() > 2 && omap.contains(&oid1) && omap.contains(&oid2){ 
-                            failed = true;
-                            break; 
-                        } else {
-                            shared_events.insert(*event);
-                        }
-                    }
-                    if !failed {
-                        to_add.push((oid1, oid2, EventAdd::MULTI(shared_events.to_owned()), Relations::PEELER));
-                        to_add.push((oid2, oid1, EventAdd::MULTI(shared_events), Relations::PEELER));
+                // equal lengths are decided by the string ids, the numeric ids depend on the import order
+                let src_first = match src_oe.len().cmp(&tar_oe.len()) {
+                    Ordering::Equal => log.object_map.get_by_right(&oid1) <= log.object_map.get_by_right(&oid2),
+                    order => order == Ordering::Less
+                };
+                let shorter_oe = if src_first {src_oe} else {tar_oe};
+                let mut shared_events: IntSet<usize> = IntSet::default();
+                let mut failed: Option<usize> = None;
+                for event in shorter_oe.iter() {
+                    let omap = &log.events.get(event).unwrap().omap;
+                    if omap.len() > 2 && omap.contains(&oid1) && omap.contains(&oid2){
+                        failed = Some(*event);
+                        break;
+                    } else {
+                        shared_events.insert(*event);
                     }
                 }
+                if tracer.check(failed.is_none(), || match failed {
+                    Some(eid) => format!("shared event {} involves a third object", ev(&eid)),
+                    None => "no shared event involves a third object".to_string()
+                }) {
+                    return Some(EventAdd::MULTI(shared_events));
+                }
             },
             Relations::ENGAGES => {
-                if oid1 < oid2 {
-                    let src_oe_set: IntSet<_> = IntSet::<usize>::from_iter(src_oe.clone());
-                    let tar_oe_set: IntSet<_> = IntSet::<usize>::from_iter(tar_oe.clone());
-                    if !tar_oe_set.contains(src_oe.first().unwrap()) &&
-                       !tar_oe_set.contains(src_oe.last().unwrap()) &&
-                       !src_oe_set.contains(tar_oe.first().unwrap()) &&
-                       !src_oe_set.contains(tar_oe.last().unwrap()) {
-                            let shared_events: IntSet<usize> = src_oe_set.intersection(&tar_oe_set).map(|i| *i).collect();
-                            to_add.push((oid1, oid2, EventAdd::MULTI(shared_events.to_owned()), Relations::ENGAGES));
-                            to_add.push((oid2, oid1, EventAdd::MULTI(shared_events), Relations::ENGAGES));
-                       }
+                let (src_oe_set, tar_oe_set): (IntSet<usize>, IntSet<usize>) = match dense {
+                    Some(_) => Default::default(),
+                    None => (IntSet::from_iter(src_oe.clone()), IntSet::from_iter(tar_oe.clone()))
+                };
+                let in_src = |eid: &usize| dense.map_or_else(|| src_oe_set.contains(eid), |dense| dense.contains(oid1, *eid));
+                let in_tar = |eid: &usize| dense.map_or_else(|| tar_oe_set.contains(eid), |dense| dense.contains(oid2, *eid));
+                let src_first = src_oe.first().unwrap();
+                let src_last = src_oe.last().unwrap();
+                let tar_first = tar_oe.first().unwrap();
+                let tar_last = tar_oe.last().unwrap();
+                if tracer.check(!in_tar(src_first), || format!("source first event {} {} part of the target lifecycle", ev(src_first), is(in_tar(src_first)))) &&
+                   tracer.check(!in_tar(src_last), || format!("source last event {} {} part of the target lifecycle", ev(src_last), is(in_tar(src_last)))) &&
+                   tracer.check(!in_src(tar_first), || format!("target first event {} {} part of the source lifecycle", ev(tar_first), is(in_src(tar_first)))) &&
+                   tracer.check(!in_src(tar_last), || format!("target last event {} {} part of the source lifecycle", ev(tar_last), is(in_src(tar_last)))) {
+                    let shared_events: IntSet<usize> = match dense {
+                        Some(dense) => dense.shared(oid1, oid2),
+                        None => src_oe_set.intersection(&tar_oe_set).copied().collect()
+                    };
+                    return Some(EventAdd::MULTI(shared_events));
+                }
+            },
+            Relations::SPLIT => {
+                let src_e = src_oe.last().unwrap();
+                let tar_e = tar_oe.first().unwrap();
+                let types_match = config.same_type(src_type, tar_type);
+                if tracer.check(types_match, || format!("source type {} {} the target type ({})", src_type, is(types_match), tar_type)) &&
+                   tracer.check(src_e == tar_e, || format!("source last event {} {} the target's first event ({})", ev(src_e), is(src_e == tar_e), ev(tar_e))) {
+                    let conforming = split_conforming(log, config, oid1).1.len();
+                    if tracer.check(conforming > 1, || format!("{} objects of type {} start at {}, more than one is required", conforming, src_type, ev(src_e))) {
+                        return Some(EventAdd::SINGLE(*src_e));
+                    }
+                }
+            },
+            Relations::OVERLAPS => {
+                // distinct births and deaths keep it apart from COBIRTH, CODEATH and COLIFE
+                let (src_first, src_last, tar_first, tar_last) = (src_oe.first().unwrap(), src_oe.last().unwrap(), tar_oe.first().unwrap(), tar_oe.last().unwrap());
+                if tracer.check(src_first != tar_first, || format!("source first event {} {} the target's first event ({})", ev(src_first), is(src_first == tar_first), ev(tar_first))) &&
+                   tracer.check(src_last != tar_last, || format!("source last event {} {} the target's last event ({})", ev(src_last), is(src_last == tar_last), ev(tar_last))) {
+                    let e_set = match dense {
+                        Some(dense) => dense.shared(oid1, oid2),
+                        None => IntSet::from_iter(src_oe.intersect(tar_oe.to_vec()))
+                    };
+                    if tracer.check(!e_set.is_empty(), || format!("the objects share {} events", e_set.len())) {
+                        return Some(EventAdd::MULTI(e_set));
+                    }
                 }
-
             },
-            _ => {}
+            Relations::REACHABLE => {
+                tracer.check(false, || "REACHABLE follows DESCENDANTS chains over the whole graph and is not evaluated per pair".to_string());
+            }
         }
-        to_add
+        None
     }
 }
 
 
 #[derive(Debug, Clone)]
+pub struct TraceStep {
+    pub condition: String,
+    pub passed: bool
+}
+
+#[derive(Debug, Clone)]
+pub struct TraceResult {
+    pub source: String,
+    pub target: String,
+    pub relation: Relations,
+    pub steps: Vec<TraceStep>
+}
+
+impl TraceResult {
+    pub fn holds(&self) -> bool {
+        !self.steps.is_empty() && self.steps.iter().all(|step| step.passed)
+    }
+
+    pub fn failed_step(&self) -> Option<&TraceStep> {
+        self.steps.iter().find(|step| !step.passed)
+    }
+}
+
+impl fmt::Display for TraceResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} {} -> {}: {}", self.relation, self.source, self.target, if self.holds() {"edge"} else {"no edge"})?;
+        for step in &self.steps {
+            writeln!(f, "  [{}] {}", if step.passed {"ok"} else {"fail"}, step.condition)?;
+        }
+        Ok(())
+    }
+}
+
+// records the predicate steps of a relation when tracing, does nothing during generation
+struct Tracer {
+    steps: Option<Vec<TraceStep>>
+}
+
+impl Tracer {
+    fn silent() -> Self {
+        Self { steps: None }
+    }
+
+    fn recording() -> Self {
+        Self { steps: Some(vec![]) }
+    }
+
+    fn check<F: FnOnce() -> String>(&mut self, passed: bool, describe: F) -> bool {
+        if let Some(steps) = &mut self.steps {
+            steps.push(TraceStep { condition: describe(), passed });
+        }
+        passed
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EventAdd {
     SINGLE(usize),
     MULTI(IntSet<usize>)
 }
 
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct NodeInfo {
     pub node_type: String,
+    // contracted objects of a super-node, empty for plain objects
+    pub members: Vec<usize>
+}
+
+impl NodeInfo {
+    pub fn pretty(&self, oid: usize, log: &Ocel) -> String {
+        let events: Vec<&str> = match log.objects.get(&oid) {
+            Some(obj) => obj.events.iter().map(|eid| resolve_label(&log.event_map, eid)).collect(),
+            None => vec![]
+        };
+        format!("{} ({}): [{}]", resolve_label(&log.object_map, &oid), self.node_type, events.join(", "))
+    }
 }
 
-#[derive(Debug, Default)]
+// how much of the supporting events generation keeps per relation instance
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone, EnumString, Serialize, Deserialize)]
+pub enum Evidence {
+    #[default]
+    Full,
+    CountsOnly,
+    None
+}
+
+// generation checks the log before any relation is evaluated, every variant names the offending ids as found in the log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcdgError {
+    // an event references an object missing from the objects or the object ids of the log
+    MissingObject { event: String, object: String },
+    // an object taking part in events without any event in its lifecycle
+    EmptyLifecycle(String),
+    // a lifecycle holding an event missing from the events of the log
+    MissingEvent { object: String, event: String },
+    // a serialized graph that cannot be read back
+    Invalid(String),
+    // an object id without a node in the graph
+    UnknownObject(usize)
+}
+
+impl fmt::Display for OcdgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcdgError::MissingObject { event, object } => write!(f, "Event {} references the object {} which is not part of the log", event, object),
+            OcdgError::EmptyLifecycle(object) => write!(f, "Object {} takes part in events but its lifecycle is empty, call Ocel::rebuild_derived_indices after mutating the log", object),
+            OcdgError::MissingEvent { object, event } => write!(f, "The lifecycle of object {} holds the event {} which is not part of the log", object, event),
+            OcdgError::Invalid(message) => write!(f, "Invalid ocdg: {}", message),
+            OcdgError::UnknownObject(oid) => write!(f, "The graph has no object with the id {}", oid)
+        }
+    }
+}
+
+impl Error for OcdgError {}
+
+// which events the shared-event relations (INTERACTS, MINION, PEELER, ENGAGES, OVERLAPS) look at.
+// OverlapOnly keeps the events between the later birth and the earlier death of the two objects,
+// birth and death being the timestamps of the first and last lifecycle events. Shared events only
+// fall outside of that window when the lifecycles are out of timestamp order.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone, EnumString)]
+pub enum RelationScope {
+    #[default]
+    WholeLifecycle,
+    OverlapOnly
+}
+
+// what links the source's last and the target's first event of a handover that spans two events
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub enum HandoverWitness {
+    // an object takes part in both events
+    #[default]
+    SharedObject,
+    // both events carry the same value for this attribute
+    Attribute(String)
+}
+
+// which events a DESCENDANTS edge keeps as its evidence
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone, EnumString)]
+pub enum DescendantsEvidence {
+    // the first event of the target, in which the source gives birth to it
+    #[default]
+    BirthOnly,
+    // the birth and every later event the two objects share
+    AllSharedEvents
+}
+
+// what defines the birth of an object for COBIRTH, DESCENDANTS, INHERITANCE and CONSUMES
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub enum BirthSource {
+    // the first event of the lifecycle
+    #[default]
+    FirstEvent,
+    // the first event at or after the timestamp in this object attribute, earlier events count as clock skew
+    Attribute(String),
+    // the earlier of the attribute and the first event, the birth event stays the first event
+    Earliest(String)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GenerationConfig {
+    pub relations: Vec<Relations>,
+    pub evidence: Evidence,
+    pub type_matching: TypeMatching,
+    pub hierarchy: TypeHierarchy,
+    pub scope: RelationScope,
+    // INHERITANCE and CONSUMES also hold when the target is born within this gap after the source dies
+    // and the witness links the two events, the objects do not need to share an event
+    pub inheritance_max_gap: Option<Duration>,
+    pub consumes_max_gap: Option<Duration>,
+    pub handover_witness: HandoverWitness,
+    pub descendants_evidence: DescendantsEvidence,
+    pub birth_source: BirthSource
+}
+
+impl GenerationConfig {
+    fn same_type(&self, a: &str, b: &str) -> bool {
+        self.hierarchy.is_same_type_at(a, b, self.type_matching)
+    }
+
+    fn max_gap(&self, rel: Relations) -> Option<Duration> {
+        match rel {
+            Relations::INHERITANCE => self.inheritance_max_gap,
+            Relations::CONSUMES => self.consumes_max_gap,
+            _ => None
+        }
+    }
+
+    fn witnessed(&self, log: &Ocel, src_e: usize, tar_e: usize) -> bool {
+        let (src, tar) = (log.events.event(&src_e), log.events.event(&tar_e));
+        match &self.handover_witness {
+            HandoverWitness::SharedObject => src.omap.iter().any(|oid| tar.omap.contains(oid)),
+            HandoverWitness::Attribute(attribute) => src.vmap.get(attribute).is_some_and(|value| tar.vmap.get(attribute) == Some(value))
+        }
+    }
+}
+
+pub type EventIndex = IntMap<usize, Vec<(usize, usize, Relations)>>;
+pub type ReverseIndex = IntMap<usize, IntMap<usize, IntSet<u8>>>;
+pub type TypeIndex = AHashMap<String, Vec<usize>>;
+
+#[derive(Default)]
 pub struct Ocdg {
     pub net: DiGraph<usize, usize>,
     pub edge_attributes: IntMap<usize, NodeInfo>,
@@ -232,19 +586,581 @@ pub struct Ocdg {
     pub event_map: BiMap<String, usize>,
     pub inodes: IntMap<usize, NodeIndex>,
     pub iedges: IntMap<usize, IntMap<usize, EdgeIndex>>,
-    pub irels: IntMap<usize, IntMap<usize,IntMap<usize, IntSet<usize>>>>
+    pub irels: IntMap<usize, IntMap<usize,IntMap<usize, IntSet<usize>>>>,
+    // eid -> relation instances it evidences, holds one entry per event in irels when built
+    pub ievents: Option<EventIndex>,
+    // tar -> src -> relation indices, the keys of irels turned around. holds one byte per relation instance besides the
+    // maps, about the size of irels without its events, and is kept up to date by the generation and reindex when built
+    pub irels_in: Option<ReverseIndex>,
+    // node type -> its objects sorted, built by the first lookup by type and dropped by reindex
+    pub itypes: OnceLock<TypeIndex>,
+    pub evidence: Evidence,
+    pub irel_counts: IntMap<usize, IntMap<usize, IntMap<usize, usize>>>,
+    pub metadata: Option<GenerationMetadata>,
+    // oid -> the event ids of its lifecycle, held by graphs read without their log (import_ocdg_json)
+    pub lifecycles: IntMap<usize, Vec<String>>
+}
+
+pub struct EdgeContext<'a> {
+    pub src: usize,
+    pub tar: usize,
+    pub src_type: &'a str,
+    pub tar_type: &'a str,
+    pub relations: &'a IntMap<usize, IntSet<usize>>,
+    pub first_timestamp: Option<DateTime<Utc>>,
+    pub last_timestamp: Option<DateTime<Utc>>
+}
+
+impl<'a> EdgeContext<'a> {
+    pub fn has_relation(&self, rel: Relations) -> bool {
+        self.relations.contains_key(&rel.relation_index().into())
+    }
+
+    pub fn relation_counts(&self) -> Vec<(Relations, usize)> {
+        let mut counts: Vec<(Relations, usize)> = self.relations.iter()
+                                                      .filter_map(|(rel, events)| Relations::from_index(*rel).map(|r| (r, events.len())))
+                                                      .collect();
+        counts.sort_by_key(|(rel, _)| rel.relation_index());
+        counts
+    }
+
+    pub fn event_count(&self) -> usize {
+        self.relations.values().flatten().copied().collect::<IntSet<usize>>().len()
+    }
+}
+
+// shared read-only across threads (e.g. Arc<Ocdg>), so interior mutability is limited to the OnceLock of the lazy indices
+assert_impl_all!(Ocdg: Send, Sync);
+
+impl fmt::Debug for Ocdg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Ocdg")
+         .field("nodes", &self.net.node_count())
+         .field("edges", &self.net.edge_count())
+         .field("objects", &self.object_map.len())
+         .field("events", &self.event_map.len())
+         .field("event_index", &self.ievents.is_some())
+         .finish()
+    }
 }
 
 impl Ocdg {
 
+    pub fn pretty(&self, log: &Ocel, limit: usize) -> String {
+        let mut out = format!("{:?}\n", self);
+        let mut oids: Vec<(&str, &usize)> = self.node_attributes.keys()
+                                                .map(|oid| (resolve_label(&log.object_map, oid), oid))
+                                                .collect();
+        oids.sort();
+
+        for (label, oid) in oids.iter().take(limit) {
+            let event_count = log.objects.get(oid).map_or(0, |obj| obj.events.len());
+            let mut rel_counts: BTreeMap<usize, usize> = BTreeMap::new();
+            if let Some(targets) = self.irels.get(oid) {
+                for rel in targets.values().flat_map(|rels| rels.keys()) {
+                    *rel_counts.entry(*rel).or_default() += 1;
+                }
+            }
+            let mut top_rels: Vec<(usize, usize)> = rel_counts.into_iter().collect();
+            top_rels.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            let top_rels: Vec<String> = top_rels.iter()
+                                                .take(3)
+                                                .map(|(rel, count)| match Relations::from_index(*rel) {
+                                                    Some(r) => format!("{}={}", r, count),
+                                                    None => format!("{}={}", rel, count)
+                                                })
+                                                .collect();
+            out.push_str(&format!("{} ({}): {} events, relations: [{}]\n", label, self.node_attributes[oid].node_type, event_count, top_rels.join(", ")));
+        }
+
+        if oids.len() > limit {
+            out.push_str(&format!("... {} more\n", oids.len() - limit));
+        }
+        out
+    }
+
+    pub fn retain_edges<F>(&mut self, f: F) where F: FnMut(EdgeContext) -> bool {
+        self.retain_edges_with(None, false, f);
+    }
+
+    pub fn retain_edges_with<F>(&mut self, log: Option<&Ocel>, prune_isolated: bool, mut f: F) where F: FnMut(EdgeContext) -> bool {
+        let mut removed: AHashSet<(usize, usize)> = AHashSet::new();
+        for (src, targets) in &self.irels {
+            for (tar, rels) in targets {
+                let timestamps: Vec<DateTime<Utc>> = match log {
+                    Some(log) => rels.values().flatten().filter_map(|eid| log.events.get(eid)).map(|ev| ev.timestamp).collect(),
+                    None => vec![]
+                };
+                let context = EdgeContext { src: *src,
+                                            tar: *tar,
+                                            src_type: &self.node_attributes[src].node_type,
+                                            tar_type: &self.node_attributes[tar].node_type,
+                                            relations: rels,
+                                            first_timestamp: timestamps.iter().min().copied(),
+                                            last_timestamp: timestamps.iter().max().copied() };
+                if !f(context) {
+                    removed.insert((*src, *tar));
+                }
+            }
+        }
+
+        for (src, tar) in &removed {
+            if let Some(targets) = self.irels.get_mut(src) {
+                targets.remove(tar);
+                if targets.is_empty() {
+                    self.irels.remove(src);
+                }
+            }
+            if let Some(targets) = self.irel_counts.get_mut(src) {
+                targets.remove(tar);
+                if targets.is_empty() {
+                    self.irel_counts.remove(src);
+                }
+            }
+        }
+        self.net.retain_edges(|g, edge| match g.edge_endpoints(edge) {
+            Some((a, b)) => !removed.contains(&(g[a], g[b])),
+            None => true
+        });
+
+        if prune_isolated {
+            let isolated: IntSet<usize> = self.inodes.iter()
+                                                     .filter(|(_, node)| self.net.neighbors_undirected(**node).next().is_none())
+                                                     .map(|(oid, _)| *oid)
+                                                     .collect();
+            for oid in &isolated {
+                self.node_attributes.remove(oid);
+                self.object_map.remove_by_right(oid);
+            }
+            self.net.retain_nodes(|g, node| !isolated.contains(&g[node]));
+        }
+
+        self.reindex();
+    }
+
+    // keeps the objects whose type is one of the given types or a descendant of one
+    pub fn retain_types(&mut self, types: &[&str], hierarchy: &TypeHierarchy) {
+        let removed: IntSet<usize> = self.node_attributes.iter()
+                                                         .filter(|(_, info)| !types.iter().any(|object_type| hierarchy.is_a(&info.node_type, object_type)))
+                                                         .map(|(oid, _)| *oid)
+                                                         .collect();
+        self.remove_objects(&removed);
+    }
+
+    // false when the graph has no such object
+    pub fn remove_object(&mut self, oid: usize) -> bool {
+        if !self.node_attributes.contains_key(&oid) {
+            return false;
+        }
+        self.remove_objects(&IntSet::from_iter([oid]));
+        true
+    }
+
+    // returns the number of objects removed
+    pub fn remove_objects_of_type(&mut self, obj_type: &str) -> usize {
+        let removed: IntSet<usize> = self.iter_objects_by_type(obj_type).collect();
+        self.remove_objects(&removed);
+        removed.len()
+    }
+
+    // the nodes with every edge touching them, the lookups and built indices are rebuilt once for the whole set
+    fn remove_objects(&mut self, removed: &IntSet<usize>) {
+        for oid in removed {
+            self.node_attributes.remove(oid);
+            self.object_map.remove_by_right(oid);
+            self.irels.remove(oid);
+            self.irel_counts.remove(oid);
+        }
+        for targets in self.irels.values_mut() {
+            targets.retain(|tar, _| !removed.contains(tar));
+        }
+        self.irels.retain(|_, targets| !targets.is_empty());
+        for targets in self.irel_counts.values_mut() {
+            targets.retain(|tar, _| !removed.contains(tar));
+        }
+        self.irel_counts.retain(|_, targets| !targets.is_empty());
+        self.net.retain_nodes(|g, node| !removed.contains(&g[node]));
+
+        self.reindex();
+    }
+
+    // a copy holding only the given relations, edges left without any are dropped and the nodes are kept
+    pub fn filter_relations(&self, rels: &[Relations]) -> Ocdg {
+        self.filter_relations_with(rels, false)
+    }
+
+    // with prune_isolated the nodes left without an edge are dropped as well
+    pub fn filter_relations_with(&self, rels: &[Relations], prune_isolated: bool) -> Ocdg {
+        let kept: IntSet<usize> = rels.iter().map(|rel| rel.relation_index().into()).collect();
+        self.induced_copy(|_| true, |_, _, rel| kept.contains(&rel), prune_isolated)
+    }
+
+    // the objects at most hops edges away from oid, following edges in either direction that hold one of the relations
+    // (any relation for None), with the edges between them restricted to the relations
+    pub fn ego_network(&self, oid: usize, hops: usize, rels: Option<&[Relations]>) -> Result<Ocdg, OcdgError> {
+        if !self.inodes.contains_key(&oid) {
+            return Err(OcdgError::UnknownObject(oid));
+        }
+        let kept: Option<IntSet<usize>> = rels.map(|rels| rels.iter().map(|rel| rel.relation_index().into()).collect());
+        let keeps = |rels: &IntMap<usize, IntSet<usize>>| kept.as_ref().is_none_or(|kept| rels.keys().any(|rel| kept.contains(rel)));
+
+        let mut ego: IntSet<usize> = IntSet::from_iter([oid]);
+        let mut frontier: Vec<usize> = vec![oid];
+        for _ in 0..hops {
+            let mut next: Vec<usize> = vec![];
+            for current in frontier {
+                let outgoing = self.irels.get(&current).into_iter().flatten().filter(|(_, rels)| keeps(rels)).map(|(tar, _)| *tar);
+                let incoming = self.net.neighbors_directed(self.inodes[&current], Direction::Incoming)
+                                       .map(|node| self.net[node])
+                                       .filter(|src| self.irels.get(src).and_then(|targets| targets.get(&current)).is_some_and(&keeps));
+                for neighbour in outgoing.chain(incoming).collect::<Vec<usize>>() {
+                    if ego.insert(neighbour) {
+                        next.push(neighbour);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        Ok(self.induced_copy(|oid| ego.contains(&oid), |_, _, rel| kept.as_ref().is_none_or(|kept| kept.contains(&rel)), false))
+    }
+
+    // a copy with the kept objects and the kept (source, target, relation) instances between them, the lookups point into a new net
+    fn induced_copy<N, R>(&self, keep_object: N, keep_relation: R, prune_isolated: bool) -> Ocdg where N: Fn(usize) -> bool, R: Fn(usize, usize, usize) -> bool {
+        let mut filtered = Ocdg { evidence: self.evidence,
+                                  event_map: self.event_map.clone(),
+                                  ievents: self.ievents.as_ref().map(|_| Default::default()),
+                                  irels_in: self.irels_in.as_ref().map(|_| Default::default()),
+                                  metadata: self.metadata.clone(),
+                                  ..Default::default() };
+        for (src, targets) in self.irels.iter().filter(|(src, _)| keep_object(**src)) {
+            for (tar, rels) in targets.iter().filter(|(tar, _)| keep_object(**tar)) {
+                let rels: IntMap<usize, IntSet<usize>> = rels.iter().filter(|(rel, _)| keep_relation(*src, *tar, **rel)).map(|(rel, events)| (*rel, events.clone())).collect();
+                if !rels.is_empty() {
+                    filtered.irels.entry(*src).or_default().insert(*tar, rels);
+                }
+            }
+        }
+        for (src, targets) in self.irel_counts.iter().filter(|(src, _)| keep_object(**src)) {
+            for (tar, counts) in targets.iter().filter(|(tar, _)| keep_object(**tar)) {
+                let counts: IntMap<usize, usize> = counts.iter().filter(|(rel, _)| keep_relation(*src, *tar, **rel)).map(|(rel, count)| (*rel, *count)).collect();
+                if !counts.is_empty() {
+                    filtered.irel_counts.entry(*src).or_default().insert(*tar, counts);
+                }
+            }
+        }
+
+        filtered.lifecycles = self.lifecycles.iter().filter(|(oid, _)| keep_object(**oid)).map(|(oid, events)| (*oid, events.clone())).collect();
+        let connected: IntSet<usize> = filtered.irels.iter().flat_map(|(src, targets)| targets.keys().chain([src])).copied().collect();
+        let mut oids: Vec<usize> = self.node_attributes.keys().copied().filter(|oid| keep_object(*oid) && (!prune_isolated || connected.contains(oid))).collect();
+        oids.sort_unstable();
+        for oid in oids {
+            let node = &self.node_attributes[&oid];
+            filtered.node_attributes.insert(oid, NodeInfo { node_type: node.node_type.to_owned(), members: node.members.clone() });
+            if let Some(label) = self.object_map.get_by_right(&oid) {
+                filtered.object_map.insert(label.to_owned(), oid);
+            }
+            filtered.inodes.insert(oid, filtered.net.add_node(oid));
+        }
+        let mut edges: Vec<(usize, usize)> = filtered.irels.iter().flat_map(|(src, targets)| targets.keys().map(move |tar| (*src, *tar))).collect();
+        edges.sort_unstable();
+        for (src, tar) in edges {
+            filtered.net.add_edge(filtered.inodes[&src], filtered.inodes[&tar], 0);
+        }
+        filtered.reindex();
+        filtered
+    }
+
+    // the graph through a single relation, every node is kept so that the object counts match
+    pub fn relation_induced_subgraph(&self, rel: Relations) -> Ocdg {
+        self.filter_relations(&[rel])
+    }
+
+    // the objects of the two types and the edges from one type to the other, with a single type its edges within the type
+    pub fn type_induced_subgraph(&self, type_a: &str, type_b: &str) -> Ocdg {
+        let node_type = |oid: usize| self.node_attributes.get(&oid).map(|node| node.node_type.as_str());
+        self.induced_copy(|oid| node_type(oid).is_some_and(|obj_type| obj_type == type_a || obj_type == type_b),
+                          |src, tar, _| type_a == type_b || node_type(src) != node_type(tar),
+                          false)
+    }
+
+    pub fn object_count(&self) -> usize {
+        self.node_attributes.len()
+    }
+
+    // petgraph moves indices around on removal so rebuild the lookups in one pass
+    fn reindex(&mut self) {
+        self.rebuild_lookups();
+        self.refresh_edge_weights();
+
+        let used_events: IntSet<usize> = self.irels.values()
+                                                   .flat_map(|targets| targets.values())
+                                                   .flat_map(|rels| rels.values())
+                                                   .flatten()
+                                                   .copied()
+                                                   .collect();
+        self.event_map.retain(|_, eid| used_events.contains(eid));
+        let node_attributes = &self.node_attributes;
+        self.lifecycles.retain(|oid, _| node_attributes.contains_key(oid));
+
+        if self.ievents.is_some() {
+            self.build_event_index();
+        }
+        if self.irels_in.is_some() {
+            self.build_reverse_index();
+        }
+        self.itypes.take();
+    }
+
+    fn rebuild_lookups(&mut self) {
+        self.inodes = self.net.node_indices().map(|node| (self.net[node], node)).collect();
+        self.iedges.clear();
+        for edge in self.net.edge_indices() {
+            let (a, b) = self.net.edge_endpoints(edge).expect("Edge index comes from the graph");
+            self.iedges.entry(self.net[a]).or_default().insert(self.net[b], edge);
+        }
+    }
+
+    pub fn build_event_index(&mut self) {
+        let mut ievents: EventIndex = IntMap::default();
+        for (src, targets) in &self.irels {
+            for (tar, rels) in targets {
+                for (rel, events) in rels {
+                    if let Some(relation) = Relations::from_index(*rel) {
+                        for eid in events {
+                            ievents.entry(*eid).or_default().push((*src, *tar, relation));
+                        }
+                    }
+                }
+            }
+        }
+        for instances in ievents.values_mut() {
+            instances.sort_by_key(|(src, tar, rel)| (*src, *tar, rel.relation_index()));
+        }
+        self.ievents = Some(ievents);
+    }
+
+    pub fn build_reverse_index(&mut self) {
+        let mut irels_in: ReverseIndex = IntMap::default();
+        for (src, targets) in &self.irels {
+            for (tar, rels) in targets {
+                irels_in.entry(*tar).or_default().entry(*src).or_default().extend(rels.keys().filter_map(|rel| u8::try_from(*rel).ok()));
+            }
+        }
+        self.irels_in = Some(irels_in);
+    }
+
+    // built on the first call, the node attributes only change together with a reindex that drops it
+    pub fn type_index(&self) -> &TypeIndex {
+        self.itypes.get_or_init(|| {
+            let mut itypes: TypeIndex = AHashMap::new();
+            for (oid, node) in &self.node_attributes {
+                itypes.entry(node.node_type.to_owned()).or_default().push(*oid);
+            }
+            for oids in itypes.values_mut() {
+                oids.sort_unstable();
+            }
+            itypes
+        })
+    }
+
+    // the objects of the node type, sorted
+    pub fn get_objects_by_type(&self, obj_type: &str) -> &[usize] {
+        self.type_index().get(obj_type).map_or(&[], |oids| oids.as_slice())
+    }
+
+    pub fn iter_objects_by_type<'a>(&'a self, obj_type: &str) -> impl Iterator<Item = usize> + 'a {
+        self.get_objects_by_type(obj_type).iter().copied()
+    }
+
+    // whether src points at tar with the relation, holds for every evidence mode
+    pub fn has_relation(&self, src: usize, tar: usize, rel: Relations) -> bool {
+        self.irels.get(&src).and_then(|targets| targets.get(&tar)).is_some_and(|rels| rels.contains_key(&rel.relation_index().into()))
+    }
+
+    pub fn has_any_relation(&self, src: usize, tar: usize) -> bool {
+        self.irels.get(&src).and_then(|targets| targets.get(&tar)).is_some_and(|rels| !rels.is_empty())
+    }
+
+    // every (src, tar) pair holding the relation, sorted
+    pub fn objects_with_relation(&self, rel: Relations) -> Vec<(usize, usize)> {
+        let rel: usize = rel.relation_index().into();
+        let mut pairs: Vec<(usize, usize)> = self.irels.iter()
+                                                 .flat_map(|(src, targets)| targets.iter().filter(|(_, rels)| rels.contains_key(&rel)).map(move |(tar, _)| (*src, *tar)))
+                                                 .collect();
+        pairs.sort_unstable();
+        pairs
+    }
+
+    // the relations src points at tar with, in the order of their index
+    pub fn relations_between(&self, src: usize, tar: usize) -> Vec<Relations> {
+        let mut relations: Vec<Relations> = self.irels.get(&src)
+                                                      .and_then(|targets| targets.get(&tar))
+                                                      .into_iter()
+                                                      .flat_map(|rels| rels.keys())
+                                                      .filter_map(|rel| Relations::from_index(*rel))
+                                                      .collect();
+        relations.sort_by_key(|rel| rel.relation_index());
+        relations
+    }
+
+    // the relations src points at tar with and their evidence events, in the order of their index.
+    // the event sets are empty unless the graph keeps Evidence::Full
+    pub fn get_relations_between(&self, src: usize, tar: usize) -> Vec<(Relations, &IntSet<usize>)> {
+        let mut relations: Vec<(Relations, &IntSet<usize>)> = self.irels.get(&src)
+                                                                        .and_then(|targets| targets.get(&tar))
+                                                                        .into_iter()
+                                                                        .flatten()
+                                                                        .filter_map(|(rel, events)| Relations::from_index(*rel).map(|rel| (rel, events)))
+                                                                        .collect();
+        relations.sort_by_key(|(rel, _)| rel.relation_index());
+        relations
+    }
+
+    // same as relation_events, None without the relation or without Evidence::Full
+    pub fn get_events_for_relation(&self, src: usize, tar: usize, rel: Relations) -> Option<&IntSet<usize>> {
+        self.relation_events(src, tar, rel)
+    }
+
+    // the objects oid points at with the relation (Outgoing) or that point at oid with it (Incoming), sorted
+    pub fn neighbors_with_relation(&self, oid: usize, rel: Relations, direction: Direction) -> Vec<usize> {
+        let mut neighbours: Vec<usize> = self.neighbors_with_relation_iter(oid, rel, direction).collect();
+        neighbours.sort_unstable();
+        neighbours
+    }
+
+    // the same neighbours in no particular order. incoming ones are found over the incoming edges of the net,
+    // which every relation instance has, instead of scanning irels
+    pub fn neighbors_with_relation_iter(&self, oid: usize, rel: Relations, direction: Direction) -> impl Iterator<Item = usize> + '_ {
+        match direction {
+            Direction::Outgoing => Either::Left(self.irels.get(&oid)
+                                                          .into_iter()
+                                                          .flatten()
+                                                          .filter(move |(_, rels)| rels.contains_key(&rel.relation_index().into()))
+                                                          .map(|(tar, _)| *tar)),
+            Direction::Incoming => Either::Right(self.predecessors_with_relation_iter(oid, rel))
+        }
+    }
+
+    // the objects pointing at oid with the relation, sorted
+    pub fn predecessors_with_relation(&self, oid: usize, rel: Relations) -> Vec<usize> {
+        let mut predecessors: Vec<usize> = self.predecessors_with_relation_iter(oid, rel).collect();
+        predecessors.sort_unstable();
+        predecessors
+    }
+
+    // read from the reverse index when it is built, otherwise from the incoming edges of the net
+    fn predecessors_with_relation_iter(&self, oid: usize, rel: Relations) -> impl Iterator<Item = usize> + '_ {
+        match &self.irels_in {
+            Some(irels_in) => Either::Left(irels_in.get(&oid)
+                                                   .into_iter()
+                                                   .flatten()
+                                                   .filter(move |(_, rels)| rels.contains(&rel.relation_index()))
+                                                   .map(|(src, _)| *src)),
+            None => Either::Right(self.inodes.get(&oid)
+                                             .into_iter()
+                                             .flat_map(|node| self.net.neighbors_directed(*node, Direction::Incoming))
+                                             .map(|node| self.net[node])
+                                             .filter(move |src| self.has_relation(*src, oid, rel)))
+        }
+    }
+
+    pub fn relation_events(&self, src: usize, tar: usize, rel: Relations) -> Option<&IntSet<usize>> {
+        if self.evidence != Evidence::Full {
+            return None;
+        }
+        self.irels.get(&src)?.get(&tar)?.get(&rel.relation_index().into())
+    }
+
+    pub fn relation_support(&self, src: usize, tar: usize, rel: Relations) -> Option<usize> {
+        match self.evidence {
+            Evidence::Full => self.relation_events(src, tar, rel).map(|events| events.len()),
+            Evidence::CountsOnly => self.irel_counts.get(&src)?.get(&tar)?.get(&rel.relation_index().into()).copied(),
+            Evidence::None => None
+        }
+    }
+
+    // the weight of the edge in the net: the distinct events supporting any of its relations. with counts only
+    // an event supporting several relations is counted once per relation, without evidence the weight is 0
+    pub fn edge_support(&self, src: usize, tar: usize) -> usize {
+        match self.evidence {
+            Evidence::Full => {
+                let rels = match self.irels.get(&src).and_then(|targets| targets.get(&tar)) {
+                    Some(rels) => rels,
+                    None => return 0
+                };
+                match rels.len() {
+                    0 => 0,
+                    1 => rels.values().map(|events| events.len()).sum(),
+                    _ => rels.values().flatten().copied().collect::<IntSet<usize>>().len()
+                }
+            },
+            Evidence::CountsOnly => self.irel_counts.get(&src).and_then(|targets| targets.get(&tar)).map_or(0, |counts| counts.values().sum()),
+            Evidence::None => 0
+        }
+    }
+
+    pub(crate) fn refresh_edge_weights(&mut self) {
+        for edge in self.net.edge_indices() {
+            let (a, b) = self.net.edge_endpoints(edge).expect("Edge index comes from the graph");
+            self.net[edge] = self.edge_support(self.net[a], self.net[b]);
+        }
+    }
+
+    pub fn relations_of_event(&self, eid: usize) -> Option<Vec<(usize, usize, Relations)>> {
+        if self.evidence != Evidence::Full {
+            return None;
+        }
+        if let Some(ievents) = &self.ievents {
+            return Some(ievents.get(&eid).cloned().unwrap_or_default());
+        }
+
+        let mut instances: Vec<(usize, usize, Relations)> = vec![];
+        for (src, targets) in &self.irels {
+            for (tar, rels) in targets {
+                for (rel, events) in rels {
+                    if events.contains(&eid) {
+                        if let Some(relation) = Relations::from_index(*rel) {
+                            instances.push((*src, *tar, relation));
+                        }
+                    }
+                }
+            }
+        }
+        instances.sort_by_key(|(src, tar, rel)| (*src, *tar, rel.relation_index()));
+        Some(instances)
+    }
+
     fn init_object_key(&mut self, oid: usize) {
         self.node_attributes.insert(oid, NodeInfo::default());
     }
 
 
     fn apply_new_edges(&mut self, edge: (usize, usize), eids: EventAdd, rel: Relations) {
-            self.iedges.entry(edge.0).or_default().entry(edge.1).or_insert_with(|| self.net.add_edge(self.inodes[&edge.0], self.inodes[&edge.1], 0));
-            match self.irels.entry(edge.0).or_default().entry(edge.1).or_default().entry(rel.relation_index().into()) {
+            let edge_index = *self.iedges.entry(edge.0).or_default().entry(edge.1).or_insert_with(|| self.net.add_edge(self.inodes[&edge.0], self.inodes[&edge.1], 0));
+            if let Some(irels_in) = self.irels_in.as_mut() {
+                irels_in.entry(edge.1).or_default().entry(edge.0).or_default().insert(rel.relation_index());
+            }
+            if self.evidence != Evidence::Full {
+                // presence only, the event set stays empty and does not allocate
+                self.irels.entry(edge.0).or_default().entry(edge.1).or_default().entry(rel.relation_index().into()).or_default();
+                if self.evidence == Evidence::CountsOnly {
+                    let count = match eids {
+                        EventAdd::SINGLE(_) => 1,
+                        EventAdd::MULTI(multi) => multi.len()
+                    };
+                    *self.irel_counts.entry(edge.0).or_default().entry(edge.1).or_default().entry(rel.relation_index().into()).or_default() += count;
+                    self.net[edge_index] += count;
+                }
+                return;
+            }
+            let rels = self.irels.entry(edge.0).or_default().entry(edge.1).or_default();
+            // the weight grows by the events no relation of the edge holds yet
+            let unseen = |eid: &usize| !rels.values().any(|events| events.contains(eid));
+            let added = match &eids {
+                EventAdd::SINGLE(single) => usize::from(unseen(single)),
+                EventAdd::MULTI(multi) => multi.iter().filter(|eid| unseen(eid)).count()
+            };
+            match rels.entry(rel.relation_index().into()) {
                 Entry::Vacant(e) => {
                     if let EventAdd::MULTI(multi) = eids {
                         e.insert(multi);
@@ -262,82 +1178,400 @@ impl Ocdg {
                     }
                     
                 }
-            }  
+            }
+            self.net[edge_index] += added;
     }
 
 
 }
 
-pub fn generate_ocdg(log: &Ocel, relations: &Vec<Relations>) -> Ocdg {
-    let mut ocdg: Ocdg = Ocdg::default();
-    let rel_inst: Vec<_> = relations.iter().filter(|r| r.relation_type() == 2).collect();
-    let rel_whole: Vec<_> = relations.iter().filter(|r| r.relation_type() == 1).collect();
-    let mut neighbours: IntMap<usize, IntSet<usize>> = IntMap::default();
+pub fn generate_ocdg(log: &Ocel, relations: &[Relations]) -> Result<Ocdg, OcdgError> {
+    generate_ocdg_with(log, relations, Evidence::Full)
+}
 
-    for (eid, data) in &log.events {
-        for oid in &data.omap {
-            if !ocdg.node_attributes.contains_key(oid) {
-                let new_node = ocdg.net.add_node(*oid);
-                ocdg.object_map.insert(log.object_map.get_by_right(&oid).expect("This cannot occur").to_owned(), *oid);
-                ocdg.init_object_key(*oid);
-                ocdg.inodes.entry(*oid).or_insert(new_node);
-                let curr_obj = &log.objects[oid];
-                ocdg.node_attributes.entry(*oid).or_default().node_type = curr_obj.obj_type.to_owned();
+pub fn generate_ocdg_with(log: &Ocel, relations: &[Relations], evidence: Evidence) -> Result<Ocdg, OcdgError> {
+    generate_ocdg_config(log, &GenerationConfig { relations: relations.to_vec(), evidence, ..Default::default() })
+}
 
-            }
-            neighbours.entry(*oid).or_default().extend(&log.events.get(&eid).unwrap().omap);
-        }
+pub fn generate_ocdg_config(log: &Ocel, config: &GenerationConfig) -> Result<Ocdg, OcdgError> {
+    generate_ocdg_report(log, config).map(|(ocdg, _)| ocdg)
+}
+
+pub fn generate_ocdg_report(log: &Ocel, config: &GenerationConfig) -> Result<(Ocdg, GenerationReport), OcdgError> {
+    generate_in_batches(log, config, None).map(|generated| generated.expect("Generation without a sink runs to completion"))
+}
+
+// objects are evaluated this many at a time, the sink sees the edges of every batch before they are applied
+// and stops the generation by returning false, which returns None
+pub(crate) type EdgeSink<'a> = (usize, &'a mut dyn FnMut(&[(usize, usize, EventAdd, Relations)]) -> bool);
+
+pub(crate) fn generate_in_batches(log: &Ocel, config: &GenerationConfig, mut sink: Option<EdgeSink>) -> Result<Option<(Ocdg, GenerationReport)>, OcdgError> {
+    let started = Instant::now();
+    let generated_at = Utc::now();
+    let relations = &config.relations;
+    let mut report = GenerationReport::default();
+    let GenerationInput { mut ocdg, neighbours, dense, handovers } = GenerationInput::new(log, config, &mut report)?;
+    // objects and edges are handled in id order so that node and edge indices do not depend on the hash map iteration order
+    let mut oids: Vec<usize> = ocdg.inodes.keys().copied().collect();
+    oids.sort_unstable();
+    let batch_size = sink.as_ref().map_or(oids.len(), |(batch_size, _)| *batch_size).max(1);
 
+    // the closure needs the DESCENDANTS edges, they are evaluated but not added when only REACHABLE is asked for
+    let closures: Vec<&Relations> = relations.iter().filter(|rel| rel.relation_type() == 4).collect();
+    let hidden_descendants = !closures.is_empty() && !relations.contains(&Relations::DESCENDANTS);
+    let mut evaluated = config.clone();
+    if hidden_descendants {
+        evaluated.relations.push(Relations::DESCENDANTS);
     }
+    let mut chains: IntMap<usize, Vec<(usize, usize)>> = IntMap::default();
 
-    let new_edges: Vec<(usize, usize, EventAdd, Relations)> = ocdg.inodes.par_iter()
-                           .map(|(oid, _)| whole_instance_edges(&log, &ocdg, oid, &neighbours, &rel_whole, &rel_inst))
-                           .flatten()
-                           .collect();
+    let mut ev_added: BTreeSet<usize> = BTreeSet::new();
+    // the closures run in a second pass once every DESCENDANTS edge is known
+    let passes = if closures.is_empty() {1} else {2};
+    for pass in 0..passes {
+        if pass == 1 {
+            for targets in chains.values_mut() {
+                targets.sort_unstable();
+            }
+        }
+        for batch in oids.chunks(batch_size) {
+            let new_edges: Vec<(usize, usize, EventAdd, Relations)> = if pass == 0 {
+                let phase = Instant::now();
+                let mut new_edges: Vec<(usize, usize, EventAdd, Relations)> = batch.par_iter()
+                                       .map(|oid| instance_edges(log, &evaluated, &dense, oid, &neighbours, &handovers))
+                                       .flatten()
+                                       .collect();
+                report.add(GenerationPhase::InstanceRelations, phase.elapsed());
+                let phase = Instant::now();
+                new_edges.par_extend(batch.par_iter().flat_map(|oid| whole_edges(log, &evaluated, oid)));
+                report.add(GenerationPhase::WholeRelations, phase.elapsed());
+                new_edges.sort_by_key(edge_order);
+                if !closures.is_empty() {
+                    // the chains step over the births whichever evidence the DESCENDANTS edges keep
+                    for (src, tar, _, rel) in &new_edges {
+                        if *rel == Relations::DESCENDANTS {
+                            chains.entry(*src).or_default().push((*tar, config.birth_source.birth(log, *tar).event));
+                        }
+                    }
+                    if hidden_descendants {
+                        new_edges.retain(|(_, _, _, rel)| *rel != Relations::DESCENDANTS);
+                    }
+                }
+                new_edges
+            } else {
+                let phase = Instant::now();
+                let mut new_edges: Vec<(usize, usize, EventAdd, Relations)> = batch.par_iter()
+                                                                                   .flat_map(|oid| closures.iter().flat_map(|rel| rel.execute_closure(&chains, *oid)).collect::<Vec<_>>())
+                                                                                   .collect();
+                new_edges.sort_by_key(edge_order);
+                report.add(GenerationPhase::WholeRelations, phase.elapsed());
+                new_edges
+            };
+            let phase = Instant::now();
+            if let Some((_, send)) = sink.as_mut() {
+                if !send(&new_edges) {
+                    return Ok(None);
+                }
+            }
 
-    let mut ev_added: AHashSet<usize> = AHashSet::new();
-    for edge in new_edges {
-        match edge.2.clone() {
-            EventAdd::SINGLE(ev) => {
-                ev_added.insert(ev);
-            },
-            EventAdd::MULTI(evs) => {
-                for ev in evs {
-                    ev_added.insert(ev);
-                    
+            for edge in new_edges {
+                match edge.2.clone() {
+                    EventAdd::SINGLE(ev) => {
+                        ev_added.insert(ev);
+                    },
+                    EventAdd::MULTI(evs) => {
+                        for ev in evs {
+                            ev_added.insert(ev);
+                        }
+                    }
                 }
+
+                ocdg.apply_new_edges((edge.0, edge.1), edge.2, edge.3);
             }
+            report.add(GenerationPhase::EdgeApplication, phase.elapsed());
         }
-
-        ocdg.apply_new_edges((edge.0, edge.1), edge.2, edge.3);
     }
     
+    let phase = Instant::now();
     // add event mappings
     for ev in ev_added {
         ocdg.event_map.insert(log.event_map.get_by_right(&ev).expect("This cannot fail ever").to_owned(), ev);
     }
-    ocdg
+
+    let serialized = serde_json::json!({"relations": relations.iter().map(|rel| rel.to_string()).collect::<Vec<String>>(),
+                                        "evidence": format!("{:?}", config.evidence),
+                                        "type_matching": format!("{:?}", config.type_matching),
+                                        "scope": format!("{:?}", config.scope),
+                                        "inheritance_max_gap_ms": config.inheritance_max_gap.map(|gap| gap.num_milliseconds()),
+                                        "consumes_max_gap_ms": config.consumes_max_gap.map(|gap| gap.num_milliseconds()),
+                                        "handover_witness": format!("{:?}", config.handover_witness),
+                                        "descendants_evidence": format!("{:?}", config.descendants_evidence),
+                                        "birth_source": format!("{:?}", config.birth_source)});
+    ocdg.metadata = Some(GenerationMetadata { crate_version: env!("CARGO_PKG_VERSION").to_owned(),
+                                              config: serialized,
+                                              log_fingerprint: log_fingerprint(log),
+                                              generated_at,
+                                              duration_ms: started.elapsed().as_millis() as u64,
+                                              edge_counts: ocdg.relation_instance_counts() });
+    report.add(GenerationPhase::EdgeApplication, phase.elapsed());
+    report.edge_counts = ocdg.relation_instance_counts();
+    Ok(Some((ocdg, report)))
+}
+
+
+// everything generation needs before the objects are evaluated: the graph with its nodes, the objects sharing an event
+// with each object, the dense lifecycles and the handover candidates
+struct GenerationInput {
+    ocdg: Ocdg,
+    neighbours: IntMap<usize, IntSet<usize>>,
+    dense: DenseLifecycles,
+    handovers: IntMap<usize, IntSet<usize>>
+}
+
+impl GenerationInput {
+    fn new(log: &Ocel, config: &GenerationConfig, report: &mut GenerationReport) -> Result<Self, OcdgError> {
+        let started = Instant::now();
+        let mut ocdg: Ocdg = Ocdg { evidence: config.evidence, ..Default::default() };
+        let mut neighbours: IntMap<usize, IntSet<usize>> = IntMap::default();
+
+        // events without objects are skipped here and never reach the relation checks
+        let mut eids: Vec<&usize> = log.events.keys().collect();
+        eids.sort_unstable();
+        for (eid, data) in eids.into_iter().map(|eid| (eid, log.events.event(eid))).filter(|(_, data)| !data.omap.is_empty()) {
+            let mut omap: Vec<&usize> = data.omap.iter().collect();
+            omap.sort_unstable();
+            for oid in omap {
+                if !ocdg.node_attributes.contains_key(oid) {
+                    let (curr_obj, label) = match (log.objects.get(oid), log.object_map.get_by_right(oid)) {
+                        (Some(curr_obj), Some(label)) => (curr_obj, label),
+                        _ => return Err(OcdgError::MissingObject { event: id_or_index(&log.event_map, eid), object: id_or_index(&log.object_map, oid) })
+                    };
+                    // the relation checks read the lifecycles without bounds checks, so they have to hold only known events
+                    if curr_obj.events.is_empty() {
+                        return Err(OcdgError::EmptyLifecycle(label.to_owned()));
+                    }
+                    if let Some(missing) = curr_obj.events.iter().find(|eid| !log.events.contains_key(eid)) {
+                        return Err(OcdgError::MissingEvent { object: label.to_owned(), event: id_or_index(&log.event_map, missing) });
+                    }
+                    let new_node = ocdg.net.add_node(*oid);
+                    ocdg.object_map.insert(label.to_owned(), *oid);
+                    ocdg.init_object_key(*oid);
+                    ocdg.inodes.entry(*oid).or_insert(new_node);
+                    ocdg.node_attributes.entry(*oid).or_default().node_type = curr_obj.obj_type.to_owned();
+
+                }
+                neighbours.entry(*oid).or_default().extend(&data.omap);
+            }
+
+        }
+
+        let empty_omaps = log.events.values().filter(|event| event.omap.is_empty()).count();
+        if empty_omaps > 0 {
+            report.warnings.push(format!("{} events reference no objects and were skipped", empty_omaps));
+        }
+        let unreferenced = log.objects.len().saturating_sub(ocdg.node_attributes.len());
+        if unreferenced > 0 {
+            report.warnings.push(format!("{} objects are part of no event and have no node", unreferenced));
+        }
+        if let Some(attribute) = config.birth_source.attribute() {
+            let quality = birth_quality(log, attribute);
+            if !quality.early.is_empty() {
+                report.warnings.push(format!("{} objects have events before their {}, see birth_quality", quality.early.len(), attribute));
+            }
+        }
+        report.add(GenerationPhase::NodeConstruction, started.elapsed());
+
+        let started = Instant::now();
+        debug_assert!(log.is_time_ordered(), "Object lifecycles are out of timestamp order, call Ocel::rebuild_derived_indices after mutating the log");
+        let dense = DenseLifecycles::new(log);
+        let handovers = handover_candidates(log, config, &neighbours);
+        report.add(GenerationPhase::PrimitiveRelations, started.elapsed());
+        Ok(GenerationInput { ocdg, neighbours, dense, handovers })
+    }
 }
 
 
-fn whole_instance_edges(log: &Ocel, ocdg:&Ocdg, oid1: &usize, neighs: &IntMap<usize, IntSet<usize>>, rel_whole: &Vec<&Relations>, rel_inst: &Vec<&Relations>) -> Vec<(usize, usize, EventAdd, Relations)> {
+pub fn trace_pair(log: &Ocel, oid1: usize, oid2: usize, rel: Relations) -> TraceResult {
+    trace_pair_config(log, &GenerationConfig::default(), oid1, oid2, rel)
+}
+
+pub fn trace_pair_config(log: &Ocel, config: &GenerationConfig, oid1: usize, oid2: usize, rel: Relations) -> TraceResult {
+    let mut tracer = Tracer::recording();
+    let src_label = resolve_label(&log.object_map, &oid1);
+    let tar_label = resolve_label(&log.object_map, &oid2);
+
+    let known = tracer.check(log.objects.contains_key(&oid1), || format!("source {} {} an object of the log", src_label, is(log.objects.contains_key(&oid1)))) &&
+                tracer.check(log.objects.contains_key(&oid2), || format!("target {} {} an object of the log", tar_label, is(log.objects.contains_key(&oid2))));
+
+    if known {
+        let src_oe = &log.objects[&oid1].events;
+        let tar_oe = &log.objects[&oid2].events;
+        let shared = src_oe.intersect(tar_oe.to_vec()).len();
+        // generation only ever compares objects that appear together in an event, or handover candidates
+        if tracer.check(oid1 != oid2, || "source and target are different objects".to_string()) &&
+           tracer.check(!src_oe.is_empty() && !tar_oe.is_empty(), || "both objects have events".to_string()) &&
+           tracer.check(shared > 0 || config.max_gap(rel).is_some(), || format!("source and target share {} events", shared)) {
+            rel.evidence(log, config, None, oid1, oid2, &mut tracer);
+        }
+    }
+
+    TraceResult { source: src_label.to_owned(), target: tar_label.to_owned(), relation: rel, steps: tracer.steps.unwrap_or_default() }
+}
+
+fn is(condition: bool) -> &'static str {
+    if condition {"is"} else {"is not"}
+}
+
+fn event_label<'a>(log: &'a Ocel, eid: &usize) -> &'a str {
+    resolve_label(&log.event_map, eid)
+}
+
+// edges of a batch are applied by source, target, relation and single event, the parallel evaluation fixes no order
+fn edge_order(edge: &(usize, usize, EventAdd, Relations)) -> (usize, usize, u8, usize) {
+    let eid = match &edge.2 {
+        EventAdd::SINGLE(eid) => *eid,
+        EventAdd::MULTI(eids) => eids.iter().min().copied().unwrap_or(usize::MAX)
+    };
+    (edge.0, edge.1, edge.3.relation_index(), eid)
+}
+
+fn whole_instance_edges(log: &Ocel, config: &GenerationConfig, dense: &DenseLifecycles, oid1: &usize, neighs: &IntMap<usize, IntSet<usize>>, handovers: &IntMap<usize, IntSet<usize>>) -> Vec<(usize, usize, EventAdd, Relations)> {
+    let mut oid_edges = whole_edges(log, config, oid1);
+    oid_edges.extend(instance_edges(log, config, dense, oid1, neighs, handovers));
+    oid_edges
+}
+
+fn whole_edges(log: &Ocel, config: &GenerationConfig, oid1: &usize) -> Vec<(usize, usize, EventAdd, Relations)> {
+    config.relations.iter()
+                    .filter(|r| r.relation_type() == 1)
+                    .flat_map(|rel| rel.execute_whole(log, config, *oid1))
+                    .collect()
+}
+
+// the last event of the source and the objects of its type that start with it, the source included when it is born
+// in its last event. generation and tracing both decide SPLIT with it, the source is never a target of its own split
+fn split_conforming(log: &Ocel, config: &GenerationConfig, oid1: usize) -> (usize, IntSet<usize>) {
+    let src = &log.objects[&oid1];
+    let src_e = *src.events.last().unwrap();
+    let conforming = log.events.event(&src_e).omap.iter()
+                                            .copied()
+                                            .filter(|oid2| config.same_type(&src.obj_type, &log.objects[oid2].obj_type) && log.objects[oid2].events.first() == Some(&src_e))
+                                            .collect();
+    (src_e, conforming)
+}
+
+fn instance_edges(log: &Ocel, config: &GenerationConfig, dense: &DenseLifecycles, oid1: &usize, neighs: &IntMap<usize, IntSet<usize>>, handovers: &IntMap<usize, IntSet<usize>>) -> Vec<(usize, usize, EventAdd, Relations)> {
         // println!("{:?} reporting in!", &oid1);
+        let rel_inst: Vec<_> = config.relations.iter().filter(|r| r.relation_type() == 2).collect();
         let mut oid_edges: Vec<(usize, usize, EventAdd, Relations)> = vec![];
-        for rel in rel_whole {
-            oid_edges.extend(rel.execute_whole(&log, &ocdg, neighs, *oid1));
-        }
         for oid2 in neighs.get(oid1).unwrap() {
             if oid1 != oid2 {
-                for rel in rel_inst {
-                    oid_edges.extend(rel.execute(&log, &ocdg, *oid1, *oid2));
+                for rel in &rel_inst {
+                    oid_edges.extend(rel.execute(log, config, dense, *oid1, *oid2));
                 }
             }
 
         }
+        for oid2 in handovers.get(oid1).into_iter().flatten() {
+            for rel in rel_inst.iter().filter(|rel| config.max_gap(***rel).is_some()) {
+                oid_edges.extend(rel.execute(log, config, dense, *oid1, *oid2));
+            }
+        }
         oid_edges
 
 }
 
+// objects born within the largest gap after another object dies, without sharing an event with it
+fn handover_candidates(log: &Ocel, config: &GenerationConfig, neighs: &IntMap<usize, IntSet<usize>>) -> IntMap<usize, IntSet<usize>> {
+    let mut candidates: IntMap<usize, IntSet<usize>> = IntMap::default();
+    let max_gap = match config.relations.iter().filter_map(|rel| config.max_gap(*rel)).max() {
+        Some(max_gap) => max_gap,
+        None => return candidates
+    };
+    let mut births: Vec<(DateTime<Utc>, usize, usize)> = log.objects.iter()
+                                                            .filter_map(|(oid, obj)| obj.events.first().map(|eid| (log.events.event(eid).timestamp, *eid, *oid)))
+                                                            .collect();
+    births.sort_unstable();
+
+    for (oid1, obj) in &log.objects {
+        let src_e = match obj.events.last() {
+            Some(src_e) => *src_e,
+            None => continue
+        };
+        let died = log.events.event(&src_e).timestamp;
+        let start = births.partition_point(|(born, _, _)| *born < died);
+        for (_, tar_e, oid2) in births[start..].iter().take_while(|(born, _, _)| *born - died <= max_gap) {
+            if oid1 != oid2 && *tar_e != src_e && !neighs.get(oid1).is_some_and(|neighs| neighs.contains(oid2)) && config.witnessed(log, src_e, *tar_e) {
+                candidates.entry(*oid1).or_default().insert(*oid2);
+            }
+        }
+    }
+    candidates
+}
+
+fn resolve_label<'a>(map: &'a BiMap<String, usize>, id: &usize) -> &'a str {
+    map.get_by_right(id).map_or("?", |label| label.as_str())
+}
+
+// ids without a label are shown as their internal index
+fn id_or_index(map: &BiMap<String, usize>, id: &usize) -> String {
+    map.get_by_right(id).map_or_else(|| format!("#{}", id), |label| label.to_owned())
+}
+
+// both lifecycles restricted to the window in which both objects are alive
+fn overlap_lifecycles(log: &Ocel, src_oe: &[usize], tar_oe: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let time = |eid: &usize| log.events.event(eid).timestamp;
+    let (src_first, src_last, tar_first, tar_last) = match (src_oe.first(), src_oe.last(), tar_oe.first(), tar_oe.last()) {
+        (Some(src_first), Some(src_last), Some(tar_first), Some(tar_last)) => (src_first, src_last, tar_first, tar_last),
+        _ => return (vec![], vec![])
+    };
+    let start = time(src_first).max(time(tar_first));
+    let end = time(src_last).min(time(tar_last));
+    let within = |lifecycle: &[usize]| lifecycle.iter().copied().filter(|eid| (start..=end).contains(&time(eid))).collect();
+    (within(src_oe), within(tar_oe))
+}
+
+// lifecycles over dense event ids, long lifecycles also get a bitset once it is no larger than the id list
+struct DenseLifecycles {
+    events: DenseEvents,
+    lifecycles: IntMap<usize, Vec<usize>>,
+    bits: IntMap<usize, EventBitSet>
+}
+
+impl DenseLifecycles {
+    fn new(log: &Ocel) -> Self {
+        let events = log.dense_events();
+        let lifecycles: IntMap<usize, Vec<usize>> = log.objects.keys().map(|oid| (*oid, events.lifecycle(log, *oid))).collect();
+        let bits: IntMap<usize, EventBitSet> = lifecycles.iter()
+                                                         .filter(|(_, lifecycle)| lifecycle.len() * 64 >= events.len())
+                                                         .map(|(oid, lifecycle)| (*oid, events.bitset(lifecycle)))
+                                                         .collect();
+        Self { events, lifecycles, bits }
+    }
+
+    fn contains(&self, oid: usize, eid: usize) -> bool {
+        let dense = match self.events.to_dense(eid) {
+            Some(dense) => dense,
+            None => return false
+        };
+        match self.bits.get(&oid) {
+            Some(bits) => bits.contains(dense),
+            None => self.lifecycles.get(&oid).is_some_and(|lifecycle| lifecycle.binary_search(&dense).is_ok())
+        }
+    }
+
+    // shared events with their original ids
+    fn shared(&self, oid1: usize, oid2: usize) -> IntSet<usize> {
+        let (a, b) = (&self.lifecycles[&oid1], &self.lifecycles[&oid2]);
+        let (shorter, longer_oid) = if a.len() <= b.len() {(a, oid2)} else {(b, oid1)};
+        let shared: Vec<usize> = match self.bits.get(&longer_oid) {
+            Some(bits) => shorter.iter().copied().filter(|dense| bits.contains(*dense)).collect(),
+            None => intersection_count_sorted_vec(a, b).into_iter().collect()
+        };
+        shared.into_iter().filter_map(|dense| self.events.to_original(dense)).collect()
+    }
+}
+
 fn intersection_count_sorted_vec(a: &[usize], b: &[usize]) -> IntSet<usize> {
     let mut intersected: IntSet<usize> = IntSet::default();
     let mut b_iter = b.iter();
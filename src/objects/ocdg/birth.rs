@@ -0,0 +1,92 @@
+use std::borrow::Cow;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::objects::ocdg::{resolve_label, BirthSource};
+use crate::objects::ocel::importer::timestamp::{builtin_formats, TimestampFormat};
+use crate::objects::ocel::Ocel;
+
+// when an object counts as born and the event that gives birth to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Birth {
+    pub timestamp: DateTime<Utc>,
+    pub event: usize
+}
+
+// an object with events before the time its creation attribute declares
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EarlyEvents {
+    pub object: String,
+    pub created_at: DateTime<Utc>,
+    // in lifecycle order
+    pub events: Vec<String>
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BirthQuality {
+    // sorted by object
+    pub early: Vec<EarlyEvents>,
+    // objects without the attribute or with a value that is no timestamp, sorted. their birth is always the first event
+    pub missing: Vec<String>
+}
+
+// strings in any built-in format, numbers as epoch seconds or milliseconds
+pub fn attribute_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    let text: Cow<str> = match value {
+        Value::String(text) => Cow::Borrowed(text),
+        Value::Number(number) => Cow::Owned(number.to_string()),
+        _ => return None
+    };
+    TimestampFormat::Rfc3339.parse(&text).or_else(|| builtin_formats().iter().find_map(|format| format.parse(&text)))
+}
+
+impl BirthSource {
+    // the attribute falls back to the first event when the object does not carry it or its whole lifecycle comes earlier
+    pub fn birth(&self, log: &Ocel, oid: usize) -> Birth {
+        let lifecycle = &log.objects[&oid].events;
+        let first = Birth { timestamp: log.events.event(&lifecycle[0]).timestamp, event: lifecycle[0] };
+        let created_at = match self {
+            BirthSource::FirstEvent => return first,
+            BirthSource::Attribute(attribute) | BirthSource::Earliest(attribute) => match log.objects[&oid].ovmap.get(attribute).and_then(attribute_timestamp) {
+                Some(created_at) => created_at,
+                None => return first
+            }
+        };
+        match self {
+            BirthSource::Earliest(_) => Birth { timestamp: created_at.min(first.timestamp), event: first.event },
+            _ => lifecycle.iter()
+                          .find(|eid| log.events.event(eid).timestamp >= created_at)
+                          .map_or(first, |eid| Birth { timestamp: created_at, event: *eid })
+        }
+    }
+
+    // the attribute the birth is read from, None for FirstEvent
+    pub fn attribute(&self) -> Option<&str> {
+        match self {
+            BirthSource::FirstEvent => None,
+            BirthSource::Attribute(attribute) | BirthSource::Earliest(attribute) => Some(attribute)
+        }
+    }
+}
+
+// the objects whose lifecycle starts before the creation attribute, usually clock skew between systems
+pub fn birth_quality(log: &Ocel, attribute: &str) -> BirthQuality {
+    let mut quality = BirthQuality::default();
+    for (oid, obj) in &log.objects {
+        let object = resolve_label(&log.object_map, oid).to_owned();
+        let Some(created_at) = obj.ovmap.get(attribute).and_then(attribute_timestamp) else {
+            quality.missing.push(object);
+            continue;
+        };
+        let events: Vec<String> = obj.events.iter()
+                                     .filter(|eid| log.events.get(eid).is_some_and(|event| event.timestamp < created_at))
+                                     .map(|eid| resolve_label(&log.event_map, eid).to_owned())
+                                     .collect();
+        if !events.is_empty() {
+            quality.early.push(EarlyEvents { object, created_at, events });
+        }
+    }
+    quality.early.sort_by(|a, b| a.object.cmp(&b.object));
+    quality.missing.sort();
+    quality
+}
@@ -0,0 +1,51 @@
+use nohash_hasher::{IntMap, IntSet};
+use petgraph::unionfind::UnionFind;
+use petgraph::visit::EdgeRef;
+
+use crate::objects::ocdg::{Ocdg, Relations};
+
+impl Ocdg {
+    // weakly connected components, the independent process executions of the log. sorted by their smallest object id
+    pub fn connected_components(&self) -> Vec<IntSet<usize>> {
+        self.connected_components_with(None)
+    }
+
+    // only edges holding one of the relations join two objects, objects without any are components of their own
+    pub fn connected_components_with(&self, relations: Option<&[Relations]>) -> Vec<IntSet<usize>> {
+        let kept: Option<IntSet<usize>> = relations.map(|relations| relations.iter().map(|rel| rel.relation_index().into()).collect());
+        let mut sets: UnionFind<usize> = UnionFind::new(self.net.node_count());
+        for edge in self.net.edge_references() {
+            let (src, tar) = (self.net[edge.source()], self.net[edge.target()]);
+            let joins = kept.as_ref().is_none_or(|kept| {
+                self.irels.get(&src).and_then(|targets| targets.get(&tar)).is_some_and(|rels| rels.keys().any(|rel| kept.contains(rel)))
+            });
+            if joins {
+                sets.union(edge.source().index(), edge.target().index());
+            }
+        }
+
+        let mut components: IntMap<usize, IntSet<usize>> = IntMap::default();
+        for node in self.net.node_indices() {
+            let oid = *self.net.node_weight(node).expect("Node index comes from the graph");
+            components.entry(sets.find(node.index())).or_default().insert(oid);
+        }
+        let mut components: Vec<IntSet<usize>> = components.into_values().collect();
+        components.sort_by_key(|component| component.iter().min().copied());
+        components
+    }
+
+    // the objects connected to oid over edges in either direction, the object included. None for unknown objects
+    pub fn component_of(&self, oid: usize) -> Option<IntSet<usize>> {
+        let start = *self.inodes.get(&oid)?;
+        let mut component: IntSet<usize> = IntSet::from_iter([oid]);
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for neighbour in self.net.neighbors_undirected(node) {
+                if component.insert(self.net[neighbour]) {
+                    stack.push(neighbour);
+                }
+            }
+        }
+        Some(component)
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::BTreeSet;
+use nohash_hasher::{IntMap, IntSet};
+
+use crate::objects::ocdg::{NodeInfo, Ocdg, Relations};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractNaming {
+    // label of the member with the smallest oid
+    Representative,
+    // all member labels, sorted and joined with the separator
+    Joined(String),
+    // prefix followed by the position of the group
+    Prefixed(String)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ContractionMap {
+    // super-node oid -> contracted oids, sorted
+    pub members: IntMap<usize, Vec<usize>>,
+    // contracted oid -> super-node oid
+    pub node_of: IntMap<usize, usize>
+}
+
+impl ContractionMap {
+    pub fn resolve(&self, oid: usize) -> usize {
+        self.node_of.get(&oid).copied().unwrap_or(oid)
+    }
+
+    pub fn expand(&self, node: usize) -> Vec<usize> {
+        self.members.get(&node).cloned().unwrap_or_else(|| vec![node])
+    }
+
+    pub fn is_super_node(&self, node: usize) -> bool {
+        self.members.contains_key(&node)
+    }
+}
+
+impl Ocdg {
+    // connected components of the COLIFE edges, every object in a group shares the same lifecycle
+    pub fn colife_groups(&self) -> Vec<Vec<usize>> {
+        let colife: usize = Relations::COLIFE.relation_index().into();
+        let mut seen: IntSet<usize> = IntSet::default();
+        let mut oids: Vec<usize> = self.node_attributes.keys().copied().collect();
+        oids.sort_unstable();
+
+        let mut groups: Vec<Vec<usize>> = vec![];
+        for oid in oids {
+            if !seen.insert(oid) {
+                continue;
+            }
+            let mut group: Vec<usize> = vec![];
+            let mut stack: Vec<usize> = vec![oid];
+            while let Some(current) = stack.pop() {
+                group.push(current);
+                if let Some(targets) = self.irels.get(&current) {
+                    for (tar, rels) in targets {
+                        if rels.contains_key(&colife) && seen.insert(*tar) {
+                            stack.push(*tar);
+                        }
+                    }
+                }
+            }
+            if group.len() > 1 {
+                group.sort_unstable();
+                groups.push(group);
+            }
+        }
+        groups
+    }
+
+    // groups with fewer than two known objects are left alone, an object only joins the first group it appears in
+    pub fn contract(&self, groups: &[Vec<usize>], naming: ContractNaming) -> (Ocdg, ContractionMap) {
+        let mut map = ContractionMap::default();
+        let mut names: IntMap<usize, String> = IntMap::default();
+        for (index, group) in groups.iter().enumerate() {
+            let members: Vec<usize> = group.iter()
+                                               .copied()
+                                               .filter(|oid| self.node_attributes.contains_key(oid) && !map.node_of.contains_key(oid))
+                                               .collect::<BTreeSet<usize>>()
+                                               .into_iter()
+                                               .collect();
+            if members.len() < 2 {
+                continue;
+            }
+            let node = members[0];
+            let label = |oid: &usize| self.object_map.get_by_right(oid).map_or_else(|| oid.to_string(), |label| label.to_owned());
+            let name = match &naming {
+                ContractNaming::Representative => label(&node),
+                ContractNaming::Joined(separator) => {
+                    let mut labels: Vec<String> = members.iter().map(label).collect();
+                    labels.sort();
+                    labels.join(separator)
+                },
+                ContractNaming::Prefixed(prefix) => format!("{}{}", prefix, index)
+            };
+            for oid in &members {
+                map.node_of.insert(*oid, node);
+            }
+            names.insert(node, name);
+            map.members.insert(node, members);
+        }
+
+        let mut contracted = Ocdg { evidence: self.evidence,
+                                    event_map: self.event_map.clone(),
+                                    ievents: self.ievents.as_ref().map(|_| Default::default()),
+                                    irels_in: self.irels_in.as_ref().map(|_| Default::default()),
+                                    ..Default::default() };
+        let mut oids: Vec<usize> = self.node_attributes.keys().map(|oid| map.resolve(*oid)).collect::<IntSet<usize>>().into_iter().collect();
+        oids.sort_unstable();
+        for oid in oids {
+            let node_info = match map.members.get(&oid) {
+                Some(members) => {
+                    let types: BTreeSet<&str> = members.iter().map(|member| self.node_attributes[member].node_type.as_str()).collect();
+                    NodeInfo { node_type: types.into_iter().collect::<Vec<&str>>().join("+"), members: members.clone() }
+                },
+                None => NodeInfo { node_type: self.node_attributes[&oid].node_type.to_owned(), members: self.node_attributes[&oid].members.clone() }
+            };
+            let name = match names.get(&oid) {
+                Some(name) => name.to_owned(),
+                None => self.object_map.get_by_right(&oid).map_or_else(|| oid.to_string(), |label| label.to_owned())
+            };
+            contracted.object_map.insert(name, oid);
+            contracted.node_attributes.insert(oid, node_info);
+            contracted.inodes.insert(oid, contracted.net.add_node(oid));
+        }
+
+        for (src, targets) in &self.irels {
+            let new_src = map.resolve(*src);
+            for (tar, rels) in targets {
+                let new_tar = map.resolve(*tar);
+                if new_src == new_tar {
+                    continue;
+                }
+                let edge_rels = contracted.irels.entry(new_src).or_default().entry(new_tar).or_default();
+                for (rel, events) in rels {
+                    edge_rels.entry(*rel).or_default().extend(events);
+                }
+            }
+        }
+
+        // the events behind the counts are gone, the largest member count is the safe lower bound
+        for (src, targets) in &self.irel_counts {
+            let new_src = map.resolve(*src);
+            for (tar, counts) in targets {
+                let new_tar = map.resolve(*tar);
+                if new_src == new_tar {
+                    continue;
+                }
+                let edge_counts = contracted.irel_counts.entry(new_src).or_default().entry(new_tar).or_default();
+                for (rel, count) in counts {
+                    let current = edge_counts.entry(*rel).or_default();
+                    *current = (*current).max(*count);
+                }
+            }
+        }
+
+        for (src, targets) in &contracted.irels {
+            for tar in targets.keys() {
+                contracted.net.add_edge(contracted.inodes[src], contracted.inodes[tar], 0);
+            }
+        }
+        contracted.reindex();
+
+        (contracted, map)
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::Write;
+use nohash_hasher::IntSet;
+
+use crate::objects::exporter::StreamingExporter;
+use crate::objects::ocdg::{Evidence, Ocdg};
+use crate::objects::ocel::Ocel;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActivityCoverage {
+    pub activity: String,
+    pub events: usize,
+    pub uncovered: usize,
+    // uncovered events that only touched a single object
+    pub single_object: usize
+}
+
+impl ActivityCoverage {
+    pub fn uncovered_rate(&self) -> f64 {
+        if self.events == 0 {0.0} else {self.uncovered as f64 / self.events as f64}
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UncoveredReport {
+    // labels of the events without relation evidence, sorted
+    pub events: Vec<String>,
+    // sorted by activity
+    pub activities: Vec<ActivityCoverage>
+}
+
+impl UncoveredReport {
+    pub fn activity(&self, activity: &str) -> Option<&ActivityCoverage> {
+        self.activities.iter().find(|coverage| coverage.activity == activity)
+    }
+
+    pub fn uncovered_rate(&self) -> f64 {
+        let total: usize = self.activities.iter().map(|coverage| coverage.events).sum();
+        if total == 0 {0.0} else {self.events.len() as f64 / total as f64}
+    }
+
+    pub fn export_csv(&self, file_path: &str) -> Result<bool, Box<dyn Error>> {
+        UncoveredCsv(self).export(file_path)?;
+        Ok(true)
+    }
+}
+
+pub struct UncoveredCsv<'a>(pub &'a UncoveredReport);
+
+// one chunk for the header and one per activity
+impl<'a> StreamingExporter for UncoveredCsv<'a> {
+    fn chunk_count(&self) -> usize {
+        self.0.activities.len() + 1
+    }
+
+    fn write_chunk(&self, index: usize, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        match index {
+            0 => writeln!(f, "activity,events,uncovered,single_object,uncovered_rate")?,
+            _ => {
+                let coverage = &self.0.activities[index - 1];
+                let activity = if coverage.activity.contains([',', '"', '\n']) {
+                    format!("\"{}\"", coverage.activity.replace('"', "\"\""))
+                } else {
+                    coverage.activity.to_owned()
+                };
+                writeln!(f, "{},{},{},{},{}", activity, coverage.events, coverage.uncovered, coverage.single_object, coverage.uncovered_rate())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Ocdg {
+    // events of the log that are not evidence of any relation instance, only known with full evidence
+    pub fn uncovered_events(&self, ocel: &Ocel) -> Option<UncoveredReport> {
+        if self.evidence != Evidence::Full {
+            return None;
+        }
+        let covered: IntSet<usize> = match &self.ievents {
+            Some(ievents) => ievents.keys().copied().collect(),
+            None => self.irels.values()
+                              .flat_map(|targets| targets.values())
+                              .flat_map(|rels| rels.values())
+                              .flatten()
+                              .copied()
+                              .collect()
+        };
+
+        let mut activities: BTreeMap<&str, ActivityCoverage> = BTreeMap::new();
+        let mut events: Vec<String> = vec![];
+        for (label, eid) in &ocel.event_map {
+            let event = &ocel.events.event(eid);
+            let coverage = activities.entry(event.activity).or_insert_with(|| ActivityCoverage { activity: event.activity.to_owned(), ..Default::default() });
+            coverage.events += 1;
+            if !covered.contains(eid) {
+                coverage.uncovered += 1;
+                if event.omap.len() < 2 {
+                    coverage.single_object += 1;
+                }
+                events.push(label.to_owned());
+            }
+        }
+        events.sort();
+
+        Some(UncoveredReport { events, activities: activities.into_values().collect() })
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+use ahash::AHashMap;
+
+use crate::objects::ocdg::{Evidence, Ocdg, Relations};
+use crate::objects::ocel::Ocel;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DensityEntry {
+    pub src_type: String,
+    pub tar_type: String,
+    pub relation: Relations,
+    pub edges: usize,
+    pub max_edges: usize,
+    pub density: f64,
+    pub flagged: bool,
+    pub suggested_min_edge_events: Option<usize>
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DensityReport {
+    pub target_density: f64,
+    pub entries: Vec<DensityEntry>
+}
+
+impl DensityReport {
+    pub fn flagged(&self) -> impl Iterator<Item = &DensityEntry> {
+        self.entries.iter().filter(|entry| entry.flagged)
+    }
+
+    // drops the relation instances of flagged combinations below the suggested support
+    pub fn apply(&self, ocdg: &mut Ocdg) {
+        let thresholds: AHashMap<(&str, &str, usize), usize> = self.flagged()
+                                                                   .filter_map(|entry| entry.suggested_min_edge_events.map(|t| ((entry.src_type.as_str(), entry.tar_type.as_str(), entry.relation.relation_index().into()), t)))
+                                                                   .collect();
+        let node_types: AHashMap<usize, String> = ocdg.node_attributes.iter().map(|(oid, node)| (*oid, node.node_type.to_owned())).collect();
+        let mut dropped: Vec<(usize, usize, Relations)> = vec![];
+        for (src, targets) in &ocdg.irels {
+            for (tar, rels) in targets {
+                for rel in rels.keys() {
+                    let relation = match Relations::from_index(*rel) {
+                        Some(relation) => relation,
+                        None => continue
+                    };
+                    if let Some(threshold) = thresholds.get(&(node_types[src].as_str(), node_types[tar].as_str(), *rel)) {
+                        if ocdg.relation_support(*src, *tar, relation).unwrap_or(0) < *threshold {
+                            dropped.push((*src, *tar, relation));
+                        }
+                    }
+                }
+            }
+        }
+        for (src, tar, relation) in dropped {
+            let rel: usize = relation.relation_index().into();
+            if let Some(rels) = ocdg.irels.get_mut(&src).and_then(|targets| targets.get_mut(&tar)) {
+                rels.remove(&rel);
+            }
+            if let Some(counts) = ocdg.irel_counts.get_mut(&src).and_then(|targets| targets.get_mut(&tar)) {
+                counts.remove(&rel);
+            }
+        }
+        ocdg.retain_edges(|edge| !edge.relations.is_empty());
+    }
+}
+
+impl Ocdg {
+    pub fn density_report(&self, ocel: &Ocel, target_density: f64) -> DensityReport {
+        let mut type_counts: AHashMap<&str, usize> = AHashMap::new();
+        for obj in ocel.objects.values() {
+            *type_counts.entry(obj.obj_type.as_str()).or_default() += 1;
+        }
+
+        // edge support distribution per (type, type, relation)
+        let mut supports: BTreeMap<(&str, &str, usize), Vec<usize>> = BTreeMap::new();
+        for (src, targets) in &self.irels {
+            for (tar, rels) in targets {
+                for rel in rels.keys() {
+                    let support = Relations::from_index(*rel).and_then(|relation| self.relation_support(*src, *tar, relation)).unwrap_or(0);
+                    supports.entry((&self.node_attributes[src].node_type, &self.node_attributes[tar].node_type, *rel))
+                            .or_default()
+                            .push(support);
+                }
+            }
+        }
+
+        let mut report = DensityReport { target_density, entries: vec![] };
+        for ((src_type, tar_type, rel), mut support) in supports {
+            let relation = match Relations::from_index(rel) {
+                Some(relation) => relation,
+                None => continue
+            };
+            let max_edges = type_counts.get(src_type).copied().unwrap_or(0) * type_counts.get(tar_type).copied().unwrap_or(0);
+            let density = if max_edges == 0 {0.0} else {support.len() as f64 / max_edges as f64};
+            let flagged = density > target_density;
+
+            // smallest support threshold that keeps at most target_density * max_edges edges
+            let suggested_min_edge_events = if flagged && self.evidence != Evidence::None {
+                support.sort_unstable();
+                let allowed = (target_density * max_edges as f64).floor() as usize;
+                let mut threshold = support.last().copied().unwrap_or(0) + 1;
+                for candidate in support.iter().copied().rev() {
+                    let kept = support.len() - support.partition_point(|s| *s < candidate);
+                    if kept > allowed {
+                        break;
+                    }
+                    threshold = candidate;
+                }
+                Some(threshold)
+            } else {
+                None
+            };
+
+            report.entries.push(DensityEntry { src_type: src_type.to_owned(),
+                                               tar_type: tar_type.to_owned(),
+                                               relation,
+                                               edges: support.len(),
+                                               max_edges,
+                                               density,
+                                               flagged,
+                                               suggested_min_edge_events });
+        }
+        report
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::mem::size_of;
+use std::time::Instant;
+use ahash::AHashSet;
+use nohash_hasher::{IntMap, IntSet};
+use petgraph::graph::{EdgeIndex, NodeIndex};
+
+use crate::algo::privacy::SplitMix;
+use crate::objects::ocdg::metadata::GenerationReport;
+use crate::objects::ocdg::{whole_instance_edges, EventAdd, Evidence, GenerationConfig, GenerationInput, NodeInfo, OcdgError, Relations};
+use crate::objects::ocel::Ocel;
+
+#[derive(Debug, Clone, Copy)]
+pub struct EstimateConfig {
+    // objects evaluated for the estimate, all of them when the log has fewer
+    pub sample_objects: usize,
+    pub seed: u64
+}
+
+impl Default for EstimateConfig {
+    fn default() -> Self {
+        Self { sample_objects: 500, seed: 0 }
+    }
+}
+
+// expected value with a 95% confidence range, the range is empty when every object was sampled
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EstimateRange {
+    pub low: f64,
+    pub expected: f64,
+    pub high: f64
+}
+
+impl EstimateRange {
+    fn exact(value: f64) -> Self {
+        Self { low: value, expected: value, high: value }
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        self.low <= value && value <= self.high
+    }
+
+    fn scaled(&self, factor: f64) -> Self {
+        Self { low: self.low * factor, expected: self.expected * factor, high: self.high * factor }
+    }
+
+    fn plus(&self, other: &EstimateRange) -> Self {
+        Self { low: self.low + other.low, expected: self.expected + other.expected, high: self.high + other.high }
+    }
+}
+
+impl fmt::Display for EstimateRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.0} [{:.0}, {:.0}]", self.expected, self.low, self.high)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GenerationEstimate {
+    // objects appearing in an event, exact
+    pub nodes: usize,
+    // object pairs the relations are evaluated on (objects sharing an event plus handover candidates), exact
+    pub pairs: usize,
+    pub sampled_objects: usize,
+    // measured seconds per evaluated pair on the sample, one thread
+    pub pair_cost: f64,
+    // relation name -> relation instances
+    pub edges: BTreeMap<String, EstimateRange>,
+    // distinct (source, target) pairs holding any relation
+    pub graph_edges: EstimateRange,
+    pub evidence_events: EstimateRange,
+    pub peak_bytes: EstimateRange,
+    // seconds, the evaluation is spread over the rayon threads
+    pub runtime: EstimateRange
+}
+
+impl fmt::Display for GenerationEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:<28} {}", "nodes", self.nodes)?;
+        writeln!(f, "{:<28} {}", "evaluated pairs", self.pairs)?;
+        writeln!(f, "{:<28} {} ({:.3} us per pair)", "sampled objects", self.sampled_objects, self.pair_cost * 1e6)?;
+        for (rel, range) in &self.edges {
+            writeln!(f, "{:<28} {}", rel, range)?;
+        }
+        writeln!(f, "{:<28} {}", "graph edges", self.graph_edges)?;
+        writeln!(f, "{:<28} {:.1} MB [{:.1}, {:.1}]", "peak memory", self.peak_bytes.expected / 1e6, self.peak_bytes.low / 1e6, self.peak_bytes.high / 1e6)?;
+        writeln!(f, "{:<28} {:.2} s [{:.2}, {:.2}]", "runtime", self.runtime.expected, self.runtime.low, self.runtime.high)
+    }
+}
+
+struct ObjectSample {
+    units: f64,
+    per_relation: BTreeMap<String, f64>,
+    graph_edges: f64,
+    events: f64,
+    seconds: f64
+}
+
+pub fn estimate(log: &Ocel, config: &GenerationConfig) -> Result<GenerationEstimate, OcdgError> {
+    estimate_with(log, config, &EstimateConfig::default())
+}
+
+// builds everything generation needs up front, evaluates a sample of objects exactly like generation does and scales
+// the results by the evaluated pairs. each object costs a fixed amount plus one unit per pair
+pub fn estimate_with(log: &Ocel, config: &GenerationConfig, estimate_config: &EstimateConfig) -> Result<GenerationEstimate, OcdgError> {
+    let started = Instant::now();
+    let input = GenerationInput::new(log, config, &mut GenerationReport::default())?;
+    let setup = started.elapsed().as_secs_f64();
+
+    let mut oids: Vec<usize> = input.ocdg.inodes.keys().copied().collect();
+    oids.sort_unstable();
+    let units = |oid: &usize| -> f64 {
+        let pairs = input.neighbours.get(oid).map_or(0, |neighs| neighs.len().saturating_sub(1)) + input.handovers.get(oid).map_or(0, |cands| cands.len());
+        (pairs + 1) as f64
+    };
+    let pairs: usize = oids.iter().map(|oid| units(oid) as usize - 1).sum();
+    let total_units: f64 = oids.iter().map(units).sum();
+
+    let mut rng = SplitMix(estimate_config.seed);
+    for i in (1..oids.len()).rev() {
+        oids.swap(i, (rng.next_u64() % (i as u64 + 1)) as usize);
+    }
+    oids.truncate(estimate_config.sample_objects.max(1));
+
+    // a graph edge can be found from both of its objects, it counts for the first sampled one
+    let mut seen: AHashSet<(usize, usize)> = AHashSet::new();
+    let mut samples: Vec<ObjectSample> = vec![];
+    let mut chains: IntMap<usize, Vec<(usize, usize)>> = IntMap::default();
+    for oid in &oids {
+        let evaluated = Instant::now();
+        let mut edges = whole_instance_edges(log, config, &input.dense, oid, &input.neighbours, &input.handovers);
+        if config.relations.contains(&Relations::REACHABLE) {
+            edges.extend(reachable_edges(log, config, &input, &mut chains, *oid));
+        }
+        let seconds = evaluated.elapsed().as_secs_f64();
+
+        let mut per_relation: BTreeMap<String, f64> = config.relations.iter().map(|rel| (rel.to_string(), 0.0)).collect();
+        let mut graph_edges = 0;
+        let mut events = 0;
+        for (src, tar, eids, rel) in &edges {
+            *per_relation.entry(rel.to_string()).or_default() += 1.0;
+            if seen.insert((*src, *tar)) {
+                graph_edges += 1;
+            }
+            events += match eids {
+                EventAdd::SINGLE(_) => 1,
+                EventAdd::MULTI(eids) => eids.len()
+            };
+        }
+        samples.push(ObjectSample { units: units(oid), per_relation, graph_edges: graph_edges as f64, events: events as f64, seconds });
+    }
+
+    let population = input.ocdg.inodes.len();
+    let scale = |values: Vec<(f64, f64)>| ratio_estimate(&values, total_units, population);
+    let mut estimate = GenerationEstimate { nodes: population, pairs, sampled_objects: samples.len(), ..Default::default() };
+    for rel in &config.relations {
+        let name = rel.to_string();
+        estimate.edges.insert(name.to_owned(), scale(samples.iter().map(|sample| (sample.units, sample.per_relation[&name])).collect()));
+    }
+    estimate.graph_edges = scale(samples.iter().map(|sample| (sample.units, sample.graph_edges)).collect());
+    estimate.evidence_events = scale(samples.iter().map(|sample| (sample.units, sample.events)).collect());
+    let seconds = scale(samples.iter().map(|sample| (sample.units, sample.seconds)).collect());
+    let sampled_units: f64 = samples.iter().map(|sample| sample.units).sum();
+    estimate.pair_cost = if sampled_units > 0.0 {samples.iter().map(|sample| sample.seconds).sum::<f64>() / sampled_units} else {0.0};
+    estimate.runtime = seconds.scaled(1.0 / rayon::current_num_threads() as f64).plus(&EstimateRange::exact(setup));
+
+    // the log and the neighbourhoods stay alive next to the growing graph
+    let instances = estimate.edges.values().fold(EstimateRange::default(), |total, range| total.plus(range));
+    let fixed = log.memory_breakdown().total() +
+                input.neighbours.values().map(|neighs| size_of::<(usize, IntSet<usize>)>() + neighs.len() * size_of::<usize>()).sum::<usize>() +
+                input.ocdg.object_map.iter().map(|(label, _)| 2 * (size_of::<String>() + size_of::<usize>()) + label.len()).sum::<usize>() +
+                population * (size_of::<usize>() + 2 * size_of::<EdgeIndex>() + size_of::<(usize, NodeIndex)>() + size_of::<(usize, NodeInfo)>());
+    let edge_bytes = size_of::<usize>() + 2 * size_of::<NodeIndex>() + 2 * size_of::<EdgeIndex>() + size_of::<(usize, EdgeIndex)>() + size_of::<(usize, IntSet<usize>)>();
+    let instance_bytes = match config.evidence {
+        Evidence::CountsOnly => size_of::<(usize, IntSet<usize>)>() + size_of::<(usize, usize)>(),
+        _ => size_of::<(usize, IntSet<usize>)>()
+    };
+    let mut peak = EstimateRange::exact(fixed as f64).plus(&estimate.graph_edges.scaled(edge_bytes as f64)).plus(&instances.scaled(instance_bytes as f64));
+    if config.evidence == Evidence::Full {
+        peak = peak.plus(&estimate.evidence_events.scaled(size_of::<usize>() as f64));
+    }
+    estimate.peak_bytes = peak;
+    Ok(estimate)
+}
+
+// the DESCENDANTS chains below the object are evaluated on demand and shared across the sample
+fn reachable_edges(log: &Ocel, config: &GenerationConfig, input: &GenerationInput, chains: &mut IntMap<usize, Vec<(usize, usize)>>, oid: usize) -> Vec<(usize, usize, EventAdd, Relations)> {
+    let mut stack = vec![oid];
+    while let Some(src) = stack.pop() {
+        if chains.contains_key(&src) {
+            continue;
+        }
+        let mut targets: Vec<(usize, usize)> = input.neighbours.get(&src).into_iter().flatten()
+                                                    .filter(|tar| **tar != src)
+                                                    .flat_map(|tar| Relations::DESCENDANTS.execute(log, config, &input.dense, src, *tar))
+                                                    .filter_map(|(_, tar, eids, _)| match eids {
+                                                        EventAdd::SINGLE(eid) => Some((tar, eid)),
+                                                        EventAdd::MULTI(_) => None
+                                                    })
+                                                    .collect();
+        targets.sort_unstable();
+        stack.extend(targets.iter().map(|(tar, _)| *tar));
+        chains.insert(src, targets);
+    }
+    Relations::REACHABLE.execute_closure(chains, oid)
+}
+
+// ratio estimator of a population total from (units, value) samples, scaled to the known total units.
+// the range uses the normal approximation with the finite population correction
+fn ratio_estimate(samples: &[(f64, f64)], total_units: f64, population: usize) -> EstimateRange {
+    let n = samples.len() as f64;
+    let sampled_units: f64 = samples.iter().map(|(units, _)| units).sum();
+    if n == 0.0 || sampled_units == 0.0 {
+        return EstimateRange::default();
+    }
+    let ratio = samples.iter().map(|(_, value)| value).sum::<f64>() / sampled_units;
+    let expected = ratio * total_units;
+    if n < 2.0 || samples.len() >= population {
+        return EstimateRange::exact(expected);
+    }
+    let residuals = samples.iter().map(|(units, value)| (value - ratio * units).powi(2)).sum::<f64>() / (n - 1.0);
+    let population = population as f64;
+    let error = 1.96 * population * ((1.0 - n / population) * residuals / n).sqrt();
+    EstimateRange { low: (expected - error).max(0.0), expected, high: expected + error }
+}
@@ -1,18 +1,312 @@
 pub(crate) mod variants;
+pub(crate) mod style;
+pub(crate) mod split;
 
 use std::error::Error;
+use std::io::Write;
+use std::path::Path;
+
+use bimap::BiMap;
+use serde_json::Value;
 
 use crate::objects::ocel::Ocel;
 
-use self::variants::gexf::{export_gexf_ocdg, export_gexf_ocdg_string};
+use self::split::{node_size, edge_size, partition};
+use self::variants::gexf::{export_gexf_ocdg, export_gexf_ocdg_part, export_gexf_ocdg_string};
+use self::variants::graphml::{export_graphml_ocdg, export_graphml_ocdg_part, export_graphml_ocdg_styled, graphml_ocdg_string, write_graphml_ocdg};
+use self::variants::cytoscape::export_cytoscape_ocdg;
+use self::variants::dot::OcdgDot;
+use self::variants::json::export_json_ocdg;
+use self::variants::neighbors::write_neighbor_documents;
+use crate::objects::exporter::{ExportError, Exporter};
+
+use super::{Ocdg, Relations};
+
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    // one integer attribute per relation holding its supporting event count, instead of the event lists
+    pub per_relation_keys: bool,
+    // node colors need the log, see export_ocdg_graphml_styled
+    pub style: Option<VisualStyle>,
+    // graphml only: a string attribute per relation with its supporting event ids joined by |, e.g. rel_INHERITANCE_events
+    pub relation_events: bool,
+    // graphml only: the relations written, edges left without any are dropped. None writes all of them
+    pub relations: Option<Vec<Relations>>
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DotOptions {
+    // one color per relation, edges holding several relations are drawn as parallel strokes
+    pub color_relations: bool,
+    // nodes are filled with their color, edge widths go along with the relation colors
+    pub style: Option<VisualStyle>,
+    // only these relations are drawn, edges left without any are dropped. None draws all of them
+    pub relations: Option<Vec<Relations>>,
+    // a graph instead of a digraph, both directions of a pair are one edge holding the relations of either
+    pub undirected: bool
+}
+
+// colors nodes by an object attribute and widens edges by the supporting events of one relation.
+// the mapping is written into the export as a legend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisualStyle {
+    // attribute of the ovmap, its distinct values get the palette colors in sorted order and share them once it runs out
+    pub node_color_by: Option<String>,
+    pub palette: Vec<String>,
+    // edges with the relation are 1 wide plus one for every bucket bound their event count reaches
+    pub edge_width_by: Option<Relations>,
+    // ascending event count bounds
+    pub buckets: Vec<usize>
+}
+
+impl Default for VisualStyle {
+    fn default() -> Self {
+        let palette = ["#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f", "#bcbd22", "#17becf"];
+        Self { node_color_by: None, palette: palette.iter().map(|color| color.to_string()).collect(), edge_width_by: None, buckets: vec![2, 5, 10] }
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    GraphMl,
+    Gexf
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitMode {
+    // whole connected components packed into files up to the limit, no edge crosses two files
+    Component,
+    // the objects spread over this many files by a hash of their id. an edge is written with its source,
+    // so the targets in other files are repeated as nodes
+    NodeHash(usize)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SizeCheck {
+    // gephi and yed struggle with files of a few hundred megabytes
+    pub max_bytes: u64,
+    // None writes a single file however large the estimate is
+    pub split: Option<SplitMode>
+}
+
+impl Default for SizeCheck {
+    fn default() -> Self {
+        Self { max_bytes: 256 * 1024 * 1024, split: None }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportReport {
+    pub estimated_bytes: u64,
+    // the files written with their sizes, the parts in order when split
+    pub files: Vec<(String, u64)>,
+    // the json listing the parts, only written when the graph is split
+    pub manifest: Option<String>,
+    pub warnings: Vec<String>
+}
+
+// the size of a graphml or gexf export from the labels and relations of the graph, without writing it
+pub fn estimate_export_size(g: &Ocdg, format: GraphFormat, options: &ExportOptions) -> u64 {
+    let nodes: u64 = g.node_attributes.keys().map(|oid| node_size(g, *oid, format)).sum();
+    let edges: u64 = g.irels.iter().flat_map(|(src, targets)| targets.keys().map(move |tar| (*src, *tar))).map(|(src, tar)| edge_size(g, src, tar, format, options)).sum();
+    nodes + edges
+}
 
-use super::Ocdg;
+// exports after estimating the size. over the limit a warning is reported and with a split mode the graph is written as
+// <stem>.part<n>.<extension> files next to the path, tied together by <stem>.manifest.json
+pub fn export_ocdg_checked(g: &Ocdg, file_path: &str, format: GraphFormat, options: &ExportOptions, check: &SizeCheck) -> Result<ExportReport, Box<dyn Error>> {
+    let mut report = ExportReport { estimated_bytes: estimate_export_size(g, format, options), ..Default::default() };
+    let split = match check.split {
+        Some(split) if report.estimated_bytes > check.max_bytes => split,
+        _ => {
+            if report.estimated_bytes > check.max_bytes {
+                report.warnings.push(format!("The export is estimated at {} bytes, over the limit of {} bytes", report.estimated_bytes, check.max_bytes));
+            }
+            let part = g.node_attributes.keys().copied().collect();
+            let bytes = export_part(g, file_path, format, options, &part)?;
+            report.files.push((file_path.to_owned(), bytes));
+            return Ok(report);
+        }
+    };
+    report.warnings.push(format!("The export is estimated at {} bytes, over the limit of {} bytes, it is split by {:?}", report.estimated_bytes, check.max_bytes, split));
 
+    let path = Path::new(file_path);
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("ocdg");
+    let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or(match format {
+        GraphFormat::GraphMl => "graphml",
+        GraphFormat::Gexf => "gexf"
+    });
+    let mut parts: Vec<serde_json::Value> = vec![];
+    for (index, part) in partition(g, split, check.max_bytes, format, options).iter().enumerate() {
+        let part_path = path.with_file_name(format!("{}.part{}.{}", stem, index + 1, extension));
+        let part_path = part_path.to_str().ok_or("The export path is not valid unicode")?;
+        let bytes = export_part(g, part_path, format, options, part)?;
+        if bytes > check.max_bytes {
+            report.warnings.push(format!("{} holds a component of {} bytes, over the limit of {} bytes", part_path, bytes, check.max_bytes));
+        }
+        let edges: usize = part.iter().filter_map(|oid| g.irels.get(oid)).map(|targets| targets.len()).sum();
+        let file_name = Path::new(part_path).file_name().and_then(|name| name.to_str()).unwrap_or(part_path);
+        parts.push(serde_json::json!({"file": file_name, "objects": part.len(), "edges": edges, "bytes": bytes}));
+        report.files.push((part_path.to_owned(), bytes));
+    }
+
+    let manifest = path.with_file_name(format!("{}.manifest.json", stem));
+    let manifest = manifest.to_str().ok_or("The export path is not valid unicode")?;
+    let document = serde_json::json!({"format": format!("{:?}", format),
+                                      "split": format!("{:?}", split),
+                                      "estimated_bytes": report.estimated_bytes,
+                                      "max_bytes": check.max_bytes,
+                                      "parts": parts});
+    document.export(manifest)?;
+    report.manifest = Some(manifest.to_owned());
+    Ok(report)
+}
+
+fn export_part(g: &Ocdg, file_path: &str, format: GraphFormat, options: &ExportOptions, part: &split::Part) -> Result<u64, Box<dyn Error>> {
+    match format {
+        GraphFormat::GraphMl => export_graphml_ocdg_part(g, file_path, options, part),
+        GraphFormat::Gexf => export_gexf_ocdg_part(g, file_path, options, part)
+    }
+}
 
 pub fn export_ocdg_string(g: &Ocdg, log: &Ocel, file_path: &str) -> Result<bool, Box<dyn Error>> {
     return export_gexf_ocdg_string(g, log, file_path);
 }
 
 pub fn export_ocdg(g: &Ocdg, file_path: &str) -> Result<bool, Box<dyn Error>> {
-    return export_gexf_ocdg(g, file_path);
+    return export_gexf_ocdg(g, file_path, &ExportOptions::default());
+}
+
+pub fn export_ocdg_with(g: &Ocdg, file_path: &str, options: &ExportOptions) -> Result<bool, Box<dyn Error>> {
+    export_gexf_ocdg(g, file_path, options)
+}
+
+pub fn export_ocdg_graphml(g: &Ocdg, file_path: &str, options: &ExportOptions) -> Result<bool, Box<dyn Error>> {
+    export_graphml_ocdg(g, file_path, options)
+}
+
+// with the node colors of ExportOptions::style, which are read from the objects of the log
+pub fn export_ocdg_graphml_styled(g: &Ocdg, log: &Ocel, file_path: &str, options: &ExportOptions) -> Result<bool, Box<dyn Error>> {
+    export_graphml_ocdg_styled(g, log, file_path, options)
+}
+
+// the same document into any writer, errors of the writer are returned
+pub fn export_ocdg_graphml_to<W: Write>(g: &Ocdg, writer: &mut W, options: &ExportOptions) -> Result<(), ExportError> {
+    write_graphml_ocdg(g, writer, options)
+}
+
+// the graphml document as a string, the node colors of the style are read from the log
+pub fn ocdg_to_graphml(g: &Ocdg, log: &Ocel, options: &ExportOptions) -> String {
+    graphml_ocdg_string(g, log, options)
+}
+
+// graphviz source with the object ids and types as node labels and the relation names as edge labels
+pub fn ocdg_to_dot(g: &Ocdg, log: &Ocel, options: &DotOptions) -> String {
+    OcdgDot { g, log, options }.to_dot()
+}
+
+pub fn export_ocdg_dot(g: &Ocdg, log: &Ocel, file_path: &str, options: &DotOptions) -> Result<bool, Box<dyn Error>> {
+    OcdgDot { g, log, options }.export(file_path)?;
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct JsonExportOptions {
+    // graphs with more edges hold event counts instead of event lists to bound the size of the document
+    pub max_edges_with_events: usize
+}
+
+impl Default for JsonExportOptions {
+    fn default() -> Self {
+        Self { max_edges_with_events: 100_000 }
+    }
+}
+
+// nodes with their object id, type and lifecycle in the log, edges with the supporting events of every relation, all by string ids.
+// nodes and edges are sorted by id so that a graph read back with import_ocdg_json exports the same document with the same log
+pub fn export_ocdg_json(log: &Ocel, g: &Ocdg) -> Value {
+    export_json_ocdg(Some(log), g, &JsonExportOptions::default())
+}
+
+pub fn export_ocdg_json_with(log: &Ocel, g: &Ocdg, options: &JsonExportOptions) -> Value {
+    export_json_ocdg(Some(log), g, options)
+}
+
+// without the log the lifecycles come from the graph, a graph read with import_ocdg_json exports the document it was read from
+pub fn export_ocdg_json_graph(g: &Ocdg) -> Value {
+    export_json_ocdg(None, g, &JsonExportOptions::default())
+}
+
+pub fn export_ocdg_json_graph_with(g: &Ocdg, options: &JsonExportOptions) -> Value {
+    export_json_ocdg(None, g, options)
+}
+
+// the elements json of cytoscape.js with the object ids as node ids and the supporting event counts of the relations on the edges,
+// one integer key per relation with ExportOptions::per_relation_keys. the relations of the options are honoured, the style is not
+pub fn export_ocdg_cytoscape(g: &Ocdg, options: &ExportOptions) -> Value {
+    export_cytoscape_ocdg(g, options)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NeighborOptions {
+    // per relation, the neighbours with the most supporting events are kept. None keeps all of them
+    pub max_neighbors: Option<usize>,
+    // None writes every relation
+    pub relations: Option<Vec<Relations>>
+}
+
+// json lines for search indexing, one document per object in id order with its type, its attributes in the log and per relation
+// the objects it points at, each with its type, supporting event count and latest event time. returns the number of documents
+pub fn export_neighbor_documents<W: Write>(log: &Ocel, g: &Ocdg, writer: &mut W, options: &NeighborOptions) -> Result<usize, Box<dyn Error>> {
+    write_neighbor_documents(log, g, writer, options)
+}
+
+// one sorted line per edge and relation: source, target, relation and the sorted evidence events, all as string ids.
+// stable between runs and versions, meant for diffing the semantics of two graphs
+pub fn canonical_edges(g: &Ocdg) -> String {
+    let label = |map: &BiMap<String, usize>, id: &usize| map.get_by_right(id).cloned().unwrap_or_else(|| id.to_string());
+    let mut lines: Vec<String> = vec![];
+    for (src, targets) in &g.irels {
+        for (tar, rels) in targets {
+            for (rel, eids) in rels {
+                let rel = Relations::from_index(*rel).map_or_else(|| rel.to_string(), |rel| rel.to_string());
+                let mut events: Vec<String> = eids.iter().map(|eid| label(&g.event_map, eid)).collect();
+                events.sort();
+                lines.push(format!("{}\t{}\t{}\t{}\n", label(&g.object_map, src), label(&g.object_map, tar), rel, events.join(",")));
+            }
+        }
+    }
+    lines.sort();
+    lines.concat()
+}
+
+pub fn export_ocdg_canonical(g: &Ocdg, file_path: &str) -> Result<bool, Box<dyn Error>> {
+    CanonicalEdges(canonical_edges(g)).export(file_path)?;
+    Ok(true)
+}
+
+struct CanonicalEdges(String);
+
+impl Exporter for CanonicalEdges {
+    fn write_content(&self, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        f.write_all(self.0.as_bytes())?;
+        Ok(())
+    }
+}
+
+pub(crate) fn relation_key(rel: Relations) -> String {
+    format!("rel_{}", rel)
+}
+
+pub(crate) fn relation_events_key(rel: Relations) -> String {
+    format!("rel_{}_events", rel)
+}
+
+// graphs generated without evidence still know that the relation holds
+pub(crate) fn relation_count(g: &Ocdg, src: usize, tar: usize, rel: Relations) -> usize {
+    match g.irels.get(&src).and_then(|targets| targets.get(&tar)) {
+        Some(rels) if rels.contains_key(&rel.relation_index().into()) => g.relation_support(src, tar, rel).unwrap_or(1),
+        _ => 0
+    }
 }
@@ -0,0 +1,102 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use nohash_hasher::IntSet;
+
+use crate::objects::ocdg::exporter::{ExportOptions, GraphFormat, SplitMode};
+use crate::objects::ocdg::{Ocdg, Relations};
+
+// the objects whose nodes and outgoing edges a file holds, the targets of those edges are written along so that every file is a valid graph
+pub(crate) type Part = IntSet<usize>;
+
+// fixed markup per node and edge, measured on the writers with empty values
+const GRAPHML_NODE: u64 = 74;
+const GRAPHML_EDGE: u64 = 95;
+const GEXF_NODE: u64 = 78;
+const GEXF_EDGE: u64 = 56;
+
+fn digits(value: usize) -> u64 {
+    value.checked_ilog10().map_or(1, |log| log as u64 + 1)
+}
+
+fn label_len(g: &Ocdg, oid: usize) -> u64 {
+    g.object_map.get_by_right(&oid).map_or(digits(oid), |label| label.len() as u64)
+}
+
+// the bytes of the labels of the evidence events, with their separators
+fn events_len(g: &Ocdg, src: usize, tar: usize, rel: Relations, separator: u64) -> u64 {
+    g.relation_events(src, tar, rel)
+     .into_iter()
+     .flatten()
+     .map(|eid| g.event_map.get_by_right(eid).map_or(digits(*eid), |label| label.len() as u64) + separator)
+     .sum()
+}
+
+pub(crate) fn node_size(g: &Ocdg, oid: usize, format: GraphFormat) -> u64 {
+    let node_type = g.node_attributes.get(&oid).map_or(0, |node| node.node_type.len() as u64);
+    let fixed = match format {
+        GraphFormat::GraphMl => GRAPHML_NODE,
+        GraphFormat::Gexf => GEXF_NODE
+    };
+    fixed + digits(oid) + label_len(g, oid) + node_type
+}
+
+pub(crate) fn edge_size(g: &Ocdg, src: usize, tar: usize, format: GraphFormat, options: &ExportOptions) -> u64 {
+    let relations = g.relations_between(src, tar);
+    let names: u64 = relations.iter().map(|rel| rel.to_string().len() as u64 + 1).sum();
+    let endpoints = digits(src) + digits(tar);
+    match format {
+        GraphFormat::GraphMl => {
+            let mut size = GRAPHML_EDGE + endpoints + 2 * names + relations.len() as u64 * 4;
+            if options.relation_events {
+                size += relations.iter().map(|rel| 35 + rel.to_string().len() as u64 + events_len(g, src, tar, *rel, 1)).sum::<u64>();
+            }
+            size
+        },
+        // the event lists are written as escaped debug strings, every label gets two &quot; and a separator
+        GraphFormat::Gexf => GEXF_EDGE + endpoints + relations.iter().map(|rel| 31 + events_len(g, src, tar, *rel, 14)).sum::<u64>()
+    }
+}
+
+// the nodes the part owns and the edges leaving them
+pub(crate) fn part_size(g: &Ocdg, part: &Part, format: GraphFormat, options: &ExportOptions) -> u64 {
+    part.iter()
+        .map(|oid| node_size(g, *oid, format) + g.irels.get(oid).into_iter().flatten().map(|(tar, _)| edge_size(g, *oid, *tar, format, options)).sum::<u64>())
+        .sum()
+}
+
+// whole components are packed into parts in the order of their smallest object until a part would exceed the limit.
+// a component larger than the limit is a part of its own and over it
+pub(crate) fn partition(g: &Ocdg, mode: SplitMode, max_bytes: u64, format: GraphFormat, options: &ExportOptions) -> Vec<Part> {
+    match mode {
+        SplitMode::Component => {
+            let mut parts: Vec<(Part, u64)> = vec![];
+            for component in g.connected_components() {
+                let size = part_size(g, &component, format, options);
+                match parts.last_mut() {
+                    Some((part, part_bytes)) if *part_bytes + size <= max_bytes => {
+                        part.extend(component);
+                        *part_bytes += size;
+                    },
+                    _ => parts.push((component, size))
+                }
+            }
+            parts.into_iter().map(|(part, _)| part).collect()
+        },
+        SplitMode::NodeHash(count) => {
+            let count = count.max(1);
+            let mut parts: Vec<Part> = vec![Part::default(); count];
+            for oid in g.node_attributes.keys() {
+                let mut hasher = DefaultHasher::new();
+                oid.hash(&mut hasher);
+                parts[(hasher.finish() % count as u64) as usize].insert(*oid);
+            }
+            parts.retain(|part| !part.is_empty());
+            parts
+        }
+    }
+}
+
+// the owned objects and the targets of their edges
+pub(crate) fn written_nodes(g: &Ocdg, part: &Part) -> IntSet<usize> {
+    part.iter().flat_map(|oid| g.irels.get(oid).into_iter().flatten().map(|(tar, _)| *tar).chain([*oid])).collect()
+}
@@ -0,0 +1,66 @@
+use std::collections::BTreeSet;
+use nohash_hasher::IntMap;
+use serde_json::Value;
+
+use crate::objects::ocdg::exporter::{relation_count, VisualStyle};
+use crate::objects::ocdg::Ocdg;
+use crate::objects::ocel::Ocel;
+
+// a style applied to one graph: the color of every node holding the attribute and the legend lines
+pub(crate) struct ResolvedStyle<'a> {
+    style: &'a VisualStyle,
+    pub(crate) node_colors: IntMap<usize, String>,
+    pub(crate) legend: Vec<String>
+}
+
+impl<'a> ResolvedStyle<'a> {
+    // without a log no node is colored
+    pub(crate) fn new(style: &'a VisualStyle, g: &Ocdg, log: Option<&Ocel>) -> Self {
+        let mut node_colors: IntMap<usize, String> = IntMap::default();
+        let mut legend: Vec<String> = vec![];
+        if let (Some(attribute), Some(log), false) = (&style.node_color_by, log, style.palette.is_empty()) {
+            let values: IntMap<usize, String> = g.node_attributes.keys()
+                                                 .filter_map(|oid| log.objects.get(oid)?.ovmap.get(attribute).map(|value| (*oid, attribute_label(value))))
+                                                 .collect();
+            let distinct: BTreeSet<&String> = values.values().collect();
+            let colors: Vec<(&String, &String)> = distinct.into_iter().zip(style.palette.iter().cycle()).collect();
+            for (value, color) in &colors {
+                legend.push(format!("node color {}={}: {}", attribute, value, color));
+            }
+            for (oid, value) in &values {
+                let (_, color) = colors.iter().find(|(candidate, _)| *candidate == value).expect("Every value got a color");
+                node_colors.insert(*oid, color.to_string());
+            }
+        }
+        if let Some(rel) = style.edge_width_by {
+            // width w holds the counts reaching w - 1 bounds, bounds at or below the previous one leave their width empty
+            let mut lower = 1;
+            for (width, bound) in style.buckets.iter().enumerate() {
+                if *bound == lower + 1 {
+                    legend.push(format!("edge width {}: {} {} events", width + 1, lower, rel));
+                } else if *bound > lower {
+                    legend.push(format!("edge width {}: {} to {} {} events", width + 1, lower, bound - 1, rel));
+                }
+                lower = lower.max(*bound);
+            }
+            legend.push(format!("edge width {}: {} or more {} events", style.buckets.len() + 1, lower, rel));
+        }
+        Self { style, node_colors, legend }
+    }
+
+    // None for edges without the relation
+    pub(crate) fn edge_width(&self, g: &Ocdg, src: usize, tar: usize) -> Option<usize> {
+        let count = relation_count(g, src, tar, self.style.edge_width_by?);
+        match count {
+            0 => None,
+            count => Some(1 + self.style.buckets.iter().filter(|bound| count >= **bound).count())
+        }
+    }
+}
+
+fn attribute_label(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.to_owned(),
+        other => other.to_string()
+    }
+}
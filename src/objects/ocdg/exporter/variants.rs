@@ -1 +1,6 @@
+pub(super) mod cytoscape;
 pub(super) mod gexf;
+pub(super) mod graphml;
+pub(super) mod dot;
+pub(super) mod json;
+pub(super) mod neighbors;
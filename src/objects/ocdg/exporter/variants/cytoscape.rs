@@ -0,0 +1,47 @@
+use std::collections::BTreeMap;
+use bimap::BiMap;
+use serde_json::Value;
+use strum::IntoEnumIterator;
+
+use crate::objects::ocdg::exporter::{relation_count, relation_key, ExportOptions};
+use crate::objects::ocdg::variants::cytoscape::{CytoscapeJson, EdgeDataCy, ElementCy, ElementsCy, GraphDataCy, NodeDataCy, FORMAT_VERSION, GENERATED_BY};
+use crate::objects::ocdg::{Ocdg, Relations};
+
+pub(crate) fn export_cytoscape_ocdg(g: &Ocdg, options: &ExportOptions) -> Value {
+    let label = |map: &BiMap<String, usize>, id: &usize| map.get_by_right(id).cloned().unwrap_or_else(|| id.to_string());
+    let written = |rel: &Relations| options.relations.as_ref().is_none_or(|relations| relations.contains(rel));
+
+    let mut nodes: Vec<ElementCy<NodeDataCy>> = g.node_attributes.iter()
+                                                 .map(|(oid, node)| ElementCy { data: NodeDataCy { id: label(&g.object_map, oid),
+                                                                                                   node_type: node.node_type.to_owned(),
+                                                                                                   members: (!node.members.is_empty()).then_some(node.members.len()) } })
+                                                 .collect();
+    nodes.sort_by(|a, b| a.data.id.cmp(&b.data.id));
+
+    let mut edges: Vec<ElementCy<EdgeDataCy>> = vec![];
+    for (src, targets) in &g.irels {
+        for (tar, rels) in targets {
+            let mut present: Vec<Relations> = rels.keys().filter_map(|rel| Relations::from_index(*rel)).filter(written).collect();
+            if present.is_empty() {
+                continue;
+            }
+            present.sort_by_key(|rel| rel.relation_index());
+            let (source, target) = (label(&g.object_map, src), label(&g.object_map, tar));
+            let interaction = present.iter().map(|rel| rel.to_string()).collect::<Vec<String>>().join(",");
+            let (support, relation_keys) = if options.per_relation_keys {
+                // json has no declared defaults, so absent relations are written as 0
+                (None, Relations::iter().filter(written).map(|rel| (relation_key(rel), relation_count(g, *src, *tar, rel))).collect())
+            } else {
+                (Some(present.iter().map(|rel| (rel.to_string(), relation_count(g, *src, *tar, *rel))).collect()), BTreeMap::new())
+            };
+            edges.push(ElementCy { data: EdgeDataCy { id: format!("{}->{}", source, target), source, target, interaction, support, relation_keys } });
+        }
+    }
+    edges.sort_by(|a, b| (&a.data.source, &a.data.target).cmp(&(&b.data.source, &b.data.target)));
+
+    let document = CytoscapeJson { format_version: FORMAT_VERSION.to_owned(),
+                                   generated_by: GENERATED_BY.to_owned(),
+                                   data: GraphDataCy { generation: g.metadata().cloned() },
+                                   elements: ElementsCy { nodes, edges } };
+    serde_json::to_value(document).expect("The document has string keys only")
+}
@@ -0,0 +1,115 @@
+use std::{collections::BTreeMap, error::Error, io::Write};
+
+use crate::objects::{exporter::Exporter, ocdg::{exporter::{style::ResolvedStyle, DotOptions}, Ocdg, Relations}, ocel::Ocel};
+
+pub(crate) struct OcdgDot<'a> {
+    pub(crate) g: &'a Ocdg,
+    pub(crate) log: &'a Ocel,
+    pub(crate) options: &'a DotOptions
+}
+
+impl<'a> OcdgDot<'a> {
+    pub(crate) fn to_dot(&self) -> String {
+        let mut dot = format!("{} ocdg {{\n    node [shape=box];\n", if self.options.undirected { "graph" } else { "digraph" });
+        let style = self.options.style.as_ref().map(|style| ResolvedStyle::new(style, self.g, Some(self.log)));
+        for line in style.iter().flat_map(|style| &style.legend) {
+            dot.push_str(&format!("    // {}\n", line));
+        }
+        // every node of the graph, also the ones without edges
+        let mut oids: Vec<&usize> = self.g.node_attributes.keys().collect();
+        oids.sort();
+        for oid in oids {
+            let label = self.log.object_map.get_by_right(oid).or_else(|| self.g.object_map.get_by_right(oid)).map_or("?", |label| label.as_str());
+            dot.push_str(&format!("    n{} [label=\"{}\\n{}\"", oid, escape_dot(label), escape_dot(&self.g.node_attributes[oid].node_type)));
+            if let Some(color) = style.as_ref().and_then(|style| style.node_colors.get(oid)) {
+                dot.push_str(&format!(", style=filled, fillcolor=\"{}\"", escape_dot(color)));
+            }
+            dot.push_str("];\n");
+        }
+
+        let (arrow, pairs) = if self.options.undirected { ("--", self.undirected_pairs()) } else { ("->", self.directed_pairs()) };
+        for ((src, tar), present) in pairs {
+            let names: Vec<String> = present.iter().map(|rel| rel.to_string()).collect();
+            dot.push_str(&format!("    n{} {} n{} [label=\"{}\"", src, arrow, tar, names.join("\\n")));
+            if self.options.color_relations {
+                // one parallel stroke per relation
+                let colors: Vec<&str> = present.iter().map(|rel| relation_color(*rel)).collect();
+                dot.push_str(&format!(", color=\"{}\"", colors.join(":")));
+            }
+            let width = style.as_ref().and_then(|style| {
+                let width = style.edge_width(self.g, src, tar);
+                if self.options.undirected { width.max(style.edge_width(self.g, tar, src)) } else { width }
+            });
+            if let Some(width) = width {
+                dot.push_str(&format!(", penwidth={}", width));
+            }
+            dot.push_str("];\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    // the drawn relations of every edge in the order of their index, edges sorted
+    fn directed_pairs(&self) -> Vec<((usize, usize), Vec<Relations>)> {
+        let mut pairs: Vec<((usize, usize), Vec<Relations>)> = vec![];
+        for (src, targets) in &self.g.irels {
+            for (tar, rels) in targets {
+                let mut present: Vec<Relations> = rels.keys().filter_map(|rel| Relations::from_index(*rel)).filter(|rel| self.drawn(*rel)).collect();
+                if !present.is_empty() {
+                    present.sort_by_key(|rel| rel.relation_index());
+                    pairs.push(((*src, *tar), present));
+                }
+            }
+        }
+        pairs.sort_by_key(|(pair, _)| *pair);
+        pairs
+    }
+
+    // both directions merged into the pair with the smaller oid first
+    fn undirected_pairs(&self) -> Vec<((usize, usize), Vec<Relations>)> {
+        let mut pairs: BTreeMap<(usize, usize), Vec<Relations>> = BTreeMap::new();
+        for ((src, tar), present) in self.directed_pairs() {
+            pairs.entry((src.min(tar), src.max(tar))).or_default().extend(present);
+        }
+        for present in pairs.values_mut() {
+            present.sort_by_key(|rel| rel.relation_index());
+            present.dedup();
+        }
+        pairs.into_iter().collect()
+    }
+
+    fn drawn(&self, rel: Relations) -> bool {
+        self.options.relations.as_ref().is_none_or(|relations| relations.contains(&rel))
+    }
+}
+
+impl<'a> Exporter for OcdgDot<'a> {
+    fn write_content(&self, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        f.write_all(self.to_dot().as_bytes())?;
+        Ok(())
+    }
+}
+
+// shared-event relations stay muted, the lineage relations get the loud colors
+fn relation_color(rel: Relations) -> &'static str {
+    match rel {
+        Relations::INTERACTS => "gray60",
+        Relations::COLIFE => "darkgreen",
+        Relations::COBIRTH => "green3",
+        Relations::CODEATH => "olivedrab",
+        Relations::DESCENDANTS => "blue",
+        Relations::INHERITANCE => "purple",
+        Relations::CONSUMES => "darkorange",
+        Relations::SPLIT => "red",
+        Relations::MERGE => "magenta",
+        Relations::MINION => "brown",
+        Relations::PEELER => "cyan4",
+        Relations::ENGAGES => "goldenrod",
+        Relations::REACHABLE => "lightblue",
+        Relations::OVERLAPS => "gray80"
+    }
+}
+
+fn escape_dot(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -1,19 +1,22 @@
-use std::{fs::OpenOptions, io::{BufWriter, Write}, error::Error};
-use quick_xml::se::to_string;
+use std::error::Error;
 use strum::IntoEnumIterator;
 
-use crate::objects::{ocdg::{variants::gexf::{Gexf, NodeGexf, AttValuesGexf, AttValueGexf, EdgeGexf, AttributesGexf, AttributeGexf}, Ocdg, Relations}, ocel::Ocel};
+use crate::objects::{exporter::Exporter, ocdg::{exporter::{relation_count, relation_key, split::{written_nodes, Part}, ExportOptions}, variants::gexf::{Gexf, NodeGexf, AttValuesGexf, AttValueGexf, EdgeGexf, AttributesGexf, AttributeGexf}, Ocdg, Relations}, ocel::Ocel};
 
 
 
 pub(crate) fn export_gexf_ocdg_string(g: &Ocdg, log: &Ocel, file_path: &str) -> Result<bool, Box<dyn Error>> {
 
     let mut gexf_repr: Gexf = Gexf::new();
+    if let Some(metadata) = g.metadata() {
+        gexf_repr.meta.keywords = Some(serde_json::to_string(metadata)?);
+    }
 
     // object attr
     let mut node_attrs: Vec<AttributeGexf> = vec![];
     node_attrs.push(AttributeGexf { id: 0.to_string(), title: "type".to_string(), attr_type: "string".to_string()});
     node_attrs.push(AttributeGexf { id: 1.to_string(), title: "object_events".to_string(), attr_type: "liststring".to_string()});
+    node_attrs.push(AttributeGexf { id: 2.to_string(), title: "members".to_string(), attr_type: "integer".to_string()});
     gexf_repr.graph.attributes.push(AttributesGexf { class: "node".to_string(), attributes: node_attrs });
 
     // edge attr
@@ -32,8 +35,11 @@ pub(crate) fn export_gexf_ocdg_string(g: &Ocdg, log: &Ocel, file_path: &str) ->
         attrvalues.push(AttValueGexf { attr: 0.to_string(), value: data.node_type.to_owned() });
         let oe_s: Vec<String> = log.objects.get(oid).unwrap().events.iter().map(|e_number| log.event_map.get_by_right(e_number).expect("This can't fail").to_owned()).collect();
         attrvalues.push(AttValueGexf { attr: 1.to_string(), value: format!("{:?}", oe_s) });
+        if !data.members.is_empty() {
+            attrvalues.push(AttValueGexf { attr: 2.to_string(), value: data.members.len().to_string() });
+        }
 
-        gexf_repr.graph.nodes.nodes.push(NodeGexf {id: oid.to_string(), label: log.object_map.get_by_right(oid).expect("This can't fail").to_owned(), attvalues: AttValuesGexf {attvalues: attrvalues}});
+        gexf_repr.graph.nodes.nodes.push(NodeGexf {id: oid.to_string(), label: g.object_map.get_by_right(oid).or_else(|| log.object_map.get_by_right(oid)).expect("This can't fail").to_owned(), attvalues: AttValuesGexf {attvalues: attrvalues}});
     }
 
     for (src, edge_data) in &g.irels {
@@ -50,51 +56,73 @@ pub(crate) fn export_gexf_ocdg_string(g: &Ocdg, log: &Ocel, file_path: &str) ->
     }
 
 
-    let mut ocdg_xml = r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string();
-    ocdg_xml.push_str(&to_string(&gexf_repr).unwrap());
-
-    let output_file = OpenOptions::new().create(true).write(true).truncate(true).open(file_path).unwrap();
-    let mut f = BufWriter::new(output_file);
-    f.write_all(ocdg_xml.as_bytes()).expect("Unable to write data");
+    gexf_repr.export(file_path)?;
 
     Ok(true)
 }
 
 
 
-pub(crate) fn export_gexf_ocdg(g: &Ocdg, file_path: &str) -> Result<bool, Box<dyn Error>> {
+pub(crate) fn export_gexf_ocdg(g: &Ocdg, file_path: &str, options: &ExportOptions) -> Result<bool, Box<dyn Error>> {
+    gexf_document(g, options, None)?.export(file_path)?;
+    Ok(true)
+}
 
+// only the objects of the part and their outgoing edges, see export_ocdg_checked
+pub(crate) fn export_gexf_ocdg_part(g: &Ocdg, file_path: &str, options: &ExportOptions, part: &Part) -> Result<u64, Box<dyn Error>> {
+    Ok(gexf_document(g, options, Some(part))?.export(file_path)?)
+}
+
+fn gexf_document(g: &Ocdg, options: &ExportOptions, part: Option<&Part>) -> Result<Gexf, Box<dyn Error>> {
+    let nodes = part.map(|part| written_nodes(g, part));
     let mut gexf_repr: Gexf = Gexf::new();
+    if let Some(metadata) = g.metadata() {
+        gexf_repr.meta.keywords = Some(serde_json::to_string(metadata)?);
+    }
 
     // object attr
     let mut node_attrs: Vec<AttributeGexf> = vec![];
     node_attrs.push(AttributeGexf { id: 0.to_string(), title: "type".to_string(), attr_type: "string".to_string()});
+    node_attrs.push(AttributeGexf { id: 2.to_string(), title: "members".to_string(), attr_type: "integer".to_string()});
     gexf_repr.graph.attributes.push(AttributesGexf { class: "node".to_string(), attributes: node_attrs });
 
     // edge attr
     let mut edge_attrs: Vec<AttributeGexf> = vec![];
 
     for rel in Relations::iter() {
-        edge_attrs.push(AttributeGexf {id: rel.relation_index().to_string(), title: rel.to_string(), attr_type: "liststring".to_string()});
+        if options.per_relation_keys {
+            edge_attrs.push(AttributeGexf {id: rel.relation_index().to_string(), title: relation_key(rel), attr_type: "integer".to_string()});
+        } else {
+            edge_attrs.push(AttributeGexf {id: rel.relation_index().to_string(), title: rel.to_string(), attr_type: "liststring".to_string()});
+        }
     }
     
 
     gexf_repr.graph.attributes.push(AttributesGexf { class: "edge".to_string(), attributes: edge_attrs });
     
 
-    for (oid, data) in &g.node_attributes {
+    for (oid, data) in g.node_attributes.iter().filter(|(oid, _)| nodes.as_ref().is_none_or(|nodes| nodes.contains(oid))) {
         let mut attrvalues: Vec<AttValueGexf> = vec![];
         attrvalues.push(AttValueGexf { attr: 0.to_string(), value: data.node_type.to_owned() });
+        if !data.members.is_empty() {
+            attrvalues.push(AttValueGexf { attr: 2.to_string(), value: data.members.len().to_string() });
+        }
 
         gexf_repr.graph.nodes.nodes.push(NodeGexf {id: oid.to_string(), label: g.object_map.get_by_right(oid).expect("This can't fail").to_owned(), attvalues: AttValuesGexf {attvalues: attrvalues}});
     }
 
-    for (src, edge_data) in &g.irels {
+    for (src, edge_data) in g.irels.iter().filter(|(src, _)| part.is_none_or(|part| part.contains(src))) {
         for (tar, rels) in edge_data {
             let mut attrvalues: Vec<AttValueGexf> = vec![];
-            for (r, events) in rels {
-                let ev_s: Vec<String> = events.iter().map(|eid| g.event_map.get_by_right(eid).expect("This can't fail").to_owned()).collect();
-                attrvalues.push(AttValueGexf { attr: r.to_string(), value: format!("{:?}", ev_s) });
+            if options.per_relation_keys {
+                for rel in Relations::iter() {
+                    attrvalues.push(AttValueGexf { attr: rel.relation_index().to_string(), value: relation_count(g, *src, *tar, rel).to_string() });
+                }
+            } else {
+                for (r, events) in rels {
+                    let ev_s: Vec<String> = events.iter().map(|eid| g.event_map.get_by_right(eid).expect("This can't fail").to_owned()).collect();
+                    attrvalues.push(AttValueGexf { attr: r.to_string(), value: format!("{:?}", ev_s) });
+                }
             }
 
             gexf_repr.graph.edges.edges.push(EdgeGexf { source: src.to_string(), target: tar.to_string(), attvalues: AttValuesGexf { attvalues: attrvalues } });
@@ -103,13 +131,6 @@ pub(crate) fn export_gexf_ocdg(g: &Ocdg, file_path: &str) -> Result<bool, Box<dy
     }
 
 
-    let mut ocdg_xml = r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string();
-    ocdg_xml.push_str(&to_string(&gexf_repr).unwrap());
-
-    let output_file = OpenOptions::new().create(true).write(true).truncate(true).open(file_path).unwrap();
-    let mut f = BufWriter::new(output_file);
-    f.write_all(ocdg_xml.as_bytes()).expect("Unable to write data");
-
-    Ok(true)
+    Ok(gexf_repr)
 }
 
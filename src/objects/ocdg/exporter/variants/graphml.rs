@@ -0,0 +1,153 @@
+use std::{error::Error, io::Write};
+use strum::IntoEnumIterator;
+
+use crate::objects::{exporter::{ExportError, Exporter}, ocdg::{exporter::{relation_count, relation_events_key, relation_key, split::{written_nodes, Part}, style::ResolvedStyle, ExportOptions}, Ocdg, Relations}, ocel::Ocel};
+
+struct GraphMl<'a> {
+    g: &'a Ocdg,
+    // only needed for the node colors of the style
+    log: Option<&'a Ocel>,
+    options: &'a ExportOptions,
+    // only these objects and their outgoing edges, see export_ocdg_checked
+    part: Option<&'a Part>
+}
+
+impl<'a> GraphMl<'a> {
+    fn written(&self, rel: Relations) -> bool {
+        self.options.relations.as_ref().is_none_or(|relations| relations.contains(&rel))
+    }
+
+    fn write_keys(&self, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        if self.g.metadata().is_some() {
+            writeln!(f, r#"  <key id="generation" for="graph" attr.name="generation" attr.type="string"/>"#)?;
+        }
+        writeln!(f, r#"  <key id="label" for="node" attr.name="label" attr.type="string"/>"#)?;
+        writeln!(f, r#"  <key id="type" for="node" attr.name="type" attr.type="string"/>"#)?;
+        writeln!(f, r#"  <key id="members" for="node" attr.name="members" attr.type="int"/>"#)?;
+        if self.options.per_relation_keys {
+            for rel in Relations::iter().filter(|rel| self.written(*rel)) {
+                let key = relation_key(rel);
+                writeln!(f, r#"  <key id="{}" for="edge" attr.name="{}" attr.type="int"><default>0</default></key>"#, key, key)?;
+            }
+        } else {
+            writeln!(f, r#"  <key id="relations" for="edge" attr.name="relations" attr.type="string"/>"#)?;
+            writeln!(f, r#"  <key id="support" for="edge" attr.name="support" attr.type="string"/>"#)?;
+        }
+        if self.options.relation_events {
+            for rel in Relations::iter().filter(|rel| self.written(*rel)) {
+                let key = relation_events_key(rel);
+                writeln!(f, r#"  <key id="{}" for="edge" attr.name="{}" attr.type="string"/>"#, key, key)?;
+            }
+        }
+        if self.options.style.is_some() {
+            writeln!(f, r#"  <key id="legend" for="graph" attr.name="legend" attr.type="string"/>"#)?;
+            writeln!(f, r#"  <key id="color" for="node" attr.name="color" attr.type="string"/>"#)?;
+            writeln!(f, r#"  <key id="width" for="edge" attr.name="width" attr.type="int"/>"#)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Exporter for GraphMl<'a> {
+    fn write_content(&self, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(f, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+        self.write_keys(f)?;
+        writeln!(f, r#"  <graph id="ocdg" edgedefault="directed">"#)?;
+        if let Some(metadata) = self.g.metadata() {
+            writeln!(f, r#"    <data key="generation">{}</data>"#, escape_xml(&serde_json::to_string(metadata)?))?;
+        }
+        let style = self.options.style.as_ref().map(|style| ResolvedStyle::new(style, self.g, self.log));
+        if let Some(style) = &style {
+            // one legend entry per line
+            writeln!(f, r#"    <data key="legend">{}</data>"#, escape_xml(&style.legend.join("\n")))?;
+        }
+
+        let nodes = self.part.map(|part| written_nodes(self.g, part));
+        let mut oids: Vec<&usize> = self.g.node_attributes.keys().filter(|oid| nodes.as_ref().is_none_or(|nodes| nodes.contains(oid))).collect();
+        oids.sort();
+        for oid in oids {
+            let node = &self.g.node_attributes[oid];
+            let label = self.g.object_map.get_by_right(oid).map_or("?", |label| label.as_str());
+            write!(f, r#"    <node id="n{}"><data key="label">{}</data><data key="type">{}</data>"#, oid, escape_xml(label), escape_xml(&node.node_type))?;
+            if !node.members.is_empty() {
+                write!(f, r#"<data key="members">{}</data>"#, node.members.len())?;
+            }
+            if let Some(color) = style.as_ref().and_then(|style| style.node_colors.get(oid)) {
+                write!(f, r#"<data key="color">{}</data>"#, escape_xml(color))?;
+            }
+            writeln!(f, "</node>")?;
+        }
+
+        let mut edges: Vec<(&usize, &usize)> = self.g.irels.iter()
+                                                           .filter(|(src, _)| self.part.is_none_or(|part| part.contains(src)))
+                                                           .flat_map(|(src, targets)| targets.keys().map(move |tar| (src, tar)))
+                                                           .collect();
+        edges.sort();
+        for (src, tar) in edges {
+            let mut present: Vec<Relations> = self.g.irels[src][tar].keys().filter_map(|rel| Relations::from_index(*rel)).filter(|rel| self.written(*rel)).collect();
+            if present.is_empty() {
+                continue;
+            }
+            present.sort_by_key(|rel| rel.relation_index());
+            write!(f, r#"    <edge source="n{}" target="n{}">"#, src, tar)?;
+            if self.options.per_relation_keys {
+                // absent relations fall back to the declared default of 0
+                for rel in &present {
+                    write!(f, r#"<data key="{}">{}</data>"#, relation_key(*rel), relation_count(self.g, *src, *tar, *rel))?;
+                }
+            } else {
+                let names: Vec<String> = present.iter().map(|rel| rel.to_string()).collect();
+                // supporting events per relation in the order of the names, e.g. INTERACTS:2,COBIRTH:1
+                let support: Vec<String> = present.iter().map(|rel| format!("{}:{}", rel, relation_count(self.g, *src, *tar, *rel))).collect();
+                write!(f, r#"<data key="relations">{}</data><data key="support">{}</data>"#, names.join(","), support.join(","))?;
+            }
+            if self.options.relation_events {
+                // left out without evidence events
+                for rel in &present {
+                    let Some(eids) = self.g.relation_events(*src, *tar, *rel) else { continue };
+                    let mut events: Vec<String> = eids.iter().map(|eid| self.g.event_map.get_by_right(eid).cloned().unwrap_or_else(|| eid.to_string())).collect();
+                    events.sort();
+                    write!(f, r#"<data key="{}">{}</data>"#, relation_events_key(*rel), escape_xml(&events.join("|")))?;
+                }
+            }
+            if let Some(width) = style.as_ref().and_then(|style| style.edge_width(self.g, *src, *tar)) {
+                write!(f, r#"<data key="width">{}</data>"#, width)?;
+            }
+            writeln!(f, "</edge>")?;
+        }
+
+        writeln!(f, "  </graph>")?;
+        writeln!(f, "</graphml>")?;
+        Ok(())
+    }
+}
+
+pub(crate) fn export_graphml_ocdg(g: &Ocdg, file_path: &str, options: &ExportOptions) -> Result<bool, Box<dyn Error>> {
+    GraphMl { g, log: None, options, part: None }.export(file_path)?;
+    Ok(true)
+}
+
+pub(crate) fn export_graphml_ocdg_styled(g: &Ocdg, log: &Ocel, file_path: &str, options: &ExportOptions) -> Result<bool, Box<dyn Error>> {
+    GraphMl { g, log: Some(log), options, part: None }.export(file_path)?;
+    Ok(true)
+}
+
+pub(crate) fn export_graphml_ocdg_part(g: &Ocdg, file_path: &str, options: &ExportOptions, part: &Part) -> Result<u64, Box<dyn Error>> {
+    Ok(GraphMl { g, log: None, options, part: Some(part) }.export(file_path)?)
+}
+
+pub(crate) fn write_graphml_ocdg(g: &Ocdg, writer: &mut dyn Write, options: &ExportOptions) -> Result<(), ExportError> {
+    GraphMl { g, log: None, options, part: None }.write_to(writer)?;
+    Ok(())
+}
+
+pub(crate) fn graphml_ocdg_string(g: &Ocdg, log: &Ocel, options: &ExportOptions) -> String {
+    let mut buffer: Vec<u8> = vec![];
+    GraphMl { g, log: Some(log), options, part: None }.write_content(&mut buffer).expect("Writing to a vec does not fail");
+    String::from_utf8(buffer).expect("The document is written from strings")
+}
+
+fn escape_xml(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
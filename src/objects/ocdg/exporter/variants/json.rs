@@ -0,0 +1,57 @@
+use std::collections::BTreeMap;
+use bimap::BiMap;
+use serde_json::Value;
+
+use crate::objects::ocdg::exporter::{relation_count, JsonExportOptions};
+use crate::objects::ocdg::variants::json::{EdgeJson, JsonEvidence, NodeJson, OcdgJson, SupportJson, SCHEMA, VERSION};
+use crate::objects::ocdg::{Evidence, Ocdg, Relations};
+use crate::objects::ocel::Ocel;
+
+pub(crate) fn export_json_ocdg(log: Option<&Ocel>, g: &Ocdg, options: &JsonExportOptions) -> Value {
+    let label = |map: &BiMap<String, usize>, id: &usize| map.get_by_right(id).cloned().unwrap_or_else(|| id.to_string());
+    let edge_count: usize = g.irels.values().map(|targets| targets.len()).sum();
+    let evidence = match g.evidence {
+        Evidence::Full if edge_count <= options.max_edges_with_events => JsonEvidence::Events,
+        Evidence::Full | Evidence::CountsOnly => JsonEvidence::Counts,
+        Evidence::None => JsonEvidence::None
+    };
+
+    let mut nodes: Vec<NodeJson> = g.node_attributes.iter()
+                                    .map(|(oid, node)| {
+                                        let object_id = label(&g.object_map, oid);
+                                        // the log is looked up by the string id, the internal ids of an imported graph differ from the log's.
+                                        // objects the log does not know keep the lifecycle the graph was read with
+                                        let event_list = log.and_then(|log| log.object_map.get_by_left(&object_id)
+                                                                               .and_then(|oid| log.objects.get(oid))
+                                                                               .map(|obj| obj.events.iter().map(|eid| label(&log.event_map, eid)).collect()))
+                                                            .or_else(|| g.lifecycles.get(oid).cloned())
+                                                            .unwrap_or_default();
+                                        NodeJson { object_id, node_type: node.node_type.to_owned(), event_list }
+                                    })
+                                    .collect();
+    nodes.sort_by(|a, b| a.object_id.cmp(&b.object_id));
+
+    let mut edges: Vec<EdgeJson> = vec![];
+    for (src, targets) in &g.irels {
+        for (tar, rels) in targets {
+            let mut relations: BTreeMap<String, SupportJson> = BTreeMap::new();
+            for rel in rels.keys().filter_map(|rel| Relations::from_index(*rel)) {
+                let support = match evidence {
+                    JsonEvidence::Events => {
+                        let mut events: Vec<String> = rels[&rel.relation_index().into()].iter().map(|eid| label(&g.event_map, eid)).collect();
+                        events.sort();
+                        SupportJson::Events(events)
+                    },
+                    JsonEvidence::Counts => SupportJson::Count(relation_count(g, *src, *tar, rel)),
+                    JsonEvidence::None => SupportJson::Present(())
+                };
+                relations.insert(rel.to_string(), support);
+            }
+            edges.push(EdgeJson { source_id: label(&g.object_map, src), target_id: label(&g.object_map, tar), relations });
+        }
+    }
+    edges.sort_by(|a, b| (&a.source_id, &a.target_id).cmp(&(&b.source_id, &b.target_id)));
+
+    let document = OcdgJson { schema: SCHEMA.to_owned(), version: VERSION, evidence, nodes, edges };
+    serde_json::to_value(document).expect("The document has string keys only")
+}
@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::Write;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::objects::ocdg::exporter::{relation_count, NeighborOptions};
+use crate::objects::ocdg::{resolve_label, Ocdg, Relations};
+use crate::objects::ocel::Ocel;
+
+#[derive(Serialize)]
+struct NeighborJson<'a> {
+    id: &'a str,
+    #[serde(rename = "type")]
+    obj_type: &'a str,
+    event_count: usize,
+    // the latest supporting event, null without event lists
+    last_ts: Option<DateTime<Utc>>
+}
+
+#[derive(Serialize)]
+struct NeighborDocument<'a> {
+    id: &'a str,
+    #[serde(rename = "type")]
+    obj_type: &'a str,
+    attributes: BTreeMap<&'a str, &'a Value>,
+    neighbors: BTreeMap<String, Vec<NeighborJson<'a>>>
+}
+
+// one line per object in id order, only the document of the current object is held
+pub(crate) fn write_neighbor_documents(log: &Ocel, g: &Ocdg, writer: &mut dyn Write, options: &NeighborOptions) -> Result<usize, Box<dyn Error>> {
+    let mut labels: Vec<(&str, usize)> = g.node_attributes.keys().map(|oid| (resolve_label(&g.object_map, oid), *oid)).collect();
+    labels.sort_unstable();
+
+    for (id, oid) in &labels {
+        let attributes: BTreeMap<&str, &Value> = log.object_map.get_by_left(*id)
+                                                               .and_then(|log_oid| log.objects.get(log_oid))
+                                                               .into_iter()
+                                                               .flat_map(|obj| obj.ovmap.iter().map(|(name, value)| (name.as_str(), value)))
+                                                               .collect();
+        let mut neighbors: BTreeMap<String, Vec<NeighborJson>> = BTreeMap::new();
+        for (tar, rels) in g.irels.get(oid).into_iter().flatten() {
+            for rel in rels.keys().filter_map(|rel| Relations::from_index(*rel)) {
+                if options.relations.as_ref().is_some_and(|relations| !relations.contains(&rel)) {
+                    continue;
+                }
+                // the events of the graph are found in the log by their string ids
+                let last_ts = g.relation_events(*oid, *tar, rel)
+                               .into_iter()
+                               .flatten()
+                               .filter_map(|eid| g.event_map.get_by_right(eid).and_then(|event| log.event_map.get_by_left(event)))
+                               .filter_map(|eid| log.events.get(eid).map(|event| event.timestamp))
+                               .max();
+                neighbors.entry(rel.to_string()).or_default().push(NeighborJson { id: resolve_label(&g.object_map, tar),
+                                                                                   obj_type: g.node_attributes.get(tar).map_or("", |node| node.node_type.as_str()),
+                                                                                   event_count: relation_count(g, *oid, *tar, rel),
+                                                                                   last_ts });
+            }
+        }
+        // the best supported neighbours are kept when capped
+        for list in neighbors.values_mut() {
+            list.sort_by(|a, b| b.event_count.cmp(&a.event_count).then(a.id.cmp(b.id)));
+            if let Some(max_neighbors) = options.max_neighbors {
+                list.truncate(max_neighbors);
+            }
+        }
+
+        let document = NeighborDocument { id, obj_type: &g.node_attributes[oid].node_type, attributes, neighbors };
+        serde_json::to_writer(&mut *writer, &document)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(labels.len())
+}
@@ -0,0 +1,86 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use chrono::{DateTime, Utc};
+
+use crate::objects::ocdg::Ocdg;
+use crate::objects::ocel::Ocel;
+
+// the behaviour of an object without any of its ids. the gaps between consecutive events are bucketed by the
+// binary logarithm of their seconds so that re-imports with slightly different timestamps still match
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Signature {
+    pub obj_type: String,
+    pub activities: Vec<String>,
+    pub gap_buckets: Vec<u32>,
+    // neighbour type -> number of neighbours of that type in the graph
+    pub neighbour_types: BTreeMap<String, usize>
+}
+
+// objects missing from the log or the graph contribute nothing to their part of the signature
+pub fn signature(ocel: &Ocel, ocdg: &Ocdg, oid: usize) -> Signature {
+    let (obj_type, lifecycle) = match ocel.objects.get(&oid) {
+        Some(object) => (object.obj_type.to_owned(), object.events.as_slice()),
+        None => (String::new(), &[][..])
+    };
+    let events: Vec<(&str, DateTime<Utc>)> = lifecycle.iter().filter_map(|eid| ocel.events.get(eid)).map(|event| (event.activity, event.timestamp)).collect();
+    let gap_buckets = events.windows(2)
+                            .map(|pair| {
+                                let seconds = (pair[1].1 - pair[0].1).num_seconds().max(0) as u64;
+                                u64::BITS - seconds.leading_zeros()
+                            })
+                            .collect();
+
+    let mut neighbour_types: BTreeMap<String, usize> = BTreeMap::new();
+    for neighbour in ocdg.irels.get(&oid).into_iter().flat_map(|targets| targets.keys()) {
+        if let Some(node) = ocdg.node_attributes.get(neighbour) {
+            *neighbour_types.entry(node.node_type.to_owned()).or_default() += 1;
+        }
+    }
+    Signature { obj_type, activities: events.into_iter().map(|(activity, _)| activity.to_owned()).collect(), gap_buckets, neighbour_types }
+}
+
+pub fn object(ocel: &Ocel, ocdg: &Ocdg, oid: usize) -> u64 {
+    fingerprint(&signature(ocel, ocdg, oid))
+}
+
+fn fingerprint(signature: &Signature) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    signature.hash(&mut hasher);
+    hasher.finish()
+}
+
+// (oid in log a, oid in log b) of the objects behaving the same in both logs. equal fingerprints are verified on the
+// full signatures, objects sharing a signature are paired in the order of their first event and then of their ids
+pub fn find_duplicates(log_a: &Ocel, ocdg_a: &Ocdg, log_b: &Ocel, ocdg_b: &Ocdg) -> Vec<(usize, usize)> {
+    let buckets_b = buckets(log_b, ocdg_b);
+
+    let mut duplicates: Vec<(usize, usize)> = vec![];
+    for (fingerprint, candidates_a) in buckets(log_a, ocdg_a) {
+        let Some(candidates_b) = buckets_b.get(&fingerprint) else { continue };
+        // a fingerprint collision holds several signatures, only equal ones are paired
+        for group in candidates_a.chunk_by(|a, b| a.0 == b.0) {
+            let matching = candidates_b.iter().filter(|candidate| candidate.0 == group[0].0);
+            duplicates.extend(group.iter().zip(matching).map(|(a, b)| (a.3, b.3)));
+        }
+    }
+    duplicates.sort_unstable();
+    duplicates
+}
+
+// objects by fingerprint, each bucket sorted by signature, first event and id
+type Candidate<'a> = (Signature, Option<DateTime<Utc>>, &'a str, usize);
+
+fn buckets<'a>(log: &'a Ocel, ocdg: &Ocdg) -> BTreeMap<u64, Vec<Candidate<'a>>> {
+    let mut buckets: BTreeMap<u64, Vec<Candidate>> = BTreeMap::new();
+    for (oid, obj) in &log.objects {
+        let first = obj.events.first().and_then(|eid| log.events.get(eid)).map(|event| event.timestamp);
+        let label = log.object_map.get_by_right(oid).map_or("", |label| label.as_str());
+        let signature = signature(log, ocdg, *oid);
+        buckets.entry(fingerprint(&signature)).or_default().push((signature, first, label, *oid));
+    }
+    for candidates in buckets.values_mut() {
+        candidates.sort_unstable();
+    }
+    buckets
+}
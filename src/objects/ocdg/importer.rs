@@ -3,9 +3,13 @@ use std::error::Error;
 
 use crate::objects::ocel::Ocel;
 
+use serde_json::Value;
+
+use self::variants::cytoscape::import_cytoscape_ocdg;
 use self::variants::gexf::{import_gexf_ocdg, import_gexf_ocdg_link_ocel};
+use self::variants::json::import_json_ocdg;
 
-use super::Ocdg;
+use super::{Ocdg, OcdgError};
 
 pub fn import_ocdg(file_path: &str) -> Result<Ocdg, Box<dyn Error>> {
     return import_gexf_ocdg(file_path);
@@ -14,3 +18,13 @@ pub fn import_ocdg(file_path: &str) -> Result<Ocdg, Box<dyn Error>> {
 pub fn import_ocdg_link_ocel(file_path: &str, log: &Ocel) -> Result<Ocdg, Box<dyn Error>> {
     return import_gexf_ocdg_link_ocel(file_path, log);
 }
+
+// a document of export_ocdg_json without its log, the event lists of the nodes are kept in Ocdg::lifecycles
+pub fn import_ocdg_json(v: &Value) -> Result<Ocdg, OcdgError> {
+    import_json_ocdg(v)
+}
+
+// a document of export_ocdg_cytoscape, the relations keep their supporting event counts only
+pub fn import_ocdg_cytoscape(v: &Value) -> Result<Ocdg, OcdgError> {
+    import_cytoscape_ocdg(v)
+}
@@ -1 +1,3 @@
+pub(super) mod cytoscape;
 pub(super) mod gexf;
+pub(super) mod json;
@@ -0,0 +1,49 @@
+use std::str::FromStr;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::objects::ocdg::variants::cytoscape::CytoscapeJson;
+use crate::objects::ocdg::{Evidence, Ocdg, OcdgError, Relations};
+
+// the relations keep their supporting event counts, objects are numbered in the order of the nodes
+pub(crate) fn import_cytoscape_ocdg(v: &Value) -> Result<Ocdg, OcdgError> {
+    let document = CytoscapeJson::deserialize(v).map_err(|e| OcdgError::Invalid(e.to_string()))?;
+
+    let mut ocdg = Ocdg { evidence: Evidence::CountsOnly, metadata: document.data.generation, ..Default::default() };
+    for (oid, node) in document.elements.nodes.into_iter().enumerate() {
+        if ocdg.object_map.contains_left(&node.data.id) {
+            return Err(OcdgError::Invalid(format!("the object {} is more than one node", node.data.id)));
+        }
+        ocdg.inodes.insert(oid, ocdg.net.add_node(oid));
+        ocdg.object_map.insert(node.data.id, oid);
+        // only the number of members of a contracted node travels, the members themselves are not restored
+        ocdg.node_attributes.entry(oid).or_default().node_type = node.data.node_type;
+    }
+
+    for edge in document.elements.edges {
+        let edge = edge.data;
+        let oid = |id: &String| ocdg.object_map.get_by_left(id).copied().ok_or_else(|| OcdgError::Invalid(format!("the edge {} has no node {}", edge.id, id)));
+        let (src, tar) = (oid(&edge.source)?, oid(&edge.target)?);
+        if ocdg.iedges.get(&src).is_some_and(|targets| targets.contains_key(&tar)) {
+            return Err(OcdgError::Invalid(format!("the edge {} is listed more than once", edge.id)));
+        }
+
+        // per relation keys hold every written relation, the ones at 0 do not hold
+        let support: Vec<(String, usize)> = match edge.support {
+            Some(support) => support.into_iter().collect(),
+            None => edge.relation_keys.into_iter()
+                                      .filter(|(_, count)| *count > 0)
+                                      .map(|(key, count)| key.strip_prefix("rel_").map(|name| (name.to_owned(), count)).ok_or_else(|| OcdgError::Invalid(format!("the edge {} has the unknown key {}", edge.id, key))))
+                                      .collect::<Result<Vec<(String, usize)>, OcdgError>>()?
+        };
+        let index = ocdg.net.add_edge(ocdg.inodes[&src], ocdg.inodes[&tar], 0);
+        ocdg.iedges.entry(src).or_default().insert(tar, index);
+        for (name, count) in support {
+            let rel: usize = Relations::from_str(&name).map_err(|_| OcdgError::Invalid(format!("unknown relation {}", name)))?.relation_index().into();
+            ocdg.irels.entry(src).or_default().entry(tar).or_default().entry(rel).or_default();
+            ocdg.irel_counts.entry(src).or_default().entry(tar).or_default().insert(rel, count);
+        }
+    }
+    ocdg.refresh_edge_weights();
+    Ok(ocdg)
+}
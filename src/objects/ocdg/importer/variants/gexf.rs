@@ -11,6 +11,9 @@ pub fn import_gexf_ocdg(file_path: &str) -> Result<Ocdg, Box<dyn Error>> {
    let g: Gexf = from_str(&s)?;
 
    let mut ocdg: Ocdg = Ocdg::default();
+   if let Some(keywords) = &g.meta.keywords {
+       ocdg.metadata = Some(serde_json::from_str(keywords)?);
+   }
 
    for obj in g.graph.nodes.nodes {
        let oid = obj.id.parse::<usize>()?;
@@ -51,6 +54,7 @@ pub fn import_gexf_ocdg(file_path: &str) -> Result<Ocdg, Box<dyn Error>> {
 
    }
 
+   ocdg.refresh_edge_weights();
    Ok(ocdg)
 }
 
@@ -62,6 +66,9 @@ pub fn import_gexf_ocdg_link_ocel(file_path: &str, log: &Ocel) -> Result<Ocdg, B
    let g: Gexf = from_str(&s)?;
 
    let mut ocdg: Ocdg = Ocdg::default();
+   if let Some(keywords) = &g.meta.keywords {
+       ocdg.metadata = Some(serde_json::from_str(keywords)?);
+   }
 
    let file_to_log: IntMap<usize, &usize> = IntMap::from_iter(g.graph.nodes.nodes.iter()
                                                                                  .map(|node| (node.id.parse::<usize>().unwrap(), log.object_map.get_by_left(&node.label).unwrap())));
@@ -93,5 +100,6 @@ pub fn import_gexf_ocdg_link_ocel(file_path: &str, log: &Ocel) -> Result<Ocdg, B
 
    }
 
+   ocdg.refresh_edge_weights();
    Ok(ocdg)
 }
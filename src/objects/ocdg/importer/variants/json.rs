@@ -0,0 +1,70 @@
+use std::str::FromStr;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::objects::ocdg::variants::json::{JsonEvidence, OcdgJson, SupportJson, SCHEMA};
+use crate::objects::ocdg::{Evidence, Ocdg, OcdgError, Relations};
+
+// objects are numbered in the order of the nodes and events in the order they are first met
+pub(crate) fn import_json_ocdg(v: &Value) -> Result<Ocdg, OcdgError> {
+    let document = OcdgJson::deserialize(v).map_err(|e| OcdgError::Invalid(e.to_string()))?;
+    if document.schema != SCHEMA {
+        return Err(OcdgError::Invalid(format!("the schema {} is not {}", document.schema, SCHEMA)));
+    }
+
+    let mut ocdg = Ocdg { evidence: match document.evidence {
+                              JsonEvidence::Events => Evidence::Full,
+                              JsonEvidence::Counts => Evidence::CountsOnly,
+                              JsonEvidence::None => Evidence::None
+                          },
+                          ..Default::default() };
+    for (oid, node) in document.nodes.into_iter().enumerate() {
+        if ocdg.object_map.contains_left(&node.object_id) {
+            return Err(OcdgError::Invalid(format!("the object {} is more than one node", node.object_id)));
+        }
+        ocdg.inodes.insert(oid, ocdg.net.add_node(oid));
+        ocdg.object_map.insert(node.object_id, oid);
+        ocdg.node_attributes.entry(oid).or_default().node_type = node.node_type;
+        if !node.event_list.is_empty() {
+            ocdg.lifecycles.insert(oid, node.event_list);
+        }
+    }
+
+    for edge in document.edges {
+        let oid = |id: &String| ocdg.object_map.get_by_left(id).copied().ok_or_else(|| OcdgError::Invalid(format!("the edge {} -> {} has no node {}", edge.source_id, edge.target_id, id)));
+        let (src, tar) = (oid(&edge.source_id)?, oid(&edge.target_id)?);
+        if ocdg.iedges.get(&src).is_some_and(|targets| targets.contains_key(&tar)) {
+            return Err(OcdgError::Invalid(format!("the edge {} -> {} is listed more than once", edge.source_id, edge.target_id)));
+        }
+        let index = ocdg.net.add_edge(ocdg.inodes[&src], ocdg.inodes[&tar], 0);
+        ocdg.iedges.entry(src).or_default().insert(tar, index);
+
+        for (name, support) in edge.relations {
+            let rel: usize = Relations::from_str(&name).map_err(|_| OcdgError::Invalid(format!("unknown relation {}", name)))?.relation_index().into();
+            let events = ocdg.irels.entry(src).or_default().entry(tar).or_default().entry(rel).or_default();
+            match (ocdg.evidence, support) {
+                (Evidence::Full, SupportJson::Events(eids)) => {
+                    for eid in eids {
+                        let next = ocdg.event_map.len();
+                        let eid = match ocdg.event_map.get_by_left(&eid) {
+                            Some(eid) => *eid,
+                            None => {
+                                ocdg.event_map.insert(eid, next);
+                                next
+                            }
+                        };
+                        events.insert(eid);
+                    }
+                },
+                (Evidence::CountsOnly, SupportJson::Count(count)) => {
+                    ocdg.irel_counts.entry(src).or_default().entry(tar).or_default().insert(rel, count);
+                },
+                (Evidence::None, SupportJson::Present(())) => {},
+                (_, support) => return Err(OcdgError::Invalid(format!("the support {:?} of {} on {} -> {} does not match the evidence {:?}",
+                                                                      support, name, edge.source_id, edge.target_id, document.evidence)))
+            }
+        }
+    }
+    ocdg.refresh_edge_weights();
+    Ok(ocdg)
+}
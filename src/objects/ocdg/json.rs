@@ -0,0 +1,98 @@
+use bimap::BiMap;
+use nohash_hasher::{IntMap, IntSet};
+use petgraph::graph::{DiGraph, NodeIndex};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use super::{metadata::GenerationMetadata, EventIndex, Evidence, NodeInfo, Ocdg, OcdgError};
+
+// petgraph has no serde by default, the net is written as its node weights and (source index, target index, weight) edges.
+// the lookups into the net are left out and rebuilt on reading
+#[derive(Serialize)]
+struct OcdgRef<'a> {
+    nodes: Vec<usize>,
+    edges: Vec<(usize, usize, usize)>,
+    edge_attributes: &'a IntMap<usize, NodeInfo>,
+    node_attributes: &'a IntMap<usize, NodeInfo>,
+    object_map: &'a BiMap<String, usize>,
+    event_map: &'a BiMap<String, usize>,
+    irels: &'a IntMap<usize, IntMap<usize, IntMap<usize, IntSet<usize>>>>,
+    ievents: &'a Option<EventIndex>,
+    evidence: Evidence,
+    irel_counts: &'a IntMap<usize, IntMap<usize, IntMap<usize, usize>>>,
+    metadata: &'a Option<GenerationMetadata>
+}
+
+#[derive(Deserialize)]
+struct OcdgRepr {
+    nodes: Vec<usize>,
+    edges: Vec<(usize, usize, usize)>,
+    edge_attributes: IntMap<usize, NodeInfo>,
+    node_attributes: IntMap<usize, NodeInfo>,
+    object_map: BiMap<String, usize>,
+    event_map: BiMap<String, usize>,
+    irels: IntMap<usize, IntMap<usize, IntMap<usize, IntSet<usize>>>>,
+    ievents: Option<EventIndex>,
+    evidence: Evidence,
+    irel_counts: IntMap<usize, IntMap<usize, IntMap<usize, usize>>>,
+    metadata: Option<GenerationMetadata>
+}
+
+impl Serialize for Ocdg {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        OcdgRef { nodes: self.net.raw_nodes().iter().map(|node| node.weight).collect(),
+                  edges: self.net.raw_edges().iter().map(|edge| (edge.source().index(), edge.target().index(), edge.weight)).collect(),
+                  edge_attributes: &self.edge_attributes,
+                  node_attributes: &self.node_attributes,
+                  object_map: &self.object_map,
+                  event_map: &self.event_map,
+                  irels: &self.irels,
+                  ievents: &self.ievents,
+                  evidence: self.evidence,
+                  irel_counts: &self.irel_counts,
+                  metadata: &self.metadata }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ocdg {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = OcdgRepr::deserialize(deserializer)?;
+        let mut net: DiGraph<usize, usize> = DiGraph::with_capacity(repr.nodes.len(), repr.edges.len());
+        for oid in &repr.nodes {
+            net.add_node(*oid);
+        }
+        for (src, tar, weight) in repr.edges {
+            if src >= repr.nodes.len() || tar >= repr.nodes.len() {
+                return Err(de::Error::custom(format!("edge {} -> {} points outside of the {} nodes", src, tar, repr.nodes.len())));
+            }
+            net.add_edge(NodeIndex::new(src), NodeIndex::new(tar), weight);
+        }
+
+        let mut ocdg = Ocdg { net,
+                              edge_attributes: repr.edge_attributes,
+                              node_attributes: repr.node_attributes,
+                              object_map: repr.object_map,
+                              event_map: repr.event_map,
+                              irels: repr.irels,
+                              ievents: repr.ievents,
+                              evidence: repr.evidence,
+                              irel_counts: repr.irel_counts,
+                              metadata: repr.metadata,
+                              ..Default::default() };
+        ocdg.rebuild_lookups();
+        if ocdg.inodes.len() != ocdg.net.node_count() {
+            return Err(de::Error::custom("the same object is more than one node"));
+        }
+        Ok(ocdg)
+    }
+}
+
+impl Ocdg {
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).expect("Maps with integer keys serialize to json")
+    }
+
+    pub fn from_json(v: &Value) -> Result<Ocdg, OcdgError> {
+        Ocdg::deserialize(v).map_err(|e| OcdgError::Invalid(e.to_string()))
+    }
+}
@@ -0,0 +1,106 @@
+use std::cmp::Ordering;
+
+use crate::objects::ocdg::{Ocdg, Relations};
+
+// sorted and deduplicated (src, tar) pairs
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EdgeSet {
+    edges: Vec<(usize, usize)>
+}
+
+impl FromIterator<(usize, usize)> for EdgeSet {
+    fn from_iter<I: IntoIterator<Item = (usize, usize)>>(iter: I) -> Self {
+        let mut edges: Vec<(usize, usize)> = iter.into_iter().collect();
+        edges.sort_unstable();
+        edges.dedup();
+        Self { edges }
+    }
+}
+
+impl EdgeSet {
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn edges(&self) -> &[(usize, usize)] {
+        &self.edges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(usize, usize)> {
+        self.edges.iter()
+    }
+
+    pub fn contains(&self, src: usize, tar: usize) -> bool {
+        self.edges.binary_search(&(src, tar)).is_ok()
+    }
+
+    pub fn union(&self, other: &EdgeSet) -> EdgeSet {
+        self.merge(other, true, true, true)
+    }
+
+    pub fn intersect(&self, other: &EdgeSet) -> EdgeSet {
+        self.merge(other, false, true, false)
+    }
+
+    pub fn difference(&self, other: &EdgeSet) -> EdgeSet {
+        self.merge(other, true, false, false)
+    }
+
+    // single pass over both sorted vecs, the flags pick which side of the comparison is kept
+    fn merge(&self, other: &EdgeSet, keep_left: bool, keep_both: bool, keep_right: bool) -> EdgeSet {
+        let mut edges: Vec<(usize, usize)> = vec![];
+        let (mut left, mut right) = (self.edges.iter().peekable(), other.edges.iter().peekable());
+        loop {
+            match (left.peek(), right.peek()) {
+                (Some(a), Some(b)) => match a.cmp(b) {
+                    Ordering::Less => {
+                        if keep_left {edges.push(**a);}
+                        left.next();
+                    },
+                    Ordering::Greater => {
+                        if keep_right {edges.push(**b);}
+                        right.next();
+                    },
+                    Ordering::Equal => {
+                        if keep_both {edges.push(**a);}
+                        left.next();
+                        right.next();
+                    }
+                },
+                (Some(a), None) => {
+                    if keep_left {edges.push(**a);}
+                    left.next();
+                },
+                (None, Some(b)) => {
+                    if keep_right {edges.push(**b);}
+                    right.next();
+                },
+                (None, None) => break
+            }
+        }
+        EdgeSet { edges }
+    }
+}
+
+impl Ocdg {
+    pub fn layer(&self, rel: Relations) -> EdgeSet {
+        let rel: usize = rel.relation_index().into();
+        self.irels.iter()
+                  .flat_map(|(src, targets)| targets.iter().filter(|(_, rels)| rels.contains_key(&rel)).map(move |(tar, _)| (*src, *tar)))
+                  .collect()
+    }
+
+    pub fn edge_set(&self) -> EdgeSet {
+        self.irels.iter()
+                  .flat_map(|(src, targets)| targets.keys().map(move |tar| (*src, *tar)))
+                  .collect()
+    }
+
+    pub fn retain_edge_set(&mut self, edges: &EdgeSet) {
+        self.retain_edges(|edge| edges.contains(edge.src, edge.tar));
+    }
+}
@@ -0,0 +1,106 @@
+use std::{collections::{hash_map::DefaultHasher, BTreeMap}, hash::{Hash, Hasher}, time::Duration};
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use strum::EnumIter;
+
+use crate::objects::ocdg::{resolve_label, Ocdg, Relations};
+use crate::objects::ocel::Ocel;
+
+// what produced a graph, travels with it through exports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenerationMetadata {
+    pub crate_version: String,
+    pub config: Value,
+    pub log_fingerprint: String,
+    pub generated_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub edge_counts: BTreeMap<String, usize>
+}
+
+impl GenerationMetadata {
+    pub fn same_config(&self, other: &GenerationMetadata) -> bool {
+        self.config == other.config && self.log_fingerprint == other.log_fingerprint
+    }
+
+    // describes why two graphs are not comparable, None if they are
+    pub fn config_mismatch(&self, other: &GenerationMetadata) -> Option<String> {
+        if self.config != other.config {
+            Some(format!("graphs were generated with different configs: {} and {}", self.config, other.config))
+        } else if self.log_fingerprint != other.log_fingerprint {
+            Some(format!("graphs were generated from different logs: {} and {}", self.log_fingerprint, other.log_fingerprint))
+        } else {
+            None
+        }
+    }
+}
+
+// the steps of a generation run in the order they start, the batches of a run repeat the relation and application phases
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, EnumIter, Serialize, Deserialize)]
+pub enum GenerationPhase {
+    // the nodes and the objects each object shares an event with
+    NodeConstruction,
+    // the dense lifecycles and handover candidates every relation check reads
+    PrimitiveRelations,
+    // the relations checked per pair of objects
+    InstanceRelations,
+    // the relations over a group of objects, SPLIT over the objects of an event and REACHABLE over the DESCENDANTS chains
+    WholeRelations,
+    // adding the found edges to the graph, the event map and the metadata
+    EdgeApplication
+}
+
+// what a run did and how long each phase took, generate_ocdg_report hands it out
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GenerationReport {
+    pub phases: BTreeMap<GenerationPhase, Duration>,
+    pub edge_counts: BTreeMap<String, usize>,
+    pub warnings: Vec<String>
+}
+
+impl GenerationReport {
+    pub fn total(&self) -> Duration {
+        self.phases.values().sum()
+    }
+
+    pub(crate) fn add(&mut self, phase: GenerationPhase, duration: Duration) {
+        *self.phases.entry(phase).or_default() += duration;
+    }
+}
+
+impl Ocdg {
+    pub fn metadata(&self) -> Option<&GenerationMetadata> {
+        self.metadata.as_ref()
+    }
+
+    pub(crate) fn relation_instance_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for rel in self.irels.values().flat_map(|targets| targets.values()).flat_map(|rels| rels.keys()) {
+            if let Some(relation) = Relations::from_index(*rel) {
+                *counts.entry(relation.to_string()).or_default() += 1;
+            }
+        }
+        counts
+    }
+}
+
+// independent of the internal ids so that re-imports of the same file match
+pub fn log_fingerprint(log: &Ocel) -> String {
+    let mut hasher = DefaultHasher::new();
+    let mut objects: Vec<(&str, &str)> = log.objects.iter()
+                                            .map(|(oid, obj)| (resolve_label(&log.object_map, oid), obj.obj_type.as_str()))
+                                            .collect();
+    objects.sort_unstable();
+    objects.hash(&mut hasher);
+
+    let mut events: Vec<(&str, &str, i64, Vec<&str>)> = log.events.iter()
+                                                          .map(|(eid, event)| {
+                                                              let mut omap: Vec<&str> = event.omap.iter().map(|oid| resolve_label(&log.object_map, oid)).collect();
+                                                              omap.sort_unstable();
+                                                              (resolve_label(&log.event_map, eid), event.activity, event.timestamp.timestamp_nanos(), omap)
+                                                          })
+                                                          .collect();
+    events.sort_unstable();
+    events.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
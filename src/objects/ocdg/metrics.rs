@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+use itertools::Either;
+use nohash_hasher::{IntMap, IntSet};
+use petgraph::Direction;
+
+use crate::objects::ocdg::{Ocdg, Relations};
+
+#[derive(Debug, Clone, Default)]
+pub struct MotifConfig {
+    // only edges with one of these relations count, all edges when None
+    pub relations: Option<Vec<Relations>>
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TriadCount {
+    pub closed: usize,
+    pub open: usize
+}
+
+pub type TypeTriple = (String, String, String);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MotifCounts {
+    pub triangles: usize,
+    // connected triples whose outer objects are not adjacent
+    pub open_triads: usize,
+    // keyed by the sorted object types of the three objects
+    pub triads: BTreeMap<TypeTriple, TriadCount>
+}
+
+impl MotifCounts {
+    pub fn triad(&self, a: &str, b: &str, c: &str) -> TriadCount {
+        self.triads.get(&type_triple(a, b, c)).copied().unwrap_or_default()
+    }
+
+    // closed triples over all connected triples
+    pub fn transitivity(&self) -> f64 {
+        let closed = 3 * self.triangles;
+        if closed + self.open_triads == 0 {0.0} else {closed as f64 / (closed + self.open_triads) as f64}
+    }
+}
+
+// counts on the undirected projection of the graph
+pub fn motifs(ocdg: &Ocdg, config: &MotifConfig) -> MotifCounts {
+    let allowed: Option<IntSet<usize>> = config.relations.as_ref().map(|relations| relations.iter().map(|rel| rel.relation_index().into()).collect());
+    let mut adjacency: IntMap<usize, IntSet<usize>> = IntMap::default();
+    for (src, targets) in &ocdg.irels {
+        for (tar, rels) in targets {
+            if src == tar || !allowed.as_ref().map_or(!rels.is_empty(), |allowed| rels.keys().any(|rel| allowed.contains(rel))) {
+                continue;
+            }
+            adjacency.entry(*src).or_default().insert(*tar);
+            adjacency.entry(*tar).or_default().insert(*src);
+        }
+    }
+    let node_type = |oid: &usize| ocdg.node_attributes.get(oid).map_or("", |info| info.node_type.as_str());
+    let mut counts = MotifCounts::default();
+
+    // node iterator over the edges oriented from lower to higher (degree, oid), every triangle is found once
+    let rank = |oid: &usize| (adjacency[oid].len(), *oid);
+    let forward: IntMap<usize, Vec<usize>> = adjacency.iter()
+                                                      .map(|(oid, neighbours)| (*oid, neighbours.iter().copied().filter(|neighbour| rank(neighbour) > rank(oid)).collect()))
+                                                      .collect();
+    for (u, u_forward) in &forward {
+        let marked: IntSet<usize> = u_forward.iter().copied().collect();
+        for v in u_forward {
+            for w in &forward[v] {
+                if marked.contains(w) {
+                    counts.triangles += 1;
+                    counts.triads.entry(type_triple(node_type(u), node_type(v), node_type(w))).or_default().closed += 1;
+                }
+            }
+        }
+    }
+
+    for (center, neighbours) in &adjacency {
+        let neighbours: Vec<usize> = neighbours.iter().copied().collect();
+        for (index, a) in neighbours.iter().enumerate() {
+            for b in &neighbours[index + 1..] {
+                if !adjacency[a].contains(b) {
+                    counts.open_triads += 1;
+                    counts.triads.entry(type_triple(node_type(a), node_type(center), node_type(b))).or_default().open += 1;
+                }
+            }
+        }
+    }
+    counts
+}
+
+fn type_triple(a: &str, b: &str, c: &str) -> TypeTriple {
+    let mut types = [a, b, c];
+    types.sort_unstable();
+    (types[0].to_owned(), types[1].to_owned(), types[2].to_owned())
+}
+
+impl Ocdg {
+    // (in-degree, out-degree) of every object over the edges holding the relation, or any relation when None.
+    // neighbours are counted once however many relations link them
+    pub fn object_degrees(&self, rel: Option<Relations>) -> IntMap<usize, (usize, usize)> {
+        self.node_attributes.keys()
+            .map(|oid| (*oid, (self.degree_neighbours(*oid, rel, Direction::Incoming).count(), self.degree_neighbours(*oid, rel, Direction::Outgoing).count())))
+            .collect()
+    }
+
+    // the k objects with the most neighbours in either direction, most first and ties by id. a symmetric relation links
+    // a pair both ways but adds one neighbour to each
+    pub fn top_k_by_degree(&self, rel: Option<Relations>, k: usize) -> Vec<(usize, usize)> {
+        let mut degrees: Vec<(usize, usize)> = self.node_attributes.keys()
+                                                   .map(|oid| {
+                                                       let neighbours: IntSet<usize> = self.degree_neighbours(*oid, rel, Direction::Incoming)
+                                                                                           .chain(self.degree_neighbours(*oid, rel, Direction::Outgoing))
+                                                                                           .collect();
+                                                       (*oid, neighbours.len())
+                                                   })
+                                                   .collect();
+        degrees.sort_unstable_by_key(|(oid, degree)| (usize::MAX - degree, *oid));
+        degrees.truncate(k);
+        degrees
+    }
+
+    fn degree_neighbours(&self, oid: usize, rel: Option<Relations>, direction: Direction) -> impl Iterator<Item = usize> + '_ {
+        match rel {
+            Some(rel) => Either::Left(self.neighbors_with_relation_iter(oid, rel, direction)),
+            None => Either::Right(self.inodes.get(&oid).into_iter().flat_map(move |node| self.net.neighbors_directed(*node, direction)).map(|node| self.net[node]))
+        }
+    }
+}
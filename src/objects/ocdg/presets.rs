@@ -0,0 +1,83 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use strum::{EnumIter, EnumString, IntoEnumIterator};
+
+use crate::objects::ocdg::Relations;
+
+// named bundles of relations for the common questions asked of a graph
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, EnumIter, EnumString)]
+pub enum Preset {
+    // where objects come from and what they turn into: DESCENDANTS, INHERITANCE, CONSUMES, SPLIT, MERGE.
+    // for traceability, bills of material and tracking material through transformations
+    Lineage,
+    // objects starting and ending together: COBIRTH, CODEATH, COLIFE.
+    // for batching, objects handled as a unit and synchronisation points
+    Lifecycle,
+    // objects working on each other during their lifecycles: INTERACTS, ENGAGES, PEELER, MINION.
+    // for collaboration, resources and hubs
+    Interaction,
+    // every relation, when the question is not known yet
+    All
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Preset {
+    // the preset holding exactly the relations, in any order
+    pub fn matching(relations: &[Relations]) -> Option<Preset> {
+        let selection = RelationSet::new(relations.to_vec());
+        Preset::iter().find(|preset| RelationSet::preset(*preset) == selection)
+    }
+}
+
+// a deduplicated selection of relations in declaration order
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RelationSet {
+    relations: Vec<Relations>
+}
+
+impl RelationSet {
+    pub fn new(relations: Vec<Relations>) -> Self {
+        let mut relations = relations;
+        relations.sort_unstable_by_key(|rel| rel.relation_index());
+        relations.dedup();
+        Self { relations }
+    }
+
+    pub fn preset(preset: Preset) -> Self {
+        let relations = match preset {
+            Preset::Lineage => vec![Relations::DESCENDANTS, Relations::INHERITANCE, Relations::CONSUMES, Relations::SPLIT, Relations::MERGE],
+            Preset::Lifecycle => vec![Relations::COBIRTH, Relations::CODEATH, Relations::COLIFE],
+            Preset::Interaction => vec![Relations::INTERACTS, Relations::ENGAGES, Relations::PEELER, Relations::MINION],
+            Preset::All => Relations::iter().collect()
+        };
+        Self::new(relations)
+    }
+
+    // every name is a relation or a preset, presets are expanded
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> Result<Self, Box<dyn Error>> {
+        let mut relations = vec![];
+        for name in names {
+            let name = name.as_ref();
+            match (Relations::from_str(name), Preset::from_str(name)) {
+                (Ok(rel), _) => relations.push(rel),
+                (_, Ok(preset)) => relations.extend(RelationSet::preset(preset).relations),
+                _ => return Err(format!("unknown relation {}", name).into())
+            }
+        }
+        Ok(Self::new(relations))
+    }
+
+    pub fn relations(&self) -> &[Relations] {
+        &self.relations
+    }
+
+    pub fn into_vec(self) -> Vec<Relations> {
+        self.relations
+    }
+}
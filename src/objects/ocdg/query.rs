@@ -0,0 +1,319 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+use nohash_hasher::{IntMap, IntSet};
+use petgraph::Direction;
+use strum::EnumString;
+
+use crate::objects::ocdg::{Ocdg, Relations};
+use crate::objects::ocel::Ocel;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, EnumString)]
+pub enum QueryOrder {
+    // most edges first
+    ByDegree,
+    #[default]
+    ByOid,
+    // earliest birth in the log first, objects missing from the log last
+    ByFirstTimestamp
+}
+
+// every order breaks ties by oid, so the pages of an unchanged graph never overlap or skip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryLimits {
+    pub offset: usize,
+    pub limit: usize,
+    pub order: QueryOrder
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self { offset: 0, limit: 100, order: QueryOrder::default() }
+    }
+}
+
+impl QueryLimits {
+    // the whole result on one page
+    pub fn all(order: QueryOrder) -> Self {
+        Self { offset: 0, limit: usize::MAX, order }
+    }
+
+    pub fn page(&self, offset: usize) -> Self {
+        Self { offset, ..*self }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    // size of the whole result
+    pub total: usize,
+    // None on the last page
+    pub next_offset: Option<usize>
+}
+
+// objects the source points at with at least one of the relations
+pub fn neighbors_with(ocdg: &Ocdg, log: &Ocel, oid: usize, relations: &[Relations], limits: &QueryLimits) -> Page<usize> {
+    let neighbours: Vec<usize> = ocdg.irels.get(&oid)
+                                           .into_iter()
+                                           .flatten()
+                                           .filter(|(_, rels)| carries(rels.keys(), relations))
+                                           .map(|(tar, _)| *tar)
+                                           .collect();
+    paginate(ocdg, log, neighbours, limits)
+}
+
+// every object reachable from the source over edges with at least one of the relations, without the source
+pub fn impact_set(ocdg: &Ocdg, log: &Ocel, oid: usize, relations: &[Relations], limits: &QueryLimits) -> Page<usize> {
+    let mut visited: IntSet<usize> = IntSet::from_iter([oid]);
+    let mut queue: VecDeque<usize> = VecDeque::from([oid]);
+    while let Some(current) = queue.pop_front() {
+        for (tar, rels) in ocdg.irels.get(&current).into_iter().flatten() {
+            if carries(rels.keys(), relations) && visited.insert(*tar) {
+                queue.push_back(*tar);
+            }
+        }
+    }
+    visited.remove(&oid);
+    paginate(ocdg, log, visited.into_iter().collect(), limits)
+}
+
+impl Ocdg {
+    // every object oid reaches over edges with one of the relations, at most max_depth hops away and without oid itself
+    pub fn descendants_closure(&self, oid: usize, rels: &[Relations], max_depth: Option<usize>) -> IntSet<usize> {
+        self.closure(oid, rels, max_depth, Direction::Outgoing)
+    }
+
+    // every object reaching oid over edges with one of the relations, the inverse of descendants_closure
+    pub fn ancestors_closure(&self, oid: usize, rels: &[Relations], max_depth: Option<usize>) -> IntSet<usize> {
+        self.closure(oid, rels, max_depth, Direction::Incoming)
+    }
+
+    // the fewest hops from one object to the other over edges in their direction holding one of the relations (any for None),
+    // each hop with the relation that allows it, the first one when several do. Some(empty) for from == to
+    pub fn relation_path(&self, from: usize, to: usize, rels: Option<&[Relations]>) -> Option<Vec<(usize, usize, Relations)>> {
+        if !self.inodes.contains_key(&from) || !self.inodes.contains_key(&to) {
+            return None;
+        }
+        let mut parents: IntMap<usize, (usize, Relations)> = IntMap::default();
+        let mut visited: IntSet<usize> = IntSet::from_iter([from]);
+        let mut queue: VecDeque<usize> = VecDeque::from([from]);
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                break;
+            }
+            // in id order so that ties between paths are broken the same way every time
+            let mut hops: Vec<(usize, Relations)> = self.irels.get(&current)
+                                                              .into_iter()
+                                                              .flatten()
+                                                              .filter_map(|(tar, rels_held)| {
+                                                                  let mut allowed: Vec<Relations> = rels_held.keys()
+                                                                                                             .filter_map(|rel| Relations::from_index(*rel))
+                                                                                                             .filter(|rel| rels.is_none_or(|rels| rels.contains(rel)))
+                                                                                                             .collect();
+                                                                  allowed.sort_by_key(|rel| rel.relation_index());
+                                                                  allowed.first().map(|rel| (*tar, *rel))
+                                                              })
+                                                              .collect();
+            hops.sort_unstable_by_key(|(tar, _)| *tar);
+            for (tar, rel) in hops {
+                if visited.insert(tar) {
+                    parents.insert(tar, (current, rel));
+                    queue.push_back(tar);
+                }
+            }
+        }
+
+        if !visited.contains(&to) {
+            return None;
+        }
+        let mut path: Vec<(usize, usize, Relations)> = vec![];
+        let mut current = to;
+        while current != from {
+            let (parent, rel) = parents[&current];
+            path.push((parent, current, rel));
+            current = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // breadth first so that each object is met at its smallest depth, visited objects are never queued twice
+    // which ends cycles like the ones INTERACTS forms
+    fn closure(&self, oid: usize, rels: &[Relations], max_depth: Option<usize>, direction: Direction) -> IntSet<usize> {
+        let mut visited: IntSet<usize> = IntSet::from_iter([oid]);
+        let mut queue: VecDeque<(usize, usize)> = VecDeque::from([(oid, 0)]);
+        while let Some((current, depth)) = queue.pop_front() {
+            if max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                continue;
+            }
+            for rel in rels {
+                for neighbour in self.neighbors_with_relation_iter(current, *rel, direction) {
+                    if visited.insert(neighbour) {
+                        queue.push_back((neighbour, depth + 1));
+                    }
+                }
+            }
+        }
+        visited.remove(&oid);
+        visited
+    }
+}
+
+// the longest path a pattern may describe
+pub const MAX_PATH_HOPS: usize = 4;
+
+// object types joined by relation arrows, e.g. "order -SPLIT-> item <-CONSUMES- invoice". * matches any type,
+// -REL-> follows the relation from the left object and <-REL- from the right one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathPattern {
+    // None for *
+    pub types: Vec<Option<String>>,
+    // one hop between each two types
+    pub hops: Vec<(Relations, Direction)>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    Empty,
+    // a token that should be an object type, like an arrow in its place
+    ExpectedType(String),
+    ExpectedArrow(String),
+    UnknownRelation(String),
+    // a pattern ending with an arrow
+    MissingType,
+    TooLong(usize)
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatternError::Empty => write!(f, "The pattern is empty"),
+            PatternError::ExpectedType(token) => write!(f, "Expected an object type or * but found {}", token),
+            PatternError::ExpectedArrow(token) => write!(f, "Expected -RELATION-> or <-RELATION- but found {}", token),
+            PatternError::UnknownRelation(name) => write!(f, "{} is not a relation", name),
+            PatternError::MissingType => write!(f, "The pattern ends with an arrow"),
+            PatternError::TooLong(hops) => write!(f, "The pattern has {} hops, at most {} are supported", hops, MAX_PATH_HOPS)
+        }
+    }
+}
+
+impl Error for PatternError {}
+
+impl PathPattern {
+    pub fn parse(pattern: &str) -> Result<PathPattern, PatternError> {
+        let tokens: Vec<&str> = pattern.split_whitespace().collect();
+        if tokens.is_empty() {
+            return Err(PatternError::Empty);
+        }
+        let mut types: Vec<Option<String>> = vec![];
+        let mut hops: Vec<(Relations, Direction)> = vec![];
+        for (position, token) in tokens.iter().enumerate() {
+            if position % 2 == 0 {
+                if token.starts_with('-') || token.starts_with("<-") {
+                    return Err(PatternError::ExpectedType(token.to_string()));
+                }
+                types.push(if *token == "*" { None } else { Some(token.to_string()) });
+                continue;
+            }
+            let (name, direction) = if let Some(name) = token.strip_prefix("<-").and_then(|rest| rest.strip_suffix('-')) {
+                (name, Direction::Incoming)
+            } else if let Some(name) = token.strip_prefix('-').and_then(|rest| rest.strip_suffix("->")) {
+                (name, Direction::Outgoing)
+            } else {
+                return Err(PatternError::ExpectedArrow(token.to_string()));
+            };
+            let rel = Relations::from_str(&name.to_uppercase()).map_err(|_| PatternError::UnknownRelation(name.to_owned()))?;
+            hops.push((rel, direction));
+        }
+        if types.len() == hops.len() {
+            return Err(PatternError::MissingType);
+        }
+        if hops.len() > MAX_PATH_HOPS {
+            return Err(PatternError::TooLong(hops.len()));
+        }
+        Ok(PathPattern { types, hops })
+    }
+
+    // every path matching the pattern without visiting an object twice, as oid sequences. the paths are ordered by their
+    // first object in the order of the limits and then by the oids that follow, the total counts every matching path
+    pub fn find(&self, ocdg: &Ocdg, log: &Ocel, limits: &QueryLimits) -> Page<Vec<usize>> {
+        let mut starts: Vec<usize> = ocdg.inodes.keys().copied().filter(|oid| self.matches(ocdg, 0, *oid)).collect();
+        sort_oids(ocdg, log, &mut starts, limits.order);
+        let window = limits.offset..limits.offset.saturating_add(limits.limit);
+        let mut total = 0;
+        let mut found: Vec<Vec<usize>> = vec![];
+        for start in starts {
+            self.extend(ocdg, &mut vec![start], &window, &mut total, &mut found);
+        }
+        page(found, total, limits)
+    }
+
+    // only the paths whose position falls into the window are kept, all of them are counted
+    fn extend(&self, ocdg: &Ocdg, path: &mut Vec<usize>, window: &Range<usize>, total: &mut usize, found: &mut Vec<Vec<usize>>) {
+        let hop = path.len() - 1;
+        let Some((rel, direction)) = self.hops.get(hop) else {
+            if window.contains(total) {
+                found.push(path.clone());
+            }
+            *total += 1;
+            return;
+        };
+        let last = *path.last().expect("Paths start with an object");
+        for next in ocdg.neighbors_with_relation(last, *rel, *direction) {
+            if path.contains(&next) || !self.matches(ocdg, hop + 1, next) {
+                continue;
+            }
+            path.push(next);
+            self.extend(ocdg, path, window, total, found);
+            path.pop();
+        }
+    }
+
+    fn matches(&self, ocdg: &Ocdg, position: usize, oid: usize) -> bool {
+        match &self.types[position] {
+            None => true,
+            Some(obj_type) => ocdg.node_attributes.get(&oid).is_some_and(|node| node.node_type == *obj_type)
+        }
+    }
+}
+
+fn carries<'a, I: Iterator<Item = &'a usize>>(mut rels: I, relations: &[Relations]) -> bool {
+    rels.any(|rel| Relations::from_index(*rel).is_some_and(|relation| relations.contains(&relation)))
+}
+
+fn paginate(ocdg: &Ocdg, log: &Ocel, mut oids: Vec<usize>, limits: &QueryLimits) -> Page<usize> {
+    sort_oids(ocdg, log, &mut oids, limits.order);
+    let total = oids.len();
+    let end = limits.offset.saturating_add(limits.limit).min(total);
+    let items = oids.get(limits.offset..end).map_or_else(Vec::new, |page| page.to_vec());
+    page(items, total, limits)
+}
+
+fn sort_oids(ocdg: &Ocdg, log: &Ocel, oids: &mut [usize], order: QueryOrder) {
+    match order {
+        QueryOrder::ByDegree => {
+            let degree = |oid: &usize| ocdg.inodes.get(oid).map_or(0, |node| ocdg.net.edges_directed(*node, Direction::Outgoing).count() + ocdg.net.edges_directed(*node, Direction::Incoming).count());
+            oids.sort_by_cached_key(|oid| (usize::MAX - degree(oid), *oid));
+        },
+        QueryOrder::ByOid => oids.sort_unstable(),
+        QueryOrder::ByFirstTimestamp => {
+            let birth = |oid: &usize| log.objects.get(oid).and_then(|object| object.events.iter().map(|eid| log.events.event(eid).timestamp).min());
+            // None sorts first, so flip it behind every timestamp
+            oids.sort_by_cached_key(|oid| {
+                let birth = birth(oid);
+                (birth.is_none(), birth, *oid)
+            });
+        }
+    }
+}
+
+// the items of the page within a result of total items
+fn page<T>(items: Vec<T>, total: usize, limits: &QueryLimits) -> Page<T> {
+    let end = limits.offset.saturating_add(limits.limit).min(total);
+    // an empty limit never moves forward
+    let next_offset = if end < total && end > limits.offset { Some(end) } else { None };
+    Page { items, total, next_offset }
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::Write;
+use chrono::{DateTime, Duration, Utc};
+use rayon::prelude::*;
+
+use crate::objects::exporter::StreamingExporter;
+use crate::objects::ocdg::{generate_ocdg_config, GenerationConfig, Ocdg, OcdgError};
+use crate::objects::ocel::Ocel;
+
+// start inclusive, end exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>
+}
+
+impl Interval {
+    pub fn contains(&self, timestamp: &DateTime<Utc>) -> bool {
+        self.start <= *timestamp && *timestamp < self.end
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalStats {
+    pub interval: Interval,
+    pub nodes: usize,
+    pub edges: usize,
+    pub events: usize,
+    // relation name -> relation instances
+    pub edge_counts: BTreeMap<String, usize>
+}
+
+#[derive(Default)]
+pub struct OcdgSeries {
+    // ordered by interval start
+    entries: Vec<(Interval, Ocdg)>
+}
+
+impl OcdgSeries {
+    // windows start at the first event and move by the stride until they pass the last event
+    pub fn by_window(ocel: &Ocel, window: Duration, stride: Duration, config: &GenerationConfig) -> Result<OcdgSeries, OcdgError> {
+        let first = ocel.events.values().map(|event| event.timestamp).min();
+        let last = ocel.events.values().map(|event| event.timestamp).max();
+        let (mut start, last) = match (first, last) {
+            (Some(first), Some(last)) if window > Duration::zero() && stride > Duration::zero() => (first, last),
+            _ => return Ok(OcdgSeries::default())
+        };
+
+        let mut intervals: Vec<Interval> = vec![];
+        while start <= last {
+            intervals.push(Interval { start, end: start + window });
+            start = start + stride;
+        }
+        let entries: Vec<(Interval, Ocdg)> = intervals.into_par_iter()
+                                                      .map(|interval| generate_ocdg_config(&ocel.between(&interval.start, &interval.end), config).map(|ocdg| (interval, ocdg)))
+                                                      .collect::<Result<_, _>>()?;
+        Ok(OcdgSeries { entries })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[(Interval, Ocdg)] {
+        &self.entries
+    }
+
+    pub fn intervals(&self) -> Vec<Interval> {
+        self.entries.iter().map(|(interval, _)| *interval).collect()
+    }
+
+    // the latest window that contains the timestamp
+    pub fn at(&self, timestamp: &DateTime<Utc>) -> Option<&Ocdg> {
+        self.all_at(timestamp).last().copied()
+    }
+
+    // overlapping windows, in the order of their start
+    pub fn all_at(&self, timestamp: &DateTime<Utc>) -> Vec<&Ocdg> {
+        self.entries.iter().filter(|(interval, _)| interval.contains(timestamp)).map(|(_, ocdg)| ocdg).collect()
+    }
+
+    pub fn statistics(&self) -> Vec<IntervalStats> {
+        self.entries.iter()
+                    .map(|(interval, ocdg)| IntervalStats { interval: *interval,
+                                                             nodes: ocdg.net.node_count(),
+                                                             edges: ocdg.net.edge_count(),
+                                                             events: ocdg.event_map.len(),
+                                                             edge_counts: ocdg.relation_instance_counts() })
+                    .collect()
+    }
+
+    pub fn export_edge_counts_csv(&self, file_path: &str) -> Result<bool, Box<dyn Error>> {
+        SeriesCountsCsv(self.statistics()).export(file_path)?;
+        Ok(true)
+    }
+}
+
+pub struct SeriesCountsCsv(pub Vec<IntervalStats>);
+
+// long format, one chunk for the header and one per interval
+impl StreamingExporter for SeriesCountsCsv {
+    fn chunk_count(&self) -> usize {
+        self.0.len() + 1
+    }
+
+    fn write_chunk(&self, index: usize, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        match index {
+            0 => writeln!(f, "start,end,relation,count")?,
+            _ => {
+                let stats = &self.0[index - 1];
+                for (relation, count) in &stats.edge_counts {
+                    writeln!(f, "{},{},{},{}", stats.interval.start.to_rfc3339(), stats.interval.end.to_rfc3339(), relation, count)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use ahash::AHashMap;
+use nohash_hasher::{IntMap, IntSet};
+use strum::EnumString;
+
+use crate::objects::ocdg::{Ocdg, Relations};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+pub enum SimilarityMetric {
+    Jaccard,
+    Cosine
+}
+
+// sparse (neighbour, relation) vectors with an inverted index, queries only score objects sharing a feature
+pub struct SimilarityIndex<'a> {
+    ocdg: &'a Ocdg,
+    pub same_type: bool,
+    vectors: IntMap<usize, AHashMap<(usize, usize), f64>>,
+    postings: AHashMap<(usize, usize), Vec<usize>>
+}
+
+impl<'a> SimilarityIndex<'a> {
+    pub fn new(ocdg: &'a Ocdg) -> Self {
+        let mut vectors: IntMap<usize, AHashMap<(usize, usize), f64>> = IntMap::default();
+        let mut postings: AHashMap<(usize, usize), Vec<usize>> = AHashMap::new();
+        for (src, targets) in &ocdg.irels {
+            for (tar, rels) in targets {
+                for rel in rels.keys() {
+                    // without kept evidence every relation instance weighs the same
+                    let weight = Relations::from_index(*rel).and_then(|relation| ocdg.relation_support(*src, *tar, relation)).unwrap_or(1).max(1);
+                    vectors.entry(*src).or_default().insert((*tar, *rel), weight as f64);
+                    postings.entry((*tar, *rel)).or_default().push(*src);
+                }
+            }
+        }
+        Self { ocdg, same_type: true, vectors, postings }
+    }
+
+    pub fn candidates(&self, oid: usize) -> IntSet<usize> {
+        let node_type = self.ocdg.node_attributes.get(&oid).map(|node| node.node_type.as_str());
+        self.vectors.get(&oid)
+                    .into_iter()
+                    .flat_map(|vector| vector.keys())
+                    .filter_map(|feature| self.postings.get(feature))
+                    .flatten()
+                    .copied()
+                    .filter(|other| *other != oid)
+                    .filter(|other| !self.same_type || self.ocdg.node_attributes.get(other).map(|node| node.node_type.as_str()) == node_type)
+                    .collect()
+    }
+
+    pub fn similarity(&self, a: usize, b: usize, metric: SimilarityMetric) -> f64 {
+        let (vec_a, vec_b) = match (self.vectors.get(&a), self.vectors.get(&b)) {
+            (Some(vec_a), Some(vec_b)) => (vec_a, vec_b),
+            _ => return 0.0
+        };
+        let shared = vec_a.keys().filter(|feature| vec_b.contains_key(feature));
+        match metric {
+            SimilarityMetric::Jaccard => {
+                let shared = shared.count();
+                shared as f64 / (vec_a.len() + vec_b.len() - shared) as f64
+            },
+            SimilarityMetric::Cosine => {
+                let dot: f64 = shared.map(|feature| vec_a[feature] * vec_b[feature]).sum();
+                let norm = |vector: &AHashMap<(usize, usize), f64>| vector.values().map(|weight| weight * weight).sum::<f64>().sqrt();
+                dot / (norm(vec_a) * norm(vec_b))
+            }
+        }
+    }
+
+    // most similar objects first, ties broken by oid
+    pub fn top_k(&self, oid: usize, k: usize, metric: SimilarityMetric) -> Vec<(usize, f64)> {
+        let mut scored: Vec<(usize, f64)> = self.candidates(oid).into_iter()
+                                                .map(|other| (other, self.similarity(oid, other, metric)))
+                                                .collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).expect("Scores are never NaN").then(a.0.cmp(&b.0)));
+        scored.truncate(k);
+        scored
+    }
+}
+
+pub fn top_k(ocdg: &Ocdg, oid: usize, k: usize, metric: SimilarityMetric) -> Vec<(usize, f64)> {
+    SimilarityIndex::new(ocdg).top_k(oid, k, metric)
+}
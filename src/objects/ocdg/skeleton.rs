@@ -0,0 +1,160 @@
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use strum::IntoEnumIterator;
+
+use crate::objects::ocdg::{Evidence, Ocdg, Relations};
+use crate::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+
+// relations whose evidence events hold both objects, the omaps of the skeleton are rebuilt from these
+const EVIDENCE_BASED: [Relations; 4] = [Relations::INTERACTS, Relations::COLIFE, Relations::COBIRTH, Relations::CODEATH];
+
+pub const SKELETON_ACTIVITY: &str = "event";
+
+pub struct SkeletonLog {
+    pub log: Ocel,
+    // relations of the graph and whether regenerating the skeleton with them is guaranteed to reproduce their instances and evidence
+    pub guarantees: Vec<(Relations, bool)>
+}
+
+// a synthetic log that reproduces the evidence-based relations of a graph generated with full evidence and the whole lifecycle scope.
+// objects keep their labels and types, events keep their internal ids but get anonymous labels, one activity and timestamps one
+// minute apart in an order that keeps every known first and last event of an object in place. objects whose first (last) event
+// is not evidence of COBIRTH, COLIFE (CODEATH, COLIFE) get a private birth (death) event, so that no relation appears that the
+// graph does not have. the other relations are regenerated as well but only match by chance
+pub fn to_skeleton_log(ocdg: &Ocdg) -> Option<SkeletonLog> {
+    if ocdg.evidence != Evidence::Full {
+        return None;
+    }
+    let relations = graph_relations(ocdg);
+    let whole_lifecycle = ocdg.metadata().is_none_or(|metadata| metadata.config["scope"].as_str().is_none_or(|scope| scope == "WholeLifecycle"));
+    let guarantees: Vec<(Relations, bool)> = relations.iter().map(|rel| (*rel, whole_lifecycle && EVIDENCE_BASED.contains(rel))).collect();
+
+    let mut omaps: IntMap<usize, IntSet<usize>> = IntMap::default();
+    let mut lifecycles: IntMap<usize, IntSet<usize>> = IntMap::default();
+    let mut firsts: IntMap<usize, usize> = IntMap::default();
+    let mut lasts: IntMap<usize, usize> = IntMap::default();
+    for (src, targets) in &ocdg.irels {
+        for (tar, rels) in targets {
+            for (rel, eids) in rels {
+                let rel = match Relations::from_index(*rel) {
+                    Some(rel) if EVIDENCE_BASED.contains(&rel) => rel,
+                    _ => continue
+                };
+                for eid in eids {
+                    omaps.entry(*eid).or_default().extend([*src, *tar]);
+                    lifecycles.entry(*src).or_default().insert(*eid);
+                    lifecycles.entry(*tar).or_default().insert(*eid);
+                }
+                // COBIRTH and CODEATH hold their single event. COLIFE holds the whole lifecycle, so both boundaries are
+                // in the skeleton without knowing which events they are
+                let single = eids.iter().next().copied();
+                match rel {
+                    Relations::COBIRTH => {
+                        firsts.extend(single.iter().flat_map(|eid| [(*src, *eid), (*tar, *eid)]));
+                    },
+                    Relations::CODEATH => {
+                        lasts.extend(single.iter().flat_map(|eid| [(*src, *eid), (*tar, *eid)]));
+                    },
+                    Relations::COLIFE => {
+                        for oid in [*src, *tar] {
+                            lifecycles.entry(oid).or_default().extend(eids);
+                            firsts.entry(oid).or_insert(usize::MAX);
+                            lasts.entry(oid).or_insert(usize::MAX);
+                        }
+                    },
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let order = event_order(&omaps, &lifecycles, &firsts, &lasts);
+    let start = Utc.ymd(2000, 1, 1).and_hms(0, 0, 0);
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![SKELETON_ACTIVITY.to_owned()], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (position, eid) in order.iter().enumerate() {
+        let timestamp = start + Duration::minutes(position as i64 + 1);
+        log.events.insert(*eid, OcelEvent { activity: SKELETON_ACTIVITY.to_owned(), timestamp, vmap: AHashMap::new(), omap: omaps[eid].clone() });
+        log.event_map.insert(format!("e{}", eid), *eid);
+    }
+
+    let mut next_eid = order.iter().max().map_or(0, |eid| eid + 1);
+    let end = start + Duration::minutes(order.len() as i64 + 1);
+    let mut oids: Vec<usize> = ocdg.inodes.keys().copied().collect();
+    oids.sort_unstable();
+    for oid in oids {
+        let label = ocdg.object_map.get_by_right(&oid).cloned().unwrap_or_else(|| oid.to_string());
+        let obj_type = ocdg.node_attributes.get(&oid).map_or_else(String::new, |info| info.node_type.to_owned());
+        log.objects.insert(oid, OcelObject { obj_type, ovmap: AHashMap::new(), events: vec![] });
+        for (known, timestamp, kind) in [(firsts.contains_key(&oid), start, "birth"), (lasts.contains_key(&oid), end, "death")] {
+            if !known {
+                log.events.insert(next_eid, OcelEvent { activity: SKELETON_ACTIVITY.to_owned(), timestamp, vmap: AHashMap::new(), omap: IntSet::from_iter([oid]) });
+                log.event_map.insert(format!("{}-{}", kind, label), next_eid);
+                next_eid += 1;
+            }
+        }
+        log.object_map.insert(label, oid);
+    }
+    log.rebuild_derived_indices();
+    Some(SkeletonLog { log, guarantees })
+}
+
+// the relations the graph was generated with, or those it holds when it carries no metadata
+fn graph_relations(ocdg: &Ocdg) -> Vec<Relations> {
+    let recorded: Option<Vec<Relations>> = ocdg.metadata()
+                                               .and_then(|metadata| metadata.config["relations"].as_array().cloned())
+                                               .map(|names| names.iter().filter_map(|name| name.as_str().and_then(|name| Relations::from_str(name).ok())).collect());
+    recorded.unwrap_or_else(|| {
+        let present: IntSet<usize> = ocdg.irels.values().flat_map(|targets| targets.values().flat_map(|rels| rels.keys().copied())).collect();
+        Relations::iter().filter(|rel| present.contains(&rel.relation_index().into())).collect()
+    })
+}
+
+// known first events go before and known last events after the other events of their object, ties are broken by event id.
+// the original log satisfies these constraints, so the order exists for graphs generated from a log
+fn event_order(omaps: &IntMap<usize, IntSet<usize>>, lifecycles: &IntMap<usize, IntSet<usize>>, firsts: &IntMap<usize, usize>, lasts: &IntMap<usize, usize>) -> Vec<usize> {
+    let mut after: IntMap<usize, IntSet<usize>> = IntMap::default();
+    for (oid, events) in lifecycles {
+        // boundaries only known through COLIFE are not pinned to an event
+        let first = firsts.get(oid).filter(|eid| **eid != usize::MAX).copied();
+        let last = lasts.get(oid).filter(|eid| **eid != usize::MAX).copied();
+        for eid in events {
+            if let Some(first) = first.filter(|first| first != eid) {
+                after.entry(first).or_default().insert(*eid);
+            }
+            if let Some(last) = last.filter(|last| last != eid) {
+                after.entry(*eid).or_default().insert(last);
+            }
+        }
+    }
+    let mut incoming: IntMap<usize, usize> = omaps.keys().map(|eid| (*eid, 0)).collect();
+    for targets in after.values() {
+        for eid in targets {
+            *incoming.entry(*eid).or_default() += 1;
+        }
+    }
+
+    let mut ready: BTreeSet<usize> = incoming.iter().filter(|(_, count)| **count == 0).map(|(eid, _)| *eid).collect();
+    let mut order: Vec<usize> = vec![];
+    while let Some(eid) = ready.pop_first() {
+        order.push(eid);
+        for next in after.get(&eid).into_iter().flatten() {
+            let count = incoming.get_mut(next).expect("Every constrained event has an omap");
+            *count -= 1;
+            if *count == 0 {
+                ready.insert(*next);
+            }
+        }
+    }
+    // contradicting constraints cannot come from a log, the remaining events follow by id
+    let placed: IntSet<usize> = order.iter().copied().collect();
+    let mut rest: Vec<usize> = omaps.keys().copied().filter(|eid| !placed.contains(eid)).collect();
+    rest.sort_unstable();
+    order.extend(rest);
+    order
+}
@@ -0,0 +1,55 @@
+use std::error::Error;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use crossbeam_channel::{bounded, Receiver};
+
+use crate::objects::ocdg::{generate_in_batches, EventAdd, GenerationConfig, Ocdg, Relations};
+use crate::objects::ocel::Ocel;
+
+pub const DEFAULT_CAPACITY: usize = 16;
+pub const DEFAULT_BATCH_OBJECTS: usize = 256;
+
+// one relation instance as found during generation, every (source, target, relation) is sent exactly once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamedEdge {
+    pub source: String,
+    pub target: String,
+    pub relation: Relations,
+    // number of events supporting the instance
+    pub events: usize
+}
+
+pub type EdgeBatch = Vec<StreamedEdge>;
+pub type GenerationHandle = JoinHandle<Result<Ocdg, Box<dyn Error + Send + Sync>>>;
+
+pub fn generate_ocdg_channel(log: Arc<Ocel>, config: GenerationConfig) -> (GenerationHandle, Receiver<EdgeBatch>) {
+    generate_ocdg_channel_bounded(log, config, DEFAULT_CAPACITY, DEFAULT_BATCH_OBJECTS)
+}
+
+// generation runs on its own thread and blocks once capacity batches are waiting, so a slow consumer throttles it.
+// every batch holds the edges of batch_objects source objects, empty batches are not sent.
+// dropping the receiver stops the generation and the handle returns an error
+pub fn generate_ocdg_channel_bounded(log: Arc<Ocel>, config: GenerationConfig, capacity: usize, batch_objects: usize) -> (GenerationHandle, Receiver<EdgeBatch>) {
+    let (sender, receiver) = bounded(capacity);
+    let handle = thread::spawn(move || {
+        let mut send = |edges: &[(usize, usize, EventAdd, Relations)]| {
+            if edges.is_empty() {
+                return true;
+            }
+            let batch: EdgeBatch = edges.iter()
+                                        .map(|(src, tar, events, rel)| StreamedEdge { source: label(&log, src), target: label(&log, tar), relation: *rel,
+                                                                                     events: match events {
+                                                                                         EventAdd::SINGLE(_) => 1,
+                                                                                         EventAdd::MULTI(eids) => eids.len()
+                                                                                     } })
+                                        .collect();
+            sender.send(batch).is_ok()
+        };
+        generate_in_batches(&log, &config, Some((batch_objects, &mut send)))?.map(|(ocdg, _)| ocdg).ok_or_else(|| "The receiver was dropped before the generation finished".into())
+    });
+    (handle, receiver)
+}
+
+fn label(log: &Ocel, oid: &usize) -> String {
+    log.object_map.get_by_right(oid).cloned().unwrap_or_else(|| oid.to_string())
+}
@@ -1 +1,3 @@
 pub(crate) mod gexf;
+pub(crate) mod cytoscape;
+pub(crate) mod json;
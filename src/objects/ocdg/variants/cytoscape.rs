@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+
+use crate::objects::ocdg::GenerationMetadata;
+
+pub(crate) const FORMAT_VERSION: &str = "1.0";
+pub(crate) const GENERATED_BY: &str = "pmrs";
+
+// the elements json of cytoscape.js, which the desktop app imports as well
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct CytoscapeJson {
+    pub format_version: String,
+    pub generated_by: String,
+    pub data: GraphDataCy,
+    pub elements: ElementsCy
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+pub(crate) struct GraphDataCy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generation: Option<GenerationMetadata>
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct ElementsCy {
+    pub nodes: Vec<ElementCy<NodeDataCy>>,
+    pub edges: Vec<ElementCy<EdgeDataCy>>
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct ElementCy<T> {
+    pub data: T
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct NodeDataCy {
+    // the object id
+    pub id: String,
+    #[serde(rename = "type")]
+    pub node_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<usize>
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct EdgeDataCy {
+    // source->target by object ids
+    pub id: String,
+    pub source: String,
+    pub target: String,
+    // the relation names joined by commas, the edge label of cytoscape
+    pub interaction: String,
+    // relation name -> supporting events, left out with per relation keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub support: Option<BTreeMap<String, usize>>,
+    // with per relation keys: rel_COBIRTH -> supporting events for every written relation, 0 when absent
+    #[serde(flatten)]
+    pub relation_keys: BTreeMap<String, usize>
+}
@@ -1,6 +1,10 @@
 
+use std::{error::Error, io::Write};
+use quick_xml::se::to_string;
 use serde::{Serialize, Deserialize};
 
+use crate::objects::exporter::Exporter;
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename="gexf")]
 pub struct Gexf {
@@ -19,7 +23,10 @@ pub struct Meta {
     #[serde(rename = "$unflatten=creator")]
     pub creator: String,
     #[serde(rename = "$unflatten=description")]
-    pub description: String
+    pub description: String,
+    // generation metadata as json
+    #[serde(rename = "$unflatten=keywords", skip_serializing_if = "Option::is_none", default)]
+    pub keywords: Option<String>
 }
 
 
@@ -108,6 +115,14 @@ impl Default for GraphGexf {
 
 impl Default for Meta {
     fn default() -> Self {
-        Self { creator: format!("Made with {} version {} by {}.",env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), env!("CARGO_PKG_AUTHORS")), description: "Object-Centric Directed Graph".to_owned() }
+        Self { creator: format!("Made with {} version {} by {}.",env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), env!("CARGO_PKG_AUTHORS")), description: "Object-Centric Directed Graph".to_owned(), keywords: None }
+    }
+}
+
+impl Exporter for Gexf {
+    fn write_content(&self, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        f.write_all(r#"<?xml version="1.0" encoding="UTF-8"?>"#.as_bytes())?;
+        f.write_all(to_string(self)?.as_bytes())?;
+        Ok(())
     }
 }
@@ -0,0 +1,50 @@
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+
+pub(crate) const SCHEMA: &str = "pmrs-ocdg";
+pub(crate) const VERSION: u32 = 1;
+
+// a graph by the string ids of its objects and events, readable without the log or the crate
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct OcdgJson {
+    pub schema: String,
+    pub version: u32,
+    pub evidence: JsonEvidence,
+    pub nodes: Vec<NodeJson>,
+    pub edges: Vec<EdgeJson>
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum JsonEvidence {
+    // every relation lists its supporting events
+    Events,
+    // every relation holds the number of its supporting events
+    Counts,
+    // the relations only hold
+    None
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct NodeJson {
+    pub object_id: String,
+    pub node_type: String,
+    // the lifecycle of the object in the log, empty for objects the log does not know
+    pub event_list: Vec<String>
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct EdgeJson {
+    pub source_id: String,
+    pub target_id: String,
+    // relation name -> support in the form of the evidence
+    pub relations: BTreeMap<String, SupportJson>
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub(crate) enum SupportJson {
+    Events(Vec<String>),
+    Count(usize),
+    Present(())
+}
@@ -1,61 +1,156 @@
 pub mod importer;
 pub mod exporter;
 pub mod validator;
+pub mod enrichment;
+pub mod merge;
+pub mod dedupe;
+pub mod window;
+pub mod hierarchy;
+pub(crate) mod columns;
+pub(crate) mod dense;
+pub(crate) mod indices;
+pub(crate) mod memory;
+pub mod removal;
+pub mod view;
+pub mod derive;
+pub mod flatten;
 
 use bimap::BiMap;
-use serde::{Serialize, Deserialize};
+use serde::{de::{self, MapAccess, Visitor}, Deserialize, Deserializer, Serialize};
 use nohash_hasher::{IntMap, IntSet};
 use serde_json::Value;
 use chrono::{DateTime, Utc};
 use indexmap::IndexMap;
 use ahash::{AHashMap, AHashSet, RandomState};
 use std::cmp::Ordering;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::fmt;
+use static_assertions::assert_impl_all;
 
+use self::importer::timestamp::TimestampParser;
 
+// the items of the internal modules that belong to the log api
+pub use self::columns::{EventColumns, EventRef, Omap, Vmap};
+pub use self::dense::{DenseEvents, EventBitSet};
+pub use self::memory::MemoryBreakdown;
+
+
+// importers read the timestamps as raw values first so that they can be parsed with custom formats,
+// and the object references as raw ids so that numeric ids keep all their digits
 #[derive(Serialize, Deserialize, Debug)]
-pub struct OcelSerde {
+#[serde(bound(serialize = "T: Serialize, I: Serialize + Eq + Hash", deserialize = "T: Deserialize<'de>, I: Deserialize<'de> + Eq + Hash"))]
+pub struct OcelSerde<T = DateTime<Utc>, I = String> {
     #[serde(alias = "ocel:global-log", rename(serialize = "ocel:global-log"))]
     pub global_log: AHashMap<String, Value>,
-    #[serde(alias = "ocel:global-event", rename(serialize = "ocel:global-event"))]
+    #[serde(default, alias = "ocel:global-event", rename(serialize = "ocel:global-event"))]
     pub global_event: AHashMap<String, Value>,
-    #[serde(alias = "ocel:global-object", rename(serialize = "ocel:global-object"))]
+    #[serde(default, alias = "ocel:global-object", rename(serialize = "ocel:global-object"))]
     pub global_object: AHashMap<String, Value>,
-    #[serde(alias = "ocel:objects", rename(serialize = "ocel:objects"))]
+    #[serde(default, deserialize_with = "unique_ids", alias = "ocel:objects", rename(serialize = "ocel:objects"))]
     pub objects: AHashMap<String, OcelObjectSerde>,
-    #[serde(alias = "ocel:events", rename(serialize = "ocel:events"))]
-    pub events: IndexMap<String, OcelEventSerde, RandomState>,
+    #[serde(deserialize_with = "unique_ids", alias = "ocel:events", rename(serialize = "ocel:events"))]
+    pub events: IndexMap<String, OcelEventSerde<T, I>, RandomState>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct OcelObjectSerde{
     #[serde(alias = "ocel:type", rename(serialize = "ocel:type"))]
     pub obj_type: String,
-    #[serde(alias = "ocel:ovmap", rename(serialize = "ocel:ovmap"))]
+    #[serde(default, alias = "ocel:ovmap", rename(serialize = "ocel:ovmap"))]
     pub ovmap: AHashMap<String, Value>
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-pub struct OcelEventSerde {
+#[serde(bound(serialize = "T: Serialize, I: Serialize + Eq + Hash", deserialize = "T: Deserialize<'de>, I: Deserialize<'de> + Eq + Hash"))]
+pub struct OcelEventSerde<T = DateTime<Utc>, I = String> {
     #[serde(alias = "ocel:activity", rename(serialize = "ocel:activity"))]
     pub activity: String,
     #[serde(alias = "ocel:timestamp", rename(serialize = "ocel:timestamp"))]
-    pub timestamp: DateTime<Utc>,
+    pub timestamp: T,
     #[serde(alias = "ocel:omap", rename(serialize = "ocel:omap"))]
-    pub omap: AHashSet<String>,
-    #[serde(alias = "ocel:vmap", rename(serialize = "ocel:vmap"))]
+    pub omap: AHashSet<I>,
+    #[serde(default, alias = "ocel:vmap", rename(serialize = "ocel:vmap"))]
     pub vmap: AHashMap<String, Value>,
 }
 
+// json maps keep the last of repeated keys, the ids of events and objects have to be unique
+fn unique_ids<'de, D, V, M>(deserializer: D) -> Result<M, D::Error>
+where D: Deserializer<'de>, V: Deserialize<'de>, M: FromIterator<(String, V)> {
+    struct Entries<V>(PhantomData<V>);
+
+    impl<'de, V: Deserialize<'de>> Visitor<'de> for Entries<V> {
+        type Value = Vec<(String, V)>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a map of ids")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut seen: AHashSet<String> = AHashSet::new();
+            let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+            while let Some((id, value)) = map.next_entry::<String, V>()? {
+                if !seen.insert(id.to_owned()) {
+                    return Err(de::Error::custom(format!("the id {} is used more than once", id)));
+                }
+                entries.push((id, value));
+            }
+            Ok(entries)
+        }
+    }
+
+    Ok(deserializer.deserialize_map(Entries(PhantomData))?.into_iter().collect())
+}
+
 #[derive(Debug, Clone)]
 pub struct Ocel {
     pub global_log: AHashMap<String, Value>,
     pub global_event: AHashMap<String, Value>,
     pub global_object: AHashMap<String, Value>,
-    pub events: IntMap<usize, OcelEvent>,
+    // the events as columns, see EventColumns
+    pub events: EventColumns,
     pub objects: IntMap<usize, OcelObject>,
     pub object_map: BiMap<String, usize>,
     pub event_map: BiMap<String, usize>,
-    pub activities: Vec<String>
+    pub activities: Vec<String>,
+    // object-to-object relations of OCEL 2.0 logs, source -> targets as written in the log
+    pub object_relations: IntMap<usize, IntSet<usize>>,
+    // attribute values of OCEL 2.0 logs with their declared types, the maps of the events and objects hold them as json
+    pub typed_attributes: TypedAttributes
+}
+
+assert_impl_all!(Ocel: Send, Sync);
+
+// the relations whose source and target are both among the objects
+pub(crate) fn relations_among(relations: &IntMap<usize, IntSet<usize>>, objects: &IntMap<usize, OcelObject>) -> IntMap<usize, IntSet<usize>> {
+    relations.iter()
+             .filter(|(src, _)| objects.contains_key(src))
+             .map(|(src, targets)| (*src, targets.iter().copied().filter(|tar| objects.contains_key(tar)).collect::<IntSet<usize>>()))
+             .filter(|(_, targets)| !targets.is_empty())
+             .collect()
+}
+
+// event and object id -> attribute name -> value, empty for logs without declared attribute types
+#[derive(Debug, Clone, Default)]
+pub struct TypedAttributes {
+    pub events: IntMap<usize, AHashMap<String, OcelAttributeValue>>,
+    pub objects: IntMap<usize, AHashMap<String, OcelAttributeValue>>
+}
+
+impl TypedAttributes {
+    pub fn event(&self, eid: &usize, attribute: &str) -> Option<&OcelAttributeValue> {
+        self.events.get(eid).and_then(|values| values.get(attribute))
+    }
+
+    pub fn object(&self, oid: &usize, attribute: &str) -> Option<&OcelAttributeValue> {
+        self.objects.get(oid).and_then(|values| values.get(attribute))
+    }
+
+    // the values of the events and objects that are kept
+    pub(crate) fn among(&self, events: &EventColumns, objects: &IntMap<usize, OcelObject>) -> Self {
+        TypedAttributes { events: self.events.iter().filter(|(eid, _)| events.contains_key(eid)).map(|(eid, values)| (*eid, values.clone())).collect(),
+                          objects: self.objects.iter().filter(|(oid, _)| objects.contains_key(oid)).map(|(oid, values)| (*oid, values.clone())).collect() }
+    }
 }
 
 
@@ -92,3 +187,58 @@ impl PartialEq for OcelEvent {
         self.timestamp == other.timestamp
     }
 }
+
+pub(crate) fn value_key(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.to_owned(),
+        other => other.to_string()
+    }
+}
+
+// an attribute value read with the type declared for it by an OCEL 2.0 log, kept in Ocel::typed_attributes
+#[derive(Debug, Clone, PartialEq)]
+pub enum OcelAttributeValue {
+    String(String),
+    Number(serde_json::Number),
+    Boolean(bool),
+    Timestamp(DateTime<Utc>)
+}
+
+impl OcelAttributeValue {
+    // the declared types are string, integer, float, boolean and time. numbers and booleans written as text are parsed,
+    // undeclared attributes keep the type of their json value
+    pub fn parse(value: &Value, attribute_type: Option<&str>, timestamps: &TimestampParser) -> Result<Self, String> {
+        let text = value_key(value);
+        let mismatch = || format!("value {} is not of type {}", value, attribute_type.unwrap_or_default());
+        match (attribute_type, value) {
+            (Some("string"), _) => Ok(OcelAttributeValue::String(text)),
+            (Some("integer"), Value::Number(number)) if number.is_i64() || number.is_u64() => Ok(OcelAttributeValue::Number(number.clone())),
+            (Some("integer"), Value::String(_)) => text.trim().parse::<i64>().map(|number| OcelAttributeValue::Number(number.into())).map_err(|_| mismatch()),
+            (Some("float"), Value::Number(number)) => Ok(OcelAttributeValue::Number(number.clone())),
+            (Some("float"), Value::String(_)) => text.trim().parse::<f64>().ok()
+                                                     .and_then(serde_json::Number::from_f64)
+                                                     .map(OcelAttributeValue::Number)
+                                                     .ok_or_else(mismatch),
+            (Some("boolean"), Value::Bool(flag)) => Ok(OcelAttributeValue::Boolean(*flag)),
+            (Some("boolean"), Value::String(_)) => text.trim().parse::<bool>().map(OcelAttributeValue::Boolean).map_err(|_| mismatch()),
+            (Some("time"), _) => timestamps.parse(&text).map(OcelAttributeValue::Timestamp).map_err(|e| e.to_string()),
+            (Some(other), _) if !["integer", "float", "boolean"].contains(&other) => Err(format!("unknown attribute type {}", other)),
+            (Some(_), _) => Err(mismatch()),
+            (None, Value::Number(number)) => Ok(OcelAttributeValue::Number(number.clone())),
+            (None, Value::Bool(flag)) => Ok(OcelAttributeValue::Boolean(*flag)),
+            (None, _) => Ok(OcelAttributeValue::String(text))
+        }
+    }
+}
+
+// the json form in the attribute maps, timestamps become rfc 3339 text like every timestamp attribute of the jsonocel format
+impl From<OcelAttributeValue> for Value {
+    fn from(value: OcelAttributeValue) -> Self {
+        match value {
+            OcelAttributeValue::String(text) => Value::String(text),
+            OcelAttributeValue::Number(number) => Value::Number(number),
+            OcelAttributeValue::Boolean(flag) => Value::Bool(flag),
+            OcelAttributeValue::Timestamp(timestamp) => Value::String(timestamp.to_rfc3339())
+        }
+    }
+}
@@ -0,0 +1,516 @@
+use std::fmt;
+use std::mem::size_of;
+use std::ops::{Deref, Index};
+use ahash::AHashMap;
+use chrono::{DateTime, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use serde_json::Value;
+
+use crate::objects::ocel::memory::value_heap_size;
+use crate::objects::ocel::OcelEvent;
+
+// the events of a log as columns, one row per event in insertion order. activities are interned, the omaps share one
+// buffer cut by per row ranges and every attribute is a sparse column, so the store is a few dozen allocations instead
+// of several per event and drops in one go. removed rows stay behind as tombstones until the store is compacted
+#[derive(Clone, Default)]
+pub struct EventColumns {
+    eids: Vec<usize>,
+    live: Vec<bool>,
+    rows: IntMap<usize, usize>,
+    timestamps: Vec<DateTime<Utc>>,
+    activity_ids: Vec<u32>,
+    activities: Vec<String>,
+    activity_index: AHashMap<String, u32>,
+    // the objects of a row are omap_buffer[start..end], sorted
+    omap_ranges: Vec<(usize, usize)>,
+    omap_buffer: Vec<usize>,
+    // buffer entries no live range points to anymore
+    omap_garbage: usize,
+    attribute_names: Vec<String>,
+    attribute_index: AHashMap<String, usize>,
+    // per attribute the (row, value) pairs of the rows having it, sorted by row
+    attribute_columns: Vec<Vec<(usize, Value)>>
+}
+
+// an event of the store, the fields borrow from the columns
+#[derive(Debug, Clone, Copy)]
+pub struct EventRef<'a> {
+    pub activity: &'a str,
+    pub timestamp: DateTime<Utc>,
+    pub omap: Omap<'a>,
+    pub vmap: Vmap<'a>
+}
+
+// the sorted object ids of an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Omap<'a>(&'a [usize]);
+
+// the attributes of an event, looked up in the attribute columns
+#[derive(Clone, Copy)]
+pub struct Vmap<'a> {
+    columns: &'a EventColumns,
+    row: usize
+}
+
+impl EventColumns {
+    pub fn with_capacity(capacity: usize) -> Self {
+        EventColumns { eids: Vec::with_capacity(capacity),
+                       live: Vec::with_capacity(capacity),
+                       timestamps: Vec::with_capacity(capacity),
+                       activity_ids: Vec::with_capacity(capacity),
+                       omap_ranges: Vec::with_capacity(capacity),
+                       ..Default::default() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn contains_key(&self, eid: &usize) -> bool {
+        self.rows.contains_key(eid)
+    }
+
+    pub fn get(&self, eid: &usize) -> Option<EventRef<'_>> {
+        self.rows.get(eid).map(|row| self.row(*row))
+    }
+
+    // like indexing a map, panics for ids the store does not hold
+    pub fn event(&self, eid: &usize) -> EventRef<'_> {
+        match self.get(eid) {
+            Some(event) => event,
+            None => panic!("the log has no event with the id {}", eid)
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter { columns: self, row: 0, remaining: self.len() }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &usize> + '_ {
+        self.iter().map(|(eid, _)| eid)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = EventRef<'_>> + '_ {
+        self.iter().map(|(_, event)| event)
+    }
+
+    // an event inserted under a known id replaces the old one in its row, the old one is returned
+    pub fn insert(&mut self, eid: usize, event: OcelEvent) -> Option<OcelEvent> {
+        let mut omap: Vec<usize> = event.omap.into_iter().collect();
+        omap.sort_unstable();
+        let row = match self.rows.get(&eid) {
+            Some(row) => *row,
+            None => {
+                self.push(eid, &event.activity, event.timestamp, &omap, event.vmap);
+                return None;
+            }
+        };
+        let replaced = self.row(row).to_event();
+        self.timestamps[row] = event.timestamp;
+        self.activity_ids[row] = self.intern(&event.activity);
+        let (start, end) = self.omap_ranges[row];
+        if omap.len() <= end - start {
+            self.omap_buffer[start..start + omap.len()].copy_from_slice(&omap);
+            self.omap_ranges[row] = (start, start + omap.len());
+        } else {
+            self.omap_ranges[row] = (self.omap_buffer.len(), self.omap_buffer.len() + omap.len());
+            self.omap_buffer.extend_from_slice(&omap);
+        }
+        self.omap_garbage += end - start - omap.len().min(end - start);
+        for column in self.attribute_columns.iter_mut() {
+            if let Ok(position) = column.binary_search_by_key(&row, |(row, _)| *row) {
+                column.remove(position);
+            }
+        }
+        for (name, value) in event.vmap {
+            self.set_row_attribute(row, &name, value);
+        }
+        Some(replaced)
+    }
+
+    // changes an event through its owned form, false if the store does not hold it
+    pub fn update(&mut self, eid: &usize, change: impl FnOnce(&mut OcelEvent)) -> bool {
+        let mut event = match self.get(eid) {
+            Some(event) => event.to_event(),
+            None => return false
+        };
+        change(&mut event);
+        self.insert(*eid, event);
+        true
+    }
+
+    pub fn remove(&mut self, eid: &usize) -> Option<OcelEvent> {
+        let row = self.rows.remove(eid)?;
+        let removed = self.row(row).to_event();
+        self.live[row] = false;
+        let (start, end) = self.omap_ranges[row];
+        self.omap_garbage += end - start;
+        self.omap_ranges[row] = (start, start);
+        if self.eids.len() > 64 && self.eids.len() > 2 * self.rows.len() {
+            self.compact();
+        }
+        Some(removed)
+    }
+
+    pub fn clear(&mut self) {
+        *self = EventColumns::default();
+    }
+
+    pub fn retain(&mut self, mut keep: impl FnMut(&usize, EventRef<'_>) -> bool) {
+        let dropped: Vec<usize> = self.iter().filter(|(eid, event)| !keep(eid, *event)).map(|(eid, _)| *eid).collect();
+        for eid in dropped {
+            self.remove(&eid);
+        }
+    }
+
+    // returns the value the event had before
+    pub fn set_attribute(&mut self, eid: &usize, name: &str, value: Value) -> Option<Value> {
+        let row = *self.rows.get(eid)?;
+        self.set_row_attribute(row, name, value)
+    }
+
+    // adds an object to the omap of an event, false if the event is unknown or already references it
+    pub fn insert_object(&mut self, eid: &usize, oid: usize) -> bool {
+        let row = match self.rows.get(eid) {
+            Some(row) => *row,
+            None => return false
+        };
+        let (start, end) = self.omap_ranges[row];
+        let position = match self.omap_buffer[start..end].binary_search(&oid) {
+            Ok(_) => return false,
+            Err(position) => start + position
+        };
+        // the grown omap moves to the end of the buffer
+        let moved = self.omap_buffer.len();
+        self.omap_buffer.extend_from_within(start..position);
+        self.omap_buffer.push(oid);
+        self.omap_buffer.extend_from_within(position..end);
+        self.omap_ranges[row] = (moved, self.omap_buffer.len());
+        self.omap_garbage += end - start;
+        true
+    }
+
+    // drops the objects not kept from the omaps of all events, the ranges shrink in place
+    pub fn retain_objects(&mut self, mut keep: impl FnMut(&usize) -> bool) {
+        for row in 0..self.eids.len() {
+            let (start, end) = self.omap_ranges[row];
+            let mut kept = start;
+            for position in start..end {
+                if keep(&self.omap_buffer[position]) {
+                    self.omap_buffer[kept] = self.omap_buffer[position];
+                    kept += 1;
+                }
+            }
+            self.omap_garbage += end - kept;
+            self.omap_ranges[row] = (start, kept);
+        }
+    }
+
+    // a store of the kept events, copied column by column
+    pub fn filtered(&self, mut keep: impl FnMut(&usize, EventRef<'_>) -> bool) -> EventColumns {
+        let mut filtered = EventColumns::default();
+        for (eid, event) in self.iter().filter(|(eid, event)| keep(eid, *event)) {
+            filtered.push(*eid, event.activity, event.timestamp, &event.omap, event.vmap.iter().map(|(name, value)| (name.to_owned(), value.clone())));
+        }
+        filtered
+    }
+
+    // drops the tombstones and the omap entries no event points to anymore
+    pub fn compact(&mut self) {
+        if self.eids.len() != self.rows.len() || self.omap_garbage > 0 {
+            *self = self.filtered(|_, _| true);
+        }
+    }
+
+    // estimated bytes of the event rows with their activities, the omap buffer and the attribute columns,
+    // tombstones and unused omap entries included
+    pub(crate) fn memory_size(&self) -> (usize, usize, usize) {
+        let rows = self.eids.len() * EventColumns::row_size()
+                   + self.activities.iter().map(|activity| 2 * (size_of::<String>() + activity.len()) + size_of::<u32>()).sum::<usize>();
+        let omaps = self.omap_buffer.len() * size_of::<usize>();
+        let attributes = self.attribute_names.iter().map(|name| 2 * (size_of::<String>() + name.len()) + size_of::<usize>() + size_of::<Vec<(usize, Value)>>()).sum::<usize>()
+                         + self.attribute_columns.iter().flatten().map(|(_, value)| size_of::<(usize, Value)>() + value_heap_size(value)).sum::<usize>();
+        (rows, omaps, attributes)
+    }
+
+    // bytes a row takes in the id, liveness, timestamp, activity and omap range columns and the id lookup
+    pub fn row_size() -> usize {
+        size_of::<usize>() + size_of::<bool>() + size_of::<(usize, usize)>() + size_of::<DateTime<Utc>>() + size_of::<u32>() + size_of::<(usize, usize)>()
+    }
+
+    // a new event with a sorted omap, the importers push without building an OcelEvent first
+    pub(crate) fn push(&mut self, eid: usize, activity: &str, timestamp: DateTime<Utc>, omap: &[usize], vmap: impl IntoIterator<Item = (String, Value)>) {
+        let row = self.eids.len();
+        self.eids.push(eid);
+        self.live.push(true);
+        self.rows.insert(eid, row);
+        self.timestamps.push(timestamp);
+        let activity_id = self.intern(activity);
+        self.activity_ids.push(activity_id);
+        let start = self.omap_buffer.len();
+        self.omap_buffer.extend_from_slice(omap);
+        self.omap_ranges.push((start, self.omap_buffer.len()));
+        // the row is the last one, so pushing keeps every column sorted
+        for (name, value) in vmap {
+            let column = self.attribute_column(&name);
+            self.attribute_columns[column].push((row, value));
+        }
+    }
+
+    fn intern(&mut self, activity: &str) -> u32 {
+        if let Some(activity_id) = self.activity_index.get(activity) {
+            return *activity_id;
+        }
+        self.activities.push(activity.to_owned());
+        self.activity_index.insert(activity.to_owned(), (self.activities.len() - 1) as u32);
+        (self.activities.len() - 1) as u32
+    }
+
+    fn attribute_column(&mut self, name: &str) -> usize {
+        if let Some(column) = self.attribute_index.get(name) {
+            return *column;
+        }
+        self.attribute_names.push(name.to_owned());
+        self.attribute_columns.push(vec![]);
+        self.attribute_index.insert(name.to_owned(), self.attribute_names.len() - 1);
+        self.attribute_names.len() - 1
+    }
+
+    fn set_row_attribute(&mut self, row: usize, name: &str, value: Value) -> Option<Value> {
+        let column = self.attribute_column(name);
+        let column = &mut self.attribute_columns[column];
+        match column.binary_search_by_key(&row, |(row, _)| *row) {
+            Ok(position) => Some(std::mem::replace(&mut column[position].1, value)),
+            Err(position) => {
+                column.insert(position, (row, value));
+                None
+            }
+        }
+    }
+
+    fn row(&self, row: usize) -> EventRef<'_> {
+        let (start, end) = self.omap_ranges[row];
+        EventRef { activity: &self.activities[self.activity_ids[row] as usize],
+                   timestamp: self.timestamps[row],
+                   omap: Omap(&self.omap_buffer[start..end]),
+                   vmap: Vmap { columns: self, row } }
+    }
+}
+
+impl fmt::Debug for EventColumns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// same ids with the same activities, timestamps, objects and attributes
+impl PartialEq for EventColumns {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(eid, event)| match other.get(eid) {
+            Some(other) => event.activity == other.activity && event.timestamp == other.timestamp && event.omap == other.omap && event.vmap == other.vmap,
+            None => false
+        })
+    }
+}
+
+impl FromIterator<(usize, OcelEvent)> for EventColumns {
+    fn from_iter<T: IntoIterator<Item = (usize, OcelEvent)>>(iter: T) -> Self {
+        let mut columns = EventColumns::default();
+        columns.extend(iter);
+        columns
+    }
+}
+
+impl Extend<(usize, OcelEvent)> for EventColumns {
+    fn extend<T: IntoIterator<Item = (usize, OcelEvent)>>(&mut self, iter: T) {
+        for (eid, event) in iter {
+            self.insert(eid, event);
+        }
+    }
+}
+
+pub struct Iter<'a> {
+    columns: &'a EventColumns,
+    row: usize,
+    remaining: usize
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a usize, EventRef<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.row < self.columns.eids.len() {
+            let row = self.row;
+            self.row += 1;
+            if self.columns.live[row] {
+                self.remaining -= 1;
+                return Some((&self.columns.eids[row], self.columns.row(row)));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl<'a> IntoIterator for &'a EventColumns {
+    type Item = (&'a usize, EventRef<'a>);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl EventRef<'_> {
+    pub fn to_event(&self) -> OcelEvent {
+        OcelEvent { activity: self.activity.to_owned(), timestamp: self.timestamp, vmap: self.vmap.to_map(), omap: self.omap.to_set() }
+    }
+}
+
+impl<'a> Omap<'a> {
+    pub fn iter(&self) -> std::slice::Iter<'a, usize> {
+        self.0.iter()
+    }
+
+    pub fn contains(&self, oid: &usize) -> bool {
+        self.0.binary_search(oid).is_ok()
+    }
+
+    pub fn to_set(&self) -> IntSet<usize> {
+        self.0.iter().copied().collect()
+    }
+}
+
+impl<'a> Deref for Omap<'a> {
+    type Target = [usize];
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a> IntoIterator for Omap<'a> {
+    type Item = &'a usize;
+    type IntoIter = std::slice::Iter<'a, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &Omap<'a> {
+    type Item = &'a usize;
+    type IntoIter = std::slice::Iter<'a, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a> Vmap<'a> {
+    pub fn get(&self, name: &str) -> Option<&'a Value> {
+        let column = &self.columns.attribute_columns[*self.columns.attribute_index.get(name)?];
+        column.binary_search_by_key(&self.row, |(row, _)| *row).ok().map(|position| &column[position].1)
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    pub fn iter(&self) -> VmapIter<'a> {
+        VmapIter { columns: self.columns, row: self.row, column: 0 }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &'a String> + 'a {
+        self.iter().map(|(name, _)| name)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &'a Value> + 'a {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    pub fn to_map(&self) -> AHashMap<String, Value> {
+        self.iter().map(|(name, value)| (name.to_owned(), value.clone())).collect()
+    }
+}
+
+impl<Q: AsRef<str> + ?Sized> Index<&Q> for Vmap<'_> {
+    type Output = Value;
+
+    // like indexing a map, panics for attributes the event does not have
+    fn index(&self, name: &Q) -> &Self::Output {
+        match self.get(name.as_ref()) {
+            Some(value) => value,
+            None => panic!("the event has no attribute {}", name.as_ref())
+        }
+    }
+}
+
+impl fmt::Debug for Vmap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for Vmap<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(name, value)| other.get(name) == Some(value))
+    }
+}
+
+pub struct VmapIter<'a> {
+    columns: &'a EventColumns,
+    row: usize,
+    column: usize
+}
+
+impl<'a> Iterator for VmapIter<'a> {
+    type Item = (&'a String, &'a Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.column < self.columns.attribute_columns.len() {
+            let column = &self.columns.attribute_columns[self.column];
+            let name = &self.columns.attribute_names[self.column];
+            self.column += 1;
+            if let Ok(position) = column.binary_search_by_key(&self.row, |(row, _)| *row) {
+                return Some((name, &column[position].1));
+            }
+        }
+        None
+    }
+}
+
+impl<'a> IntoIterator for Vmap<'a> {
+    type Item = (&'a String, &'a Value);
+    type IntoIter = VmapIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &Vmap<'a> {
+    type Item = (&'a String, &'a Value);
+    type IntoIter = VmapIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
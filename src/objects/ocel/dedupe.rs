@@ -0,0 +1,98 @@
+use ahash::AHashMap;
+use chrono::Duration;
+
+use crate::objects::ocel::{Ocel, value_key};
+use crate::objects::ocel::merge::AttributeConflict;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampKey {
+    Exact,
+    // events in the same bucket since the epoch count as simultaneous
+    Bucket(Duration)
+}
+
+#[derive(Debug, Clone)]
+pub struct DedupKeys {
+    pub timestamp: TimestampKey,
+    pub activity: bool,
+    pub omap: bool,
+    pub attributes: Vec<String>,
+    pub conflict: AttributeConflict
+}
+
+impl Default for DedupKeys {
+    fn default() -> Self {
+        Self { timestamp: TimestampKey::Exact, activity: true, omap: true, attributes: vec![], conflict: AttributeConflict::KeepFirst }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DedupReport {
+    // kept event id and the ids merged into it
+    pub merged: Vec<(String, Vec<String>)>
+}
+
+impl DedupReport {
+    pub fn removed_count(&self) -> usize {
+        self.merged.iter().map(|(_, removed)| removed.len()).sum()
+    }
+}
+
+type EventKey = (i64, Option<String>, Vec<usize>, Vec<Option<String>>);
+
+impl Ocel {
+    pub fn dedupe_events(&mut self, keys: &DedupKeys) -> DedupReport {
+        let mut groups: AHashMap<EventKey, Vec<usize>> = AHashMap::new();
+        for (eid, event) in &self.events {
+            let timestamp = match keys.timestamp {
+                TimestampKey::Exact => event.timestamp.timestamp_nanos(),
+                TimestampKey::Bucket(bucket) => event.timestamp.timestamp_nanos().div_euclid(bucket.num_nanoseconds().unwrap_or(i64::MAX).max(1))
+            };
+            let mut omap: Vec<usize> = if keys.omap {event.omap.iter().copied().collect()} else {vec![]};
+            omap.sort_unstable();
+            let key: EventKey = (timestamp,
+                                 keys.activity.then(|| event.activity.to_owned()),
+                                 omap,
+                                 keys.attributes.iter().map(|attr| event.vmap.get(attr).map(value_key)).collect());
+            groups.entry(key).or_default().push(*eid);
+        }
+
+        // the event imported first survives
+        let mut duplicates: Vec<Vec<usize>> = groups.into_iter().map(|(_, group)| group).filter(|group| group.len() > 1).collect();
+        for group in duplicates.iter_mut() {
+            group.sort_unstable();
+        }
+        duplicates.sort_unstable();
+
+        let mut report = DedupReport::default();
+        for group in duplicates {
+            let kept = group[0];
+            let mut removed_ids: Vec<String> = vec![];
+            for eid in &group[1..] {
+                let removed = self.events.remove(eid).expect("Groups only hold known events");
+                for (attr, value) in removed.vmap {
+                    if keys.conflict == AttributeConflict::KeepSecond || !self.events.event(&kept).vmap.contains_key(&attr) {
+                        self.events.set_attribute(&kept, &attr, value);
+                    }
+                }
+                for oid in &removed.omap {
+                    if self.events.insert_object(&kept, *oid) {
+                        self.objects.get_mut(oid).expect("Events only reference known objects").events.push(kept);
+                    }
+                    self.objects.get_mut(oid).expect("Events only reference known objects").events.retain(|oe| oe != eid);
+                }
+                if let Some((id, _)) = self.event_map.remove_by_right(eid) {
+                    removed_ids.push(id);
+                }
+            }
+            removed_ids.sort();
+            report.merged.push((self.event_map.get_by_right(&kept).map_or_else(|| kept.to_string(), |id| id.to_owned()), removed_ids));
+        }
+
+        if !report.merged.is_empty() {
+            self.rebuild_derived_indices();
+        }
+        report.merged.sort();
+        report
+    }
+}
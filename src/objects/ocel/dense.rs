@@ -0,0 +1,93 @@
+use nohash_hasher::IntMap;
+
+use crate::objects::ocel::Ocel;
+
+// events numbered 0..n in timestamp order, ties broken by the original id
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DenseEvents {
+    original: Vec<usize>,
+    dense: IntMap<usize, usize>
+}
+
+impl DenseEvents {
+    pub fn len(&self) -> usize {
+        self.original.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.original.is_empty()
+    }
+
+    pub fn to_dense(&self, eid: usize) -> Option<usize> {
+        self.dense.get(&eid).copied()
+    }
+
+    pub fn to_original(&self, dense: usize) -> Option<usize> {
+        self.original.get(dense).copied()
+    }
+
+    // dense lifecycle of an object, sorted
+    pub fn lifecycle(&self, log: &Ocel, oid: usize) -> Vec<usize> {
+        let mut lifecycle: Vec<usize> = log.objects.get(&oid)
+                                                   .map(|obj| obj.events.iter().filter_map(|eid| self.to_dense(*eid)).collect())
+                                                   .unwrap_or_default();
+        lifecycle.sort_unstable();
+        lifecycle
+    }
+
+    pub fn bitset(&self, dense_ids: &[usize]) -> EventBitSet {
+        let mut bits = EventBitSet::new(self.len());
+        for dense in dense_ids {
+            bits.insert(*dense);
+        }
+        bits
+    }
+}
+
+// fixed-size set over dense event ids
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventBitSet {
+    words: Vec<u64>
+}
+
+impl EventBitSet {
+    pub fn new(capacity: usize) -> Self {
+        Self { words: vec![0; capacity.div_ceil(64)] }
+    }
+
+    pub fn insert(&mut self, dense: usize) {
+        if dense / 64 >= self.words.len() {
+            self.words.resize(dense / 64 + 1, 0);
+        }
+        self.words[dense / 64] |= 1 << (dense % 64);
+    }
+
+    pub fn contains(&self, dense: usize) -> bool {
+        self.words.get(dense / 64).is_some_and(|word| word & (1 << (dense % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    pub fn intersection_len(&self, other: &EventBitSet) -> usize {
+        self.words.iter().zip(&other.words).map(|(a, b)| (a & b).count_ones() as usize).sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, word)| (0..64).filter(move |bit| word & (1 << bit) != 0).map(move |bit| index * 64 + bit))
+    }
+}
+
+impl Ocel {
+    pub fn dense_events(&self) -> DenseEvents {
+        let mut original: Vec<usize> = self.events.keys().copied().collect();
+        original.sort_unstable_by_key(|eid| (self.events.event(eid).timestamp, *eid));
+        let dense: IntMap<usize, usize> = original.iter().enumerate().map(|(dense, eid)| (*eid, dense)).collect();
+        DenseEvents { original, dense }
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+use serde_json::Value;
+
+use crate::objects::ocel::{EventRef, Ocel, OcelEvent};
+
+// conditions on the activity and the attributes of an event
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributePredicate {
+    Activity(String),
+    Equals(String, Value),
+    // numeric comparisons, attributes holding numbers as text are parsed, others never match
+    GreaterThan(String, f64),
+    LessThan(String, f64),
+    Exists(String),
+    And(Vec<AttributePredicate>),
+    Or(Vec<AttributePredicate>),
+    Not(Box<AttributePredicate>)
+}
+
+impl AttributePredicate {
+    pub fn matches(&self, event: EventRef<'_>) -> bool {
+        match self {
+            AttributePredicate::Activity(activity) => event.activity == activity,
+            AttributePredicate::Equals(attr, value) => event.vmap.get(attr) == Some(value),
+            AttributePredicate::GreaterThan(attr, bound) => numeric(event, attr).is_some_and(|value| value > *bound),
+            AttributePredicate::LessThan(attr, bound) => numeric(event, attr).is_some_and(|value| value < *bound),
+            AttributePredicate::Exists(attr) => event.vmap.contains_key(attr),
+            AttributePredicate::And(predicates) => predicates.iter().all(|predicate| predicate.matches(event)),
+            AttributePredicate::Or(predicates) => predicates.iter().any(|predicate| predicate.matches(event)),
+            AttributePredicate::Not(predicate) => !predicate.matches(event)
+        }
+    }
+}
+
+fn numeric(event: EventRef<'_>, attr: &str) -> Option<f64> {
+    match event.vmap.get(attr)? {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.parse().ok(),
+        _ => None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeriveMode {
+    // set the rule name as a boolean attribute on the matching events
+    Flag,
+    // add a copy of every matching event with the rule name as activity and the same timestamp and objects
+    Clone
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivedActivity {
+    pub name: String,
+    pub predicate: AttributePredicate
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeriveReport {
+    // rule name -> matching events
+    pub matched: BTreeMap<String, usize>,
+    // ids of the cloned events
+    pub added: Vec<String>
+}
+
+// rules are evaluated on the log as it was passed in, so clones never match another rule.
+// a clone gets the id "<event id>:<rule name>" and follows the original in the lifecycles of its objects
+pub fn add_flags(ocel: &mut Ocel, rules: &[DerivedActivity], mode: DeriveMode) -> DeriveReport {
+    let mut eids: Vec<usize> = ocel.events.keys().copied().collect();
+    eids.sort_unstable();
+    let mut report = DeriveReport::default();
+    let mut clones: Vec<(String, OcelEvent)> = vec![];
+    for rule in rules {
+        let matching: Vec<usize> = eids.iter().copied().filter(|eid| rule.predicate.matches(ocel.events.event(eid))).collect();
+        *report.matched.entry(rule.name.to_owned()).or_default() += matching.len();
+        for eid in matching {
+            match mode {
+                DeriveMode::Flag => {
+                    ocel.events.set_attribute(&eid, &rule.name, Value::Bool(true));
+                },
+                DeriveMode::Clone => {
+                    let event = ocel.events.event(&eid);
+                    let id = ocel.event_map.get_by_right(&eid).cloned().unwrap_or_else(|| eid.to_string());
+                    clones.push((format!("{}:{}", id, rule.name),
+                                 OcelEvent { activity: rule.name.to_owned(), timestamp: event.timestamp, vmap: event.vmap.to_map(), omap: event.omap.to_set() }));
+                }
+            }
+        }
+    }
+
+    if !clones.is_empty() {
+        let mut next_eid = ocel.events.keys().max().map_or(0, |eid| eid + 1);
+        for (id, event) in clones {
+            // a rule listed twice clones an event once
+            if ocel.event_map.contains_left(&id) {
+                continue;
+            }
+            ocel.events.insert(next_eid, event);
+            ocel.event_map.insert(id.to_owned(), next_eid);
+            report.added.push(id);
+            next_eid += 1;
+        }
+        ocel.rebuild_derived_indices();
+    }
+    report
+}
@@ -0,0 +1,83 @@
+use std::{collections::HashMap, fs};
+use csv::{ReaderBuilder, Trim};
+use serde_json::Value;
+
+use crate::objects::ocel::{Ocel, value_key};
+use crate::objects::ocel::importer::OcelError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnrichKey {
+    ObjectId,
+    Attribute(String)
+}
+
+impl Ocel {
+    pub fn enrich_objects(&mut self, object_type: &str, key: &EnrichKey, data: &HashMap<String, HashMap<String, Value>>, prefix: &str) -> Vec<String> {
+        let mut matched: HashMap<&str, bool> = data.keys().map(|k| (k.as_str(), false)).collect();
+
+        for (oid, obj) in self.objects.iter_mut() {
+            if obj.obj_type != object_type {
+                continue;
+            }
+
+            let obj_key: Option<String> = match key {
+                EnrichKey::ObjectId => self.object_map.get_by_right(oid).cloned(),
+                EnrichKey::Attribute(attr) => obj.ovmap.get(attr).map(value_key)
+            };
+
+            if let Some((data_key, attrs)) = obj_key.and_then(|k| data.get_key_value(&k)) {
+                for (attr, value) in attrs {
+                    obj.ovmap.insert(format!("{}{}", prefix, attr), value.to_owned());
+                }
+                matched.insert(data_key.as_str(), true);
+            }
+        }
+
+        let mut unmatched: Vec<String> = matched.into_iter()
+                                                .filter(|(_, found)| !found)
+                                                .map(|(k, _)| k.to_owned())
+                                                .collect();
+        unmatched.sort();
+        unmatched
+    }
+
+    pub fn enrich_objects_from_csv(&mut self, object_type: &str, key: &EnrichKey, file_path: &str, prefix: &str) -> Result<Vec<String>, OcelError> {
+        let data = read_reference_csv(&fs::read_to_string(file_path)?)?;
+        Ok(self.enrich_objects(object_type, key, &data, prefix))
+    }
+}
+
+// first column is the join key, the header names the remaining attributes. quoted fields may hold commas and line breaks
+fn read_reference_csv(content: &str) -> Result<HashMap<String, HashMap<String, Value>>, OcelError> {
+    let mut reader = ReaderBuilder::new().trim(Trim::All).flexible(true).from_reader(content.as_bytes());
+    let header = reader.headers()?.clone();
+    if header.is_empty() {
+        return Err(OcelError::Missing("The reference csv file is empty.".to_owned()));
+    }
+
+    let mut data: HashMap<String, HashMap<String, Value>> = HashMap::new();
+    for (i, record) in reader.records().enumerate() {
+        let record = record?;
+        if record.len() != header.len() {
+            return Err(OcelError::RowLength { row: i + 2, columns: record.len(), header: header.len() });
+        }
+        let attrs = header.iter().skip(1)
+                          .zip(record.iter().skip(1))
+                          .map(|(name, field)| (name.to_owned(), parse_csv_value(field)))
+                          .collect();
+        data.insert(record[0].to_owned(), attrs);
+    }
+    Ok(data)
+}
+
+pub(crate) fn parse_csv_value(field: &str) -> Value {
+    if let Ok(int) = field.parse::<i64>() {
+        Value::from(int)
+    } else if let Ok(float) = field.parse::<f64>() {
+        Value::from(float)
+    } else if let Ok(boolean) = field.parse::<bool>() {
+        Value::from(boolean)
+    } else {
+        Value::from(field)
+    }
+}
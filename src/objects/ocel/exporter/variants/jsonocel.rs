@@ -2,29 +2,33 @@
 use crate::objects::ocel::{Ocel, OcelSerde, OcelEvent, OcelObject, OcelEventSerde, OcelObjectSerde};
 use ahash::{AHashMap, AHashSet, RandomState};
 use indexmap::IndexMap;
-use std::{fs::OpenOptions, io::{BufWriter, Write}, error::Error};
+use std::{io::Write, error::Error};
+use crate::objects::exporter::Exporter;
 
-pub(crate) fn export_json_ocel(log: &Ocel, file_path: &str) -> Result<bool, Box<dyn Error>> {
-    let log_serde: OcelSerde = generate_ocel_serde(log);
+pub(crate) struct JsonOcelExporter<'a> {
+    pub(crate) log: &'a Ocel,
+    pub(crate) pretty: bool
+}
 
-    let serde_ocel = serde_json::to_string(&log_serde).unwrap();
-    let output_file = OpenOptions::new().create(true).write(true).truncate(true).open(file_path).unwrap();
-    
-    let mut f = BufWriter::new(output_file);
-    f.write_all(serde_ocel.as_bytes()).expect("Unable to write data");
+impl<'a> Exporter for JsonOcelExporter<'a> {
+    fn write_content(&self, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        let log_serde: OcelSerde = generate_ocel_serde(self.log);
+        if self.pretty {
+            serde_json::to_writer_pretty(f, &log_serde)?;
+        } else {
+            serde_json::to_writer(f, &log_serde)?;
+        }
+        Ok(())
+    }
+}
 
+pub(crate) fn export_json_ocel(log: &Ocel, file_path: &str) -> Result<bool, Box<dyn Error>> {
+    JsonOcelExporter { log, pretty: false }.export(file_path)?;
     Ok(true)
 }
 
 pub(crate) fn export_json_ocel_pretty(log: &Ocel, file_path: &str) -> Result<bool, Box<dyn Error>> {
-    let log_serde: OcelSerde = generate_ocel_serde(log);
-
-    let serde_ocel = serde_json::to_string_pretty(&log_serde).unwrap();
-    let output_file = OpenOptions::new().create(true).write(true).truncate(true).open(file_path).unwrap();
-    
-    let mut f = BufWriter::new(output_file);
-    f.write_all(serde_ocel.as_bytes()).expect("Unable to write data");
-
+    JsonOcelExporter { log, pretty: true }.export(file_path)?;
     Ok(true)
 }
 
@@ -39,7 +43,7 @@ pub(self) fn generate_ocel_serde(log: &Ocel) -> OcelSerde {
 
 
     for (eid, data) in &log.events {
-        let mut serde_event = OcelEventSerde {activity: data.activity.to_owned(), timestamp: data.timestamp, vmap: data.vmap.to_owned(), omap: AHashSet::new()};
+        let mut serde_event = OcelEventSerde {activity: data.activity.to_owned(), timestamp: data.timestamp, vmap: data.vmap.to_map(), omap: AHashSet::new()};
 
         for oid in data.omap.iter() {
             serde_event.omap.insert(log.object_map.get_by_right(oid).expect("This can't fail").to_owned());
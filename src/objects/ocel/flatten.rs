@@ -0,0 +1,97 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::{DateTime, Utc};
+use nohash_hasher::IntMap;
+use rayon::prelude::*;
+
+use crate::objects::ocel::Ocel;
+
+// one event of a trace, the event id refers back to the log
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatEvent {
+    pub eid: usize,
+    pub activity: String,
+    pub timestamp: DateTime<Utc>
+}
+
+// the lifecycle of one object as a case
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatTrace {
+    pub case: String,
+    pub events: Vec<FlatEvent>
+}
+
+// how much the flattening copies events that several objects of the type share (convergence)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DuplicationStats {
+    // distinct events in the flat log
+    pub events: usize,
+    // events over all traces, every copy counted
+    pub flat_events: usize,
+    // events copied into more than one trace
+    pub duplicated_events: usize,
+    // flat events per distinct event of the activity, 1.0 when no event of it is shared
+    pub activity_factors: BTreeMap<String, f64>
+}
+
+impl DuplicationStats {
+    pub fn factor(&self) -> f64 {
+        if self.events == 0 {
+            return 1.0;
+        }
+        self.flat_events as f64 / self.events as f64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatLog {
+    pub object_type: String,
+    // sorted by case
+    pub traces: Vec<FlatTrace>,
+    pub duplication: DuplicationStats
+}
+
+impl FlatLog {
+    fn new(object_type: &str, traces: Vec<FlatTrace>) -> Self {
+        // event -> (activity, copies)
+        let mut copies: IntMap<usize, (&str, usize)> = IntMap::default();
+        for event in traces.iter().flat_map(|trace| &trace.events) {
+            copies.entry(event.eid).or_insert((event.activity.as_str(), 0)).1 += 1;
+        }
+        let mut activities: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+        for (activity, count) in copies.values() {
+            let (events, flat_events) = activities.entry(activity.to_string()).or_default();
+            *events += 1;
+            *flat_events += count;
+        }
+        let duplication = DuplicationStats { events: copies.len(),
+                                             flat_events: copies.values().map(|(_, count)| count).sum(),
+                                             duplicated_events: copies.values().filter(|(_, count)| *count > 1).count(),
+                                             activity_factors: activities.into_iter().map(|(activity, (events, flat_events))| (activity, flat_events as f64 / events as f64)).collect() };
+        FlatLog { object_type: object_type.to_owned(), traces, duplication }
+    }
+}
+
+impl Ocel {
+    // every object of the type becomes a trace of the events in its lifecycle, events shared by several of them are copied
+    pub fn flatten(&self, obj_type: &str) -> FlatLog {
+        let mut traces: Vec<FlatTrace> = self.objects
+                                             .par_iter()
+                                             .filter(|(_, obj)| obj.obj_type == obj_type)
+                                             .map(|(oid, obj)| {
+                                                 let mut events: Vec<FlatEvent> = obj.events.iter()
+                                                                                     .filter_map(|eid| self.events.get(eid).map(|event| FlatEvent { eid: *eid, activity: event.activity.to_owned(), timestamp: event.timestamp }))
+                                                                                     .collect();
+                                                 events.sort_by_key(|event| event.timestamp);
+                                                 let case = self.object_map.get_by_right(oid).map_or_else(|| oid.to_string(), |label| label.to_owned());
+                                                 FlatTrace { case, events }
+                                             })
+                                             .collect();
+        traces.sort_by(|a, b| a.case.cmp(&b.case));
+        FlatLog::new(obj_type, traces)
+    }
+
+    // the types are flattened in parallel, as are the objects of each type
+    pub fn flatten_all(&self, types: &[&str]) -> HashMap<String, FlatLog> {
+        types.par_iter().map(|obj_type| (obj_type.to_string(), self.flatten(obj_type))).collect()
+    }
+}
@@ -0,0 +1,70 @@
+use ahash::AHashMap;
+use strum::EnumString;
+
+// how object types are compared by the same-type relation checks
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumString)]
+pub enum TypeMatching {
+    #[default]
+    Exact,
+    // compare the ancestors this many levels above, types closer to the root use the root
+    Ancestor(usize)
+}
+
+// child type -> parent type, types without a parent are roots
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeHierarchy {
+    parents: AHashMap<String, String>
+}
+
+impl TypeHierarchy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_parent(mut self, child: &str, parent: &str) -> Self {
+        self.set_parent(child, parent);
+        self
+    }
+
+    // a parent that would close a cycle is ignored
+    pub fn set_parent(&mut self, child: &str, parent: &str) -> bool {
+        if child == parent || self.is_a(parent, child) {
+            return false;
+        }
+        self.parents.insert(child.to_owned(), parent.to_owned());
+        true
+    }
+
+    pub fn parent(&self, object_type: &str) -> Option<&str> {
+        self.parents.get(object_type).map(|parent| parent.as_str())
+    }
+
+    pub fn ancestor<'a>(&'a self, object_type: &'a str, level: usize) -> &'a str {
+        let mut curr = object_type;
+        for _ in 0..level {
+            match self.parent(curr) {
+                Some(parent) => curr = parent,
+                None => break
+            }
+        }
+        curr
+    }
+
+    pub fn is_a(&self, object_type: &str, ancestor: &str) -> bool {
+        let mut curr = Some(object_type);
+        while let Some(current) = curr {
+            if current == ancestor {
+                return true;
+            }
+            curr = self.parent(current);
+        }
+        false
+    }
+
+    pub fn is_same_type_at(&self, a: &str, b: &str, matching: TypeMatching) -> bool {
+        match matching {
+            TypeMatching::Exact => a == b,
+            TypeMatching::Ancestor(level) => self.ancestor(a, level) == self.ancestor(b, level)
+        }
+    }
+}
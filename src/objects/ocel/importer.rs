@@ -1,8 +1,287 @@
 pub(crate) mod variants;
+pub mod timestamp;
+pub mod stream;
 use crate::objects::ocel::Ocel;
-use crate::objects::ocel::importer::variants::jsonocel::import_json_ocel;
+use crate::objects::ocel::importer::timestamp::{TimestampError, TimestampParser};
+use crate::objects::ocel::importer::variants::csvocel::import_csv_ocel_str;
+use crate::objects::ocel::importer::variants::jsonocel2::{import_json_ocel2, import_json_ocel2_str};
+use crate::objects::ocel::importer::variants::xes::{import_xes, import_xes_str};
+use crate::objects::ocel::importer::variants::jsonocel::{import_json_ocel, import_json_ocel_profiled, import_json_ocel_str, import_json_ocel_with, JsonImport};
+use crate::objects::ocel::memory::MemoryBreakdown;
+use std::collections::HashMap;
 use std::error::Error;
+use std::{fmt, fs, io};
+use std::time::Duration;
+
+// what stops the ocel 2.0 json, xes, csv and reference csv imports
+#[derive(Debug)]
+pub enum OcelError {
+    Io(io::Error),
+    // the file is not json, xml or csv of the expected layout, with the message of the parser
+    Parse(String),
+    // an object or event id used by more than one object or event
+    DuplicateId(String),
+    // an object or event referencing an object the file does not hold, e.g. from "Event e2"
+    UnknownObject { from: String, object: String },
+    // a timestamp none of the formats of the config reads, at names what it belongs to, e.g. "Event e1"
+    Timestamp { at: String, error: TimestampError },
+    // an attribute value that does not fit its declared type
+    InvalidValue { at: String, message: String },
+    // an element, attribute or column the format requires, with what is missing
+    Missing(String),
+    // a column the csv config names that the header does not hold, or a column pattern like object_* matching none
+    UnknownColumn(String),
+    // a csv row with a different number of fields than the header, the row counts the header as 1
+    RowLength { row: usize, columns: usize, header: usize }
+}
+
+impl fmt::Display for OcelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcelError::Io(e) => write!(f, "{}", e),
+            OcelError::Parse(message) => write!(f, "{}", message),
+            OcelError::DuplicateId(id) => write!(f, "the id {} is used more than once", id),
+            OcelError::UnknownObject { from, object } => write!(f, "{} references the unknown object {}", from, object),
+            OcelError::Timestamp { at, error } => write!(f, "{}: {}", at, error),
+            OcelError::InvalidValue { at, message } => write!(f, "{}: {}", at, message),
+            OcelError::Missing(what) => write!(f, "{}", what),
+            OcelError::UnknownColumn(column) if column.ends_with('*') => write!(f, "No column of the csv matches {}", column),
+            OcelError::UnknownColumn(column) => write!(f, "The csv has no column {}", column),
+            OcelError::RowLength { row, columns, header } => write!(f, "Row {} has {} columns but the header has {}.", row, columns, header)
+        }
+    }
+}
+
+impl Error for OcelError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            OcelError::Io(e) => Some(e),
+            OcelError::Timestamp { error, .. } => Some(error),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for OcelError {
+    fn from(e: io::Error) -> Self {
+        OcelError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for OcelError {
+    fn from(e: serde_json::Error) -> Self {
+        OcelError::Parse(e.to_string())
+    }
+}
+
+impl From<quick_xml::Error> for OcelError {
+    fn from(e: quick_xml::Error) -> Self {
+        OcelError::Parse(e.to_string())
+    }
+}
+
+impl From<quick_xml::events::attributes::AttrError> for OcelError {
+    fn from(e: quick_xml::events::attributes::AttrError) -> Self {
+        OcelError::Parse(e.to_string())
+    }
+}
+
+impl From<csv::Error> for OcelError {
+    fn from(e: csv::Error) -> Self {
+        OcelError::Parse(e.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportConfig {
+    // events with more objects than this are reported as warnings
+    pub omap_warning_threshold: usize,
+    pub timestamps: TimestampParser
+}
+
+impl Default for ImportConfig {
+    fn default() -> Self {
+        Self { omap_warning_threshold: 100, timestamps: TimestampParser::default() }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportTimings {
+    pub read: Duration,
+    pub parse: Duration,
+    // building the internal ids, lifecycles and timestamps
+    pub index: Duration
+}
+
+impl ImportTimings {
+    pub fn total(&self) -> Duration {
+        self.read + self.parse + self.index
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub events: usize,
+    pub objects: usize,
+    pub omap_max: usize,
+    pub omap_p99: usize,
+    // (event id, activity, omap size) of the largest omaps, largest first
+    pub largest_omaps: Vec<(String, String, usize)>,
+    // system events that reference no object, they are kept but never form relations
+    pub empty_omaps: usize,
+    // object references written as json numbers, they are matched to the objects by their digits
+    pub numeric_ids: usize,
+    pub warnings: Vec<String>,
+    pub timings: ImportTimings,
+    // estimated bytes held at once during the import, the file contents included
+    pub peak_bytes: usize,
+    pub memory: MemoryBreakdown
+}
+
+impl ImportReport {
+    pub fn new(log: &Ocel, config: &ImportConfig) -> Self {
+        let mut sizes: Vec<(usize, &str, &str)> = log.events.iter()
+                                                     .map(|(eid, event)| (event.omap.len(), log.event_map.get_by_right(eid).map_or("?", |id| id.as_str()), event.activity))
+                                                     .collect();
+        sizes.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(b.1)));
+
+        let mut report = Self { events: log.events.len(), objects: log.objects.len(), memory: log.memory_breakdown(), ..Default::default() };
+        report.omap_max = sizes.first().map_or(0, |(size, _, _)| *size);
+        // nearest rank on the descending order
+        if !sizes.is_empty() {
+            let rank = (sizes.len() as f64 * 0.99).ceil() as usize;
+            report.omap_p99 = sizes[sizes.len() - rank].0;
+        }
+        report.empty_omaps = sizes.iter().rev().take_while(|(size, _, _)| *size == 0).count();
+        report.largest_omaps = sizes.iter().take(10).map(|(size, id, activity)| (id.to_string(), activity.to_string(), *size)).collect();
+        for (size, id, activity) in sizes.iter().take_while(|(size, _, _)| *size > config.omap_warning_threshold) {
+            let warning = format!("event {} ({}) references {} objects, more than the threshold of {}. Relation generation is quadratic in the omap size, consider excluding the activity.", id, activity, size, config.omap_warning_threshold);
+            // also logged, callers that never look at the report still see it
+            log::warn!("{}", warning);
+            report.warnings.push(warning);
+        }
+        if report.empty_omaps > 0 {
+            report.warnings.push(format!("{} events reference no objects. They are kept in the log but cannot take part in any relation.", report.empty_omaps));
+        }
+        report
+    }
+}
 
 pub fn import_ocel(file_path: &str) -> Result<Ocel, Box<dyn Error>> {
     return import_json_ocel(file_path);
 }
+
+pub fn import_ocel_with(file_path: &str, config: &ImportConfig) -> Result<Ocel, Box<dyn Error>> {
+    import_json_ocel_with(file_path, &config.timestamps)
+}
+
+pub fn import_ocel_with_report(file_path: &str, config: &ImportConfig) -> Result<(Ocel, ImportReport), Box<dyn Error>> {
+    with_report(import_json_ocel_profiled(file_path, &config.timestamps)?, config)
+}
+
+// a jsonocel document already in memory, the read timing stays zero
+pub fn import_ocel_str(content: &str, config: &ImportConfig) -> Result<Ocel, Box<dyn Error>> {
+    import_json_ocel_str(content, &config.timestamps, ImportTimings::default()).map(|import| import.log)
+}
+
+pub fn import_ocel_str_with_report(content: &str, config: &ImportConfig) -> Result<(Ocel, ImportReport), Box<dyn Error>> {
+    with_report(import_json_ocel_str(content, &config.timestamps, ImportTimings::default())?, config)
+}
+
+// OCEL 2.0 json with typed attributes and object-to-object relations, the relations go to Ocel::object_relations
+pub fn import_ocel2_json(file_path: &str, config: &ImportConfig) -> Result<Ocel, OcelError> {
+    import_json_ocel2(file_path, &config.timestamps)
+}
+
+pub fn import_ocel2_json_str(content: &str, config: &ImportConfig) -> Result<Ocel, OcelError> {
+    import_json_ocel2_str(content, &config.timestamps)
+}
+
+// every trace of an xes log becomes an object of type case named by its concept:name, every event an event of the traces it is in.
+// events of different traces with the same concept:name and time:timestamp are one event. the event concept:name is the activity,
+// time:timestamp the timestamp and org:resource with the remaining attributes stays in the vmap, lists are flattened to strings
+pub fn import_ocel_xes(file_path: &str, config: &ImportConfig) -> Result<Ocel, OcelError> {
+    import_xes(file_path, &config.timestamps)
+}
+
+pub fn import_ocel_xes_str(content: &str, config: &ImportConfig) -> Result<Ocel, OcelError> {
+    import_xes_str(content, &config.timestamps)
+}
+
+// where the objects of a csv object column get their type from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvObjectType {
+    // every object of the column has this type
+    Fixed(String),
+    // the type is read from this column of the same row
+    Column(String),
+    // object id -> type, ids missing from the map get CsvImportConfig::default_object_type
+    Map(HashMap<String, String>)
+}
+
+// a column of object ids, a name ending with * matches every header column starting with the rest of the name (object_* for object_1, object_2, ..)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvObjectColumn {
+    pub column: String,
+    pub obj_type: CsvObjectType
+}
+
+// every row is an event referencing the objects of its object columns, rows sharing an event id are one event.
+// columns that are not mapped become event attributes
+#[derive(Debug, Clone)]
+pub struct CsvImportConfig {
+    pub event_id: String,
+    pub activity: String,
+    // without a timestamp column the events are one second apart in the order of the file, starting at the unix epoch
+    pub timestamp: Option<String>,
+    pub objects: Vec<CsvObjectColumn>,
+    pub default_object_type: String,
+    pub delimiter: u8,
+    pub timestamps: TimestampParser
+}
+
+impl Default for CsvImportConfig {
+    fn default() -> Self {
+        Self { event_id: "event_id".to_owned(), activity: "activity".to_owned(), timestamp: Some("timestamp".to_owned()), objects: vec![],
+               default_object_type: "object".to_owned(), delimiter: b',', timestamps: TimestampParser::default() }
+    }
+}
+
+impl CsvImportConfig {
+    pub fn with_objects(mut self, column: &str, obj_type: CsvObjectType) -> Self {
+        self.objects.push(CsvObjectColumn { column: column.to_owned(), obj_type });
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CsvImportReport {
+    pub rows: usize,
+    pub events: usize,
+    pub objects: usize,
+    // (line in the file, reason) of the rows left out of the log
+    pub skipped: Vec<(usize, String)>,
+    pub warnings: Vec<String>
+}
+
+pub fn import_ocel_csv(file_path: &str, config: &CsvImportConfig) -> Result<(Ocel, CsvImportReport), OcelError> {
+    import_csv_ocel_str(&fs::read_to_string(file_path)?, config)
+}
+
+pub fn import_ocel_csv_str(content: &str, config: &CsvImportConfig) -> Result<(Ocel, CsvImportReport), OcelError> {
+    import_csv_ocel_str(content, config)
+}
+
+fn with_report(import: JsonImport, config: &ImportConfig) -> Result<(Ocel, ImportReport), Box<dyn Error>> {
+    let mut report = ImportReport::new(&import.log, config);
+    report.timings = import.timings;
+    report.peak_bytes = import.peak_bytes;
+    report.numeric_ids = import.numeric_ids;
+    if import.numeric_ids > 0 {
+        report.warnings.push(format!("{} object references are numbers. They were read as the ids written in the file, integral floats without their fraction.", import.numeric_ids));
+    }
+    if !import.inserted_objects.is_empty() {
+        report.warnings.push(format!("{} objects are referenced by events but missing from ocel:objects: {}. They were added with the default type and no attributes.", import.inserted_objects.len(), import.inserted_objects.join(", ")));
+    }
+    Ok((import.log, report))
+}
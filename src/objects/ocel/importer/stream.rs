@@ -0,0 +1,305 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, Read};
+use std::time::Instant;
+use ahash::{AHashMap, RandomState};
+use indexmap::IndexMap;
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+use serde::Deserializer;
+use serde_json::Value;
+
+use crate::objects::ocel::{Ocel, OcelEventSerde, OcelObjectSerde, OcelSerde};
+use crate::objects::ocel::importer::ImportTimings;
+use crate::objects::ocel::importer::timestamp::TimestampParser;
+use crate::objects::ocel::importer::variants::jsonocel::{index_json_ocel, RawId};
+
+#[derive(Debug, Clone)]
+pub struct StreamConfig {
+    // only the statistics are computed, the log is never built and repeated ids go unnoticed
+    pub stats_only: bool,
+    // counters kept for the activity and object type counts
+    pub top_k: usize,
+    // the distinct object counts use 2^precision registers per object type
+    pub hll_precision: u8,
+    pub timestamps: TimestampParser
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self { stats_only: true, top_k: 100, hll_precision: 12, timestamps: TimestampParser::default() }
+    }
+}
+
+// what a jsonocel file holds, computed while it is read with memory bounded by the counters and registers
+#[derive(Debug, Clone)]
+pub struct StreamStats {
+    pub events: usize,
+    // entries of ocel:objects
+    pub objects: usize,
+    pub activities: TopK,
+    pub object_types: TopK,
+    // omap size -> events
+    pub omap_sizes: BTreeMap<usize, usize>,
+    // object type -> distinct object ids of ocel:objects
+    pub distinct_objects: BTreeMap<String, HyperLogLog>,
+    // distinct object ids referenced by events, objects missing from ocel:objects included
+    pub referenced_objects: HyperLogLog
+}
+
+impl StreamStats {
+    fn new(config: &StreamConfig) -> Self {
+        Self { events: 0, objects: 0, activities: TopK::new(config.top_k), object_types: TopK::new(config.top_k), omap_sizes: BTreeMap::new(),
+               distinct_objects: BTreeMap::new(), referenced_objects: HyperLogLog::new(config.hll_precision) }
+    }
+}
+
+// space saving counts, exact as long as no more distinct items than counters were seen.
+// an evicted item's counter goes to the newcomer, so a count overestimates by at most its error
+#[derive(Debug, Clone)]
+pub struct TopK {
+    capacity: usize,
+    // item -> (count, overestimation)
+    counters: AHashMap<String, (usize, usize)>,
+    evictions: usize
+}
+
+impl TopK {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), counters: AHashMap::new(), evictions: 0 }
+    }
+
+    pub fn add(&mut self, item: &str) {
+        if let Some((count, _)) = self.counters.get_mut(item) {
+            *count += 1;
+            return;
+        }
+        if self.counters.len() < self.capacity {
+            self.counters.insert(item.to_owned(), (1, 0));
+            return;
+        }
+        let (evicted, min) = self.counters.iter()
+                                          .min_by(|a, b| a.1.0.cmp(&b.1.0).then(b.0.cmp(a.0)))
+                                          .map(|(item, (count, _))| (item.to_owned(), *count))
+                                          .expect("The counters are full");
+        self.counters.remove(&evicted);
+        self.counters.insert(item.to_owned(), (min + 1, min));
+        self.evictions += 1;
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.evictions == 0
+    }
+
+    // (item, count, overestimation) of the n largest counts, largest first and ties by item
+    pub fn top(&self, n: usize) -> Vec<(String, usize, usize)> {
+        let mut top: Vec<(String, usize, usize)> = self.counters.iter().map(|(item, (count, error))| (item.to_owned(), *count, *error)).collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        top.truncate(n);
+        top
+    }
+
+    pub fn count(&self, item: &str) -> Option<usize> {
+        self.counters.get(item).map(|(count, _)| *count)
+    }
+}
+
+// distinct counting in 2^precision one byte registers, the relative standard error is 1.04 / sqrt(2^precision)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyperLogLog {
+    precision: u8,
+    registers: Vec<u8>
+}
+
+impl HyperLogLog {
+    pub fn new(precision: u8) -> Self {
+        let precision = precision.clamp(4, 16);
+        Self { precision, registers: vec![0; 1 << precision] }
+    }
+
+    pub fn insert<T: Hash + ?Sized>(&mut self, item: &T) {
+        let mut hasher = DefaultHasher::new();
+        item.hash(&mut hasher);
+        let hash = hasher.finish();
+        let index = (hash >> (64 - self.precision)) as usize;
+        // the rank of the first set bit of the remaining bits, a sentinel bit bounds it
+        let rest = (hash << self.precision) | (1 << (self.precision - 1));
+        let rank = rest.leading_zeros() as u8 + 1;
+        self.registers[index] = self.registers[index].max(rank);
+    }
+
+    pub fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m)
+        };
+        let sum: f64 = self.registers.iter().map(|register| 2f64.powi(-(*register as i32))).sum();
+        let raw = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|register| **register == 0).count();
+        // linear counting is more accurate for small cardinalities
+        if raw <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+
+    pub fn standard_error(&self) -> f64 {
+        1.04 / (self.registers.len() as f64).sqrt()
+    }
+}
+
+// reads a jsonocel file entry by entry. with stats_only the log is None and only the statistics are held in memory,
+// otherwise the entries are collected and indexed like import_ocel does
+pub fn import_ocel_streaming(file_path: &str, config: &StreamConfig) -> Result<(Option<Ocel>, StreamStats), Box<dyn Error>> {
+    import_ocel_streaming_from(BufReader::new(File::open(file_path)?), config)
+}
+
+pub fn import_ocel_streaming_from<R: Read>(reader: R, config: &StreamConfig) -> Result<(Option<Ocel>, StreamStats), Box<dyn Error>> {
+    let started = Instant::now();
+    let mut sink = StreamSink { stats: StreamStats::new(config), log: None, seen_events: false };
+    if !config.stats_only {
+        sink.log = Some(OcelSerde { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                                    objects: AHashMap::new(), events: IndexMap::with_hasher(RandomState::new()) });
+    }
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    deserializer.deserialize_map(LogVisitor(&mut sink))?;
+    deserializer.end()?;
+    if !sink.seen_events {
+        return Err("missing field `ocel:events`".into());
+    }
+
+    let log = match sink.log {
+        Some(log) => {
+            let timings = ImportTimings { parse: started.elapsed(), ..Default::default() };
+            Some(index_json_ocel(log, &config.timestamps, timings, 0)?.log)
+        },
+        None => None
+    };
+    Ok((log, sink.stats))
+}
+
+struct StreamSink {
+    stats: StreamStats,
+    log: Option<OcelSerde<Value, RawId>>,
+    seen_events: bool
+}
+
+impl StreamSink {
+    fn object(&mut self, id: String, object: OcelObjectSerde) -> Result<(), String> {
+        self.stats.objects += 1;
+        self.stats.object_types.add(&object.obj_type);
+        let precision = self.stats.referenced_objects.precision;
+        self.stats.distinct_objects.entry(object.obj_type.to_owned()).or_insert_with(|| HyperLogLog::new(precision)).insert(&id);
+        if let Some(log) = self.log.as_mut() {
+            if log.objects.contains_key(&id) {
+                return Err(format!("the id {} is used more than once", id));
+            }
+            log.objects.insert(id, object);
+        }
+        Ok(())
+    }
+
+    fn event(&mut self, id: String, event: OcelEventSerde<Value, RawId>) -> Result<(), String> {
+        self.stats.events += 1;
+        self.stats.activities.add(&event.activity);
+        *self.stats.omap_sizes.entry(event.omap.len()).or_default() += 1;
+        for oid in &event.omap {
+            self.stats.referenced_objects.insert(&oid.id());
+        }
+        if let Some(log) = self.log.as_mut() {
+            if log.events.contains_key(&id) {
+                return Err(format!("the id {} is used more than once", id));
+            }
+            log.events.insert(id, event);
+        }
+        Ok(())
+    }
+}
+
+struct LogVisitor<'a>(&'a mut StreamSink);
+
+impl<'de, 'a> Visitor<'de> for LogVisitor<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a jsonocel document")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            let globals = self.0.log.as_mut();
+            match key.as_str() {
+                "ocel:global-log" | "global_log" => {
+                    let value: AHashMap<String, Value> = map.next_value()?;
+                    if let Some(log) = globals {
+                        log.global_log = value;
+                    }
+                },
+                "ocel:global-event" | "global_event" => {
+                    let value: AHashMap<String, Value> = map.next_value()?;
+                    if let Some(log) = globals {
+                        log.global_event = value;
+                    }
+                },
+                "ocel:global-object" | "global_object" => {
+                    let value: AHashMap<String, Value> = map.next_value()?;
+                    if let Some(log) = globals {
+                        log.global_object = value;
+                    }
+                },
+                "ocel:objects" | "objects" => map.next_value_seed(Entries { sink: &mut *self.0, events: false })?,
+                "ocel:events" | "events" => {
+                    self.0.seen_events = true;
+                    map.next_value_seed(Entries { sink: &mut *self.0, events: true })?
+                },
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// the entries of ocel:objects or ocel:events, handed to the sink one at a time
+struct Entries<'a> {
+    sink: &'a mut StreamSink,
+    events: bool
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for Entries<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for Entries<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map of ids")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(id) = map.next_key::<String>()? {
+            let added = if self.events {
+                let event: OcelEventSerde<Value, RawId> = map.next_value()?;
+                self.sink.event(id, event)
+            } else {
+                let object: OcelObjectSerde = map.next_value()?;
+                self.sink.object(id, object)
+            };
+            added.map_err(de::Error::custom)?;
+        }
+        Ok(())
+    }
+}
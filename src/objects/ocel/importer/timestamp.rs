@@ -0,0 +1,133 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+
+pub type TimestampFn = Arc<dyn Fn(&str) -> Option<DateTime<Utc>> + Send + Sync>;
+
+#[derive(Clone)]
+pub enum TimestampFormat {
+    // what the json format prescribes, any offset is converted to utc
+    Rfc3339,
+    // a chrono format string, read as utc unless it contains an offset
+    Pattern(String),
+    // seconds since the unix epoch, fractions allowed
+    EpochSeconds,
+    EpochMillis,
+    Custom(String, TimestampFn)
+}
+
+impl TimestampFormat {
+    pub fn parse(&self, value: &str) -> Option<DateTime<Utc>> {
+        let value = value.trim();
+        match self {
+            TimestampFormat::Rfc3339 => value.parse::<DateTime<FixedOffset>>().ok().map(|ts| ts.with_timezone(&Utc)),
+            TimestampFormat::Pattern(pattern) => DateTime::parse_from_str(value, pattern).map(|ts| ts.with_timezone(&Utc))
+                                                                                         .or_else(|_| NaiveDateTime::parse_from_str(value, pattern).map(|ts| Utc.from_utc_datetime(&ts)))
+                                                                                         .ok(),
+            TimestampFormat::EpochSeconds => epoch(value, 1_000_000_000.0),
+            TimestampFormat::EpochMillis => epoch(value, 1_000_000.0),
+            TimestampFormat::Custom(_, parse) => parse(value)
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            TimestampFormat::Rfc3339 => "rfc3339".to_owned(),
+            TimestampFormat::Pattern(pattern) => format!("\"{}\"", pattern),
+            TimestampFormat::EpochSeconds => "epoch seconds".to_owned(),
+            TimestampFormat::EpochMillis => "epoch milliseconds".to_owned(),
+            TimestampFormat::Custom(name, _) => name.to_owned()
+        }
+    }
+}
+
+impl fmt::Debug for TimestampFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TimestampFormat({})", self.name())
+    }
+}
+
+fn epoch(value: &str, nanos_per_unit: f64) -> Option<DateTime<Utc>> {
+    let units: f64 = value.parse().ok().filter(|units: &f64| units.is_finite())?;
+    let nanos = (units * nanos_per_unit).round();
+    if nanos.abs() >= i64::MAX as f64 {
+        return None;
+    }
+    let nanos = nanos as i64;
+    Some(Utc.timestamp(nanos.div_euclid(1_000_000_000), nanos.rem_euclid(1_000_000_000) as u32))
+}
+
+#[derive(Debug, Clone)]
+pub struct TimestampError {
+    pub value: String,
+    pub tried: Vec<String>
+}
+
+impl fmt::Display for TimestampError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Timestamp \"{}\" matches none of the formats tried: {}", self.value, self.tried.join(", "))
+    }
+}
+
+impl Error for TimestampError {}
+
+// formats are tried in order, the first one that parses wins
+#[derive(Debug, Clone)]
+pub struct TimestampParser {
+    pub formats: Vec<TimestampFormat>,
+    // the import picks the formats from this many leading values instead
+    pub detect: Option<usize>
+}
+
+impl Default for TimestampParser {
+    fn default() -> Self {
+        Self { formats: vec![TimestampFormat::Rfc3339], detect: None }
+    }
+}
+
+impl TimestampParser {
+    pub fn new(formats: Vec<TimestampFormat>) -> Self {
+        Self { formats, detect: None }
+    }
+
+    pub fn auto(samples: usize) -> Self {
+        Self { formats: vec![], detect: Some(samples) }
+    }
+
+    pub fn with_format(mut self, format: TimestampFormat) -> Self {
+        self.formats.push(format);
+        self
+    }
+
+    pub fn with_custom<F>(self, name: &str, parse: F) -> Self where F: Fn(&str) -> Option<DateTime<Utc>> + Send + Sync + 'static {
+        self.with_format(TimestampFormat::Custom(name.to_owned(), Arc::new(parse)))
+    }
+
+    pub fn parse(&self, value: &str) -> Result<DateTime<Utc>, TimestampError> {
+        self.formats.iter()
+                    .find_map(|format| format.parse(value))
+                    .ok_or_else(|| TimestampError { value: value.to_owned(), tried: self.formats.iter().map(|format| format.name()).collect() })
+    }
+
+    // the given formats followed by every built-in format that reads all samples
+    pub fn detected<'a, I>(&self, samples: I) -> TimestampParser where I: IntoIterator<Item = &'a str> {
+        let samples: Vec<&str> = samples.into_iter().take(self.detect.unwrap_or(usize::MAX)).collect();
+        let mut formats = self.formats.clone();
+        formats.extend(builtin_formats().into_iter().filter(|format| !samples.is_empty() && samples.iter().all(|sample| format.parse(sample).is_some())));
+        TimestampParser { formats, detect: None }
+    }
+}
+
+pub fn builtin_formats() -> Vec<TimestampFormat> {
+    let mut formats = vec![TimestampFormat::Rfc3339];
+    formats.extend(["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f", "%Y/%m/%d %H:%M:%S", "%d.%m.%Y %H:%M:%S,%3f", "%d.%m.%Y %H:%M:%S", "%d/%m/%Y %H:%M:%S"]
+                    .iter()
+                    .map(|pattern| TimestampFormat::Pattern(pattern.to_string())));
+    // plain numbers are read as seconds, values past the year 5000 as milliseconds
+    formats.push(TimestampFormat::Custom("epoch".to_owned(), Arc::new(|value: &str| {
+        let units: f64 = value.trim().parse().ok()?;
+        if units.abs() > 1e11 {epoch(value.trim(), 1_000_000.0)} else {epoch(value.trim(), 1_000_000_000.0)}
+    })));
+    formats
+}
@@ -1 +1,4 @@
+pub(super) mod csvocel;
 pub(super) mod jsonocel;
+pub(super) mod jsonocel2;
+pub(super) mod xes;
@@ -0,0 +1,188 @@
+use crate::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+use crate::objects::ocel::enrichment::parse_csv_value;
+use crate::objects::ocel::importer::{CsvImportConfig, CsvImportReport, CsvObjectType, OcelError};
+use ahash::{AHashMap, AHashSet};
+use bimap::BiMap;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use csv::{ReaderBuilder, StringRecord, Trim};
+use nohash_hasher::{IntMap, IntSet};
+use std::collections::BTreeSet;
+use serde_json::{json, Value};
+
+// an event collected from one or more rows sharing its id
+struct CsvEvent {
+    id: String,
+    activity: String,
+    timestamp: DateTime<Utc>,
+    vmap: AHashMap<String, Value>,
+    objects: Vec<usize>
+}
+
+// the header positions of an object column and where its objects get their type from
+struct ObjectColumn<'a> {
+    index: usize,
+    obj_type: &'a CsvObjectType,
+    type_index: Option<usize>
+}
+
+pub(crate) fn import_csv_ocel_str(s: &str, config: &CsvImportConfig) -> Result<(Ocel, CsvImportReport), OcelError> {
+    let mut reader = ReaderBuilder::new().delimiter(config.delimiter).trim(Trim::All).flexible(true).from_reader(s.as_bytes());
+    let header = reader.headers()?.clone();
+    let position = |column: &str| header.iter().position(|name| name == column).ok_or_else(|| OcelError::UnknownColumn(column.to_owned()));
+
+    let event_index = position(&config.event_id)?;
+    let activity_index = position(&config.activity)?;
+    let timestamp_index = match &config.timestamp {
+        Some(column) => Some(position(column)?),
+        None => None
+    };
+    let mut object_columns: Vec<ObjectColumn> = vec![];
+    for object in &config.objects {
+        let type_index = match &object.obj_type {
+            CsvObjectType::Column(column) => Some(position(column)?),
+            _ => None
+        };
+        let matching: Vec<usize> = match object.column.strip_suffix('*') {
+            Some(prefix) => header.iter().enumerate().filter(|(_, name)| name.starts_with(prefix)).map(|(index, _)| index).collect(),
+            None => vec![position(&object.column)?]
+        };
+        if matching.is_empty() {
+            return Err(OcelError::UnknownColumn(object.column.to_owned()));
+        }
+        object_columns.extend(matching.into_iter().map(|index| ObjectColumn { index, obj_type: &object.obj_type, type_index }));
+    }
+    let mapped: BTreeSet<usize> = [Some(event_index), Some(activity_index), timestamp_index].into_iter()
+                                                                                          .flatten()
+                                                                                          .chain(object_columns.iter().flat_map(|column| [Some(column.index), column.type_index]).flatten())
+                                                                                          .collect();
+    let attributes: Vec<(usize, &str)> = header.iter().enumerate().filter(|(index, _)| !mapped.contains(index)).collect();
+
+    let mut report = CsvImportReport::default();
+    let mut events: Vec<CsvEvent> = vec![];
+    let mut event_positions: AHashMap<String, usize> = AHashMap::new();
+    let mut objects: Vec<(String, String)> = vec![];
+    let mut object_positions: AHashMap<String, usize> = AHashMap::new();
+    let start = Utc.timestamp(0, 0);
+
+    let mut record = StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(true) => {},
+            Ok(false) => break,
+            Err(e) => {
+                report.rows += 1;
+                report.skipped.push((e.position().map_or(0, |position| position.line() as usize), e.to_string()));
+                continue;
+            }
+        }
+        report.rows += 1;
+        let line = record.position().map_or(0, |position| position.line() as usize);
+        let field = |index: usize| record.get(index).filter(|value| !value.is_empty());
+
+        let (id, activity) = match (field(event_index), field(activity_index)) {
+            (Some(id), Some(activity)) => (id, activity),
+            (None, _) => {
+                report.skipped.push((line, format!("missing {}", config.event_id)));
+                continue;
+            },
+            (_, None) => {
+                report.skipped.push((line, format!("missing {}", config.activity)));
+                continue;
+            }
+        };
+        let timestamp = match timestamp_index {
+            Some(index) => match field(index).map(|value| config.timestamps.parse(value)) {
+                Some(Ok(timestamp)) => timestamp,
+                Some(Err(e)) => {
+                    report.skipped.push((line, e.to_string()));
+                    continue;
+                },
+                None => {
+                    report.skipped.push((line, format!("missing {}", header.get(index).unwrap_or_default())));
+                    continue;
+                }
+            },
+            None => start + Duration::seconds(report.rows as i64 - 1)
+        };
+        if let Some(earlier) = event_positions.get(id).map(|position| &events[*position]) {
+            if earlier.activity != activity {
+                report.skipped.push((line, format!("event {} is a {} in an earlier row", id, earlier.activity)));
+                continue;
+            }
+        }
+
+        let mut oids: Vec<usize> = vec![];
+        for column in &object_columns {
+            let Some(object) = field(column.index) else { continue };
+            let obj_type = match column.obj_type {
+                CsvObjectType::Fixed(obj_type) => obj_type.as_str(),
+                CsvObjectType::Column(_) => column.type_index.and_then(field).unwrap_or(&config.default_object_type),
+                CsvObjectType::Map(types) => types.get(object).unwrap_or(&config.default_object_type)
+            };
+            let oid = match object_positions.get(object) {
+                Some(oid) => {
+                    if objects[*oid].1 != obj_type {
+                        report.warnings.push(format!("line {}: object {} has the type {} but was read as {} before, the first type is kept", line, object, obj_type, objects[*oid].1));
+                    }
+                    *oid
+                },
+                None => {
+                    object_positions.insert(object.to_owned(), objects.len());
+                    objects.push((object.to_owned(), obj_type.to_owned()));
+                    objects.len() - 1
+                }
+            };
+            oids.push(oid);
+        }
+        let vmap: AHashMap<String, Value> = attributes.iter().filter_map(|(index, name)| field(*index).map(|value| (name.to_string(), parse_csv_value(value)))).collect();
+
+        // later rows of an event add their objects and the attributes not set yet
+        match event_positions.get(id) {
+            Some(position) => {
+                let event = &mut events[*position];
+                event.objects.extend(oids);
+                for (name, value) in vmap {
+                    event.vmap.entry(name).or_insert(value);
+                }
+            },
+            None => {
+                event_positions.insert(id.to_owned(), events.len());
+                events.push(CsvEvent { id: id.to_owned(), activity: activity.to_owned(), timestamp, vmap, objects: oids });
+            }
+        }
+    }
+    if !report.skipped.is_empty() {
+        report.warnings.push(format!("{} rows were skipped, see CsvImportReport::skipped", report.skipped.len()));
+    }
+
+    let object_types: BTreeSet<&String> = objects.iter().map(|(_, obj_type)| obj_type).collect();
+    let attribute_names: Vec<&str> = attributes.iter().map(|(_, name)| *name).collect();
+    let global_log: AHashMap<String, Value> = AHashMap::from_iter([("ocel:version".to_owned(), json!("1.0")),
+                                                                   ("ocel:ordering".to_owned(), json!("timestamp")),
+                                                                   ("ocel:attribute-names".to_owned(), json!(attribute_names)),
+                                                                   ("ocel:object-types".to_owned(), json!(object_types))]);
+    let mut log = Ocel { global_log, global_event: AHashMap::new(), global_object: AHashMap::new(), events: EventColumns::default(), objects: IntMap::default(),
+                         object_map: BiMap::new(), event_map: BiMap::new(), activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (id, obj_type)) in objects.into_iter().enumerate() {
+        log.object_map.insert(id, oid);
+        log.objects.insert(oid, OcelObject { obj_type, ovmap: AHashMap::new(), events: vec![] });
+    }
+
+    // events get their ids in timestamp order so that the lifecycles are sorted, ties keep the order of the file
+    events.sort_by_key(|event| event.timestamp);
+    let mut activity_set: AHashSet<String> = AHashSet::new();
+    for (eid, event) in events.into_iter().enumerate() {
+        let omap: IntSet<usize> = event.objects.into_iter().collect();
+        for oid in &omap {
+            log.objects.get_mut(oid).expect("Every object was added").events.push(eid);
+        }
+        if activity_set.insert(event.activity.to_owned()) {
+            log.activities.push(event.activity.to_owned());
+        }
+        log.event_map.insert(event.id, eid);
+        log.events.insert(eid, OcelEvent { activity: event.activity, timestamp: event.timestamp, vmap: event.vmap, omap });
+    }
+    report.events = log.events.len();
+    report.objects = log.objects.len();
+    Ok((log, report))
+}
@@ -1,16 +1,108 @@
-use crate::objects::ocel::{Ocel, OcelSerde, OcelEvent, OcelObject};
-use ahash::AHashSet;
+use crate::objects::ocel::{EventColumns, Ocel, OcelSerde, OcelEventSerde, OcelObject, OcelObjectSerde, TypedAttributes};
+use crate::objects::ocel::importer::ImportTimings;
+use crate::objects::ocel::importer::timestamp::TimestampParser;
+use crate::objects::ocel::memory::attributes_size;
+use ahash::{AHashMap, AHashSet};
 use bimap::BiMap;
-use nohash_hasher::{IntMap, IntSet};
+use nohash_hasher::IntMap;
 use std::fs::File;
 use std::io::Read;
 use std::error::Error;
+use std::mem::size_of;
+use std::time::Instant;
+use serde::{Deserialize, Deserializer};
+use serde::de::Error as DeError;
+use serde_json::Value;
+use serde_json::value::RawValue;
+
+// an object reference as written in the file, numbers keep their literal digits instead of going through a double
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub(crate) enum RawId {
+    Text(String),
+    Number(String)
+}
+
+impl<'de> Deserialize<'de> for RawId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // boxed so that readers, which cannot lend out their input, work as well
+        let raw = Box::<RawValue>::deserialize(deserializer)?;
+        match raw.get() {
+            text if text.starts_with('"') => serde_json::from_str(text).map(RawId::Text).map_err(D::Error::custom),
+            number if number.starts_with(|c: char| c == '-' || c.is_ascii_digit()) => Ok(RawId::Number(number.to_owned())),
+            other => Err(D::Error::custom(format!("object references must be strings or numbers, found {}", other)))
+        }
+    }
+}
+
+impl RawId {
+    // integers are used as written, integral floats within double precision lose their fraction and exponent
+    pub(crate) fn id(&self) -> String {
+        match self {
+            RawId::Text(text) => text.to_owned(),
+            RawId::Number(number) if number.trim_start_matches('-').chars().all(|c| c.is_ascii_digit()) => number.to_owned(),
+            RawId::Number(number) => match number.parse::<f64>() {
+                Ok(value) if value.fract() == 0.0 && value.abs() <= 9007199254740992.0 => format!("{}", value as i64),
+                _ => number.to_owned()
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            RawId::Text(text) | RawId::Number(text) => text.len()
+        }
+    }
+}
+
+pub(crate) struct JsonImport {
+    pub log: Ocel,
+    pub timings: ImportTimings,
+    pub peak_bytes: usize,
+    // object references written as json numbers
+    pub numeric_ids: usize,
+    // objects referenced by events without an entry in ocel:objects, in the order they were met
+    pub inserted_objects: Vec<String>
+}
 
 pub(crate) fn import_json_ocel(file_path: &str) -> Result<Ocel, Box<dyn Error>> {
+    import_json_ocel_with(file_path, &TimestampParser::default())
+}
+
+pub(crate) fn import_json_ocel_with(file_path: &str, timestamps: &TimestampParser) -> Result<Ocel, Box<dyn Error>> {
+    import_json_ocel_profiled(file_path, timestamps).map(|import| import.log)
+}
+
+// also returns the phase timings and an estimate of the peak bytes held at once:
+// the file contents, the parsed document and the log, which all live until the indices are built
+pub(crate) fn import_json_ocel_profiled(file_path: &str, timestamps: &TimestampParser) -> Result<JsonImport, Box<dyn Error>> {
+    let mut timings = ImportTimings::default();
+    let started = Instant::now();
     let mut s = String::new();
     File::open(file_path)?.read_to_string(&mut s)?;
-    let log: OcelSerde = serde_json::from_str(&s)?;
-    let mut log_internal: Ocel = Ocel { global_log: log.global_log, global_event: log.global_event, global_object: log.global_object, events: IntMap::default() , objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(), activities: vec![] };
+    timings.read = started.elapsed();
+    import_json_ocel_str(&s, timestamps, timings)
+}
+
+pub(crate) fn import_json_ocel_str(s: &str, timestamps: &TimestampParser, mut timings: ImportTimings) -> Result<JsonImport, Box<dyn Error>> {
+    let started = Instant::now();
+    let log: OcelSerde<Value, RawId> = serde_json::from_str(s)?;
+    timings.parse = started.elapsed();
+    index_json_ocel(log, timestamps, timings, s.len())
+}
+
+// builds the internal ids, lifecycles and timestamps of a parsed document. held_bytes are held besides the document and the log,
+// like the file contents
+pub(crate) fn index_json_ocel(log: OcelSerde<Value, RawId>, timestamps: &TimestampParser, mut timings: ImportTimings, held_bytes: usize) -> Result<JsonImport, Box<dyn Error>> {
+    let parsed_bytes = parsed_size(&log);
+    let started = Instant::now();
+    let timestamps = match timestamps.detect {
+        Some(samples) => {
+            let samples: Vec<String> = log.events.values().take(samples).map(|data| raw_timestamp(&data.timestamp)).collect();
+            timestamps.detected(samples.iter().map(|sample| sample.as_str()))
+        },
+        None => timestamps.clone()
+    };
+    let mut log_internal: Ocel = Ocel { global_log: log.global_log, global_event: log.global_event, global_object: log.global_object, events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(), activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
     let mut activity_set: AHashSet<String> = AHashSet::new();
     
     let mut oid_nh: usize = usize::MIN; 
@@ -20,23 +112,67 @@ pub(crate) fn import_json_ocel(file_path: &str) -> Result<Ocel, Box<dyn Error>>
         oid_nh = oid_nh + 1;
     }
 
+    // objects missing from ocel:objects get the default type of ocel:global-object
+    let default_type = log_internal.global_object.get("ocel:type").and_then(|value| value.as_str()).unwrap_or_default().to_owned();
+    let mut inserted_objects: Vec<String> = vec![];
+    let mut numeric_ids = 0;
     let mut eid_nh: usize = usize::MIN;
+    let mut omap: Vec<usize> = vec![];
     for (eid, data) in log.events {
-        activity_set.insert(data.activity.clone());
-        let mut fast_event = OcelEvent {activity: data.activity, timestamp: data.timestamp, vmap: data.vmap, omap: IntSet::default()};
-        log_internal.event_map.insert(eid, eid_nh);
-
+        if !activity_set.contains(&data.activity) {
+            activity_set.insert(data.activity.clone());
+        }
+        let timestamp = timestamps.parse(&raw_timestamp(&data.timestamp)).map_err(|e| format!("Event {}: {}", eid, e))?;
+        omap.clear();
         for oid in data.omap.iter() {
-            let oid_num = *log_internal.object_map.get_by_left(oid as &str).expect("This was just added so it cannot fail.");
-            fast_event.omap.insert(oid_num);
+            if let RawId::Number(_) = oid {
+                numeric_ids += 1;
+            }
+            let oid = oid.id();
+            let oid_num = match log_internal.object_map.get_by_left(&oid) {
+                Some(oid_num) => *oid_num,
+                None => {
+                    let oid_num = oid_nh;
+                    oid_nh += 1;
+                    log_internal.object_map.insert(oid.to_owned(), oid_num);
+                    log_internal.objects.insert(oid_num, OcelObject { obj_type: default_type.to_owned(), ovmap: AHashMap::new(), events: vec![] });
+                    inserted_objects.push(oid);
+                    oid_num
+                }
+            };
+            omap.push(oid_num);
             log_internal.objects.get_mut(&oid_num).unwrap().events.push(eid_nh);
         }
 
-        log_internal.events.insert(eid_nh, fast_event);
+        log_internal.event_map.insert(eid, eid_nh);
+        omap.sort_unstable();
+        omap.dedup();
+        log_internal.events.push(eid_nh, &data.activity, timestamp, &omap, data.vmap);
         eid_nh = eid_nh + 1;
     }
 
     log_internal.activities.extend(activity_set);
-    
-    Ok(log_internal)
+    timings.index = started.elapsed();
+
+    let peak_bytes = held_bytes + parsed_bytes + log_internal.memory_breakdown().total();
+    Ok(JsonImport { log: log_internal, timings, peak_bytes, numeric_ids, inserted_objects })
+}
+
+fn parsed_size(log: &OcelSerde<Value, RawId>) -> usize {
+    let events: usize = log.events.iter()
+                                  .map(|(eid, data)| size_of::<(String, OcelEventSerde<Value, RawId>)>() + eid.len() + data.activity.len() + raw_timestamp(&data.timestamp).len() +
+                                                     data.omap.iter().map(|oid| size_of::<RawId>() + oid.len()).sum::<usize>() + attributes_size(&data.vmap))
+                                  .sum();
+    let objects: usize = log.objects.iter()
+                                    .map(|(oid, data)| size_of::<(String, OcelObjectSerde)>() + oid.len() + data.obj_type.len() + attributes_size(&data.ovmap))
+                                    .sum();
+    events + objects + attributes_size(&log.global_log) + attributes_size(&log.global_event) + attributes_size(&log.global_object)
+}
+
+// numbers are handed to the parser as written
+fn raw_timestamp(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.to_owned(),
+        other => other.to_string()
+    }
 }
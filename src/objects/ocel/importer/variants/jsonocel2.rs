@@ -0,0 +1,181 @@
+use crate::objects::ocel::{EventColumns, Ocel, OcelAttributeValue, OcelEvent, OcelObject, TypedAttributes};
+use crate::objects::ocel::importer::OcelError;
+use crate::objects::ocel::importer::timestamp::TimestampParser;
+use ahash::{AHashMap, AHashSet};
+use bimap::BiMap;
+use chrono::{DateTime, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use std::collections::BTreeSet;
+use std::fs;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+// the OCEL 2.0 json layout: declared types with their attribute types and flat lists of objects and events
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Ocel2Serde {
+    #[serde(default)]
+    object_types: Vec<TypeSerde>,
+    #[serde(default)]
+    event_types: Vec<TypeSerde>,
+    #[serde(default)]
+    objects: Vec<ObjectSerde>,
+    #[serde(default)]
+    events: Vec<EventSerde>
+}
+
+#[derive(Deserialize)]
+struct TypeSerde {
+    name: String,
+    #[serde(default)]
+    attributes: Vec<AttributeTypeSerde>
+}
+
+#[derive(Deserialize)]
+struct AttributeTypeSerde {
+    name: String,
+    #[serde(rename = "type")]
+    attribute_type: String
+}
+
+#[derive(Deserialize)]
+struct ObjectSerde {
+    id: String,
+    #[serde(rename = "type")]
+    obj_type: String,
+    #[serde(default)]
+    attributes: Vec<AttributeSerde>,
+    #[serde(default)]
+    relationships: Vec<RelationshipSerde>
+}
+
+#[derive(Deserialize)]
+struct EventSerde {
+    id: String,
+    #[serde(rename = "type")]
+    activity: String,
+    time: Value,
+    #[serde(default)]
+    attributes: Vec<AttributeSerde>,
+    #[serde(default)]
+    relationships: Vec<RelationshipSerde>
+}
+
+// object attributes carry the time they took the value, event attributes have none
+#[derive(Deserialize)]
+struct AttributeSerde {
+    name: String,
+    #[serde(default)]
+    time: Option<Value>,
+    value: Value
+}
+
+// the qualifiers are not kept
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RelationshipSerde {
+    object_id: String
+}
+
+pub(crate) fn import_json_ocel2(file_path: &str, timestamps: &TimestampParser) -> Result<Ocel, OcelError> {
+    import_json_ocel2_str(&fs::read_to_string(file_path)?, timestamps)
+}
+
+pub(crate) fn import_json_ocel2_str(s: &str, timestamps: &TimestampParser) -> Result<Ocel, OcelError> {
+    let log: Ocel2Serde = serde_json::from_str(s)?;
+    let declared = |types: &[TypeSerde]| -> AHashMap<(String, String), String> {
+        types.iter()
+             .flat_map(|declared| declared.attributes.iter().map(move |attribute| ((declared.name.to_owned(), attribute.name.to_owned()), attribute.attribute_type.to_owned())))
+             .collect()
+    };
+    let object_attributes = declared(&log.object_types);
+    let event_attributes = declared(&log.event_types);
+    let attribute_names: BTreeSet<&String> = log.object_types.iter().chain(&log.event_types).flat_map(|declared| declared.attributes.iter().map(|attribute| &attribute.name)).collect();
+
+    let global_log: AHashMap<String, Value> = AHashMap::from_iter([("ocel:version".to_owned(), json!("2.0")),
+                                                                   ("ocel:ordering".to_owned(), json!("timestamp")),
+                                                                   ("ocel:attribute-names".to_owned(), json!(attribute_names)),
+                                                                   ("ocel:object-types".to_owned(), json!(log.object_types.iter().map(|declared| &declared.name).collect::<Vec<_>>()))]);
+    let mut log_internal = Ocel { global_log, global_event: AHashMap::new(), global_object: AHashMap::new(), events: EventColumns::default(), objects: IntMap::default(),
+                                  object_map: BiMap::new(), event_map: BiMap::new(), activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+
+    for (oid_nh, object) in log.objects.iter().enumerate() {
+        if log_internal.object_map.insert_no_overwrite(object.id.to_owned(), oid_nh).is_err() {
+            return Err(OcelError::DuplicateId(object.id.to_owned()));
+        }
+        // a changing attribute ends with its latest value, attributes without a time come first
+        let mut values: Vec<(Option<DateTime<Utc>>, &AttributeSerde)> = vec![];
+        for attribute in &object.attributes {
+            let time = match &attribute.time {
+                Some(time) => Some(parse_time(time, timestamps, || format!("Object {}", object.id))?),
+                None => None
+            };
+            values.push((time, attribute));
+        }
+        values.sort_by_key(|(time, _)| *time);
+        let mut typed: AHashMap<String, OcelAttributeValue> = AHashMap::new();
+        for (_, attribute) in values {
+            let attribute_type = object_attributes.get(&(object.obj_type.to_owned(), attribute.name.to_owned())).map(|declared| declared.as_str());
+            let value = OcelAttributeValue::parse(&attribute.value, attribute_type, timestamps).map_err(|message| OcelError::InvalidValue { at: format!("Object {} attribute {}", object.id, attribute.name), message })?;
+            typed.insert(attribute.name.to_owned(), value);
+        }
+        let ovmap: AHashMap<String, Value> = typed.iter().map(|(name, value)| (name.to_owned(), value.clone().into())).collect();
+        if !typed.is_empty() {
+            log_internal.typed_attributes.objects.insert(oid_nh, typed);
+        }
+        log_internal.objects.insert(oid_nh, OcelObject { obj_type: object.obj_type.to_owned(), ovmap, events: vec![] });
+    }
+
+    for object in &log.objects {
+        let src = log_internal.object_map.get_by_left(&object.id).copied().expect("Every object was added");
+        for relationship in &object.relationships {
+            let tar = *log_internal.object_map.get_by_left(&relationship.object_id).ok_or_else(|| OcelError::UnknownObject { from: format!("Object {}", object.id), object: relationship.object_id.to_owned() })?;
+            log_internal.object_relations.entry(src).or_default().insert(tar);
+        }
+    }
+
+    // events get their ids in timestamp order so that the lifecycles are sorted, ties keep the order of the file
+    let mut events: Vec<(DateTime<Utc>, &EventSerde)> = vec![];
+    for event in &log.events {
+        events.push((parse_time(&event.time, timestamps, || format!("Event {}", event.id))?, event));
+    }
+    events.sort_by_key(|(timestamp, _)| *timestamp);
+
+    let mut activity_set: AHashSet<String> = AHashSet::new();
+    for (eid_nh, (timestamp, event)) in events.into_iter().enumerate() {
+        if log_internal.event_map.insert_no_overwrite(event.id.to_owned(), eid_nh).is_err() {
+            return Err(OcelError::DuplicateId(event.id.to_owned()));
+        }
+        let mut typed: AHashMap<String, OcelAttributeValue> = AHashMap::new();
+        for attribute in &event.attributes {
+            let attribute_type = event_attributes.get(&(event.activity.to_owned(), attribute.name.to_owned())).map(|declared| declared.as_str());
+            let value = OcelAttributeValue::parse(&attribute.value, attribute_type, timestamps).map_err(|message| OcelError::InvalidValue { at: format!("Event {} attribute {}", event.id, attribute.name), message })?;
+            typed.insert(attribute.name.to_owned(), value);
+        }
+        let vmap: AHashMap<String, Value> = typed.iter().map(|(name, value)| (name.to_owned(), value.clone().into())).collect();
+        if !typed.is_empty() {
+            log_internal.typed_attributes.events.insert(eid_nh, typed);
+        }
+        let mut omap: IntSet<usize> = IntSet::default();
+        for relationship in &event.relationships {
+            let oid = *log_internal.object_map.get_by_left(&relationship.object_id).ok_or_else(|| OcelError::UnknownObject { from: format!("Event {}", event.id), object: relationship.object_id.to_owned() })?;
+            // an object related twice under different qualifiers is in the lifecycle once
+            if omap.insert(oid) {
+                log_internal.objects.get_mut(&oid).expect("Every object was added").events.push(eid_nh);
+            }
+        }
+        activity_set.insert(event.activity.to_owned());
+        log_internal.events.insert(eid_nh, OcelEvent { activity: event.activity.to_owned(), timestamp, vmap, omap });
+    }
+
+    log_internal.activities.extend(activity_set);
+    Ok(log_internal)
+}
+
+fn parse_time<F: Fn() -> String>(value: &Value, timestamps: &TimestampParser, at: F) -> Result<DateTime<Utc>, OcelError> {
+    let raw = match value {
+        Value::String(value) => value.to_owned(),
+        other => other.to_string()
+    };
+    timestamps.parse(&raw).map_err(|error| OcelError::Timestamp { at: at(), error })
+}
@@ -0,0 +1,266 @@
+use crate::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+use crate::objects::ocel::importer::OcelError;
+use crate::objects::ocel::importer::timestamp::TimestampParser;
+use ahash::{AHashMap, AHashSet};
+use bimap::BiMap;
+use chrono::{DateTime, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::fs;
+use serde_json::{json, Value};
+
+// every trace is an object of this type
+pub(crate) const CASE_TYPE: &str = "case";
+
+const ATTRIBUTES: [&[u8]; 8] = [b"string", b"date", b"int", b"float", b"boolean", b"id", b"list", b"container"];
+
+type Attributes = Vec<(String, Value)>;
+
+// an element that is still open, the attributes of the elements below it are collected into it
+enum Frame {
+    Log(Attributes),
+    Trace(Attributes, Vec<Attributes>),
+    Event(Attributes),
+    // key, own value (null for lists and containers) and nested attributes
+    Attribute(String, Value, Attributes),
+    // the values element xes 2.0 wraps the items of a list in
+    Values(Attributes),
+    // extensions, globals, classifiers and anything unknown, nothing below them is read
+    Ignored
+}
+
+// an event collected from the traces sharing it
+struct XesEvent {
+    activity: String,
+    timestamp: DateTime<Utc>,
+    vmap: AHashMap<String, Value>,
+    objects: Vec<usize>
+}
+
+#[derive(Default)]
+struct XesLog {
+    events: Vec<XesEvent>,
+    // (activity, timestamp as written) -> position in events
+    event_positions: AHashMap<(String, String), usize>,
+    // (trace name, trace attributes)
+    objects: Vec<(String, AHashMap<String, Value>)>,
+    object_positions: AHashMap<String, usize>
+}
+
+pub(crate) fn import_xes(file_path: &str, timestamps: &TimestampParser) -> Result<Ocel, OcelError> {
+    import_xes_str(&fs::read_to_string(file_path)?, timestamps)
+}
+
+pub(crate) fn import_xes_str(s: &str, timestamps: &TimestampParser) -> Result<Ocel, OcelError> {
+    let mut reader = Reader::from_str(s);
+    reader.trim_text(true);
+
+    let mut frames: Vec<Frame> = vec![];
+    let mut global_log: Option<AHashMap<String, Value>> = None;
+    let mut xes = XesLog::default();
+
+    let mut buf: Vec<u8> = vec![];
+    loop {
+        let closed = match reader.read_event(&mut buf)? {
+            Event::Start(element) => {
+                let frame = open(&element, frames.last(), &reader)?;
+                frames.push(frame);
+                None
+            },
+            Event::Empty(element) => {
+                let frame = open(&element, frames.last(), &reader)?;
+                close(frame, &mut frames)
+            },
+            Event::End(_) => {
+                let frame = frames.pop().ok_or_else(|| OcelError::Parse("The xes closes an element that was never opened".to_owned()))?;
+                close(frame, &mut frames)
+            },
+            Event::Eof => break,
+            _ => None
+        };
+        buf.clear();
+
+        // a trace is added as soon as it is closed so that only one is held at a time
+        match closed {
+            Some(Frame::Trace(attributes, events)) => xes.add_trace(attributes, events, timestamps)?,
+            Some(Frame::Log(attributes)) => global_log = Some(attributes.into_iter().collect()),
+            _ => {}
+        }
+    }
+    let mut global_log = global_log.ok_or_else(|| OcelError::Missing("The xes has no log element".to_owned()))?;
+
+    global_log.insert("ocel:version".to_owned(), json!("1.0"));
+    global_log.insert("ocel:ordering".to_owned(), json!("timestamp"));
+    global_log.insert("ocel:object-types".to_owned(), json!([CASE_TYPE]));
+    let mut attribute_names: Vec<&String> = xes.events.iter().flat_map(|event| event.vmap.keys()).collect::<AHashSet<&String>>().into_iter().collect();
+    attribute_names.sort_unstable();
+    global_log.insert("ocel:attribute-names".to_owned(), json!(attribute_names));
+
+    let mut log = Ocel { global_log, global_event: AHashMap::new(), global_object: AHashMap::new(), events: EventColumns::default(), objects: IntMap::default(),
+                         object_map: BiMap::new(), event_map: BiMap::new(), activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (id, ovmap)) in xes.objects.into_iter().enumerate() {
+        log.object_map.insert(id, oid);
+        log.objects.insert(oid, OcelObject { obj_type: CASE_TYPE.to_owned(), ovmap, events: vec![] });
+    }
+
+    // xes events have no ids, they are named e1, e2, .. in the order they were first met
+    // and get their internal ids in timestamp order so that the lifecycles are sorted
+    let mut events: Vec<(usize, XesEvent)> = xes.events.into_iter().enumerate().collect();
+    events.sort_by_key(|(_, event)| event.timestamp);
+    let mut activity_set: AHashSet<String> = AHashSet::new();
+    for (eid, (position, event)) in events.into_iter().enumerate() {
+        let omap: IntSet<usize> = event.objects.into_iter().collect();
+        for oid in &omap {
+            log.objects.get_mut(oid).expect("Every trace was added").events.push(eid);
+        }
+        if activity_set.insert(event.activity.to_owned()) {
+            log.activities.push(event.activity.to_owned());
+        }
+        log.event_map.insert(format!("e{}", position + 1), eid);
+        log.events.insert(eid, OcelEvent { activity: event.activity, timestamp: event.timestamp, vmap: event.vmap, omap });
+    }
+    Ok(log)
+}
+
+impl XesLog {
+    fn add_trace(&mut self, attributes: Attributes, events: Vec<Attributes>, timestamps: &TimestampParser) -> Result<(), OcelError> {
+        let mut ovmap: AHashMap<String, Value> = attributes.into_iter().collect();
+        // traces without a name are named by their position in the file
+        let id = match ovmap.remove("concept:name") {
+            Some(Value::String(name)) => name,
+            Some(other) => other.to_string(),
+            None => format!("trace {}", self.objects.len() + 1)
+        };
+        let oid = match self.object_positions.get(&id) {
+            Some(oid) => {
+                // a repeated trace name is the same case, the attributes not set yet are added
+                let case = &mut self.objects[*oid].1;
+                for (key, value) in ovmap {
+                    case.entry(key).or_insert(value);
+                }
+                *oid
+            },
+            None => {
+                self.object_positions.insert(id.to_owned(), self.objects.len());
+                self.objects.push((id.to_owned(), ovmap));
+                self.objects.len() - 1
+            }
+        };
+
+        for (position, attributes) in events.into_iter().enumerate() {
+            let mut vmap: AHashMap<String, Value> = attributes.into_iter().collect();
+            let activity = match vmap.remove("concept:name") {
+                Some(Value::String(name)) => name,
+                Some(other) => other.to_string(),
+                None => return Err(OcelError::Missing(format!("Event {} of trace {} has no concept:name", position + 1, id)))
+            };
+            let raw_timestamp = match vmap.remove("time:timestamp") {
+                Some(Value::String(timestamp)) => timestamp,
+                Some(other) => other.to_string(),
+                None => return Err(OcelError::Missing(format!("Event {} of trace {} has no time:timestamp", position + 1, id)))
+            };
+
+            // traces sharing an event reference the same one
+            let key = (activity, raw_timestamp);
+            match self.event_positions.get(&key) {
+                Some(position) => {
+                    let event = &mut self.events[*position];
+                    event.objects.push(oid);
+                    for (key, value) in vmap {
+                        event.vmap.entry(key).or_insert(value);
+                    }
+                },
+                None => {
+                    let timestamp = timestamps.parse(&key.1).map_err(|error| OcelError::Timestamp { at: format!("Event {} of trace {}", position + 1, id), error })?;
+                    self.event_positions.insert(key.clone(), self.events.len());
+                    self.events.push(XesEvent { activity: key.0, timestamp, vmap, objects: vec![oid] });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn open(element: &BytesStart, parent: Option<&Frame>, reader: &Reader<&[u8]>) -> Result<Frame, OcelError> {
+    Ok(match (parent, element.name()) {
+        (Some(Frame::Ignored), _) => Frame::Ignored,
+        (None, b"log") => Frame::Log(vec![]),
+        (Some(Frame::Log(_)), b"trace") => Frame::Trace(vec![], vec![]),
+        (Some(Frame::Trace(..)), b"event") => Frame::Event(vec![]),
+        (Some(Frame::Attribute(..)), b"values") => Frame::Values(vec![]),
+        (Some(Frame::Log(_) | Frame::Trace(..) | Frame::Event(_) | Frame::Attribute(..) | Frame::Values(_)), name) if ATTRIBUTES.contains(&name) => {
+            let (key, value) = attribute(element, reader)?;
+            Frame::Attribute(key, value, vec![])
+        },
+        _ => Frame::Ignored
+    })
+}
+
+// hands a finished element to the one it is nested in, traces and the log are returned to the caller
+fn close(frame: Frame, frames: &mut [Frame]) -> Option<Frame> {
+    match frame {
+        Frame::Attribute(key, value, nested) => {
+            // lists and containers are flattened, other attributes keep their value and drop their meta attributes
+            let value = match value {
+                Value::Null => Value::String(flatten(&nested)),
+                value => value
+            };
+            if let Some(Frame::Log(attributes) | Frame::Trace(attributes, _) | Frame::Event(attributes) | Frame::Attribute(_, _, attributes) | Frame::Values(attributes)) = frames.last_mut() {
+                attributes.push((key, value));
+            }
+            None
+        },
+        Frame::Values(values) => {
+            if let Some(Frame::Attribute(_, _, attributes)) = frames.last_mut() {
+                attributes.extend(values);
+            }
+            None
+        },
+        Frame::Event(attributes) => {
+            if let Some(Frame::Trace(_, events)) = frames.last_mut() {
+                events.push(attributes);
+            }
+            None
+        },
+        Frame::Ignored => None,
+        frame => Some(frame)
+    }
+}
+
+// the key and typed value of an attribute element, values of the wrong type are kept as strings
+fn attribute(element: &BytesStart, reader: &Reader<&[u8]>) -> Result<(String, Value), OcelError> {
+    let mut key = None;
+    let mut value = None;
+    for attr in element.attributes() {
+        let attr = attr?;
+        match attr.key {
+            b"key" => key = Some(attr.unescape_and_decode_value(reader)?),
+            b"value" => value = Some(attr.unescape_and_decode_value(reader)?),
+            _ => {}
+        }
+    }
+    let key = key.ok_or_else(|| OcelError::Missing(format!("A {} attribute of the xes has no key", String::from_utf8_lossy(element.name()))))?;
+    let value = match (element.name(), value) {
+        (b"list" | b"container", _) | (_, None) => Value::Null,
+        (b"int", Some(value)) => value.parse::<i64>().map(Value::from).unwrap_or(Value::String(value)),
+        (b"float", Some(value)) => match value.parse::<f64>().ok().and_then(serde_json::Number::from_f64) {
+            Some(number) => Value::Number(number),
+            None => Value::String(value)
+        },
+        (b"boolean", Some(value)) => value.parse::<bool>().map(Value::Bool).unwrap_or(Value::String(value)),
+        (_, Some(value)) => Value::String(value)
+    };
+    Ok((key, value))
+}
+
+// key=value pairs separated by semicolons in brackets, nested lists are bracketed the same way
+fn flatten(attributes: &[(String, Value)]) -> String {
+    let pairs = attributes.iter()
+                         .map(|(key, value)| match value {
+                             Value::String(value) => format!("{}={}", key, value),
+                             other => format!("{}={}", key, other)
+                         })
+                         .collect::<Vec<String>>();
+    format!("[{}]", pairs.join(";"))
+}
@@ -0,0 +1,85 @@
+use std::error::Error;
+use ahash::{AHashMap, AHashSet};
+use chrono::{DateTime, Utc};
+use nohash_hasher::IntSet;
+use serde_json::Value;
+
+use crate::objects::ocel::{relations_among, Ocel, OcelEvent};
+
+impl Ocel {
+    // object lifecycles and activities are derived from the events, mutating apis call this before returning
+    pub fn rebuild_derived_indices(&mut self) {
+        for obj in self.objects.values_mut() {
+            obj.events.clear();
+        }
+        for (eid, event) in &self.events {
+            for oid in &event.omap {
+                if let Some(obj) = self.objects.get_mut(oid) {
+                    obj.events.push(*eid);
+                }
+            }
+        }
+        let events = &self.events;
+        for obj in self.objects.values_mut() {
+            obj.events.sort_unstable_by_key(|eid| (events.event(eid).timestamp, *eid));
+        }
+
+        // known activities keep their position, new ones are appended sorted
+        let used: AHashSet<&str> = self.events.values().map(|event| event.activity).collect();
+        let mut activities: Vec<String> = self.activities.iter().filter(|activity| used.contains(activity.as_str())).cloned().collect();
+        let known: AHashSet<String> = activities.iter().cloned().collect();
+        let mut added: Vec<String> = used.into_iter().filter(|activity| !known.contains(*activity)).map(|activity| activity.to_owned()).collect();
+        added.sort();
+        activities.extend(added);
+        self.activities = activities;
+
+        self.object_relations = relations_among(&self.object_relations, &self.objects);
+        self.typed_attributes = self.typed_attributes.among(&self.events, &self.objects);
+    }
+
+    pub fn is_time_ordered(&self) -> bool {
+        self.objects.values().all(|obj| obj.events.windows(2).all(|pair| self.events.event(&pair[0]).timestamp <= self.events.event(&pair[1]).timestamp))
+    }
+
+    // system events that reference no object, in timestamp order
+    pub fn events_without_objects(&self) -> Vec<usize> {
+        let mut eids: Vec<usize> = self.events.iter()
+                                              .filter(|(_, event)| event.omap.is_empty())
+                                              .map(|(eid, _)| *eid)
+                                              .collect();
+        eids.sort_unstable_by_key(|eid| (self.events.event(eid).timestamp, *eid));
+        eids
+    }
+
+    // returns the ids of the dropped events
+    pub fn drop_events_without_objects(&mut self) -> Vec<String> {
+        let mut dropped = vec![];
+        for eid in self.events_without_objects() {
+            self.events.remove(&eid);
+            if let Some((id, _)) = self.event_map.remove_by_right(&eid) {
+                dropped.push(id);
+            }
+        }
+        self.rebuild_derived_indices();
+        dropped
+    }
+
+    pub fn insert_event(&mut self, id: &str, activity: &str, timestamp: DateTime<Utc>, objects: &[&str], vmap: AHashMap<String, Value>) -> Result<usize, Box<dyn Error>> {
+        if self.event_map.contains_left(id) {
+            return Err(format!("event {} already exists", id).into());
+        }
+        let mut omap: IntSet<usize> = IntSet::default();
+        for object in objects {
+            match self.object_map.get_by_left(*object) {
+                Some(oid) => omap.insert(*oid),
+                None => return Err(format!("event {} references the unknown object {}", id, object).into())
+            };
+        }
+
+        let eid = self.events.keys().max().map_or(0, |eid| eid + 1);
+        self.events.insert(eid, OcelEvent { activity: activity.to_owned(), timestamp, vmap, omap });
+        self.event_map.insert(id.to_owned(), eid);
+        self.rebuild_derived_indices();
+        Ok(eid)
+    }
+}
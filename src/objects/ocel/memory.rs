@@ -0,0 +1,62 @@
+use std::mem::size_of;
+use ahash::AHashMap;
+use serde_json::Value;
+
+use crate::objects::ocel::{Ocel, OcelObject};
+
+// estimated bytes per part of the log, element counts times struct sizes plus the string contents.
+// hash table overhead and allocator slack are not included, so the real footprint is somewhat larger
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryBreakdown {
+    // event rows and the interned activity strings
+    pub events: usize,
+    // object structs and their type strings
+    pub objects: usize,
+    // object ids referenced by the events
+    pub omaps: usize,
+    // event ids of the object lifecycles
+    pub lifecycles: usize,
+    // event attribute columns, ovmap and global attributes with their values
+    pub attributes: usize,
+    // both directions of the object and event id maps
+    pub id_maps: usize,
+    pub activities: usize
+}
+
+impl MemoryBreakdown {
+    pub fn total(&self) -> usize {
+        self.events + self.objects + self.omaps + self.lifecycles + self.attributes + self.id_maps + self.activities
+    }
+}
+
+impl Ocel {
+    pub fn memory_breakdown(&self) -> MemoryBreakdown {
+        let mut breakdown = MemoryBreakdown::default();
+        (breakdown.events, breakdown.omaps, breakdown.attributes) = self.events.memory_size();
+        for obj in self.objects.values() {
+            breakdown.objects += size_of::<usize>() + size_of::<OcelObject>() + obj.obj_type.len();
+            breakdown.lifecycles += obj.events.len() * size_of::<usize>();
+            breakdown.attributes += attributes_size(&obj.ovmap);
+        }
+        breakdown.attributes += attributes_size(&self.global_log) + attributes_size(&self.global_event) + attributes_size(&self.global_object);
+        breakdown.id_maps = self.object_map.iter().chain(self.event_map.iter())
+                                               .map(|(label, _)| 2 * (size_of::<String>() + size_of::<usize>()) + label.len())
+                                               .sum();
+        breakdown.activities = self.activities.iter().map(|activity| size_of::<String>() + activity.len()).sum();
+        breakdown
+    }
+}
+
+pub(crate) fn attributes_size(attributes: &AHashMap<String, Value>) -> usize {
+    attributes.iter().map(|(key, value)| size_of::<(String, Value)>() + key.len() + value_heap_size(value)).sum()
+}
+
+// bytes a value owns beyond its own struct
+pub(crate) fn value_heap_size(value: &Value) -> usize {
+    match value {
+        Value::String(text) => text.len(),
+        Value::Array(items) => items.iter().map(|item| size_of::<Value>() + value_heap_size(item)).sum(),
+        Value::Object(fields) => fields.iter().map(|(key, field)| size_of::<(String, Value)>() + key.len() + value_heap_size(field)).sum(),
+        _ => 0
+    }
+}
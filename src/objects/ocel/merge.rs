@@ -0,0 +1,225 @@
+use ahash::{AHashMap, AHashSet};
+use bimap::BiMap;
+use nohash_hasher::{IntMap, IntSet};
+
+use crate::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes, value_key};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObjectMatcher {
+    ExactAttribute(String),
+    NormalizedId
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeConflict {
+    KeepFirst,
+    KeepSecond
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MergeConfig {
+    pub threshold: f64,
+    pub conflict: AttributeConflict
+}
+
+impl Default for MergeConfig {
+    fn default() -> Self {
+        Self { threshold: 0.8, conflict: AttributeConflict::KeepFirst }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MergeReport {
+    pub applied: Vec<(String, String, f64)>,
+    pub rejected: Vec<(String, String, f64)>
+}
+
+pub fn link_objects(log_a: &Ocel, log_b: &Ocel, matcher: &ObjectMatcher) -> Vec<(usize, usize, f64)> {
+    let mut links: Vec<(usize, usize, f64)> = vec![];
+    match matcher {
+        ObjectMatcher::ExactAttribute(attr) => {
+            let mut index: AHashMap<String, Vec<usize>> = AHashMap::new();
+            for (oid, obj) in &log_b.objects {
+                if let Some(value) = obj.ovmap.get(attr) {
+                    index.entry(value_key(value)).or_default().push(*oid);
+                }
+            }
+            for (oid, obj) in &log_a.objects {
+                if let Some(candidates) = obj.ovmap.get(attr).and_then(|value| index.get(&value_key(value))) {
+                    // ambiguous values split the confidence between all candidates
+                    let score = 1.0 / candidates.len() as f64;
+                    links.extend(candidates.iter().map(|oid_b| (*oid, *oid_b, score)));
+                }
+            }
+        },
+        ObjectMatcher::NormalizedId => {
+            let tokens_b: IntMap<usize, AHashSet<String>> = log_b.object_map.iter()
+                                                                 .map(|(id, oid)| (*oid, id_tokens(id)))
+                                                                 .collect();
+            let mut index: AHashMap<&str, Vec<usize>> = AHashMap::new();
+            for (oid, tokens) in &tokens_b {
+                for token in tokens {
+                    index.entry(token.as_str()).or_default().push(*oid);
+                }
+            }
+
+            for (id, oid) in &log_a.object_map {
+                let tokens_a = id_tokens(id);
+                let candidates: IntSet<usize> = tokens_a.iter()
+                                                        .filter_map(|token| index.get(token.as_str()))
+                                                        .flatten()
+                                                        .copied()
+                                                        .collect();
+                // best candidate by jaccard similarity of the id tokens, ties broken by id
+                let best = candidates.iter()
+                                     .map(|oid_b| {
+                                        let tokens = &tokens_b[oid_b];
+                                        let shared = tokens_a.intersection(tokens).count() as f64;
+                                        (*oid_b, shared / tokens_a.union(tokens).count() as f64)
+                                     })
+                                     .max_by(|a, b| a.1.partial_cmp(&b.1).expect("Scores are never NaN")
+                                                       .then_with(|| log_b.object_map.get_by_right(&b.0).cmp(&log_b.object_map.get_by_right(&a.0))));
+                if let Some((oid_b, score)) = best {
+                    links.push((*oid, oid_b, score));
+                }
+            }
+        }
+    }
+    links.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+    links
+}
+
+pub fn merge_with_links(log_a: &Ocel, log_b: &Ocel, links: &[(usize, usize, f64)], config: &MergeConfig) -> (Ocel, MergeReport) {
+    let mut report = MergeReport::default();
+    let mut b_to_a: IntMap<usize, usize> = IntMap::default();
+    let mut linked_a: IntSet<usize> = IntSet::default();
+    for (oid_a, oid_b, score) in links {
+        let ids = (log_a.object_map.get_by_right(oid_a).expect("Link points to log a").to_owned(),
+                   log_b.object_map.get_by_right(oid_b).expect("Link points to log b").to_owned());
+        if *score >= config.threshold && !b_to_a.contains_key(oid_b) && !linked_a.contains(oid_a) {
+            b_to_a.insert(*oid_b, *oid_a);
+            linked_a.insert(*oid_a);
+            report.applied.push((ids.0, ids.1, *score));
+        } else {
+            report.rejected.push((ids.0, ids.1, *score));
+        }
+    }
+
+    let mut merged = Ocel { global_log: log_a.global_log.to_owned(),
+                            global_event: log_a.global_event.to_owned(),
+                            global_object: log_a.global_object.to_owned(),
+                            events: EventColumns::default(),
+                            objects: IntMap::default(),
+                            object_map: BiMap::new(),
+                            event_map: BiMap::new(),
+                            activities: vec![],
+                            object_relations: IntMap::default(),
+                            typed_attributes: TypedAttributes::default() };
+
+    // objects of log a keep their ids, linked objects of log b are folded into them
+    let mut oid_nh: usize = usize::MIN;
+    for (id, oid) in &log_a.object_map {
+        let obj = &log_a.objects[oid];
+        merged.object_map.insert(id.to_owned(), oid_nh);
+        merged.objects.insert(oid_nh, OcelObject { obj_type: obj.obj_type.to_owned(), ovmap: obj.ovmap.to_owned(), events: vec![] });
+        if let Some(typed) = log_a.typed_attributes.objects.get(oid) {
+            merged.typed_attributes.objects.insert(oid_nh, typed.to_owned());
+        }
+        oid_nh += 1;
+    }
+    for (id, oid) in &log_b.object_map {
+        let obj = &log_b.objects[oid];
+        let target_id = match b_to_a.get(oid) {
+            Some(oid_a) => log_a.object_map.get_by_right(oid_a).expect("This can't fail"),
+            None => id
+        };
+        match merged.object_map.get_by_left(target_id) {
+            Some(merged_oid) => {
+                let merged_obj = merged.objects.get_mut(merged_oid).expect("This can't fail");
+                let typed = log_b.typed_attributes.objects.get(oid);
+                for (attr, value) in &obj.ovmap {
+                    if config.conflict == AttributeConflict::KeepSecond || !merged_obj.ovmap.contains_key(attr) {
+                        merged_obj.ovmap.insert(attr.to_owned(), value.to_owned());
+                        // the typed value follows the json value it belongs to
+                        let merged_typed = merged.typed_attributes.objects.entry(*merged_oid).or_default();
+                        match typed.and_then(|typed| typed.get(attr)) {
+                            Some(typed_value) => {merged_typed.insert(attr.to_owned(), typed_value.to_owned());},
+                            None => {merged_typed.remove(attr);}
+                        }
+                    }
+                }
+            },
+            None => {
+                merged.object_map.insert(target_id.to_owned(), oid_nh);
+                merged.objects.insert(oid_nh, OcelObject { obj_type: obj.obj_type.to_owned(), ovmap: obj.ovmap.to_owned(), events: vec![] });
+                if let Some(typed) = log_b.typed_attributes.objects.get(oid) {
+                    merged.typed_attributes.objects.insert(oid_nh, typed.to_owned());
+                }
+                oid_nh += 1;
+            }
+        }
+    }
+
+    // events of both logs in timestamp order so that object lifecycles stay sorted
+    let mut events: Vec<(&String, &usize, &Ocel, bool)> = log_a.event_map.iter()
+                                                               .map(|(id, eid)| (id, eid, log_a, false))
+                                                               .chain(log_b.event_map.iter().map(|(id, eid)| (id, eid, log_b, true)))
+                                                               .collect();
+    events.sort_by_key(|(_, eid, log, from_b)| (log.events.event(eid).timestamp, *from_b, **eid));
+
+    let mut activity_set: AHashSet<String> = AHashSet::new();
+    let mut eid_nh: usize = usize::MIN;
+    for (id, eid, log, from_b) in events {
+        let ev = &log.events.event(eid);
+        // identical event ids of both logs describe the same event
+        if merged.event_map.contains_left(id) {
+            continue;
+        }
+        let mut omap: IntSet<usize> = IntSet::default();
+        for oid in &ev.omap {
+            let target_id = match (from_b, b_to_a.get(oid)) {
+                (true, Some(oid_a)) => log_a.object_map.get_by_right(oid_a).expect("This can't fail"),
+                _ => log.object_map.get_by_right(oid).expect("This can't fail")
+            };
+            let merged_oid = *merged.object_map.get_by_left(target_id).expect("All objects were added before");
+            omap.insert(merged_oid);
+            merged.objects.get_mut(&merged_oid).expect("This can't fail").events.push(eid_nh);
+        }
+        activity_set.insert(ev.activity.to_owned());
+        merged.event_map.insert(id.to_owned(), eid_nh);
+        merged.events.insert(eid_nh, OcelEvent { activity: ev.activity.to_owned(), timestamp: ev.timestamp, vmap: ev.vmap.to_map(), omap });
+        if let Some(typed) = log.typed_attributes.events.get(eid) {
+            merged.typed_attributes.events.insert(eid_nh, typed.to_owned());
+        }
+        eid_nh += 1;
+    }
+    merged.activities.extend(activity_set);
+
+    // the relations of both logs on the merged objects
+    for (log, from_b) in [(log_a, false), (log_b, true)] {
+        let merged_oid = |oid: &usize| {
+            let target_id = match (from_b, b_to_a.get(oid)) {
+                (true, Some(oid_a)) => log_a.object_map.get_by_right(oid_a),
+                _ => log.object_map.get_by_right(oid)
+            };
+            target_id.and_then(|id| merged.object_map.get_by_left(id)).copied()
+        };
+        for (src, targets) in &log.object_relations {
+            if let Some(src) = merged_oid(src) {
+                let targets: Vec<usize> = targets.iter().filter_map(merged_oid).collect();
+                merged.object_relations.entry(src).or_default().extend(targets);
+            }
+        }
+    }
+    merged.object_relations.retain(|_, targets| !targets.is_empty());
+    merged.typed_attributes.objects.retain(|_, values| !values.is_empty());
+
+    (merged, report)
+}
+
+fn id_tokens(id: &str) -> AHashSet<String> {
+    id.split(|c: char| !c.is_alphanumeric())
+      .filter(|token| !token.is_empty())
+      .map(|token| token.to_lowercase())
+      .collect()
+}
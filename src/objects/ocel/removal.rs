@@ -0,0 +1,107 @@
+use std::error::Error;
+use nohash_hasher::IntSet;
+use serde_json::Value;
+
+use crate::objects::ocdg::Ocdg;
+use crate::objects::ocel::Ocel;
+
+// attribute set on tombstoned objects and on the events that only referenced tombstoned objects
+pub const REMOVED_ATTRIBUTE: &str = "removed";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemovalMode {
+    // remove the objects from the log and drop the events left without objects
+    Cascade,
+    // keep objects and events, flag them with the removed attribute
+    Tombstone,
+    // like cascade but refuse when an event would be left without objects
+    Strict
+}
+
+// what removing a set of objects touches, all lists are sorted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemovalPlan {
+    // the known objects among the requested ones
+    pub objects: Vec<usize>,
+    // events referencing only removed objects
+    pub emptied_events: Vec<usize>,
+    // events that keep at least one object
+    pub shrunk_events: Vec<usize>,
+    // objects whose graph neighbours are all removed, they stay in the log but become isolated
+    pub dependents: Vec<usize>
+}
+
+impl RemovalPlan {
+    pub fn is_strict_safe(&self) -> bool {
+        self.emptied_events.is_empty()
+    }
+}
+
+impl Ocel {
+    pub fn plan_object_removal(&self, oids: &[usize], ocdg: &Ocdg) -> RemovalPlan {
+        let removed: IntSet<usize> = oids.iter().copied().filter(|oid| self.objects.contains_key(oid)).collect();
+        let mut plan = RemovalPlan { objects: removed.iter().copied().collect(), ..Default::default() };
+        plan.objects.sort_unstable();
+
+        let mut touched: IntSet<usize> = IntSet::default();
+        for oid in &plan.objects {
+            touched.extend(&self.objects[oid].events);
+        }
+        for eid in touched {
+            if self.events.event(&eid).omap.iter().all(|oid| removed.contains(oid)) {
+                plan.emptied_events.push(eid);
+            } else {
+                plan.shrunk_events.push(eid);
+            }
+        }
+        plan.emptied_events.sort_unstable();
+        plan.shrunk_events.sort_unstable();
+
+        for (oid, node) in &ocdg.inodes {
+            if removed.contains(oid) {
+                continue;
+            }
+            let mut neighbours = ocdg.net.neighbors_undirected(*node).peekable();
+            if neighbours.peek().is_some() && neighbours.all(|neighbour| removed.contains(&ocdg.net[neighbour])) {
+                plan.dependents.push(*oid);
+            }
+        }
+        plan.dependents.sort_unstable();
+        plan
+    }
+
+    // returns the ids of the dropped events, tombstoning drops none
+    pub fn apply_removal(&mut self, plan: &RemovalPlan, mode: RemovalMode) -> Result<Vec<String>, Box<dyn Error>> {
+        if mode == RemovalMode::Strict && !plan.is_strict_safe() {
+            let events: Vec<&str> = plan.emptied_events.iter().filter_map(|eid| self.event_map.get_by_right(eid)).map(|id| id.as_str()).collect();
+            return Err(format!("removing the objects would leave the events {} without objects", events.join(", ")).into());
+        }
+
+        if mode == RemovalMode::Tombstone {
+            for oid in &plan.objects {
+                if let Some(obj) = self.objects.get_mut(oid) {
+                    obj.ovmap.insert(REMOVED_ATTRIBUTE.to_owned(), Value::Bool(true));
+                }
+            }
+            for eid in &plan.emptied_events {
+                self.events.set_attribute(eid, REMOVED_ATTRIBUTE, Value::Bool(true));
+            }
+            return Ok(vec![]);
+        }
+
+        for oid in &plan.objects {
+            self.objects.remove(oid);
+            self.object_map.remove_by_right(oid);
+        }
+        self.events.retain_objects(|oid| !plan.objects.contains(oid));
+        let mut dropped = vec![];
+        for eid in &plan.emptied_events {
+            self.events.remove(eid);
+            if let Some((id, _)) = self.event_map.remove_by_right(eid) {
+                dropped.push(id);
+            }
+        }
+        self.rebuild_derived_indices();
+        Ok(dropped)
+    }
+}
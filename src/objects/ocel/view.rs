@@ -0,0 +1,90 @@
+use std::collections::{BTreeSet, HashMap};
+use nohash_hasher::{IntMap, IntSet};
+
+use crate::objects::ocel::{relations_among, value_key, EventColumns, Ocel, OcelEvent, OcelObject};
+
+// a borrowed slice of a log, only the ids are stored until the view is turned into a log of its own
+#[derive(Debug, Clone)]
+pub struct OcelView<'a> {
+    pub log: &'a Ocel,
+    pub objects: IntSet<usize>,
+    pub events: IntSet<usize>
+}
+
+impl<'a> OcelView<'a> {
+    // the objects, their co-objects (sharing an event with one of them) and all events of both.
+    // objects further away are left out, so shared resources do not pull in other executions
+    pub fn around(log: &'a Ocel, seeds: &IntSet<usize>) -> Self {
+        Self::around_where(log, seeds, |_| true)
+    }
+
+    fn around_where<F>(log: &'a Ocel, seeds: &IntSet<usize>, co_object: F) -> Self where F: Fn(&usize) -> bool {
+        let mut objects: IntSet<usize> = IntSet::default();
+        for oid in seeds.iter().filter(|oid| log.objects.contains_key(oid)) {
+            objects.insert(*oid);
+            for eid in &log.objects[oid].events {
+                objects.extend(log.events.event(eid).omap.iter().filter(|co| seeds.contains(co) || co_object(co)));
+            }
+        }
+        let events: IntSet<usize> = objects.iter().flat_map(|oid| log.objects[oid].events.iter().copied()).collect();
+        OcelView { log, objects, events }
+    }
+
+    pub fn contains_object(&self, oid: &usize) -> bool {
+        self.objects.contains(oid)
+    }
+
+    pub fn contains_event(&self, eid: &usize) -> bool {
+        self.events.contains(eid)
+    }
+
+    // event omaps only keep the objects of the view
+    pub fn to_ocel(&self) -> Ocel {
+        let events: EventColumns = self.events.iter()
+                                           .map(|eid| {
+                                               let event = self.log.events.event(eid);
+                                               let omap = event.omap.iter().copied().filter(|oid| self.objects.contains(oid)).collect();
+                                               (*eid, OcelEvent { activity: event.activity.to_owned(), timestamp: event.timestamp, vmap: event.vmap.to_map(), omap })
+                                           })
+                                           .collect();
+        let objects: IntMap<usize, OcelObject> = self.objects.iter()
+                                                     .map(|oid| {
+                                                         let obj = &self.log.objects[oid];
+                                                         (*oid, OcelObject { obj_type: obj.obj_type.to_owned(), ovmap: obj.ovmap.clone(), events: obj.events.iter().copied().filter(|eid| events.contains_key(eid)).collect() })
+                                                     })
+                                                     .collect();
+        let activities: BTreeSet<String> = events.values().map(|event| event.activity.to_owned()).collect();
+
+        Ocel { global_log: self.log.global_log.clone(),
+               global_event: self.log.global_event.clone(),
+               global_object: self.log.global_object.clone(),
+               object_map: self.log.object_map.iter().filter(|(_, oid)| objects.contains_key(oid)).map(|(label, oid)| (label.to_owned(), *oid)).collect(),
+               event_map: self.log.event_map.iter().filter(|(_, eid)| events.contains_key(eid)).map(|(label, eid)| (label.to_owned(), *eid)).collect(),
+               object_relations: relations_among(&self.log.object_relations, &objects),
+               typed_attributes: self.log.typed_attributes.among(&events, &objects),
+               events,
+               objects,
+               activities: activities.into_iter().collect() }
+    }
+}
+
+impl Ocel {
+    // one view per value of the attribute on the objects of the type, keyed by the value as text.
+    // the objects of the type are disjoint across the views, their co-objects may appear in several.
+    // objects without the attribute are in no view
+    pub fn partition_by_attribute(&self, object_type: &str, attribute: &str) -> HashMap<String, OcelView<'_>> {
+        let mut seeds: HashMap<String, IntSet<usize>> = HashMap::new();
+        for (oid, obj) in self.objects.iter().filter(|(_, obj)| obj.obj_type == object_type) {
+            if let Some(value) = obj.ovmap.get(attribute) {
+                seeds.entry(value_key(value)).or_default().insert(*oid);
+            }
+        }
+        seeds.into_iter()
+             .map(|(value, seeds)| {
+                 // objects of the type with another value stay out even when they share an event
+                 let view = OcelView::around_where(self, &seeds, |oid| self.objects.get(oid).is_some_and(|obj| obj.obj_type != object_type));
+                 (value, view)
+             })
+             .collect()
+    }
+}
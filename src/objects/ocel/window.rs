@@ -0,0 +1,42 @@
+use std::collections::BTreeSet;
+use chrono::{DateTime, Utc};
+use nohash_hasher::IntMap;
+
+use crate::objects::ocel::{relations_among, Ocel, OcelObject};
+
+impl Ocel {
+    // the log as it was known before the cutoff, objects without an earlier event are dropped
+    pub fn prefix(&self, cutoff: &DateTime<Utc>) -> Ocel {
+        self.retain_time(|timestamp| timestamp < cutoff)
+    }
+
+    // events from start (inclusive) to end (exclusive), objects without an event in between are dropped
+    pub fn between(&self, start: &DateTime<Utc>, end: &DateTime<Utc>) -> Ocel {
+        self.retain_time(|timestamp| start <= timestamp && timestamp < end)
+    }
+
+    fn retain_time<F>(&self, keep: F) -> Ocel where F: Fn(&DateTime<Utc>) -> bool {
+        let events = self.events.filtered(|_, event| keep(&event.timestamp));
+        let objects: IntMap<usize, OcelObject> = self.objects.iter()
+                                                     .filter_map(|(oid, obj)| {
+                                                         let prefix_events: Vec<usize> = obj.events.iter().copied().filter(|eid| events.contains_key(eid)).collect();
+                                                         if prefix_events.is_empty() {
+                                                             return None;
+                                                         }
+                                                         Some((*oid, OcelObject { obj_type: obj.obj_type.to_owned(), ovmap: obj.ovmap.clone(), events: prefix_events }))
+                                                     })
+                                                     .collect();
+        let activities: BTreeSet<String> = events.values().map(|event| event.activity.to_owned()).collect();
+
+        Ocel { global_log: self.global_log.clone(),
+               global_event: self.global_event.clone(),
+               global_object: self.global_object.clone(),
+               object_map: self.object_map.iter().filter(|(_, oid)| objects.contains_key(oid)).map(|(label, oid)| (label.to_owned(), *oid)).collect(),
+               event_map: self.event_map.iter().filter(|(_, eid)| events.contains_key(eid)).map(|(label, eid)| (label.to_owned(), *eid)).collect(),
+               object_relations: relations_among(&self.object_relations, &objects),
+               typed_attributes: self.typed_attributes.among(&events, &objects),
+               events,
+               objects,
+               activities: activities.into_iter().collect() }
+    }
+}
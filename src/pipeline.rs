@@ -0,0 +1,346 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use strum::IntoEnumIterator;
+
+use crate::algo::analysis::cohorts;
+use crate::objects::exporter::Exporter;
+use crate::objects::exporter::metrics::OcelMetrics;
+use crate::objects::ocdg::exporter::{export_ocdg, export_ocdg_graphml, ExportOptions};
+use crate::objects::ocdg::estimate::{estimate as estimate_generation, GenerationEstimate};
+use crate::objects::ocdg::metrics::{motifs, MotifConfig};
+use crate::objects::ocdg::presets::{Preset, RelationSet};
+use crate::objects::ocdg::{generate_ocdg_config, Evidence, GenerationConfig, Ocdg, RelationScope, Relations};
+use crate::objects::ocel::Ocel;
+use crate::objects::ocel::exporter::export_ocel;
+use crate::objects::ocel::importer::{import_ocel_with_report, ImportConfig};
+
+// one run from input to artifacts, read from json or ron. relative paths are resolved against the config file
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineConfig {
+    pub input: String,
+    #[serde(default)]
+    pub filters: Vec<FilterStage>,
+    pub generation: Option<GenerationStage>,
+    #[serde(default)]
+    pub analyses: Vec<AnalysisStage>,
+    #[serde(default)]
+    pub exports: Vec<ExportStage>
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum FilterStage {
+    Between { start: DateTime<Utc>, end: DateTime<Utc> },
+    Prefix { cutoff: DateTime<Utc> },
+    DropEventsWithoutObjects
+}
+
+// names as printed by the enums, relations may also name a preset. all relations when none are given
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GenerationStage {
+    #[serde(default)]
+    pub relations: Vec<String>,
+    pub evidence: Option<String>,
+    pub scope: Option<String>,
+    pub inheritance_max_gap_seconds: Option<i64>,
+    pub consumes_max_gap_seconds: Option<i64>
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum AnalysisStage {
+    RelationCounts,
+    Cohorts,
+    Motifs { relations: Option<Vec<String>> },
+    UncoveredEvents { csv: Option<String> }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum ExportStage {
+    Ocel { path: String },
+    Gexf { path: String },
+    Graphml { path: String, #[serde(default)] per_relation_keys: bool },
+    Metrics { path: String },
+    // the results of all analyses as one json document
+    Analyses { path: String }
+}
+
+#[derive(Debug, Clone)]
+pub struct StageReport {
+    pub stage: String,
+    pub duration: Duration,
+    pub summary: String
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PipelineReport {
+    pub stages: Vec<StageReport>,
+    pub analyses: BTreeMap<String, Value>,
+    pub artifacts: Vec<PathBuf>
+}
+
+impl fmt::Display for PipelineReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for stage in &self.stages {
+            writeln!(f, "{:<28} {:>8.1} ms  {}", stage.stage, stage.duration.as_secs_f64() * 1000.0, stage.summary)?;
+        }
+        for artifact in &self.artifacts {
+            writeln!(f, "wrote {}", artifact.display())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct PipelineError {
+    pub stage: String,
+    pub source: Box<dyn Error>
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Pipeline stage {} failed: {}", self.stage, self.source)
+    }
+}
+
+impl Error for PipelineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+impl PipelineConfig {
+    pub fn from_file(config_path: &str) -> Result<PipelineConfig, Box<dyn Error>> {
+        let content = fs::read_to_string(config_path)?;
+        if config_path.ends_with(".ron") {
+            Ok(ron::from_str(&content)?)
+        } else {
+            Ok(serde_json::from_str(&content)?)
+        }
+    }
+
+    // replaces the relations of the generation stage, adding the stage when the config has none
+    pub fn with_preset(mut self, preset: &str) -> Result<PipelineConfig, Box<dyn Error>> {
+        let preset = Preset::from_str(preset).map_err(|_| format!("unknown preset {}", preset))?;
+        let mut generation = self.generation.unwrap_or_default();
+        generation.relations = vec![preset.to_string()];
+        self.generation = Some(generation);
+        Ok(self)
+    }
+}
+
+pub fn run(config_path: &str) -> Result<PipelineReport, PipelineError> {
+    let (config, base) = load(config_path)?;
+    run_config(&config, base)
+}
+
+fn load(config_path: &str) -> Result<(PipelineConfig, &Path), PipelineError> {
+    load_with_preset(config_path, None)
+}
+
+// the config and the directory its relative paths are resolved against, the preset replaces the generated relations
+pub fn load_with_preset<'a>(config_path: &'a str, preset: Option<&str>) -> Result<(PipelineConfig, &'a Path), PipelineError> {
+    let config = PipelineConfig::from_file(config_path)
+                                .and_then(|config| match preset {
+                                    Some(preset) => config.with_preset(preset),
+                                    None => Ok(config)
+                                })
+                                .map_err(|source| PipelineError { stage: "config".to_owned(), source })?;
+    Ok((config, Path::new(config_path).parent().unwrap_or_else(|| Path::new(""))))
+}
+
+// runs the import and filter stages and estimates the generation stage instead of running it
+pub fn estimate(config_path: &str) -> Result<(PipelineReport, GenerationEstimate), PipelineError> {
+    let (config, base) = load(config_path)?;
+    estimate_config(&config, base)
+}
+
+pub fn estimate_config(config: &PipelineConfig, base: &Path) -> Result<(PipelineReport, GenerationEstimate), PipelineError> {
+    let mut runner = Runner { base, report: PipelineReport::default() };
+    let log = runner.filtered_log(config)?;
+
+    let generation = config.generation.clone().unwrap_or_default();
+    let estimate = runner.stage("estimate generation", || {
+        let estimate = estimate_generation(&log, &generation.config()?)?;
+        let summary = format!("{} nodes, {:.0} edges expected", estimate.nodes, estimate.graph_edges.expected);
+        Ok((estimate, summary))
+    })?;
+    Ok((runner.report, estimate))
+}
+
+// stages run in the order import, filters, generation, analyses, exports
+pub fn run_config(config: &PipelineConfig, base: &Path) -> Result<PipelineReport, PipelineError> {
+    let mut runner = Runner { base, report: PipelineReport::default() };
+    let log = runner.filtered_log(config)?;
+
+    let ocdg = match &config.generation {
+        Some(generation) => Some(runner.stage("generation", || {
+            let ocdg = generate_ocdg_config(&log, &generation.config()?)?;
+            let summary = format!("{} nodes, {} edges", ocdg.net.node_count(), ocdg.net.edge_count());
+            Ok((ocdg, summary))
+        })?),
+        None => None
+    };
+
+    for analysis in &config.analyses {
+        let name = analysis.name();
+        let result = runner.stage(&format!("analysis {}", name), || {
+            let ocdg = ocdg.as_ref().ok_or("the analysis needs a generation stage")?;
+            let (value, artifact) = analysis.execute(&log, ocdg, base)?;
+            let summary = value.to_string();
+            Ok(((value, artifact), summary))
+        })?;
+        runner.report.analyses.insert(name.to_owned(), result.0);
+        runner.report.artifacts.extend(result.1);
+    }
+
+    for export in &config.exports {
+        let (name, path) = match export {
+            ExportStage::Ocel { path } => ("ocel", path),
+            ExportStage::Gexf { path } => ("gexf", path),
+            ExportStage::Graphml { path, .. } => ("graphml", path),
+            ExportStage::Metrics { path } => ("metrics", path),
+            ExportStage::Analyses { path } => ("analyses", path)
+        };
+        let path = runner.resolve(path);
+        let analyses = runner.report.analyses.clone();
+        runner.stage(&format!("export {}", name), || {
+            let file_path = path.to_string_lossy();
+            let needs_ocdg = || ocdg.as_ref().ok_or_else(|| format!("the {} export needs a generation stage", name));
+            match export {
+                ExportStage::Ocel { .. } => {export_ocel(&log, &file_path)?;},
+                ExportStage::Gexf { .. } => {export_ocdg(needs_ocdg()?, &file_path)?;},
+                ExportStage::Graphml { per_relation_keys, .. } => {export_ocdg_graphml(needs_ocdg()?, &file_path, &ExportOptions { per_relation_keys: *per_relation_keys, ..Default::default() })?;},
+                ExportStage::Metrics { .. } => {OcelMetrics { ocel: &log, ocdg: needs_ocdg()? }.export(&file_path)?;},
+                ExportStage::Analyses { .. } => {json!(analyses).export(&file_path)?;}
+            }
+            Ok(((), file_path.to_string()))
+        })?;
+        runner.report.artifacts.push(path);
+    }
+    Ok(runner.report)
+}
+
+struct Runner<'a> {
+    base: &'a Path,
+    report: PipelineReport
+}
+
+impl<'a> Runner<'a> {
+    fn resolve(&self, path: &str) -> PathBuf {
+        self.base.join(path)
+    }
+
+    fn filtered_log(&mut self, config: &PipelineConfig) -> Result<Ocel, PipelineError> {
+        let input = self.resolve(&config.input);
+        let mut log = self.stage("import", || {
+            let (log, report) = import_ocel_with_report(&input.to_string_lossy(), &ImportConfig::default())?;
+            Ok((log, format!("{} events, {} objects, {} warnings", report.events, report.objects, report.warnings.len())))
+        })?;
+
+        for filter in &config.filters {
+            let name = match filter {
+                FilterStage::Between { .. } => "filter between",
+                FilterStage::Prefix { .. } => "filter prefix",
+                FilterStage::DropEventsWithoutObjects => "filter drop_events_without_objects"
+            };
+            log = self.stage(name, || {
+                let filtered = match filter {
+                    FilterStage::Between { start, end } => log.between(start, end),
+                    FilterStage::Prefix { cutoff } => log.prefix(cutoff),
+                    FilterStage::DropEventsWithoutObjects => {
+                        let mut filtered = log.clone();
+                        filtered.drop_events_without_objects();
+                        filtered
+                    }
+                };
+                let summary = format!("{} of {} events kept", filtered.events.len(), log.events.len());
+                Ok((filtered, summary))
+            })?;
+        }
+        Ok(log)
+    }
+
+    fn stage<T, F>(&mut self, stage: &str, execute: F) -> Result<T, PipelineError> where F: FnOnce() -> Result<(T, String), Box<dyn Error>> {
+        let started = Instant::now();
+        let (result, summary) = execute().map_err(|source| PipelineError { stage: stage.to_owned(), source })?;
+        self.report.stages.push(StageReport { stage: stage.to_owned(), duration: started.elapsed(), summary });
+        Ok(result)
+    }
+}
+
+impl GenerationStage {
+    pub fn config(&self) -> Result<GenerationConfig, Box<dyn Error>> {
+        let relations = if self.relations.is_empty() {
+            Relations::iter().collect()
+        } else {
+            parse_relations(&self.relations)?
+        };
+        let evidence = match &self.evidence {
+            Some(evidence) => Evidence::from_str(evidence).map_err(|_| format!("unknown evidence {}", evidence))?,
+            None => Evidence::default()
+        };
+        let scope = match &self.scope {
+            Some(scope) => RelationScope::from_str(scope).map_err(|_| format!("unknown scope {}", scope))?,
+            None => RelationScope::default()
+        };
+        Ok(GenerationConfig { relations, evidence, scope,
+                              inheritance_max_gap: self.inheritance_max_gap_seconds.map(chrono::Duration::seconds),
+                              consumes_max_gap: self.consumes_max_gap_seconds.map(chrono::Duration::seconds),
+                              ..Default::default() })
+    }
+}
+
+impl AnalysisStage {
+    fn name(&self) -> &'static str {
+        match self {
+            AnalysisStage::RelationCounts => "relation_counts",
+            AnalysisStage::Cohorts => "cohorts",
+            AnalysisStage::Motifs { .. } => "motifs",
+            AnalysisStage::UncoveredEvents { .. } => "uncovered_events"
+        }
+    }
+
+    fn execute(&self, log: &Ocel, ocdg: &Ocdg, base: &Path) -> Result<(Value, Option<PathBuf>), Box<dyn Error>> {
+        match self {
+            AnalysisStage::RelationCounts => Ok((json!(ocdg.metadata().map(|metadata| metadata.edge_counts.clone()).unwrap_or_default()), None)),
+            AnalysisStage::Cohorts => {
+                let table = cohorts(log, ocdg);
+                Ok((json!({"cohorts": table.cohorts.len(), "largest": table.cohorts.iter().map(|cohort| cohort.size).max().unwrap_or(0)}), None))
+            },
+            AnalysisStage::Motifs { relations } => {
+                let relations = match relations {
+                    Some(relations) => Some(parse_relations(relations)?),
+                    None => None
+                };
+                let counts = motifs(ocdg, &MotifConfig { relations });
+                Ok((json!({"triangles": counts.triangles, "open_triads": counts.open_triads, "transitivity": counts.transitivity()}), None))
+            },
+            AnalysisStage::UncoveredEvents { csv } => {
+                let report = ocdg.uncovered_events(log).ok_or("uncovered events need a graph generated with full evidence")?;
+                let artifact = match csv {
+                    Some(csv) => {
+                        let path = base.join(csv);
+                        report.export_csv(&path.to_string_lossy())?;
+                        Some(path)
+                    },
+                    None => None
+                };
+                Ok((json!({"events": report.events.len(), "rate": report.uncovered_rate()}), artifact))
+            }
+        }
+    }
+}
+
+fn parse_relations(names: &[String]) -> Result<Vec<Relations>, Box<dyn Error>> {
+    Ok(RelationSet::from_names(names)?.into_vec())
+}
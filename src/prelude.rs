@@ -0,0 +1,15 @@
+// the types and entry points most programs need, `use pmrs::prelude::*;` replaces the deep module paths.
+// the modules behind these items may move, the names here stay
+pub use crate::objects::ocel::{Ocel, OcelEvent, OcelObject};
+pub use crate::objects::ocel::importer::{import_ocel, import_ocel_with, import_ocel_with_report, ImportConfig, ImportReport};
+pub use crate::objects::ocel::exporter::{export_ocel, export_ocel_pretty};
+pub use crate::objects::ocdg::{generate_ocdg, generate_ocdg_config, generate_ocdg_with, trace_pair, Evidence, GenerationConfig, Ocdg, OcdgError, RelationScope, Relations};
+pub use crate::objects::ocdg::presets::{Preset, RelationSet};
+pub use crate::objects::ocdg::importer::import_ocdg;
+pub use crate::objects::ocdg::exporter::{canonical_edges, export_ocdg, export_ocdg_graphml, export_ocdg_graphml_to, export_ocdg_with, ExportOptions, VisualStyle};
+pub use crate::objects::exporter::{ExportError, Exporter, StreamingExporter};
+pub use crate::analysis::{cohorts, lifecycle_tree, relation_timeseries, type_lift, Granularity};
+pub use crate::features::as_of;
+pub use crate::features::object_point::{object_point_features, LifecycleView, ObjectPoint, ObjectPointConfig};
+pub use crate::algo::report::object_journey;
+pub use crate::pipeline::{PipelineConfig, PipelineReport};
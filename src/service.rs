@@ -0,0 +1,163 @@
+use std::error::Error;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use ahash::AHashSet;
+use static_assertions::assert_impl_all;
+use strum::IntoEnumIterator;
+
+use crate::objects::ocdg::exporter::export_ocdg;
+use crate::objects::ocdg::{generate_ocdg_config, GenerationConfig, Ocdg, OcdgError, Relations};
+use crate::objects::ocel::Ocel;
+use crate::objects::ocel::importer::import_ocel;
+use crate::objects::ocel::merge::{merge_with_links, MergeConfig};
+
+pub type UpdateHook = Box<dyn Fn(&FrozenOcdg) + Send + Sync>;
+pub type Generator = fn(&Ocel, &GenerationConfig) -> Result<Ocdg, OcdgError>;
+
+#[derive(Debug, Clone)]
+pub struct ServiceConfig {
+    pub generation: GenerationConfig,
+    pub poll_interval: Duration,
+    pub extension: String,
+    // builds the graph of the merged log, generate_ocdg_config unless replaced
+    pub generate: Generator
+}
+
+impl Default for ServiceConfig {
+    fn default() -> Self {
+        Self { generation: GenerationConfig { relations: Relations::iter().collect(), ..Default::default() },
+               poll_interval: Duration::from_millis(500),
+               extension: "jsonocel".to_owned(),
+               generate: generate_ocdg_config }
+    }
+}
+
+// an immutable state of the watched directory, queries hold on to it while the service moves on
+#[derive(Default)]
+pub struct FrozenOcdg {
+    pub version: usize,
+    pub files: Vec<PathBuf>,
+    pub log: Option<Ocel>,
+    pub ocdg: Ocdg
+}
+
+assert_impl_all!(FrozenOcdg: Send, Sync);
+
+impl Deref for FrozenOcdg {
+    type Target = Ocdg;
+
+    fn deref(&self) -> &Ocdg {
+        &self.ocdg
+    }
+}
+
+pub struct OcdgHandle {
+    current: Arc<RwLock<Arc<FrozenOcdg>>>,
+    hooks: Arc<Mutex<Vec<UpdateHook>>>,
+    stop: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>
+}
+
+impl OcdgHandle {
+    pub fn snapshot(&self) -> Arc<FrozenOcdg> {
+        self.current.read().expect("The service never panics while holding the lock").clone()
+    }
+
+    pub fn on_update(&self, hook: UpdateHook) {
+        self.hooks.lock().expect("Hooks never panic while holding the lock").push(hook);
+    }
+
+    // export failures are dropped, the next update tries again
+    pub fn export_on_update(&self, file_path: &str) {
+        let file_path = file_path.to_owned();
+        self.on_update(Box::new(move |frozen| {
+            let _ = export_ocdg(&frozen.ocdg, &file_path);
+        }));
+    }
+
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for OcdgHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+// files are picked up in name order, files that fail to import or whose graph fails to generate are retried on the next poll
+pub fn watch(dir: &Path, config: ServiceConfig) -> Result<OcdgHandle, Box<dyn Error>> {
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()).into());
+    }
+    let current: Arc<RwLock<Arc<FrozenOcdg>>> = Arc::new(RwLock::new(Arc::new(FrozenOcdg::default())));
+    let hooks: Arc<Mutex<Vec<UpdateHook>>> = Arc::new(Mutex::new(vec![]));
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let worker = {
+        let (dir, current, hooks, stop) = (dir.to_path_buf(), current.clone(), hooks.clone(), stop.clone());
+        thread::spawn(move || {
+            let mut seen: AHashSet<PathBuf> = AHashSet::new();
+            while !stop.load(Ordering::Relaxed) {
+                let latest = current.read().expect("The service never panics while holding the lock").clone();
+                if let Some(frozen) = poll(&dir, &config, &mut seen, &latest) {
+                    let frozen = Arc::new(frozen);
+                    *current.write().expect("The service never panics while holding the lock") = frozen.clone();
+                    for hook in hooks.lock().expect("Hooks never panic while holding the lock").iter() {
+                        hook(&frozen);
+                    }
+                }
+                thread::sleep(config.poll_interval);
+            }
+        })
+    };
+
+    Ok(OcdgHandle { current, hooks, stop, worker: Some(worker) })
+}
+
+// the delta files are merged into the log so far and the graph is generated again
+fn poll(dir: &Path, config: &ServiceConfig, seen: &mut AHashSet<PathBuf>, latest: &FrozenOcdg) -> Option<FrozenOcdg> {
+    let mut new_files: Vec<PathBuf> = fs::read_dir(dir).ok()?
+                                                       .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                                                       .filter(|path| path.is_file() && !seen.contains(path))
+                                                       .filter(|path| path.extension().is_some_and(|ext| ext == config.extension.as_str()))
+                                                       .collect();
+    new_files.sort();
+
+    let mut log: Option<Ocel> = latest.log.clone();
+    let mut added: Vec<PathBuf> = vec![];
+    for path in new_files {
+        let delta = match path.to_str().map(import_ocel) {
+            Some(Ok(delta)) => delta,
+            _ => continue
+        };
+        log = Some(match log {
+            Some(log) => merge_with_links(&log, &delta, &[], &MergeConfig::default()).0,
+            None => delta
+        });
+        added.push(path);
+    }
+    if added.is_empty() {
+        return None;
+    }
+
+    // a failed generation keeps the last good snapshot, the files are not marked as seen and are merged again on the next poll
+    let ocdg = (config.generate)(log.as_ref().expect("A file was added"), &config.generation).ok()?;
+    seen.extend(added.iter().cloned());
+    let files: Vec<PathBuf> = latest.files.iter().cloned().chain(added).collect();
+    Some(FrozenOcdg { version: latest.version + 1, files, log, ocdg })
+}
@@ -0,0 +1,40 @@
+use pmrs::algo::analysis::cohorts;
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+
+#[test]
+fn test_cohorts_by_birth_event() {
+    let log: Ocel = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &[Relations::COBIRTH]).unwrap();
+    let table = cohorts(&log, &ocdg);
+    let oid = |id: &str| *log.object_map.get_by_left(id).unwrap();
+
+    assert_eq!(table.cohorts.len(), 4);
+    let first = table.cohort_of(&oid("o1")).unwrap();
+    assert_eq!(first.id, 0);
+    assert_eq!(first.size, 3);
+    assert_eq!(first.birth_activity, "place_order");
+    assert_eq!(first.object_types, vec!["item".to_string(), "order".to_string()]);
+    assert_eq!(table.object_cohort[&oid("i2")], 0);
+
+    let second = table.cohort_of(&oid("i3")).unwrap();
+    assert_eq!(second.id, 1);
+    assert_eq!(second.members, { let mut m = vec![oid("o2"), oid("i3")]; m.sort(); m });
+    assert_ne!(table.object_cohort[&oid("o1")], table.object_cohort[&oid("o2")]);
+
+    // packages split from p1 are born together
+    assert_eq!(table.cohort_of(&oid("p2")).unwrap().birth_activity, "split_package");
+    assert_eq!(table.object_cohort[&oid("p2")], table.object_cohort[&oid("p3")]);
+    assert_eq!(table.size_series().iter().map(|(_, size)| *size).collect::<Vec<usize>>(), vec![3, 2, 1, 2]);
+}
+
+#[test]
+fn test_cohorts_object_frame() {
+    let log: Ocel = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &[Relations::COBIRTH]).unwrap();
+    let frame = cohorts(&log, &ocdg).object_frame(&log);
+
+    assert_eq!(frame.shape(), (8, 2));
+    assert_eq!(frame.get_column_names(), vec!["oids", "cohort"]);
+}
@@ -0,0 +1,96 @@
+use std::cell::RefCell;
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use pmrs::objects::exporter::{manifest_path, partial_path, Exporter, StreamingExporter};
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocdg::exporter::export_ocdg;
+use pmrs::objects::ocdg::importer::import_ocdg;
+use pmrs::objects::ocel::exporter::export_ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+
+fn temp_file(name: &str) -> String {
+    let path = std::env::temp_dir().join(name);
+    let path = path.to_str().unwrap().to_owned();
+    let _ = fs::remove_file(&path);
+    let _ = fs::remove_file(partial_path(&path));
+    let _ = fs::remove_file(manifest_path(&path));
+    path
+}
+
+struct Interrupted;
+
+impl Exporter for Interrupted {
+    fn write_content(&self, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        f.write_all(&[b'x'; 100])?;
+        Err("disk unplugged".into())
+    }
+}
+
+struct Lines {
+    fail_at: Option<usize>,
+    written: RefCell<Vec<usize>>
+}
+
+impl StreamingExporter for Lines {
+    fn chunk_count(&self) -> usize {
+        6
+    }
+
+    fn write_chunk(&self, index: usize, f: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        if self.fail_at == Some(index) {
+            f.write_all(b"half a li")?;
+            return Err("process killed".into());
+        }
+        self.written.borrow_mut().push(index);
+        writeln!(f, "line {}", index)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_interrupted_export_leaves_no_file() {
+    let path = temp_file("pmrs-interrupted.txt");
+    let err = Interrupted.export(&path).unwrap_err();
+
+    assert_eq!(err.bytes_written, 100);
+    assert!(err.to_string().contains("disk unplugged"));
+    assert!(!std::path::Path::new(&path).exists());
+    assert!(!partial_path(&path).exists());
+}
+
+#[test]
+fn test_streaming_export_resumes_after_interruption() {
+    let path = temp_file("pmrs-resumable.csv");
+    let failing = Lines { fail_at: Some(3), written: RefCell::new(vec![]) };
+    assert!(failing.export(&path).is_err());
+    assert!(!std::path::Path::new(&path).exists());
+    assert!(partial_path(&path).exists());
+    assert_eq!(fs::read_to_string(manifest_path(&path)).unwrap(), "chunks=3\nbytes=21\n");
+
+    let resumed = Lines { fail_at: None, written: RefCell::new(vec![]) };
+    assert_eq!(resumed.export_resume(&path).unwrap(), 42);
+    assert_eq!(*resumed.written.borrow(), vec![3, 4, 5]);
+    assert_eq!(fs::read_to_string(&path).unwrap(), (0..6).map(|i| format!("line {}\n", i)).collect::<String>());
+    assert!(!partial_path(&path).exists());
+    assert!(!manifest_path(&path).exists());
+}
+
+#[test]
+fn test_ocel_export_is_atomic() {
+    let path = temp_file("pmrs-atomic.jsonocel");
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    assert!(export_ocel(&log, &path).unwrap());
+    assert!(!partial_path(&path).exists());
+    assert_eq!(import_ocel(&path).unwrap().events.len(), log.events.len());
+}
+
+#[test]
+fn test_gexf_export_is_atomic() {
+    let path = temp_file("pmrs-atomic.gexf");
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+    assert!(export_ocdg(&ocdg, &path).unwrap());
+    assert!(!partial_path(&path).exists());
+    assert_eq!(import_ocdg(&path).unwrap().net.edge_count(), ocdg.net.edge_count());
+}
@@ -0,0 +1,91 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use pmrs::algo::transformation::ocel::features::aggregate::{attribute, object_attribute_features, AggregateError, AggregateTarget, Aggregation, NullPolicy};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelAttributeValue, OcelEvent, OcelObject, TypedAttributes};
+use serde_json::{json, Value};
+
+// the order o1 takes part in four payments, one without an amount and one with a null amount.
+// the events are inserted in reverse timestamp order
+fn payment_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec!["pay".to_string()], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, label) in ["o1", "o2"].iter().enumerate() {
+        log.object_map.insert(label.to_string(), oid);
+        log.objects.insert(oid, OcelObject { obj_type: "order".to_string(), ovmap: AHashMap::new(), events: vec![] });
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let amounts: [(usize, Option<Value>); 5] = [(0, Some(json!(4.0))), (0, None), (0, Some(Value::Null)), (0, Some(json!(10))), (1, Some(json!("lots")))];
+    for (eid, (oid, amount)) in amounts.into_iter().enumerate() {
+        let mut vmap = AHashMap::new();
+        if let Some(amount) = amount {
+            vmap.insert("amount".to_string(), amount);
+        }
+        log.events.insert(eid, OcelEvent { activity: "pay".to_string(), timestamp: start - Duration::hours(eid as i64), vmap, omap: IntSet::from_iter([oid]) });
+        log.event_map.insert(format!("e{}", eid), eid);
+        log.objects.get_mut(&oid).unwrap().events.insert(0, eid);
+    }
+    log
+}
+
+fn o1(log: &Ocel, agg: Aggregation, policy: NullPolicy) -> f64 {
+    attribute(log, AggregateTarget::Object(0), "amount", agg, policy).unwrap()
+}
+
+#[test]
+fn test_skip_nulls() {
+    let log = payment_log();
+    assert_eq!(o1(&log, Aggregation::Sum, NullPolicy::Skip), 14.0);
+    assert_eq!(o1(&log, Aggregation::Mean, NullPolicy::Skip), 7.0);
+    assert_eq!(o1(&log, Aggregation::Min, NullPolicy::Skip), 4.0);
+    assert_eq!(o1(&log, Aggregation::Max, NullPolicy::Skip), 10.0);
+    assert_eq!(o1(&log, Aggregation::Count, NullPolicy::Skip), 2.0);
+    // e0 is the latest event
+    assert_eq!(o1(&log, Aggregation::Last, NullPolicy::Skip), 4.0);
+    let nulls = [1, 2];
+    assert!(attribute(&log, AggregateTarget::Events(&nulls), "amount", Aggregation::Mean, NullPolicy::Skip).unwrap().is_nan());
+    assert_eq!(attribute(&log, AggregateTarget::Events(&nulls), "amount", Aggregation::Sum, NullPolicy::Skip), Ok(0.0));
+}
+
+#[test]
+fn test_zero_fill_nulls() {
+    let log = payment_log();
+    assert_eq!(o1(&log, Aggregation::Sum, NullPolicy::ZeroFill), 14.0);
+    assert_eq!(o1(&log, Aggregation::Mean, NullPolicy::ZeroFill), 3.5);
+    assert_eq!(o1(&log, Aggregation::Min, NullPolicy::ZeroFill), 0.0);
+    assert_eq!(o1(&log, Aggregation::Count, NullPolicy::ZeroFill), 4.0);
+    let latest_null = [1, 3];
+    assert_eq!(attribute(&log, AggregateTarget::Events(&latest_null), "amount", Aggregation::Last, NullPolicy::ZeroFill), Ok(0.0));
+}
+
+#[test]
+fn test_nan_propagation() {
+    let log = payment_log();
+    assert!(o1(&log, Aggregation::Max, NullPolicy::Nan).is_nan());
+    assert!(o1(&log, Aggregation::Count, NullPolicy::Nan).is_nan());
+    let typed = [0, 3];
+    assert_eq!(attribute(&log, AggregateTarget::Events(&typed), "amount", Aggregation::Sum, NullPolicy::Nan), Ok(14.0));
+}
+
+#[test]
+fn test_type_checking() {
+    let log = payment_log();
+    assert_eq!(attribute(&log, AggregateTarget::Object(1), "amount", Aggregation::Sum, NullPolicy::Skip),
+               Err(AggregateError::NotNumeric { event: "e4".to_string(), attribute: "amount".to_string(), value: OcelAttributeValue::String("lots".to_string()) }));
+    // counting does not look at the type
+    assert_eq!(attribute(&log, AggregateTarget::Object(1), "amount", Aggregation::Count, NullPolicy::Skip), Ok(1.0));
+    assert_eq!(attribute(&log, AggregateTarget::Object(7), "amount", Aggregation::Sum, NullPolicy::Skip), Err(AggregateError::UnknownObject(7)));
+    assert_eq!(attribute(&log, AggregateTarget::Events(&[9]), "amount", Aggregation::Sum, NullPolicy::Skip), Err(AggregateError::UnknownEvent(9)));
+}
+
+#[test]
+fn test_object_feature_table() {
+    let log = payment_log();
+    let table = object_attribute_features(&log, &[("amount", Aggregation::Count), ("missing", Aggregation::Sum)], NullPolicy::Skip).unwrap();
+    assert_eq!(table.get_column_names(), vec!["oids", "Count:amount", "Sum:missing"]);
+    assert_eq!(table.column("Count:amount").unwrap().f64().unwrap().into_no_null_iter().collect::<Vec<f64>>(), vec![2.0, 1.0]);
+    assert_eq!(table.column("Sum:missing").unwrap().f64().unwrap().into_no_null_iter().collect::<Vec<f64>>(), vec![0.0, 0.0]);
+    assert!(object_attribute_features(&log, &[("amount", Aggregation::Mean)], NullPolicy::Skip).is_err());
+}
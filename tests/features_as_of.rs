@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use chrono::{TimeZone, Utc};
+use nohash_hasher::IntSet;
+use polars::prelude::DataFrame;
+use pmrs::algo::transformation::ocel::features::as_of;
+use pmrs::algo::transformation::ocel::features::object_point::ObjectPoint;
+use pmrs::objects::ocdg::Relations;
+use pmrs::objects::ocel::{Ocel, OcelEvent};
+use pmrs::objects::ocel::importer::import_ocel;
+use serde_json::Value;
+use strum::IntoEnumIterator;
+
+fn params() -> HashMap<ObjectPoint, Option<Value>> {
+    HashMap::from_iter([(ObjectPoint::UniqueNeighborCount, None),
+                        (ObjectPoint::ActivityExistence, None),
+                        (ObjectPoint::ActivityExistenceCount, None),
+                        (ObjectPoint::ObjectLifetime, None),
+                        (ObjectPoint::ObjectUnitSetRatio, None),
+                        (ObjectPoint::ObjectEventInteractionOperator, None)])
+}
+
+fn features(log: &Ocel, params: &HashMap<ObjectPoint, Option<Value>>) -> DataFrame {
+    let cutoff = Utc.ymd(2022, 1, 3).and_hms(0, 0, 0);
+    let relations: Vec<Relations> = Relations::iter().collect();
+    as_of(log, &relations, &cutoff, params).unwrap().sort(["oids"], false).unwrap()
+}
+
+#[test]
+fn test_prefix_truncates_lifecycles() {
+    let log: Ocel = import_ocel("logs/three-days.jsonocel").unwrap();
+    let prefix = log.prefix(&Utc.ymd(2022, 1, 3).and_hms(0, 0, 0));
+    let oid = |id: &str| *log.object_map.get_by_left(id).unwrap();
+
+    assert_eq!(prefix.events.len(), 4);
+    assert_eq!(prefix.objects[&oid("o1")].events.len(), 2);
+    // o3 and i4 only appear after the cutoff
+    assert_eq!(prefix.objects.len(), 5);
+    assert!(!prefix.object_map.contains_left("o3") && !prefix.event_map.contains_left("e5"));
+    assert_eq!(prefix.activities, vec!["check_availability", "pick_item", "place_order"]);
+}
+
+#[test]
+fn test_as_of_ignores_post_cutoff_events() {
+    let log: Ocel = import_ocel("logs/three-days.jsonocel").unwrap();
+    let params = params();
+    let before = features(&log, &params);
+
+    // rewrite everything after the cutoff: new activities, new objects in omaps and a new event
+    let mut changed = log.clone();
+    let oid = |id: &str| *log.object_map.get_by_left(id).unwrap();
+    let eid = |id: &str| *log.event_map.get_by_left(id).unwrap();
+    changed.events.update(&eid("e5"), |event| event.activity = "cancel_order".to_string());
+    changed.events.update(&eid("e7"), |event| event.omap.extend([oid("i1"), oid("o2")]));
+    changed.objects.get_mut(&oid("i1")).unwrap().events.push(eid("e7"));
+    changed.objects.get_mut(&oid("o2")).unwrap().events.push(eid("e7"));
+    let new_eid = changed.events.len();
+    changed.events.insert(new_eid, OcelEvent { activity: "return_item".to_string(),
+                                               timestamp: Utc.ymd(2022, 1, 4).and_hms(12, 0, 0),
+                                               vmap: Default::default(),
+                                               omap: IntSet::from_iter([oid("o1"), oid("i3")]) });
+    changed.event_map.insert("e8".to_string(), new_eid);
+    changed.objects.get_mut(&oid("o1")).unwrap().events.push(new_eid);
+    changed.objects.get_mut(&oid("i3")).unwrap().events.push(new_eid);
+    changed.activities.push("return_item".to_string());
+    let after = features(&changed, &params);
+
+    assert_eq!(before.height(), 5);
+    assert!(before.frame_equal(&after));
+}
+
+#[test]
+fn test_as_of_differs_from_full_log() {
+    let log: Ocel = import_ocel("logs/three-days.jsonocel").unwrap();
+    let params = HashMap::from_iter([(ObjectPoint::ObjectLifetime, None)]);
+    let prefix = features(&log, &params);
+    let full = as_of(&log, &Relations::iter().collect::<Vec<Relations>>(), &Utc.ymd(2022, 2, 1).and_hms(0, 0, 0), &params).unwrap().sort(["oids"], false).unwrap();
+
+    assert_eq!(full.height(), 7);
+    assert!(!prefix.frame_equal(&full));
+}
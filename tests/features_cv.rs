@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use polars::prelude::{DataFrame, NamedFrom, Series};
+use pmrs::algo::transformation::ocel::features::cv::{fold_label_distribution, grouped_folds};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+
+// executions of an order and an item, execution x has x + 1 events
+fn execution_log(executions: usize) -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec!["work".to_string()], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let mut eid = 0;
+    for x in 0..executions {
+        let (order, item) = (2 * x, 2 * x + 1);
+        log.object_map.insert(format!("o{}", x), order);
+        log.object_map.insert(format!("i{}", x), item);
+        log.objects.insert(order, OcelObject { obj_type: "order".to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.objects.insert(item, OcelObject { obj_type: "item".to_string(), ovmap: AHashMap::new(), events: vec![] });
+        for step in 0..=x {
+            // only the first event links the two objects
+            let omap = if step == 0 {vec![order, item]} else {vec![item]};
+            for oid in &omap {
+                log.objects.get_mut(oid).unwrap().events.push(eid);
+            }
+            log.events.insert(eid, OcelEvent { activity: "work".to_string(), timestamp: start + Duration::minutes(eid as i64), vmap: AHashMap::new(), omap: IntSet::from_iter(omap) });
+            log.event_map.insert(format!("x{}e{}", x, step), eid);
+            eid += 1;
+        }
+    }
+    log
+}
+
+// one row per event, labelled late when it is not the first event of its execution
+fn event_table(log: &Ocel) -> DataFrame {
+    let mut eids: Vec<&String> = log.event_map.left_values().collect();
+    eids.sort();
+    let labels: Vec<&str> = eids.iter().map(|eid| if eid.ends_with("e0") {"on_time"} else {"late"}).collect();
+    DataFrame::new(vec![Series::new("eids", eids.iter().map(|eid| eid.as_str()).collect::<Vec<&str>>()), Series::new("label", labels)]).unwrap()
+}
+
+fn execution_of(table: &DataFrame, row: usize) -> String {
+    table.column("eids").unwrap().utf8().unwrap().into_iter().nth(row).flatten().unwrap().split('e').next().unwrap().to_string()
+}
+
+#[test]
+fn test_no_execution_spans_folds() {
+    let log = execution_log(8);
+    let table = event_table(&log);
+    let folds = grouped_folds(&table, &log, 3, 7);
+    assert_eq!(folds.len(), 3);
+
+    let mut fold_of: BTreeMap<String, usize> = BTreeMap::new();
+    let mut held_out: Vec<usize> = vec![];
+    for (fold, (train, test)) in folds.iter().enumerate() {
+        assert_eq!(train.len() + test.len(), table.height());
+        assert!(train.iter().all(|row| !test.contains(row)));
+        for row in test {
+            assert_eq!(*fold_of.entry(execution_of(&table, *row)).or_insert(fold), fold);
+        }
+        for row in train {
+            assert_ne!(fold_of.get(&execution_of(&table, *row)), Some(&fold));
+        }
+        held_out.extend(test);
+    }
+    held_out.sort_unstable();
+    assert_eq!(held_out, (0..table.height()).collect::<Vec<usize>>());
+    assert_eq!(fold_of.len(), 8);
+    // fold sizes differ by at most the largest execution
+    let sizes: Vec<usize> = folds.iter().map(|(_, test)| test.len()).collect();
+    assert!(sizes.iter().max().unwrap() - sizes.iter().min().unwrap() <= 8);
+}
+
+#[test]
+fn test_folds_are_deterministic() {
+    let log = execution_log(8);
+    let table = event_table(&log);
+    assert_eq!(grouped_folds(&table, &log, 4, 42), grouped_folds(&table, &log, 4, 42));
+    let seeds: Vec<Vec<(Vec<usize>, Vec<usize>)>> = (0..8).map(|seed| grouped_folds(&table, &log, 4, seed)).collect();
+    assert!(seeds.iter().any(|folds| *folds != seeds[0]));
+    assert!(grouped_folds(&table, &log, 1, 42).is_empty());
+}
+
+#[test]
+fn test_object_tables_group_by_object() {
+    let log = execution_log(3);
+    let oids = ["o0", "i0", "o1", "i1", "o2", "i2", "o0"];
+    let table = DataFrame::new(vec![Series::new("oids", oids.to_vec())]).unwrap();
+    let folds = grouped_folds(&table, &log, 2, 1);
+    let repeated = folds.iter().find(|(_, test)| test.contains(&0)).unwrap();
+    assert!(repeated.1.contains(&6));
+    assert_eq!(folds.iter().map(|(_, test)| test.len()).sum::<usize>(), 7);
+}
+
+#[test]
+fn test_label_distribution() {
+    let log = execution_log(8);
+    let table = event_table(&log);
+    let folds = grouped_folds(&table, &log, 3, 7);
+    let distribution = fold_label_distribution(&table, "label", &folds).unwrap();
+
+    assert_eq!(distribution.len(), 3);
+    assert_eq!(distribution.iter().map(|counts| counts.get("on_time").copied().unwrap_or(0)).sum::<usize>(), 8);
+    assert_eq!(distribution.iter().map(|counts| counts.get("late").copied().unwrap_or(0)).sum::<usize>(), 28);
+    assert!(fold_label_distribution(&table, "missing", &folds).is_none());
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::time::Instant;
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use polars::prelude::DataFrame;
+use pmrs::algo::transformation::ocel::features::object_point::{object_point_features, object_point_features_with_metadata, ColumnSemantics, LifecycleView, ObjectPoint, ObjectPointConfig};
+use pmrs::objects::ocdg::{generate_ocdg_config, GenerationConfig, Ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+use pmrs::objects::ocel::importer::import_ocel;
+use serde_json::Value;
+use strum::IntoEnumIterator;
+
+fn params() -> HashMap<ObjectPoint, Option<Value>> {
+    HashMap::from_iter([(ObjectPoint::UniqueNeighborCount, None),
+                        (ObjectPoint::ActivityExistence, None),
+                        (ObjectPoint::ActivityExistenceCount, None),
+                        (ObjectPoint::ObjectLifetime, None),
+                        (ObjectPoint::ObjectUnitSetRatio, None),
+                        (ObjectPoint::ObjectEventInteractionOperator, None)])
+}
+
+fn ocdg(log: &Ocel) -> Ocdg {
+    generate_ocdg_config(log, &GenerationConfig { relations: Relations::iter().collect(), ..Default::default() }).unwrap()
+}
+
+fn features(log: &Ocel, ocdg: &Ocdg, params: &HashMap<ObjectPoint, Option<Value>>, lifecycle_view: LifecycleView) -> DataFrame {
+    object_point_features(ObjectPointConfig { ocel: log, ocdg, params, lifecycle_view }).sort(["oids"], false).unwrap()
+}
+
+// one object with a very long lifecycle, every tenth event is shared with a second object
+fn long_lifecycle(length: usize) -> Ocel {
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, label) in ["long", "short"].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: label.to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    for eid in 0..length {
+        let omap = if eid % 10 == 0 {IntSet::from_iter([0, 1])} else {IntSet::from_iter([0])};
+        let activity = if eid % 2 == 0 {"work"} else {"wait"};
+        log.events.insert(eid, OcelEvent { activity: activity.to_owned(), timestamp: start + Duration::seconds(eid as i64), vmap: AHashMap::new(), omap });
+        log.event_map.insert(format!("e{}", eid), eid);
+    }
+    log.rebuild_derived_indices();
+    log
+}
+
+#[test]
+fn test_views_select_events() {
+    let lifecycle: Vec<usize> = (0..10).collect();
+    assert_eq!(LifecycleView::Full.events(&lifecycle).to_vec(), lifecycle);
+    assert_eq!(LifecycleView::FirstLastK(2).events(&lifecycle).to_vec(), vec![0, 1, 8, 9]);
+    assert_eq!(LifecycleView::FirstLastK(5).events(&lifecycle).to_vec(), lifecycle);
+    // the last event is kept even when the stride skips it
+    assert_eq!(LifecycleView::Stride(4).events(&lifecycle).to_vec(), vec![0, 4, 8, 9]);
+    assert_eq!(LifecycleView::Stride(3).events(&lifecycle).to_vec(), vec![0, 3, 6, 9]);
+}
+
+#[test]
+fn test_large_window_matches_full() {
+    let params = params();
+    for path in ["logs/order-to-cash.jsonocel", "logs/three-days.jsonocel"] {
+        let log = import_ocel(path).unwrap();
+        let ocdg = ocdg(&log);
+        let longest = log.objects.values().map(|obj| obj.events.len()).max().unwrap();
+        let full = features(&log, &ocdg, &params, LifecycleView::Full);
+        assert!(full.frame_equal(&features(&log, &ocdg, &params, LifecycleView::FirstLastK(longest))), "{}", path);
+        assert!(full.frame_equal(&features(&log, &ocdg, &params, LifecycleView::Stride(1))), "{}", path);
+    }
+}
+
+#[test]
+fn test_column_semantics() {
+    let log = import_ocel("logs/order-to-cash.jsonocel").unwrap();
+    let ocdg = ocdg(&log);
+    let params = params();
+
+    let (df, metadata) = object_point_features_with_metadata(ObjectPointConfig { ocel: &log, ocdg: &ocdg, params: &params, lifecycle_view: LifecycleView::Full });
+    assert_eq!(metadata.len(), df.width() - 1);
+    assert!(metadata.values().all(|semantics| *semantics == ColumnSemantics::Exact));
+
+    let (df, metadata) = object_point_features_with_metadata(ObjectPointConfig { ocel: &log, ocdg: &ocdg, params: &params, lifecycle_view: LifecycleView::FirstLastK(1) });
+    assert_eq!(metadata.len(), df.width() - 1);
+    assert_eq!(metadata["UniqueNeighborCount"], ColumnSemantics::Exact);
+    assert_eq!(metadata["ObjectLifetime"], ColumnSemantics::Exact);
+    assert_eq!(metadata["ObjectUnitSetRatio"], ColumnSemantics::Estimate);
+    assert_eq!(metadata["ObjectEventInteractionOperator"], ColumnSemantics::Estimate);
+    assert!(metadata.iter().filter(|(name, _)| name.starts_with("ActivityExistence")).all(|(_, semantics)| *semantics == ColumnSemantics::LowerBound));
+}
+
+#[test]
+fn test_long_lifecycle_stays_bounded() {
+    let log = long_lifecycle(50_000);
+    let ocdg = ocdg(&log);
+    let params = params();
+
+    let started = Instant::now();
+    let sampled = features(&log, &ocdg, &params, LifecycleView::FirstLastK(50));
+    let strided = features(&log, &ocdg, &params, LifecycleView::Stride(100));
+    assert!(started.elapsed().as_secs() < 10);
+    let full = features(&log, &ocdg, &params, LifecycleView::Full);
+
+    for df in [&sampled, &strided, &full] {
+        for series in df.get_columns().iter().filter(|series| series.name() != "oids") {
+            let values = series.cast(&polars::prelude::DataType::Float64).unwrap();
+            assert!(values.f64().unwrap().into_no_null_iter().all(|value| value.is_finite()), "{}", series.name());
+        }
+    }
+    // counts are lower bounds of the full counts, the lifetime is exact
+    let counts = |df: &DataFrame| df.column("ActivityExistenceCount:work").unwrap().cast(&polars::prelude::DataType::Float64).unwrap().f64().unwrap().into_no_null_iter().collect::<Vec<f64>>();
+    assert_eq!(counts(&full)[0], 25_000.0);
+    assert_eq!(counts(&sampled)[0], 50.0);
+    assert!(counts(&strided)[0] <= counts(&full)[0]);
+    assert!(sampled.column("ObjectLifetime").unwrap().series_equal(full.column("ObjectLifetime").unwrap()));
+    assert!(strided.column("ObjectLifetime").unwrap().series_equal(full.column("ObjectLifetime").unwrap()));
+}
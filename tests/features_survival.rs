@@ -0,0 +1,71 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::algo::transformation::ocel::features::survival::{build, SurvivalError};
+use pmrs::objects::ocdg::{generate_ocdg, generate_ocdg_with, Evidence, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+use serde_json::json;
+
+// hours after the start: o1 is born at 0 and meets the truck at 2, o2 is born at 1 and meets it at 10,
+// o3 is born at 3 and never meets it, o4 is born with the truck at 8
+fn truck_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    let objects = [("o1", "order", Some(2.5)), ("o2", "order", None), ("o3", "order", Some(1.0)), ("o4", "order", None), ("t1", "truck", None)];
+    for (oid, (label, obj_type, weight)) in objects.iter().enumerate() {
+        let ovmap = weight.map(|weight| AHashMap::from_iter([("weight".to_string(), json!(weight))])).unwrap_or_default();
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap, events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, i64, &[&str]); 7] = [("e1", 0, &["o1"]), ("e2", 1, &["o2"]), ("e3", 2, &["o1", "t1"]), ("e4", 3, &["o3"]),
+                                             ("e5", 8, &["o4", "t1"]), ("e6", 10, &["o2", "t1"]), ("e7", 12, &["o3"])];
+    for (id, hour, objects) in events {
+        log.insert_event(id, "step", start + Duration::hours(hour), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+#[test]
+fn test_censoring_at_the_censor_time() {
+    let log = truck_log();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let table = build(&log, &ocdg, "order", Relations::INTERACTS, Some(start + Duration::hours(5))).unwrap();
+    assert_eq!(table.observation_end, start + Duration::hours(5));
+    assert_eq!(table.covariates, vec!["birth_objects", "weight"]);
+
+    // o4 is born after the censor time and never at risk
+    let rows: Vec<(&str, f64, bool)> = table.rows.iter().map(|row| (row.object.as_str(), row.duration, row.observed)).collect();
+    // o2 meets the truck after the censor time and is censored like o3
+    assert_eq!(rows, vec![("o1", 7200.0, true), ("o2", 4.0 * 3600.0, false), ("o3", 2.0 * 3600.0, false)]);
+    assert_eq!(table.rows[0].covariates, vec![0.0, 2.5]);
+    assert!(table.rows[1].covariates[1].is_nan());
+
+    let frame = table.to_dataframe();
+    assert_eq!(frame.get_column_names(), vec!["oids", "duration", "observed", "birth_objects", "weight"]);
+    assert_eq!(frame.height(), 3);
+}
+
+#[test]
+fn test_censoring_at_the_log_end() {
+    let log = truck_log();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+    let table = build(&log, &ocdg, "truck", Relations::INTERACTS, None).unwrap();
+    // the truck is born meeting o1
+    assert_eq!(table.rows.len(), 1);
+    assert!(table.rows[0].observed);
+    assert_eq!(table.rows[0].duration, 0.0);
+
+    // a censor time after the end of the log stops at the last event
+    let later = build(&log, &ocdg, "order", Relations::INTERACTS, Some(Utc.ymd(2030, 1, 1).and_hms(0, 0, 0))).unwrap();
+    assert_eq!(later.observation_end, Utc.ymd(2022, 1, 1).and_hms(12, 0, 0));
+    let rows: Vec<(&str, f64, bool)> = later.rows.iter().map(|row| (row.object.as_str(), row.duration, row.observed)).collect();
+    assert_eq!(rows, vec![("o1", 7200.0, true), ("o2", 9.0 * 3600.0, true), ("o3", 9.0 * 3600.0, false), ("o4", 0.0, true)]);
+    assert_eq!(later.rows[3].covariates[0], 1.0);
+
+    let counts = generate_ocdg_with(&log, &[Relations::INTERACTS], Evidence::CountsOnly).unwrap();
+    assert_eq!(build(&log, &counts, "order", Relations::INTERACTS, None), Err(SurvivalError::MissingEvidence(Evidence::CountsOnly)));
+}
@@ -0,0 +1,43 @@
+use pmrs::algo::analysis::lifecycle_tree;
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+
+#[test]
+fn test_lifecycle_tree_supports() {
+    let log: Ocel = import_ocel("logs/three-days.jsonocel").unwrap();
+    let tree = lifecycle_tree(&log, "item", 1);
+
+    assert_eq!(tree.nodes.len(), 5);
+    assert_eq!(tree.root().support, 4);
+    assert_eq!(tree.support(&["place_order"]), Some(4));
+    assert_eq!(tree.support(&["place_order", "pick_item"]), Some(2));
+    assert_eq!(tree.support(&["place_order", "check_availability", "pick_item"]), Some(1));
+    assert_eq!(tree.support(&["pick_item"]), None);
+    assert_eq!(tree.drop_off(1), 1);
+    // most frequent branch comes first
+    assert_eq!(tree.nodes[tree.nodes[1].children[0]].activity, "pick_item");
+}
+
+#[test]
+fn test_lifecycle_tree_pruning() {
+    let log: Ocel = import_ocel("logs/three-days.jsonocel").unwrap();
+    let tree = lifecycle_tree(&log, "item", 2);
+
+    assert_eq!(tree.nodes.len(), 3);
+    assert_eq!(tree.support(&["place_order", "check_availability"]), None);
+    assert_eq!(tree.drop_off(1), 2);
+}
+
+#[test]
+fn test_lifecycle_tree_three_variants_dot() {
+    let log: Ocel = import_ocel("logs/three-days.jsonocel").unwrap();
+    let tree = lifecycle_tree(&log, "order", 1);
+
+    assert_eq!(tree.nodes.len(), 4);
+    assert_eq!(tree.support(&["place_order", "pick_item", "pick_item"]), Some(1));
+    let dot = tree.to_dot();
+    assert!(dot.starts_with("digraph lifecycle {"));
+    assert!(dot.contains("n0 [label=\"order (3)\", shape=ellipse];"));
+    assert!(dot.contains("n1 [label=\"place_order\\nsupport 3, drop-off 1\"];"));
+    assert!(dot.contains("n0 -> n1 [label=\"3\", penwidth=5.00];"));
+}
@@ -0,0 +1,145 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+
+use pmrs::objects::exporter::Exporter;
+use pmrs::objects::exporter::export_metrics;
+use pmrs::objects::exporter::metrics::OcelMetrics;
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+type Sample = (String, BTreeMap<String, String>, f64);
+
+// a strict reader for the subset of the OpenMetrics text format the exporter uses
+fn parse_openmetrics(text: &str) -> Result<Vec<Sample>, String> {
+    let body = text.strip_suffix("# EOF\n").ok_or("missing # EOF terminator")?;
+    let mut families: HashSet<String> = HashSet::new();
+    let mut current: Option<(String, String)> = None;
+    let mut samples: Vec<Sample> = vec![];
+    for line in body.lines() {
+        if let Some(meta) = line.strip_prefix("# ") {
+            let mut parts = meta.splitn(3, ' ');
+            let (kind, name, rest) = (parts.next().unwrap_or(""), parts.next().ok_or("metadata without name")?, parts.next().ok_or("metadata without value")?);
+            match kind {
+                "TYPE" => {
+                    if !["counter", "gauge", "summary"].contains(&rest) || !families.insert(name.to_string()) {
+                        return Err(format!("bad or repeated family: {}", line));
+                    }
+                    current = Some((name.to_string(), rest.to_string()));
+                },
+                "UNIT" if !name.ends_with(&format!("_{}", rest)) => return Err(format!("unit is not a name suffix: {}", line)),
+                "HELP" | "UNIT" if current.as_ref().map(|family| family.0.as_str()) != Some(name) => return Err(format!("metadata outside of its family: {}", line)),
+                "HELP" | "UNIT" => {},
+                _ => return Err(format!("unknown metadata: {}", line))
+            }
+            continue;
+        }
+
+        let (family, family_type) = current.as_ref().ok_or("sample before any family")?;
+        let (name, rest) = line.split_at(line.find(['{', ' ']).ok_or("sample without value")?);
+        let allowed: Vec<String> = match family_type.as_str() {
+            "counter" => vec![format!("{}_total", family)],
+            "gauge" => vec![family.to_string()],
+            _ => vec![family.to_string(), format!("{}_sum", family), format!("{}_count", family)]
+        };
+        if !allowed.contains(&name.to_string()) {
+            return Err(format!("sample {} does not belong to {}", name, family));
+        }
+
+        let mut labels: BTreeMap<String, String> = BTreeMap::new();
+        let mut chars = rest.chars().peekable();
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            loop {
+                let key: String = chars.by_ref().take_while(|c| *c != '=').collect();
+                if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') || key.is_empty() || chars.next() != Some('"') {
+                    return Err(format!("bad label in {}", line));
+                }
+                let mut value = String::new();
+                loop {
+                    match chars.next().ok_or("unterminated label value")? {
+                        '\\' => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some(c @ ('\\' | '"')) => value.push(c),
+                            _ => return Err(format!("bad escape in {}", line))
+                        },
+                        '"' => break,
+                        c => value.push(c)
+                    }
+                }
+                labels.insert(key, value);
+                match chars.next() {
+                    Some(',') => continue,
+                    Some('}') => break,
+                    _ => return Err(format!("bad label separator in {}", line))
+                }
+            }
+        }
+        let value: String = chars.collect();
+        let value: f64 = value.strip_prefix(' ').ok_or("missing space before value")?.parse().map_err(|_| format!("bad value in {}", line))?;
+        samples.push((name.to_string(), labels, value));
+    }
+    Ok(samples)
+}
+
+fn metrics_of(log: &Ocel) -> String {
+    let ocdg = generate_ocdg(log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let mut out: Vec<u8> = vec![];
+    export_metrics(log, &ocdg, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+fn value(samples: &[Sample], name: &str, labels: &[(&str, &str)]) -> Option<f64> {
+    samples.iter()
+           .find(|(sample, sample_labels, _)| sample == name && labels.iter().all(|(key, value)| sample_labels.get(*key).map(|v| v.as_str()) == Some(*value)) && sample_labels.len() == labels.len())
+           .map(|(_, _, value)| *value)
+}
+
+#[test]
+fn test_metrics_are_valid_openmetrics() {
+    let log = import_ocel("logs/fleet.jsonocel").unwrap();
+    let samples = parse_openmetrics(&metrics_of(&log)).unwrap();
+
+    assert_eq!(value(&samples, "pmrs_events_total", &[("activity", "depart")]), Some(1.0));
+    assert_eq!(value(&samples, "pmrs_objects_total", &[("type", "truck")]), Some(1.0));
+    assert_eq!(value(&samples, "pmrs_relation_edges", &[("relation", "SPLIT")]), Some(0.0));
+    assert!(value(&samples, "pmrs_relation_edges", &[("relation", "INTERACTS")]).unwrap() > 0.0);
+    // the truck lives from 08:00 to 12:00
+    assert_eq!(value(&samples, "pmrs_object_lifetime_seconds", &[("type", "truck"), ("quantile", "0.5")]), Some(4.0 * 3600.0));
+    assert_eq!(value(&samples, "pmrs_object_lifetime_seconds_count", &[("type", "van")]), Some(1.0));
+    assert_eq!(value(&samples, "pmrs_event_objects", &[("activity", "load"), ("quantile", "0.99")]), Some(3.0));
+    assert_eq!(value(&samples, "pmrs_event_objects_sum", &[("activity", "arrive")]), Some(2.0));
+}
+
+#[test]
+fn test_metrics_are_stable() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let text = metrics_of(&log);
+    assert_eq!(text, metrics_of(&log));
+    parse_openmetrics(&text).unwrap();
+    assert_eq!(text.matches("pmrs_relation_edges{").count(), Relations::iter().count());
+}
+
+#[test]
+fn test_label_values_are_escaped() {
+    let mut log = import_ocel("logs/fleet.jsonocel").unwrap();
+    let eids: Vec<usize> = log.events.keys().copied().collect();
+    for eid in eids {
+        log.events.update(&eid, |event| event.activity = "say \"hi\"\\\nbye".to_string());
+    }
+    let samples = parse_openmetrics(&metrics_of(&log)).unwrap();
+    assert_eq!(value(&samples, "pmrs_events_total", &[("activity", "say \"hi\"\\\nbye")]), Some(3.0));
+}
+
+#[test]
+fn test_export_metrics_file() {
+    let log = import_ocel("logs/fleet.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let path = std::env::temp_dir().join("pmrs-metrics.txt");
+    let path = path.to_str().unwrap();
+    OcelMetrics { ocel: &log, ocdg: &ocdg }.export(path).unwrap();
+    let content = fs::read_to_string(path).unwrap();
+    fs::remove_file(path).unwrap();
+    assert_eq!(content, metrics_of(&log));
+}
@@ -0,0 +1,30 @@
+use pmrs::algo::report::object_journey;
+use pmrs::objects::ocdg::{generate_ocdg, generate_ocdg_with, Evidence, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+#[test]
+fn test_package_journey() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let p1 = *log.object_map.get_by_left("p1").unwrap();
+    let journey = object_journey(&log, &ocdg, p1).unwrap();
+    assert_eq!(journey, include_str!("snapshots/journey-p1.md"));
+}
+
+#[test]
+fn test_journey_without_evidence() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg_with(&log, &Relations::iter().collect::<Vec<Relations>>(), Evidence::None).unwrap();
+    let p2 = *log.object_map.get_by_left("p2").unwrap();
+    let journey = object_journey(&log, &ocdg, p2).unwrap();
+
+    assert_eq!(journey, "# Journey of p2 (package)\n\n\
+                         **Born** at e9 `split_package` (2022-02-03T09:00:00+00:00) together with p3 (package).\n\n\
+                         ## Lifecycle\n\n\
+                         1. **e9** `split_package` at 2022-02-03T09:00:00+00:00\n   \
+                         - meets p1 (package), p3 (package)\n\
+                         2. **e10** `deliver_package` at 2022-02-03T10:00:00+00:00\n\n\
+                         **Died** at e10 `deliver_package` (2022-02-03T10:00:00+00:00).\n");
+    assert!(object_journey(&log, &ocdg, 999).is_none());
+}
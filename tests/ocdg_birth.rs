@@ -0,0 +1,76 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::objects::ocdg::{birth_quality, generate_ocdg_report, BirthSource, GenerationConfig, Ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+use serde_json::json;
+
+// b1 takes part in e1 but declares its creation at e2, c1 declares a creation before its only event e3
+fn skewed_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let created = [("a1", None), ("b1", Some(start + Duration::minutes(1))), ("c1", Some(start - Duration::minutes(1)))];
+    for (oid, (label, created_at)) in created.iter().enumerate() {
+        let mut ovmap = AHashMap::new();
+        if let Some(created_at) = created_at {
+            ovmap.insert("created_at".to_string(), json!(created_at.to_rfc3339()));
+        }
+        log.objects.insert(oid, OcelObject { obj_type: "item".to_string(), ovmap, events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let events: [(&str, &[&str]); 3] = [("e1", &["a1", "b1"]), ("e2", &["a1", "b1"]), ("e3", &["b1", "c1"])];
+    for (minute, (id, objects)) in events.iter().enumerate() {
+        log.insert_event(id, "step", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+// (source, target, relation) of the birth dependent relations, sorted
+fn births(log: &Ocel, ocdg: &Ocdg) -> Vec<(String, String, Relations)> {
+    let label = |oid: &usize| log.object_map.get_by_right(oid).unwrap().to_owned();
+    let mut found: Vec<(String, String, Relations)> = [Relations::COBIRTH, Relations::DESCENDANTS, Relations::INHERITANCE].iter()
+                                                          .flat_map(|rel| ocdg.objects_with_relation(*rel).into_iter().map(move |(src, tar)| (src, tar, *rel)))
+                                                          .map(|(src, tar, rel)| (label(&src), label(&tar), rel))
+                                                          .collect();
+    found.sort_by(|a, b| (&a.0, &a.1, a.2.relation_index()).cmp(&(&b.0, &b.1, b.2.relation_index())));
+    found
+}
+
+fn edge(src: &str, tar: &str, rel: Relations) -> (String, String, Relations) {
+    (src.to_string(), tar.to_string(), rel)
+}
+
+#[test]
+fn test_birth_policies() {
+    let log = skewed_log();
+    let generate = |birth_source| generate_ocdg_report(&log, &GenerationConfig { relations: vec![Relations::COBIRTH, Relations::DESCENDANTS, Relations::INHERITANCE], birth_source, ..Default::default() }).unwrap();
+
+    let (first, report) = generate(BirthSource::FirstEvent);
+    assert_eq!(births(&log, &first), vec![edge("a1", "b1", Relations::COBIRTH), edge("b1", "a1", Relations::COBIRTH),
+                                          edge("b1", "c1", Relations::DESCENDANTS), edge("b1", "c1", Relations::INHERITANCE)]);
+    assert!(report.warnings.is_empty());
+
+    // b1 is born in e2 where a1 ends, c1 counts as born before b1
+    let (declared, report) = generate(BirthSource::Attribute("created_at".to_string()));
+    assert_eq!(births(&log, &declared), vec![edge("a1", "b1", Relations::DESCENDANTS), edge("a1", "b1", Relations::INHERITANCE),
+                                             edge("b1", "c1", Relations::INHERITANCE)]);
+    assert_eq!(report.warnings, vec!["1 objects have events before their created_at, see birth_quality".to_string()]);
+    assert_eq!(declared.metadata().unwrap().config["birth_source"], "Attribute(\"created_at\")");
+
+    // the birth events stay the first events, only c1 moves before b1
+    let (earliest, _) = generate(BirthSource::Earliest("created_at".to_string()));
+    assert_eq!(births(&log, &earliest), vec![edge("a1", "b1", Relations::COBIRTH), edge("b1", "a1", Relations::COBIRTH),
+                                             edge("b1", "c1", Relations::INHERITANCE)]);
+}
+
+#[test]
+fn test_birth_quality() {
+    let log = skewed_log();
+    let quality = birth_quality(&log, "created_at");
+    assert_eq!(quality.early.len(), 1);
+    assert_eq!((quality.early[0].object.as_str(), quality.early[0].events.clone()), ("b1", vec!["e1".to_string()]));
+    assert_eq!(quality.missing, vec!["a1".to_string()]);
+}
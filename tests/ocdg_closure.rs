@@ -0,0 +1,54 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+
+// o1 gives birth to p1 in e2, p1 to s1 in e3 and s1 to d1 in e4
+fn chain_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (label, obj_type)) in [("o1", "order"), ("p1", "package"), ("s1", "shipment"), ("d1", "delivery")].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, &[&str]); 4] = [("e1", &["o1"]), ("e2", &["o1", "p1"]), ("e3", &["p1", "s1"]), ("e4", &["s1", "d1"])];
+    for (minute, (id, objects)) in events.iter().enumerate() {
+        log.insert_event(id, "step", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+fn oids(log: &Ocel, labels: &[&str]) -> IntSet<usize> {
+    labels.iter().map(|label| *log.object_map.get_by_left(*label).unwrap()).collect()
+}
+
+#[test]
+fn test_closure_depth() {
+    let log = chain_log();
+    let ocdg = generate_ocdg(&log, &[Relations::DESCENDANTS, Relations::INTERACTS]).unwrap();
+    let (o1, d1) = (*log.object_map.get_by_left("o1").unwrap(), *log.object_map.get_by_left("d1").unwrap());
+    let descendants = [Relations::DESCENDANTS];
+
+    assert_eq!(ocdg.descendants_closure(o1, &descendants, None), oids(&log, &["p1", "s1", "d1"]));
+    assert_eq!(ocdg.descendants_closure(o1, &descendants, Some(2)), oids(&log, &["p1", "s1"]));
+    assert_eq!(ocdg.descendants_closure(o1, &descendants, Some(0)), IntSet::default());
+    assert_eq!(ocdg.ancestors_closure(d1, &descendants, None), oids(&log, &["o1", "p1", "s1"]));
+    assert_eq!(ocdg.ancestors_closure(d1, &descendants, Some(1)), oids(&log, &["s1"]));
+    assert!(ocdg.descendants_closure(d1, &descendants, None).is_empty());
+}
+
+#[test]
+fn test_closure_cycles() {
+    let log = chain_log();
+    let ocdg = generate_ocdg(&log, &[Relations::DESCENDANTS, Relations::INTERACTS]).unwrap();
+    let (o1, p1) = (*log.object_map.get_by_left("o1").unwrap(), *log.object_map.get_by_left("p1").unwrap());
+
+    // INTERACTS points both ways between every pair meeting in an event
+    assert!(ocdg.has_relation(o1, p1, Relations::INTERACTS) && ocdg.has_relation(p1, o1, Relations::INTERACTS));
+    assert_eq!(ocdg.descendants_closure(o1, &[Relations::INTERACTS], None), oids(&log, &["p1", "s1", "d1"]));
+    assert_eq!(ocdg.ancestors_closure(o1, &[Relations::INTERACTS, Relations::DESCENDANTS], None), oids(&log, &["p1", "s1", "d1"]));
+}
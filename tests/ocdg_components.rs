@@ -0,0 +1,41 @@
+use nohash_hasher::IntSet;
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::Ocel;
+use strum::IntoEnumIterator;
+
+fn oids(log: &Ocel, labels: &[&str]) -> IntSet<usize> {
+    labels.iter().map(|label| *log.object_map.get_by_left(*label).unwrap()).collect()
+}
+
+#[test]
+fn test_two_disjoint_executions() {
+    // o1, i1 and i2 meet in e1, r1 and p1 in e3
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+
+    let components = ocdg.connected_components();
+    assert_eq!(components.len(), 2);
+    assert!(components.contains(&oids(&log, &["o1", "i1", "i2"])));
+    assert!(components.contains(&oids(&log, &["r1", "p1"])));
+    assert!(components.windows(2).all(|pair| pair[0].iter().min() < pair[1].iter().min()));
+
+    let i2 = *log.object_map.get_by_left("i2").unwrap();
+    assert_eq!(ocdg.component_of(i2), Some(oids(&log, &["o1", "i1", "i2"])));
+    assert_eq!(ocdg.component_of(usize::MAX), None);
+}
+
+#[test]
+fn test_components_by_relation() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+
+    // i2 and o1 share their whole lifecycle, i1 goes on to e2
+    let colife = ocdg.connected_components_with(Some(&[Relations::COLIFE]));
+    assert!(colife.contains(&oids(&log, &["o1", "i2"])));
+    assert!(colife.contains(&oids(&log, &["i1"])));
+    assert_eq!(colife.iter().map(|component| component.len()).sum::<usize>(), 5);
+
+    // without any edge every object stands alone
+    assert_eq!(ocdg.connected_components_with(Some(&[])).len(), 5);
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use std::thread;
+use pmrs::algo::transformation::ocel::features::object_point::{object_direct_rel_count, unique_neighbor_count};
+use pmrs::objects::ocdg::{Ocdg, generate_ocdg, Relations};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+
+#[test]
+fn test_ocdg_parallel_read_queries() {
+    let log: Arc<Ocel> = Arc::new(import_ocel("logs/three-days.jsonocel").unwrap());
+    let ocdg: Arc<Ocdg> = Arc::new(generate_ocdg(&log, &[Relations::INTERACTS, Relations::COBIRTH]).unwrap());
+    let oids: Vec<usize> = ocdg.inodes.keys().copied().collect();
+    let expected: Vec<(usize, usize)> = oids.iter()
+                                            .map(|oid| (unique_neighbor_count(&ocdg, oid), object_direct_rel_count(&ocdg, oid, &Relations::COBIRTH)))
+                                            .collect();
+
+    let handles: Vec<_> = (0..16).map(|_| {
+        let ocdg = Arc::clone(&ocdg);
+        let log = Arc::clone(&log);
+        let oids = oids.clone();
+        let expected = expected.clone();
+        thread::spawn(move || {
+            for _ in 0..250 {
+                for (i, oid) in oids.iter().enumerate() {
+                    assert_eq!(unique_neighbor_count(&ocdg, oid), expected[i].0);
+                    assert_eq!(object_direct_rel_count(&ocdg, oid, &Relations::COBIRTH), expected[i].1);
+                    assert!(ocdg.irels.get(oid).map_or(0, |t| t.len()) <= oids.len());
+                    assert_eq!(ocdg.node_attributes[oid].node_type, log.objects[oid].obj_type);
+                }
+            }
+        })
+    }).collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
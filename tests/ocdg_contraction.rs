@@ -0,0 +1,111 @@
+use std::fs;
+
+use pmrs::objects::ocdg::contraction::ContractNaming;
+use pmrs::objects::ocdg::exporter::export_ocdg;
+use pmrs::objects::ocdg::{generate_ocdg, Ocdg, Relations};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+fn fixture() -> (Ocel, Ocdg) {
+    let log: Ocel = import_ocel("logs/pallets.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    (log, ocdg)
+}
+
+fn oid(log: &Ocel, label: &str) -> usize {
+    *log.object_map.get_by_left(label).unwrap()
+}
+
+fn assert_consistent(ocdg: &Ocdg) {
+    assert_eq!(ocdg.inodes.len(), ocdg.net.node_count());
+    assert_eq!(ocdg.node_attributes.len(), ocdg.net.node_count());
+    assert_eq!(ocdg.object_map.len(), ocdg.net.node_count());
+    for (oid, node) in &ocdg.inodes {
+        assert_eq!(ocdg.net[*node], *oid);
+    }
+    let edge_count: usize = ocdg.iedges.values().map(|targets| targets.len()).sum();
+    assert_eq!(edge_count, ocdg.net.edge_count());
+    for (src, targets) in &ocdg.iedges {
+        for (tar, edge) in targets {
+            let (a, b) = ocdg.net.edge_endpoints(*edge).unwrap();
+            assert_eq!((ocdg.net[a], ocdg.net[b]), (*src, *tar));
+            assert!(ocdg.irels[src].contains_key(tar));
+        }
+    }
+}
+
+#[test]
+fn test_colife_groups_find_planted_clique() {
+    let (log, ocdg) = fixture();
+    let mut clique = vec![oid(&log, "pa1"), oid(&log, "x1"), oid(&log, "x2")];
+    clique.sort_unstable();
+    assert_eq!(ocdg.colife_groups(), vec![clique]);
+}
+
+#[test]
+fn test_contract_planted_clique() {
+    let (log, ocdg) = fixture();
+    let (contracted, map) = ocdg.contract(&ocdg.colife_groups(), ContractNaming::Joined("+".to_string()));
+
+    let node = map.resolve(oid(&log, "x2"));
+    assert!(map.is_super_node(node));
+    assert_eq!(map.expand(node).len(), 3);
+    assert_eq!(map.expand(oid(&log, "o1")), vec![oid(&log, "o1")]);
+    assert_eq!(contracted.net.node_count(), 3);
+    assert_eq!(contracted.object_map.get_by_right(&node).unwrap(), "pa1+x1+x2");
+    assert_eq!(contracted.node_attributes[&node].node_type, "item+pallet");
+    // the clique edges between members are dropped
+    assert!(!contracted.irels.get(&node).is_some_and(|targets| targets.contains_key(&node)));
+    assert_consistent(&contracted);
+}
+
+#[test]
+fn test_contract_unions_member_edges() {
+    let (log, ocdg) = fixture();
+    let (o1, o2) = (oid(&log, "o1"), oid(&log, "o2"));
+    let (contracted, map) = ocdg.contract(&ocdg.colife_groups(), ContractNaming::Prefixed("unit".to_string()));
+    let node = map.resolve(oid(&log, "pa1"));
+    let e1 = *log.event_map.get_by_left("e1").unwrap();
+    let e3 = *log.event_map.get_by_left("e3").unwrap();
+
+    let interacts = contracted.relation_events(o1, node, Relations::INTERACTS).unwrap();
+    assert_eq!(interacts.iter().copied().collect::<Vec<usize>>(), vec![e1]);
+    assert!(contracted.relation_events(node, o2, Relations::INTERACTS).unwrap().contains(&e3));
+    assert!(contracted.relation_events(node, o1, Relations::COBIRTH).is_some());
+    assert_eq!(contracted.object_map.get_by_right(&node).unwrap(), "unit0");
+    assert!(contracted.relation_events(o1, o2, Relations::INTERACTS).is_some());
+    assert_consistent(&contracted);
+}
+
+#[test]
+fn test_contract_unions_event_sets() {
+    let (log, ocdg) = fixture();
+    let (o1, o2, pa1) = (oid(&log, "o1"), oid(&log, "o2"), oid(&log, "pa1"));
+    let (contracted, map) = ocdg.contract(&[vec![o1, o2]], ContractNaming::Representative);
+    let node = map.resolve(o2);
+    let mut events: Vec<&str> = contracted.relation_events(node, pa1, Relations::INTERACTS).unwrap()
+                                          .iter()
+                                          .map(|eid| log.event_map.get_by_right(eid).unwrap().as_str())
+                                          .collect();
+    events.sort();
+
+    assert_eq!(node, o1.min(o2));
+    assert_eq!(events, vec!["e1", "e3"]);
+    // e4 only linked the two orders to each other
+    assert!(!contracted.event_map.contains_left("e4"));
+    assert_consistent(&contracted);
+}
+
+#[test]
+fn test_export_labels_super_nodes() {
+    let (_, ocdg) = fixture();
+    let (contracted, _) = ocdg.contract(&ocdg.colife_groups(), ContractNaming::Representative);
+    let path = std::env::temp_dir().join("pmrs-contraction.gexf");
+    let path = path.to_str().unwrap();
+    export_ocdg(&contracted, path).unwrap();
+    let content = fs::read_to_string(path).unwrap();
+    fs::remove_file(path).unwrap();
+    assert!(content.contains(r#"title="members""#));
+    assert!(content.contains(r#"<attvalue for="2" value="3"/>"#));
+}
@@ -0,0 +1,84 @@
+use std::fs;
+
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use pmrs::objects::ocdg::{generate_ocdg, generate_ocdg_with, Evidence, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+use strum::IntoEnumIterator;
+
+// two orders with one item each, every item is scanned on its own in between
+fn scan_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec!["create".to_string(), "scan".to_string(), "ship".to_string()], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for o in 0..2 {
+        log.object_map.insert(format!("o{}", o), o);
+        log.objects.insert(o, OcelObject { obj_type: "order".to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(format!("i{}", o), 2 + o);
+        log.objects.insert(2 + o, OcelObject { obj_type: "item".to_string(), ovmap: AHashMap::new(), events: vec![] });
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let mut eid = 0;
+    for o in 0..2 {
+        for (activity, omap) in [("create", vec![o, 2 + o]), ("scan", vec![2 + o]), ("ship", vec![o, 2 + o])] {
+            for oid in &omap {
+                log.objects.get_mut(oid).unwrap().events.push(eid);
+            }
+            log.events.insert(eid, OcelEvent { activity: activity.to_string(), timestamp: start + Duration::minutes(eid as i64), vmap: AHashMap::new(), omap: IntSet::from_iter(omap) });
+            log.event_map.insert(format!("e{}", eid), eid);
+            eid += 1;
+        }
+    }
+    log
+}
+
+#[test]
+fn test_single_object_activity_is_uncovered() {
+    let log = scan_log();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let report = ocdg.uncovered_events(&log).unwrap();
+
+    assert_eq!(report.events, vec!["e1", "e4"]);
+    let scan = report.activity("scan").unwrap();
+    assert_eq!((scan.events, scan.uncovered, scan.single_object), (2, 2, 2));
+    assert_eq!(scan.uncovered_rate(), 1.0);
+    assert_eq!(report.activity("create").unwrap().uncovered_rate(), 0.0);
+    assert_eq!(report.activities.iter().map(|coverage| coverage.activity.as_str()).collect::<Vec<&str>>(), vec!["create", "scan", "ship"]);
+    assert!((report.uncovered_rate() - 2.0 / 6.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_filtered_pairs_are_uncovered() {
+    let log = scan_log();
+    let mut ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    ocdg.build_event_index();
+    ocdg.retain_edges(|edge| edge.src != 0 && edge.tar != 0);
+    let report = ocdg.uncovered_events(&log).unwrap();
+
+    // the pair of the first order was removed, its events lose their evidence
+    assert_eq!(report.events, vec!["e0", "e1", "e2", "e4"]);
+    let create = report.activity("create").unwrap();
+    assert_eq!((create.uncovered, create.single_object), (1, 0));
+}
+
+#[test]
+fn test_uncovered_needs_full_evidence() {
+    let log = scan_log();
+    let ocdg = generate_ocdg_with(&log, &Relations::iter().collect::<Vec<Relations>>(), Evidence::CountsOnly).unwrap();
+    assert!(ocdg.uncovered_events(&log).is_none());
+}
+
+#[test]
+fn test_export_uncovered_csv() {
+    let log = scan_log();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let path = std::env::temp_dir().join("pmrs-uncovered.csv");
+    let path = path.to_str().unwrap();
+    ocdg.uncovered_events(&log).unwrap().export_csv(path).unwrap();
+    let content = fs::read_to_string(path).unwrap();
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(content, "activity,events,uncovered,single_object,uncovered_rate\ncreate,2,0,0,0\nscan,2,2,2,1\nship,2,0,0,0\n");
+}
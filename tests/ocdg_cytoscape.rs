@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+
+use pmrs::objects::ocdg::exporter::{export_ocdg_cytoscape, ExportOptions};
+use pmrs::objects::ocdg::importer::import_ocdg_cytoscape;
+use pmrs::objects::ocdg::{generate_ocdg, Evidence, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use serde_json::{json, Value};
+use strum::IntoEnumIterator;
+
+fn fixture() -> Ocdg {
+    generate_ocdg(&import_ocel("logs/supply.jsonocel").unwrap(), &Relations::iter().collect::<Vec<Relations>>()).unwrap()
+}
+
+// through a string like a file written and read again
+fn reparse(document: &Value) -> Value {
+    serde_json::from_str(&serde_json::to_string(document).unwrap()).unwrap()
+}
+
+fn assert_same_support(restored: &Ocdg, ocdg: &Ocdg) {
+    assert_eq!(restored.object_map.left_values().collect::<BTreeSet<&String>>(), ocdg.object_map.left_values().collect::<BTreeSet<&String>>());
+    for (src, targets) in &ocdg.irels {
+        let label = |oid: &usize| ocdg.object_map.get_by_right(oid).unwrap();
+        let restored_src = *restored.object_map.get_by_left(label(src)).unwrap();
+        assert_eq!(restored.node_attributes[&restored_src].node_type, ocdg.node_attributes[src].node_type);
+        for tar in targets.keys() {
+            let restored_tar = *restored.object_map.get_by_left(label(tar)).unwrap();
+            for rel in Relations::iter() {
+                assert_eq!(restored.relation_support(restored_src, restored_tar, rel), ocdg.relation_support(*src, *tar, rel), "{} -> {} {}", label(src), label(tar), rel);
+            }
+        }
+    }
+    assert_eq!(restored.net.edge_count(), ocdg.net.edge_count());
+}
+
+#[test]
+fn test_cytoscape_round_trip() {
+    let ocdg = fixture();
+    let document = export_ocdg_cytoscape(&ocdg, &ExportOptions::default());
+    assert_eq!(document["elements"]["nodes"].as_array().unwrap().len(), ocdg.net.node_count());
+    assert_eq!(document["data"]["generation"]["crate_version"], env!("CARGO_PKG_VERSION"));
+    let edge = &document["elements"]["edges"][0]["data"];
+    assert_eq!(edge["id"], format!("{}->{}", edge["source"].as_str().unwrap(), edge["target"].as_str().unwrap()));
+    assert_eq!(edge["interaction"].as_str().unwrap().split(',').count(), edge["support"].as_object().unwrap().len());
+
+    let restored = import_ocdg_cytoscape(&reparse(&document)).unwrap();
+    assert_eq!(restored.evidence, Evidence::CountsOnly);
+    assert_eq!(restored.metadata, ocdg.metadata);
+    assert_same_support(&restored, &ocdg);
+    assert_eq!(export_ocdg_cytoscape(&restored, &ExportOptions::default()), document);
+}
+
+#[test]
+fn test_cytoscape_per_relation_keys() {
+    let ocdg = fixture();
+    let options = ExportOptions { per_relation_keys: true, ..Default::default() };
+    let document = export_ocdg_cytoscape(&ocdg, &options);
+
+    let edges = document["elements"]["edges"].as_array().unwrap();
+    // every edge holds every relation key, absent relations at 0
+    for edge in edges {
+        assert!(edge["data"].get("support").is_none());
+        for rel in Relations::iter() {
+            assert!(edge["data"][format!("rel_{}", rel)].is_u64());
+        }
+    }
+    let cobirth: BTreeSet<(String, String)> = edges.iter()
+                                                   .filter(|edge| edge["data"]["rel_COBIRTH"].as_u64().unwrap() > 0)
+                                                   .map(|edge| (edge["data"]["source"].as_str().unwrap().to_owned(), edge["data"]["target"].as_str().unwrap().to_owned()))
+                                                   .collect();
+    let label = |oid: &usize| ocdg.object_map.get_by_right(oid).unwrap().to_owned();
+    let expected: BTreeSet<(String, String)> = ocdg.irels.iter()
+                                                   .flat_map(|(src, targets)| targets.iter().filter(|(_, rels)| rels.contains_key(&Relations::COBIRTH.relation_index().into())).map(move |(tar, _)| (*src, *tar)))
+                                                   .map(|(src, tar)| (label(&src), label(&tar)))
+                                                   .collect();
+    assert!(!cobirth.is_empty());
+    assert_eq!(cobirth, expected);
+
+    let restored = import_ocdg_cytoscape(&reparse(&document)).unwrap();
+    assert_same_support(&restored, &ocdg);
+    assert_eq!(export_ocdg_cytoscape(&restored, &options), document);
+}
+
+#[test]
+fn test_cytoscape_relations_filter() {
+    let ocdg = fixture();
+    let options = ExportOptions { per_relation_keys: true, relations: Some(vec![Relations::COBIRTH]), ..Default::default() };
+    let document = export_ocdg_cytoscape(&ocdg, &options);
+    // edges without the relation are dropped and the other keys are not written
+    for edge in document["elements"]["edges"].as_array().unwrap() {
+        assert_eq!(edge["data"]["interaction"], "COBIRTH");
+        assert!(edge["data"]["rel_COBIRTH"].as_u64().unwrap() > 0);
+        assert!(edge["data"].get("rel_INTERACTS").is_none());
+    }
+}
+
+#[test]
+fn test_cytoscape_invalid() {
+    let ocdg = fixture();
+    let mut document = export_ocdg_cytoscape(&ocdg, &ExportOptions::default());
+    document["elements"]["edges"][0]["data"]["target"] = json!("missing");
+    assert!(import_ocdg_cytoscape(&document).err().unwrap().to_string().contains("has no node missing"));
+
+    let mut document = export_ocdg_cytoscape(&ocdg, &ExportOptions { per_relation_keys: true, ..Default::default() });
+    document["elements"]["edges"][0]["data"]["weight"] = json!(3);
+    assert!(import_ocdg_cytoscape(&document).err().unwrap().to_string().contains("unknown key weight"));
+    assert!(import_ocdg_cytoscape(&json!({"elements": []})).is_err());
+}
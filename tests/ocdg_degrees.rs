@@ -0,0 +1,57 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+
+// the clerk handles o1, o2 and o3 one after the other
+fn clerk_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (label, obj_type)) in [("clerk", "employee"), ("o1", "order"), ("o2", "order"), ("o3", "order")].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, &[&str]); 4] = [("e1", &["clerk", "o1"]), ("e2", &["clerk", "o2"]), ("e3", &["clerk", "o3"]), ("e4", &["o1"])];
+    for (minute, (id, objects)) in events.iter().enumerate() {
+        log.insert_event(id, "step", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+#[test]
+fn test_degrees_per_relation() {
+    let log = clerk_log();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS, Relations::DESCENDANTS]).unwrap();
+    let oid = |label: &str| *log.object_map.get_by_left(label).unwrap();
+
+    let interacts = ocdg.object_degrees(Some(Relations::INTERACTS));
+    assert_eq!(interacts[&oid("clerk")], (3, 3));
+    assert_eq!(interacts[&oid("o2")], (1, 1));
+
+    // o1 is born with the clerk, o2 and o3 descend from it
+    let descendants = ocdg.object_degrees(Some(Relations::DESCENDANTS));
+    assert_eq!(descendants[&oid("clerk")], (0, 2));
+    assert_eq!(descendants[&oid("o1")], (0, 0));
+    assert_eq!(descendants[&oid("o3")], (1, 0));
+
+    // the clerk's edge to o2 holds both relations and counts once
+    assert_eq!(ocdg.object_degrees(None)[&oid("clerk")], (3, 3));
+    assert_eq!(ocdg.object_degrees(None).len(), 4);
+}
+
+#[test]
+fn test_top_k_by_degree() {
+    let log = clerk_log();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS, Relations::DESCENDANTS]).unwrap();
+    let oid = |label: &str| *log.object_map.get_by_left(label).unwrap();
+
+    // INTERACTS links every pair both ways, each order is one neighbour of the clerk
+    assert_eq!(ocdg.top_k_by_degree(Some(Relations::INTERACTS), 2), vec![(oid("clerk"), 3), (oid("o1"), 1)]);
+    assert_eq!(ocdg.top_k_by_degree(Some(Relations::DESCENDANTS), 10), vec![(oid("clerk"), 2), (oid("o2"), 1), (oid("o3"), 1), (oid("o1"), 0)]);
+    assert_eq!(ocdg.top_k_by_degree(None, 1), vec![(oid("clerk"), 3)]);
+    assert!(ocdg.top_k_by_degree(None, 0).is_empty());
+}
@@ -0,0 +1,72 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+
+// every order interacts with every item, pair (o, i) shares 1 + (o + i) % 3 events
+fn dense_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec!["work".to_string()], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for o in 0..4 {
+        log.object_map.insert(format!("o{}", o), o);
+        log.objects.insert(o, OcelObject { obj_type: "order".to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(format!("i{}", o), 4 + o);
+        log.objects.insert(4 + o, OcelObject { obj_type: "item".to_string(), ovmap: AHashMap::new(), events: vec![] });
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let mut eid = 0;
+    for o in 0..4 {
+        for i in 0..4 {
+            for _ in 0..(1 + (o + i) % 3) {
+                let omap: IntSet<usize> = IntSet::from_iter([o, 4 + i]);
+                log.events.insert(eid, OcelEvent { activity: "work".to_string(), timestamp: start + Duration::minutes(eid as i64), vmap: AHashMap::new(), omap });
+                log.event_map.insert(format!("e{}", eid), eid);
+                log.objects.get_mut(&o).unwrap().events.push(eid);
+                log.objects.get_mut(&(4 + i)).unwrap().events.push(eid);
+                eid += 1;
+            }
+        }
+    }
+    log
+}
+
+#[test]
+fn test_density_report_flags_dense_combinations() {
+    let log = dense_log();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+    let report = ocdg.density_report(&log, 0.5);
+
+    let entry = report.entries.iter().find(|e| e.src_type == "order" && e.tar_type == "item").unwrap();
+    assert_eq!(entry.edges, 16);
+    assert_eq!(entry.max_edges, 16);
+    assert_eq!(entry.density, 1.0);
+    assert!(entry.flagged);
+    // supports are 1 x6, 2 x5, 3 x5: only the 5 edges with 3 events fit into 8 allowed edges
+    assert_eq!(entry.suggested_min_edge_events, Some(3));
+    assert_eq!(report.flagged().count(), 2);
+}
+
+#[test]
+fn test_density_report_threshold_reaches_target() {
+    let log = dense_log();
+    for target in [0.1, 0.3, 0.5, 0.75, 0.9] {
+        let mut ocdg = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+        let report = ocdg.density_report(&log, target);
+        report.apply(&mut ocdg);
+
+        let after = ocdg.density_report(&log, target);
+        assert!(after.entries.iter().all(|entry| entry.density <= target), "target {}", target);
+        assert_eq!(after.flagged().count(), 0);
+
+        // one event less would have kept too many edges
+        for entry in report.flagged() {
+            let threshold = entry.suggested_min_edge_events.unwrap();
+            let looser = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+            let kept = looser.irels.values().flat_map(|t| t.values()).filter(|rels| rels.values().any(|e| e.len() >= threshold - 1)).count() / 2;
+            assert!(threshold == 1 || kept as f64 / entry.max_edges as f64 > target);
+        }
+    }
+}
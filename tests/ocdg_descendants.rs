@@ -0,0 +1,49 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::objects::ocdg::{generate_ocdg_config, DescendantsEvidence, GenerationConfig, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+
+// p1 gives birth to c1 in e2 and meets it three more times, c1 gives birth to g1 in e6
+fn repeat_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, label) in ["p1", "c1", "g1"].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: "item".to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, &[&str]); 6] = [("e1", &["p1"]), ("e2", &["p1", "c1"]), ("e3", &["p1", "c1"]), ("e4", &["p1", "c1"]), ("e5", &["p1", "c1"]), ("e6", &["c1", "g1"])];
+    for (minute, (id, objects)) in events.iter().enumerate() {
+        log.insert_event(id, "step", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+fn labels(log: &Ocel, eids: &nohash_hasher::IntSet<usize>) -> Vec<String> {
+    let mut labels: Vec<String> = eids.iter().map(|eid| log.event_map.get_by_right(eid).unwrap().to_owned()).collect();
+    labels.sort();
+    labels
+}
+
+#[test]
+fn test_descendants_evidence_modes() {
+    let log = repeat_log();
+    let (p1, c1, g1) = (log.object_map.get_by_left("p1").copied().unwrap(), log.object_map.get_by_left("c1").copied().unwrap(), log.object_map.get_by_left("g1").copied().unwrap());
+    let config = |descendants_evidence| GenerationConfig { relations: vec![Relations::DESCENDANTS, Relations::REACHABLE], descendants_evidence, ..Default::default() };
+
+    let birth = generate_ocdg_config(&log, &config(DescendantsEvidence::default())).unwrap();
+    assert_eq!(labels(&log, birth.relation_events(p1, c1, Relations::DESCENDANTS).unwrap()), vec!["e2"]);
+
+    let shared = generate_ocdg_config(&log, &config(DescendantsEvidence::AllSharedEvents)).unwrap();
+    assert_eq!(labels(&log, shared.relation_events(p1, c1, Relations::DESCENDANTS).unwrap()), vec!["e2", "e3", "e4", "e5"]);
+    assert_eq!(labels(&log, shared.relation_events(c1, g1, Relations::DESCENDANTS).unwrap()), vec!["e6"]);
+    assert_eq!(shared.metadata().unwrap().config["descendants_evidence"], "AllSharedEvents");
+
+    // the chains follow the births in both modes
+    for ocdg in [&birth, &shared] {
+        assert_eq!(labels(&log, ocdg.relation_events(p1, g1, Relations::REACHABLE).unwrap()), vec!["e2", "e6"]);
+    }
+}
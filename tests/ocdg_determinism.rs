@@ -0,0 +1,39 @@
+use nohash_hasher::IntMap;
+use petgraph::visit::EdgeRef;
+use pmrs::objects::ocdg::{generate_ocdg, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::{EventColumns, Ocel};
+use strum::IntoEnumIterator;
+
+// (source, target) of every edge and the object of every node in index order
+fn indexed(ocdg: &Ocdg) -> (Vec<usize>, Vec<(usize, usize)>) {
+    let nodes = ocdg.net.node_indices().map(|node| ocdg.net[node]).collect();
+    let edges = ocdg.net.edge_references().map(|edge| (ocdg.net[edge.source()], ocdg.net[edge.target()])).collect();
+    (nodes, edges)
+}
+
+// the same log with its hash maps filled in reverse order
+fn reinserted(log: &Ocel) -> Ocel {
+    let mut copy = log.clone();
+    let mut events: Vec<_> = log.events.iter().map(|(eid, event)| (*eid, event.to_event())).collect();
+    events.sort_unstable_by_key(|(eid, _)| usize::MAX - eid);
+    copy.events = EventColumns::default();
+    copy.events.extend(events);
+    let mut objects: Vec<_> = log.objects.iter().map(|(oid, obj)| (*oid, obj.clone())).collect();
+    objects.sort_unstable_by_key(|(oid, _)| usize::MAX - oid);
+    copy.objects = IntMap::default();
+    copy.objects.extend(objects);
+    copy
+}
+
+#[test]
+fn test_generation_is_deterministic() {
+    let relations: Vec<Relations> = Relations::iter().collect();
+    for fixture in ["order-to-cash", "fleet", "supply"] {
+        let log = import_ocel(&format!("logs/{}.jsonocel", fixture)).unwrap();
+        let first = indexed(&generate_ocdg(&log, &relations).unwrap());
+        assert!(!first.1.is_empty());
+        assert_eq!(first, indexed(&generate_ocdg(&log, &relations).unwrap()), "{}", fixture);
+        assert_eq!(first, indexed(&generate_ocdg(&reinserted(&log), &relations).unwrap()), "{}", fixture);
+    }
+}
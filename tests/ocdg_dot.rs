@@ -0,0 +1,76 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::objects::ocdg::exporter::{export_ocdg_dot, ocdg_to_dot, DotOptions};
+use pmrs::objects::ocdg::{generate_ocdg_config, GenerationConfig, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+
+// "p\"1" spawns s1 and s2, the lonely object never meets anyone
+fn split_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (label, obj_type)) in [("p\"1", "pallet"), ("s1", "shipment"), ("s2", "shipment"), ("lonely", "shipment")].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, &[&str]); 4] = [("e1", &["p\"1"]), ("e2", &["p\"1", "s1", "s2"]), ("e3", &["s1"]), ("e4", &["lonely"])];
+    for (minute, (id, objects)) in events.iter().enumerate() {
+        log.insert_event(id, "step", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+#[test]
+fn test_dot_nodes_and_edges() {
+    let log = split_log();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::INTERACTS, Relations::DESCENDANTS], ..Default::default() }).unwrap();
+    let dot = ocdg_to_dot(&ocdg, &log, &DotOptions::default());
+
+    assert!(dot.starts_with("digraph ocdg {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(dot.contains("    n0 [label=\"p\\\"1\\npallet\"];\n"));
+    // objects without edges are still drawn
+    assert!(dot.contains("    n3 [label=\"lonely\\nshipment\"];\n"));
+    assert!(!dot.contains("n3 ->") && !dot.contains("-> n3"));
+    assert!(dot.contains("    n0 -> n1 [label=\"INTERACTS\\nDESCENDANTS\"];\n"));
+    assert!(!dot.contains("color="));
+}
+
+#[test]
+fn test_dot_colors_and_file() {
+    let log = split_log();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::INTERACTS, Relations::DESCENDANTS], ..Default::default() }).unwrap();
+    let options = DotOptions { color_relations: true, ..Default::default() };
+    let dot = ocdg_to_dot(&ocdg, &log, &options);
+    assert!(dot.contains("    n0 -> n1 [label=\"INTERACTS\\nDESCENDANTS\", color=\"gray60:blue\"];\n"));
+    assert!(dot.contains("    n1 -> n2 [label=\"INTERACTS\", color=\"gray60\"];\n"));
+
+    let path = std::env::temp_dir().join("pmrs-ocdg.dot");
+    assert!(export_ocdg_dot(&ocdg, &log, path.to_str().unwrap(), &options).unwrap());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), dot);
+}
+
+#[test]
+fn test_dot_relation_filter_and_undirected() {
+    let log = split_log();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::INTERACTS, Relations::DESCENDANTS], ..Default::default() }).unwrap();
+
+    let descendants = ocdg_to_dot(&ocdg, &log, &DotOptions { relations: Some(vec![Relations::DESCENDANTS]), ..Default::default() });
+    assert!(descendants.contains("    n0 -> n1 [label=\"DESCENDANTS\"];\n"));
+    assert!(descendants.contains("    n0 -> n2 [label=\"DESCENDANTS\"];\n"));
+    // the pairs only interacting are left out, their nodes stay
+    assert!(!descendants.contains("INTERACTS"));
+    assert!(!descendants.contains("n1 -> n0") && !descendants.contains("n1 -> n2"));
+    assert!(descendants.contains("    n1 [label=\"s1\\nshipment\"];\n"));
+
+    let undirected = ocdg_to_dot(&ocdg, &log, &DotOptions { undirected: true, ..Default::default() });
+    assert!(undirected.starts_with("graph ocdg {\n"));
+    assert!(!undirected.contains("->"));
+    // n1 -> n0 only interacts, merged with n0 -> n1 into a single edge
+    assert!(undirected.contains("    n0 -- n1 [label=\"INTERACTS\\nDESCENDANTS\"];\n"));
+    assert!(undirected.contains("    n1 -- n2 [label=\"INTERACTS\"];\n"));
+    assert_eq!(undirected.matches(" -- ").count(), 3);
+}
@@ -0,0 +1,69 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use petgraph::visit::EdgeRef;
+use pmrs::objects::ocdg::{generate_ocdg, generate_ocdg_with, Evidence, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+use strum::IntoEnumIterator;
+
+// a and b are born together and share three events, c joins b in the last one
+fn shared_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, label) in ["a", "b", "c"].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: "part".to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    for (eid, omap) in [vec![0, 1], vec![0, 1], vec![0, 1], vec![1, 2]].into_iter().enumerate() {
+        log.events.insert(eid, OcelEvent { activity: "work".to_string(), timestamp: start + Duration::minutes(eid as i64), vmap: AHashMap::new(), omap: IntSet::from_iter(omap) });
+        log.event_map.insert(format!("e{}", eid), eid);
+    }
+    log.rebuild_derived_indices();
+    log
+}
+
+fn weight(ocdg: &Ocdg, src: usize, tar: usize) -> usize {
+    ocdg.net[ocdg.iedges[&src][&tar]]
+}
+
+// the size of the union of all relation event sets of every edge
+fn assert_weights_are_unions(ocdg: &Ocdg) {
+    for edge in ocdg.net.edge_references() {
+        let (src, tar) = (ocdg.net[edge.source()], ocdg.net[edge.target()]);
+        let union: IntSet<usize> = ocdg.irels[&src][&tar].values().flatten().copied().collect();
+        assert_eq!(*edge.weight(), union.len(), "{} -> {}", src, tar);
+    }
+}
+
+#[test]
+fn test_weight_is_the_union_of_supporting_events() {
+    let log = shared_log();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    // INTERACTS holds e0, e1 and e2, COBIRTH only e0
+    assert!(ocdg.relation_events(0, 1, Relations::COBIRTH).is_some());
+    assert_eq!(weight(&ocdg, 0, 1), 3);
+    assert_eq!(weight(&ocdg, 1, 2), ocdg.edge_support(1, 2));
+    assert_weights_are_unions(&ocdg);
+
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let mut ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    assert_weights_are_unions(&ocdg);
+    // dropping relations keeps the weights in sync
+    ocdg.retain_edges(|edge| edge.relations.len() > 1);
+    assert_weights_are_unions(&ocdg);
+}
+
+#[test]
+fn test_weight_without_event_sets() {
+    let log = shared_log();
+    let relations: Vec<Relations> = vec![Relations::INTERACTS, Relations::COBIRTH];
+    let counts = generate_ocdg_with(&log, &relations, Evidence::CountsOnly).unwrap();
+    // e0 is counted for both relations
+    assert_eq!(weight(&counts, 0, 1), 4);
+    let bare = generate_ocdg_with(&log, &relations, Evidence::None).unwrap();
+    assert_eq!(weight(&bare, 0, 1), 0);
+}
@@ -0,0 +1,44 @@
+use nohash_hasher::IntSet;
+use pmrs::objects::ocdg::exporter::{ocdg_to_graphml, ExportOptions};
+use pmrs::objects::ocdg::{generate_ocdg, OcdgError, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::Ocel;
+use strum::IntoEnumIterator;
+
+fn oids(log: &Ocel, labels: &[&str]) -> IntSet<usize> {
+    labels.iter().map(|label| *log.object_map.get_by_left(*label).unwrap()).collect()
+}
+
+#[test]
+fn test_ego_network_hops() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let o1 = *log.object_map.get_by_left("o1").unwrap();
+    let nodes = |hops: usize, rels: Option<&[Relations]>| ocdg.ego_network(o1, hops, rels).unwrap().node_attributes.keys().copied().collect::<IntSet<usize>>();
+
+    assert_eq!(nodes(0, None), oids(&log, &["o1"]));
+    assert_eq!(nodes(1, None), oids(&log, &["o1", "i1", "i2"]));
+    // i2 and o1 share their whole lifecycle
+    assert_eq!(nodes(3, Some(&[Relations::COLIFE])), oids(&log, &["o1", "i2"]));
+
+    let ego = ocdg.ego_network(o1, 1, Some(&[Relations::COLIFE])).unwrap();
+    let colife: usize = Relations::COLIFE.relation_index().into();
+    assert!(ego.irels.values().flat_map(|targets| targets.values()).all(|rels| rels.keys().eq([&colife])));
+    assert_eq!(ego.net.edge_count(), 2);
+    assert_eq!(ego.object_map.len(), 2);
+    assert!(ocdg_to_graphml(&ego, &log, &ExportOptions::default()).matches("<node ").count() == 2);
+
+    assert_eq!(ocdg.ego_network(1000, 1, None).unwrap_err(), OcdgError::UnknownObject(1000));
+}
+
+#[test]
+fn test_ego_network_reaches_component() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    for oid in ocdg.node_attributes.keys() {
+        let ego = ocdg.ego_network(*oid, ocdg.object_count(), None).unwrap();
+        assert_eq!(ego.node_attributes.keys().copied().collect::<IntSet<usize>>(), ocdg.component_of(*oid).unwrap());
+        let pairs: usize = ego.irels.values().map(|targets| targets.len()).sum();
+        assert_eq!(ego.net.edge_count(), pairs);
+    }
+}
@@ -0,0 +1,65 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::objects::ocdg::estimate::estimate;
+use pmrs::objects::ocdg::{generate_ocdg, generate_ocdg_config, GenerationConfig, OcdgError, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+use strum::IntoEnumIterator;
+
+fn order_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (label, obj_type)) in [("o1", "order"), ("i1", "item"), ("i2", "item")].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, &[&str]); 3] = [("e1", &["o1", "i1", "i2"]), ("e2", &["i1"]), ("e3", &["o1", "i2"])];
+    for (minute, (id, objects)) in events.iter().enumerate() {
+        log.insert_event(id, "step", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+fn oid(log: &Ocel, label: &str) -> usize {
+    *log.object_map.get_by_left(label).unwrap()
+}
+
+#[test]
+fn test_consistent_log() {
+    let log = order_log();
+    assert!(generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).is_ok());
+}
+
+#[test]
+fn test_missing_object() {
+    let mut log = order_log();
+    let i2 = oid(&log, "i2");
+    log.objects.remove(&i2);
+    let error = generate_ocdg_config(&log, &GenerationConfig { relations: Relations::iter().collect(), ..Default::default() }).err().unwrap();
+    assert!(matches!(&error, OcdgError::MissingObject { object, .. } if object == "i2"), "{:?}", error);
+    assert!(error.to_string().contains("references the object i2 which is not part of the log"), "{}", error);
+    // the estimate walks the same input
+    assert!(estimate(&log, &GenerationConfig { relations: vec![Relations::INTERACTS], ..Default::default() }).is_err());
+}
+
+#[test]
+fn test_empty_lifecycle() {
+    let mut log = order_log();
+    let i1 = oid(&log, "i1");
+    log.objects.get_mut(&i1).unwrap().events.clear();
+    let error = generate_ocdg(&log, &[Relations::DESCENDANTS]).err().unwrap();
+    assert_eq!(error, OcdgError::EmptyLifecycle("i1".to_owned()));
+    assert!(error.to_string().starts_with("Object i1 takes part in events but its lifecycle is empty"), "{}", error);
+}
+
+#[test]
+fn test_missing_event() {
+    let mut log = order_log();
+    let o1 = oid(&log, "o1");
+    log.objects.get_mut(&o1).unwrap().events.push(99);
+    let error = generate_ocdg(&log, &[Relations::INTERACTS]).err().unwrap();
+    assert_eq!(error, OcdgError::MissingEvent { object: "o1".to_owned(), event: "#99".to_owned() });
+}
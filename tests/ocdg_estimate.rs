@@ -0,0 +1,81 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use pmrs::objects::ocdg::estimate::{estimate, estimate_with, EstimateConfig};
+use pmrs::objects::ocdg::{generate_ocdg_config, GenerationConfig, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+use strum::IntoEnumIterator;
+
+fn all_relations() -> GenerationConfig {
+    GenerationConfig { relations: Relations::iter().collect(), ..Default::default() }
+}
+
+// orders placed with two items each, every third order also gets an invoice
+fn order_log(orders: usize) -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let add_event = |log: &mut Ocel, activity: &str, omap: Vec<usize>| {
+        let eid = log.events.len();
+        log.events.insert(eid, OcelEvent { activity: activity.to_string(), timestamp: start + Duration::minutes(eid as i64), vmap: AHashMap::new(), omap: IntSet::from_iter(omap) });
+        log.event_map.insert(format!("e{}", eid), eid);
+    };
+    for x in 0..orders {
+        let objects = [("order", format!("o{}", x)), ("item", format!("i{}a", x)), ("item", format!("i{}b", x)), ("invoice", format!("r{}", x))];
+        for (obj_type, label) in &objects {
+            let oid = log.objects.len();
+            log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+            log.object_map.insert(label.to_owned(), oid);
+        }
+        let [order, item_a, item_b, invoice] = [0, 1, 2, 3].map(|i| *log.object_map.get_by_left(&objects[i].1).unwrap());
+        add_event(&mut log, "place_order", vec![order, item_a, item_b]);
+        add_event(&mut log, "pick_item", vec![item_a]);
+        add_event(&mut log, "pick_item", vec![item_b]);
+        if x % 3 == 0 {
+            add_event(&mut log, "create_invoice", vec![order, invoice]);
+        }
+        add_event(&mut log, "ship_order", vec![order]);
+    }
+    log.rebuild_derived_indices();
+    log
+}
+
+#[test]
+fn test_estimate_within_factor_two() {
+    let log = order_log(150);
+    let config = all_relations();
+    let ocdg = generate_ocdg_config(&log, &config).unwrap();
+    let estimate = estimate_with(&log, &config, &EstimateConfig { sample_objects: 60, seed: 3 }).unwrap();
+
+    assert_eq!(estimate.nodes, ocdg.net.node_count());
+    assert_eq!(estimate.sampled_objects, 60);
+    let within = |expected: f64, actual: usize| expected <= 2.0 * actual as f64 && actual as f64 <= 2.0 * expected;
+    assert!(within(estimate.graph_edges.expected, ocdg.net.edge_count()));
+    for (rel, count) in ocdg.metadata().unwrap().edge_counts.clone() {
+        if count > 0 {
+            assert!(within(estimate.edges[&rel].expected, count), "{}: {:?} vs {}", rel, estimate.edges[&rel], count);
+        }
+    }
+    assert!(estimate.graph_edges.low <= estimate.graph_edges.expected && estimate.graph_edges.expected <= estimate.graph_edges.high);
+    assert!(estimate.peak_bytes.expected > log.memory_breakdown().total() as f64);
+    assert!(estimate.runtime.expected > 0.0);
+}
+
+#[test]
+fn test_full_sample_is_exact() {
+    let log = import_ocel("logs/order-to-cash.jsonocel").unwrap();
+    let config = all_relations();
+    let ocdg = generate_ocdg_config(&log, &config).unwrap();
+    let estimate = estimate(&log, &config).unwrap();
+
+    assert_eq!(estimate.sampled_objects, 8);
+    assert_eq!(estimate.graph_edges.expected, ocdg.net.edge_count() as f64);
+    assert_eq!(estimate.graph_edges.low, estimate.graph_edges.high);
+    for (rel, count) in ocdg.metadata().unwrap().edge_counts.clone() {
+        assert_eq!(estimate.edges[&rel].expected.round() as usize, count, "{}", rel);
+    }
+    assert!(estimate.to_string().contains("graph edges"));
+}
@@ -0,0 +1,40 @@
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+#[test]
+fn test_event_index_matches_scan() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let mut ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let scanned: Vec<_> = log.events.keys().map(|eid| ocdg.relations_of_event(*eid).unwrap()).collect();
+
+    ocdg.build_event_index();
+    let indexed: Vec<_> = log.events.keys().map(|eid| ocdg.relations_of_event(*eid).unwrap()).collect();
+    assert_eq!(scanned, indexed);
+
+    // every evidence event of every relation instance is in the index
+    let entries: usize = ocdg.ievents.as_ref().unwrap().values().map(|instances| instances.len()).sum();
+    let evidence: usize = ocdg.irels.values().flat_map(|t| t.values()).flat_map(|r| r.values()).map(|e| e.len()).sum();
+    assert_eq!(entries, evidence);
+
+    let e9 = *log.event_map.get_by_left("e9").unwrap();
+    let p1 = *log.object_map.get_by_left("p1").unwrap();
+    let p2 = *log.object_map.get_by_left("p2").unwrap();
+    assert!(ocdg.relations_of_event(e9).unwrap().contains(&(p1, p2, Relations::SPLIT)));
+}
+
+#[test]
+fn test_event_index_follows_retain_edges() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let mut ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    ocdg.build_event_index();
+    ocdg.retain_edges(|edge| !edge.has_relation(Relations::SPLIT));
+
+    let e9 = *log.event_map.get_by_left("e9").unwrap();
+    let indexed = ocdg.relations_of_event(e9).unwrap();
+    assert!(!indexed.is_empty());
+    assert!(indexed.iter().all(|(_, _, rel)| *rel != Relations::SPLIT));
+
+    ocdg.ievents = None;
+    assert_eq!(indexed, ocdg.relations_of_event(e9).unwrap());
+}
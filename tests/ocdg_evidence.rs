@@ -0,0 +1,53 @@
+use std::collections::BTreeSet;
+use pmrs::objects::ocdg::{generate_ocdg_with, Evidence, Ocdg, Relations};
+use pmrs::objects::ocdg::exporter::export_ocdg;
+use pmrs::objects::ocdg::importer::import_ocdg;
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+fn presence(ocdg: &Ocdg) -> BTreeSet<(usize, usize, usize)> {
+    ocdg.irels.iter()
+              .flat_map(|(src, targets)| targets.iter().flat_map(move |(tar, rels)| rels.keys().map(move |rel| (*src, *tar, *rel))))
+              .collect()
+}
+
+fn stored_events(ocdg: &Ocdg) -> usize {
+    ocdg.irels.values().flat_map(|t| t.values()).flat_map(|r| r.values()).map(|e| e.len()).sum()
+}
+
+#[test]
+fn test_relation_presence_identical_across_modes() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let relations: Vec<Relations> = Relations::iter().collect();
+    let full = generate_ocdg_with(&log, &relations, Evidence::Full).unwrap();
+    let counts = generate_ocdg_with(&log, &relations, Evidence::CountsOnly).unwrap();
+    let bare = generate_ocdg_with(&log, &relations, Evidence::None).unwrap();
+
+    assert_eq!(presence(&full), presence(&counts));
+    assert_eq!(presence(&full), presence(&bare));
+    assert_eq!(full.net.edge_count(), bare.net.edge_count());
+
+    // only full evidence keeps the event sets around
+    assert!(stored_events(&full) > 0);
+    assert_eq!(stored_events(&counts), 0);
+    assert_eq!(stored_events(&bare), 0);
+
+    for (src, tar, rel) in presence(&full) {
+        let rel = Relations::from_index(rel).unwrap();
+        assert_eq!(full.relation_support(src, tar, rel), counts.relation_support(src, tar, rel));
+        assert!(full.relation_events(src, tar, rel).is_some());
+        assert!(counts.relation_events(src, tar, rel).is_none());
+        assert!(bare.relation_support(src, tar, rel).is_none());
+    }
+}
+
+#[test]
+fn test_reduced_evidence_queries_and_exports() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let counts = generate_ocdg_with(&log, &[Relations::INTERACTS, Relations::SPLIT], Evidence::CountsOnly).unwrap();
+    assert!(counts.relations_of_event(0).is_none());
+
+    let path = std::env::temp_dir().join("pmrs-counts-only.gexf");
+    assert!(export_ocdg(&counts, path.to_str().unwrap()).unwrap());
+    assert_eq!(import_ocdg(path.to_str().unwrap()).unwrap().net.edge_count(), counts.net.edge_count());
+}
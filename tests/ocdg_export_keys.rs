@@ -0,0 +1,191 @@
+use std::{collections::BTreeSet, fs, io::{self, Write}};
+
+use quick_xml::{events::Event, Reader};
+use pmrs::objects::ocdg::exporter::{export_ocdg_graphml, export_ocdg_graphml_to, export_ocdg_with, ocdg_to_graphml, ExportOptions};
+use pmrs::objects::ocdg::{generate_ocdg, Ocdg, Relations};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+fn fixture() -> (Ocel, Ocdg) {
+    let log: Ocel = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    (log, ocdg)
+}
+
+fn export<F>(name: &str, write: F) -> String where F: FnOnce(&str) {
+    let path = std::env::temp_dir().join(name);
+    let path = path.to_str().unwrap();
+    write(path);
+    let content = fs::read_to_string(path).unwrap();
+    fs::remove_file(path).unwrap();
+    content
+}
+
+fn attr(e: &quick_xml::events::BytesStart, name: &[u8]) -> Option<String> {
+    e.attributes().filter_map(|a| a.ok()).find(|a| a.key == name).map(|a| String::from_utf8(a.value.into_owned()).unwrap())
+}
+
+// edges whose data under the given key parses to a positive count
+fn positive_edges(content: &str, edge_tag: &[u8], data_tag: &[u8], key_attr: &[u8], key: &str) -> BTreeSet<(usize, usize)> {
+    let mut reader = Reader::from_str(content);
+    let mut buf = vec![];
+    let mut edges = BTreeSet::new();
+    let mut current: Option<(usize, usize)> = None;
+    let mut in_key = false;
+    let parse = |id: String| id.trim_start_matches('n').parse::<usize>().unwrap();
+    loop {
+        match reader.read_event(&mut buf).unwrap() {
+            Event::Start(e) | Event::Empty(e) if e.name() == edge_tag => {
+                current = Some((parse(attr(&e, b"source").unwrap()), parse(attr(&e, b"target").unwrap())));
+            },
+            Event::Start(e) if e.name() == data_tag && attr(&e, key_attr).as_deref() == Some(key) => in_key = true,
+            Event::Empty(e) if e.name() == data_tag && attr(&e, key_attr).as_deref() == Some(key) && attr(&e, b"value").unwrap().parse::<usize>().unwrap() > 0 => {
+                edges.insert(current.unwrap());
+            },
+            Event::Text(t) if in_key => {
+                if t.unescape_and_decode(&reader).unwrap().parse::<usize>().unwrap() > 0 {
+                    edges.insert(current.unwrap());
+                }
+                in_key = false;
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    edges
+}
+
+fn relation_edges(ocdg: &Ocdg, rel: Relations) -> BTreeSet<(usize, usize)> {
+    ocdg.irels.iter()
+        .flat_map(|(src, targets)| targets.iter().filter(|(_, rels)| rels.contains_key(&rel.relation_index().into())).map(move |(tar, _)| (*src, *tar)))
+        .collect()
+}
+
+#[test]
+fn test_graphml_per_relation_keys() {
+    let (_, ocdg) = fixture();
+    let content = export("pmrs-keys.graphml", |path| { export_ocdg_graphml(&ocdg, path, &ExportOptions { per_relation_keys: true, ..Default::default() }).unwrap(); });
+
+    assert_eq!(content.matches(r#"<key id="rel_COBIRTH""#).count(), 1);
+    assert!(content.find("<key").unwrap() < content.find("<graph ").unwrap());
+    assert!(content.contains(r#"<data key="generation">{&quot;crate_version&quot;"#));
+    let cobirth = positive_edges(&content, b"edge", b"data", b"key", "rel_COBIRTH");
+    assert!(!cobirth.is_empty());
+    assert_eq!(cobirth, relation_edges(&ocdg, Relations::COBIRTH));
+}
+
+#[test]
+fn test_graphml_single_relation_string() {
+    let (_, ocdg) = fixture();
+    let content = export("pmrs-plain.graphml", |path| { export_ocdg_graphml(&ocdg, path, &ExportOptions::default()).unwrap(); });
+
+    assert!(!content.contains("rel_COBIRTH"));
+    assert_eq!(content.matches(r#"<key id="relations""#).count(), 1);
+    assert_eq!(content.matches("<edge ").count(), ocdg.net.edge_count());
+}
+
+#[test]
+fn test_gexf_per_relation_keys() {
+    let (_, ocdg) = fixture();
+    let content = export("pmrs-keys.gexf", |path| { export_ocdg_with(&ocdg, path, &ExportOptions { per_relation_keys: true, ..Default::default() }).unwrap(); });
+    let cobirth_id = Relations::COBIRTH.relation_index().to_string();
+
+    assert_eq!(content.matches(r#"title="rel_COBIRTH""#).count(), 1);
+    assert_eq!(positive_edges(&content, b"edge", b"attvalue", b"for", &cobirth_id), relation_edges(&ocdg, Relations::COBIRTH));
+}
+
+// accepts a few bytes and then fails like a full disk
+struct FailingWriter(usize);
+
+impl Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.0 < buf.len() {
+            return Err(io::Error::other("no space left"));
+        }
+        self.0 -= buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_graphml_to_writer() {
+    let (_, ocdg) = fixture();
+    let mut buffer: Vec<u8> = vec![];
+    export_ocdg_graphml_to(&ocdg, &mut buffer, &ExportOptions::default()).unwrap();
+    let written = String::from_utf8(buffer).unwrap();
+    let content = export("pmrs-writer.graphml", |path| { export_ocdg_graphml(&ocdg, path, &ExportOptions::default()).unwrap(); });
+    assert_eq!(written, content);
+
+    // well-formed: the reader gets through to the end with balanced tags
+    let mut reader = Reader::from_str(&written);
+    reader.check_end_names(true);
+    let mut buf = Vec::new();
+    let mut depth = 0;
+    loop {
+        match reader.read_event(&mut buf).unwrap() {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    assert_eq!(depth, 0);
+
+    let error = export_ocdg_graphml_to(&ocdg, &mut FailingWriter(64), &ExportOptions::default()).unwrap_err();
+    assert!(error.to_string().contains("no space left"));
+    assert!(error.bytes_written > 0 && error.bytes_written <= 64);
+}
+
+#[test]
+fn test_graphml_edge_relations_and_support() {
+    let log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS, Relations::COBIRTH]).unwrap();
+    let mut buffer: Vec<u8> = vec![];
+    export_ocdg_graphml_to(&ocdg, &mut buffer, &ExportOptions::default()).unwrap();
+    let content = String::from_utf8(buffer).unwrap();
+
+    // o1 and i1 meet and are born in e1, r1 and p1 in e3
+    let node = |label: &str| *log.object_map.get_by_left(label).unwrap();
+    for (src, tar) in [("o1", "i1"), ("r1", "p1")] {
+        let edge = format!(r#"<edge source="n{}" target="n{}"><data key="relations">INTERACTS,COBIRTH</data><data key="support">INTERACTS:1,COBIRTH:1</data></edge>"#, node(src), node(tar));
+        assert!(content.contains(&edge), "{}", edge);
+    }
+    assert!(content.contains(&format!(r#"<node id="n{}"><data key="label">o1</data><data key="type">order</data>"#, node("o1"))));
+    assert_eq!(content.matches(r#"<key id="support""#).count(), 1);
+}
+
+#[test]
+fn test_graphml_string() {
+    let log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+    let content = ocdg_to_graphml(&ocdg, &log, &ExportOptions::default());
+    assert!(content.starts_with("<?xml"));
+    assert!(content.trim_end().ends_with("</graphml>"));
+
+    let mut buffer: Vec<u8> = vec![];
+    export_ocdg_graphml_to(&ocdg, &mut buffer, &ExportOptions::default()).unwrap();
+    assert_eq!(content, String::from_utf8(buffer).unwrap());
+}
+
+#[test]
+fn test_graphml_relation_events() {
+    let log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let options = ExportOptions { relation_events: true, relations: Some(vec![Relations::INHERITANCE]), ..Default::default() };
+    let content = ocdg_to_graphml(&ocdg, &log, &options);
+
+    // i2 inherits from i1 in e1
+    let node = |label: &str| *log.object_map.get_by_left(label).unwrap();
+    let edge = format!(r#"<edge source="n{}" target="n{}"><data key="relations">INHERITANCE</data><data key="support">INHERITANCE:1</data><data key="rel_INHERITANCE_events">e1</data></edge>"#, node("i2"), node("i1"));
+    assert!(content.contains(&edge), "{}", edge);
+    assert_eq!(content.matches(r#"<key id="rel_INHERITANCE_events""#).count(), 1);
+    assert!(!content.contains("rel_INTERACTS_events"));
+    assert_eq!(content.matches("<edge ").count(), relation_edges(&ocdg, Relations::INHERITANCE).len());
+}
@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::PathBuf;
+
+use pmrs::objects::exporter::partial_path;
+use pmrs::objects::ocdg::exporter::{estimate_export_size, export_ocdg_checked, export_ocdg_graphml, ExportOptions, GraphFormat, SizeCheck, SplitMode};
+use pmrs::objects::ocdg::{generate_ocdg, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+fn fixture(log: &str) -> Ocdg {
+    generate_ocdg(&import_ocel(log).unwrap(), &Relations::iter().collect::<Vec<Relations>>()).unwrap()
+}
+
+fn out_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_estimate_is_close() {
+    let ocdg = fixture("logs/supply.jsonocel");
+    let dir = out_dir("pmrs-estimate");
+    let path = dir.join("supply.graphml");
+    export_ocdg_graphml(&ocdg, path.to_str().unwrap(), &ExportOptions::default()).unwrap();
+    let written = fs::metadata(&path).unwrap().len() as f64;
+    let estimated = estimate_export_size(&ocdg, GraphFormat::GraphMl, &ExportOptions::default()) as f64;
+    // the header and keys are not part of the estimate
+    assert!(estimated > 0.5 * written && estimated < 1.5 * written, "{} {}", estimated, written);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_under_the_limit_writes_one_file() {
+    let ocdg = fixture("logs/min.jsonocel");
+    let dir = out_dir("pmrs-unsplit");
+    let path = dir.join("min.graphml");
+    let report = export_ocdg_checked(&ocdg, path.to_str().unwrap(), GraphFormat::GraphMl, &ExportOptions::default(), &SizeCheck::default()).unwrap();
+    assert!(report.warnings.is_empty());
+    assert_eq!(report.files.len(), 1);
+    assert_eq!(report.manifest, None);
+
+    // over the limit without a split mode only warns
+    let check = SizeCheck { max_bytes: 10, split: None };
+    let report = export_ocdg_checked(&ocdg, path.to_str().unwrap(), GraphFormat::GraphMl, &ExportOptions::default(), &check).unwrap();
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.files.len(), 1);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_split_by_component() {
+    // o1, i1 and i2 are one execution, r1 and p1 another
+    let ocdg = fixture("logs/min.jsonocel");
+    let dir = out_dir("pmrs-split-component");
+    let path = dir.join("min.graphml");
+    let check = SizeCheck { max_bytes: 10, split: Some(SplitMode::Component) };
+    let report = export_ocdg_checked(&ocdg, path.to_str().unwrap(), GraphFormat::GraphMl, &ExportOptions::default(), &check).unwrap();
+
+    assert_eq!(report.files.len(), 2);
+    let manifest: serde_json::Value = serde_json::from_str(&fs::read_to_string(report.manifest.as_ref().unwrap()).unwrap()).unwrap();
+    // the manifest is written through the atomic export
+    assert!(!partial_path(report.manifest.as_ref().unwrap()).exists());
+    let parts = manifest["parts"].as_array().unwrap();
+    assert_eq!(parts.len(), 2);
+    for ((file, bytes), part) in report.files.iter().zip(parts) {
+        assert!(file.ends_with(part["file"].as_str().unwrap()));
+        assert_eq!(fs::metadata(file).unwrap().len(), *bytes);
+    }
+    assert_eq!(parts.iter().map(|part| part["objects"].as_u64().unwrap()).sum::<u64>(), 5);
+    assert_eq!(parts.iter().map(|part| part["edges"].as_u64().unwrap()).sum::<u64>() as usize, ocdg.net.edge_count());
+    assert!(!path.exists());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_split_by_node_hash() {
+    let ocdg = fixture("logs/supply.jsonocel");
+    let dir = out_dir("pmrs-split-hash");
+    let path = dir.join("supply.gexf");
+    let check = SizeCheck { max_bytes: 10, split: Some(SplitMode::NodeHash(3)) };
+    let report = export_ocdg_checked(&ocdg, path.to_str().unwrap(), GraphFormat::Gexf, &ExportOptions::default(), &check).unwrap();
+
+    assert!(report.files.len() > 1 && report.files.len() <= 3);
+    let manifest = fs::read_to_string(report.manifest.unwrap()).unwrap();
+    for (file, _) in &report.files {
+        let name = PathBuf::from(file).file_name().unwrap().to_str().unwrap().to_owned();
+        assert!(name.starts_with("supply.part") && name.ends_with(".gexf"));
+        assert!(manifest.contains(&name));
+        assert!(fs::read_to_string(file).unwrap().contains("<edge "));
+    }
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,74 @@
+use pmrs::objects::ocdg::{generate_ocdg, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+fn relation_edges(ocdg: &Ocdg, rel: Relations) -> usize {
+    ocdg.irels.values().flat_map(|targets| targets.values()).filter(|rels| rels.contains_key(&rel.relation_index().into())).count()
+}
+
+#[test]
+fn test_filter_relations_counts() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let lineage = [Relations::DESCENDANTS, Relations::INHERITANCE];
+    let filtered = ocdg.filter_relations(&lineage);
+
+    for rel in Relations::iter() {
+        let expected = if lineage.contains(&rel) { relation_edges(&ocdg, rel) } else { 0 };
+        assert_eq!(relation_edges(&filtered, rel), expected, "{}", rel);
+    }
+    assert!(relation_edges(&filtered, Relations::INHERITANCE) > 0);
+
+    // the lookups point into the new net
+    let pairs: usize = filtered.irels.values().map(|targets| targets.len()).sum();
+    assert_eq!(filtered.net.edge_count(), pairs);
+    for (src, targets) in &filtered.iedges {
+        for (tar, edge) in targets {
+            assert_eq!(filtered.net.edge_endpoints(*edge), Some((filtered.inodes[src], filtered.inodes[tar])));
+        }
+    }
+    assert_eq!(filtered.net.node_count(), ocdg.net.node_count());
+    assert_eq!(filtered.object_map, ocdg.object_map);
+    // the original is untouched
+    assert!(relation_edges(&ocdg, Relations::INTERACTS) > 0);
+}
+
+#[test]
+fn test_filter_relations_prunes_isolated() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let filtered = ocdg.filter_relations_with(&[Relations::SPLIT], true);
+
+    assert!(filtered.net.node_count() < ocdg.net.node_count());
+    assert!(filtered.inodes.values().all(|node| filtered.net.neighbors_undirected(*node).next().is_some()));
+    assert_eq!(filtered.node_attributes.len(), filtered.net.node_count());
+    assert_eq!(filtered.object_map.len(), filtered.net.node_count());
+
+    let p1 = *log.object_map.get_by_left("p1").unwrap();
+    let p2 = *log.object_map.get_by_left("p2").unwrap();
+    assert_eq!(filtered.relations_between(p1, p2), vec![Relations::SPLIT]);
+    // only the evidence of the kept relations stays mapped
+    let e9 = *log.event_map.get_by_left("e9").unwrap();
+    assert_eq!(filtered.event_map.len(), 1);
+    assert!(filtered.event_map.contains_right(&e9));
+}
+
+#[test]
+fn test_relation_induced_subgraph() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let colife = ocdg.relation_induced_subgraph(Relations::COLIFE);
+
+    let colife_index: usize = Relations::COLIFE.relation_index().into();
+    assert!(colife.irels.values().flat_map(|targets| targets.values()).all(|rels| rels.len() == 1 && rels.contains_key(&colife_index)));
+    assert_eq!(relation_edges(&colife, Relations::COLIFE), relation_edges(&ocdg, Relations::COLIFE));
+    assert_eq!(colife.net.edge_count(), relation_edges(&ocdg, Relations::COLIFE));
+    assert_eq!(colife.object_count(), ocdg.object_count());
+    assert_eq!(colife.object_count(), log.objects.len());
+
+    // the lookups are valid, the other methods work on it
+    let i2 = *log.object_map.get_by_left("i2").unwrap();
+    let o1 = *log.object_map.get_by_left("o1").unwrap();
+    assert_eq!(colife.relations_between(i2, o1), vec![Relations::COLIFE]);
+    assert_eq!(colife.net.edge_endpoints(colife.iedges[&i2][&o1]), Some((colife.inodes[&i2], colife.inodes[&o1])));
+}
@@ -0,0 +1,64 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::objects::ocdg::fingerprint::{find_duplicates, object, signature};
+use pmrs::objects::ocdg::{generate_ocdg, Ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+use strum::IntoEnumIterator;
+
+// events are (activity, minutes after the start, object labels)
+fn build(objects: &[(&str, &str)], events: &[(&str, i64, &[&str])], start_day: u32) -> (Ocel, Ocdg) {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (label, obj_type)) in objects.iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, start_day).and_hms(0, 0, 0);
+    for (eid, (activity, minutes, labels)) in events.iter().enumerate() {
+        let omap = labels.iter().map(|label| *log.object_map.get_by_left(*label).unwrap()).collect();
+        log.events.insert(eid, OcelEvent { activity: activity.to_string(), timestamp: start + Duration::minutes(*minutes), vmap: AHashMap::new(), omap });
+        log.event_map.insert(format!("e{}", eid), eid);
+    }
+    log.rebuild_derived_indices();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    (log, ocdg)
+}
+
+fn oid(log: &Ocel, label: &str) -> usize {
+    *log.object_map.get_by_left(label).unwrap()
+}
+
+fn logs() -> ((Ocel, Ocdg), (Ocel, Ocdg)) {
+    let a = build(&[("o1", "order"), ("i1", "item"), ("i2", "item"), ("o2", "order")],
+                  &[("create", 0, &["o1", "i1", "i2"]), ("open", 5, &["o2"]), ("pick", 30, &["i1"]), ("cancel", 40, &["o2"]), ("ship", 120, &["o1", "i1", "i2"])], 1);
+    // the same order re-imported under other ids a week later, the order o2 paid instead of cancelled
+    let b = build(&[("x", "order"), ("order-17", "order"), ("item-b", "item"), ("item-a", "item")],
+                  &[("open", 0, &["x"]), ("create", 1, &["order-17", "item-a", "item-b"]), ("pick", 31, &["item-a"]), ("pay", 40, &["x"]), ("ship", 121, &["order-17", "item-a", "item-b"])], 8);
+    (a, b)
+}
+
+#[test]
+fn test_planted_duplicate_is_matched() {
+    let ((log_a, ocdg_a), (log_b, ocdg_b)) = logs();
+    assert_eq!(object(&log_a, &ocdg_a, oid(&log_a, "o1")), object(&log_b, &ocdg_b, oid(&log_b, "order-17")));
+    let labelled: Vec<(&str, &str)> = find_duplicates(&log_a, &ocdg_a, &log_b, &ocdg_b).into_iter()
+                                                                                        .map(|(a, b)| (log_a.object_map.get_by_right(&a).unwrap().as_str(), log_b.object_map.get_by_right(&b).unwrap().as_str()))
+                                                                                        .collect();
+    assert_eq!(labelled, vec![("o1", "order-17"), ("i1", "item-a"), ("i2", "item-b")]);
+}
+
+#[test]
+fn test_signature_parts() {
+    let ((log_a, ocdg_a), (log_b, ocdg_b)) = logs();
+    let order = signature(&log_a, &ocdg_a, oid(&log_a, "o1"));
+    assert_eq!(order.obj_type, "order");
+    assert_eq!(order.activities, vec!["create", "ship"]);
+    // 7200 seconds fall into the bucket of 4096..8191
+    assert_eq!(order.gap_buckets, vec![13]);
+    assert_eq!(order.neighbour_types.get("item"), Some(&2));
+    assert_ne!(object(&log_a, &ocdg_a, oid(&log_a, "o2")), object(&log_b, &ocdg_b, oid(&log_b, "x")));
+    assert_ne!(object(&log_a, &ocdg_a, oid(&log_a, "i1")), object(&log_a, &ocdg_a, oid(&log_a, "i2")));
+}
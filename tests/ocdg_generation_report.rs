@@ -0,0 +1,30 @@
+use ahash::AHashMap;
+use pmrs::objects::ocdg::{generate_ocdg_report, GenerationConfig, GenerationPhase, GenerationReport, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::OcelObject;
+use strum::IntoEnumIterator;
+
+#[test]
+fn test_report_phases() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let config = GenerationConfig { relations: Relations::iter().collect(), ..Default::default() };
+    let (ocdg, report) = generate_ocdg_report(&log, &config).unwrap();
+
+    assert_eq!(report.phases.keys().copied().collect::<Vec<GenerationPhase>>(), GenerationPhase::iter().collect::<Vec<GenerationPhase>>());
+    assert!(report.phases.values().all(|duration| !duration.is_zero()));
+    assert_eq!(report.edge_counts, ocdg.metadata().unwrap().edge_counts);
+    assert!(report.edge_counts.contains_key("SPLIT") && report.edge_counts.contains_key("REACHABLE"));
+    assert!(report.warnings.is_empty());
+
+    let json = serde_json::to_string(&report).unwrap();
+    assert_eq!(serde_json::from_str::<GenerationReport>(&json).unwrap(), report);
+}
+
+#[test]
+fn test_report_warnings() {
+    let mut log = import_ocel("logs/min.jsonocel").unwrap();
+    log.objects.insert(100, OcelObject { obj_type: "item".to_string(), ovmap: AHashMap::new(), events: vec![] });
+    log.object_map.insert("i100".to_string(), 100);
+    let (_, report) = generate_ocdg_report(&log, &GenerationConfig::default()).unwrap();
+    assert_eq!(report.warnings, vec!["1 objects are part of no event and have no node".to_string()]);
+}
@@ -0,0 +1,63 @@
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use pmrs::objects::ocdg::exporter::canonical_edges;
+use pmrs::objects::ocdg::{generate_ocdg, generate_ocdg_config, GenerationConfig, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+// every fixture in logs/ readable with the default timestamp formats, except the wide-omap stress log against its canonical edge list in tests/golden/<fixture>.tsv.
+// after an intended change of the relation semantics regenerate them with
+//     UPDATE_GOLDEN=1 cargo test --test ocdg_golden
+// and commit the diff of tests/golden together with the change
+const FIXTURES: [&str; 14] = ["erp", "fleet-system", "fleet", "handover", "logistics", "min", "numeric-ids",
+                              "order-to-cash", "package-handover", "pallets", "regions", "supply", "three-days", "wms"];
+
+// the edges only in the golden file and the edges only in the generated graph
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: BTreeSet<&str> = expected.lines().collect();
+    let actual: BTreeSet<&str> = actual.lines().collect();
+    let mut out = String::new();
+    for line in expected.difference(&actual) {
+        out.push_str(&format!("- {}\n", line));
+    }
+    for line in actual.difference(&expected) {
+        out.push_str(&format!("+ {}\n", line));
+    }
+    out
+}
+
+#[test]
+fn test_golden_edges() {
+    let update = env::var("UPDATE_GOLDEN").is_ok_and(|value| value == "1");
+    let mut failures = vec![];
+    for fixture in FIXTURES {
+        let log = import_ocel(&format!("logs/{}.jsonocel", fixture)).unwrap();
+        let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: Relations::iter().collect(), ..Default::default() }).unwrap();
+        let actual = canonical_edges(&ocdg);
+        let golden = Path::new("tests/golden").join(format!("{}.tsv", fixture));
+        if update {
+            fs::write(&golden, &actual).unwrap();
+            continue;
+        }
+        let expected = fs::read_to_string(&golden).unwrap_or_default();
+        if expected != actual {
+            failures.push(format!("{}:\n{}", golden.display(), diff(&expected, &actual)));
+        }
+    }
+    assert!(failures.is_empty(), "relation semantics changed, rerun with UPDATE_GOLDEN=1 if intended\n{}", failures.join("\n"));
+}
+
+// generate_ocdg borrows the log, several relation sets run against one log and the full set matches the golden file
+#[test]
+fn test_borrowed_generation() {
+    let log = import_ocel("logs/order-to-cash.jsonocel").unwrap();
+    let all: Vec<Relations> = Relations::iter().collect();
+    let interacts = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+    let ocdg = generate_ocdg(&log, &all).unwrap();
+    assert!(interacts.net.edge_count() <= ocdg.net.edge_count());
+    assert_eq!(canonical_edges(&ocdg), fs::read_to_string("tests/golden/order-to-cash.tsv").unwrap());
+    assert_eq!(log.objects.len(), ocdg.net.node_count());
+}
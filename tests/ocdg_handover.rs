@@ -0,0 +1,85 @@
+use chrono::Duration;
+use pmrs::objects::ocdg::{generate_ocdg_config, trace_pair_config, GenerationConfig, HandoverWitness, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::Ocel;
+
+// p1 is shipped by courier c1 at 10:00:00 and received as p2 together with the stock entry s1 five seconds later
+const LOG: &str = "logs/package-handover.jsonocel";
+
+fn handover_config(gap: Option<Duration>, witness: HandoverWitness) -> GenerationConfig {
+    GenerationConfig { relations: vec![Relations::INHERITANCE, Relations::CONSUMES], inheritance_max_gap: gap, consumes_max_gap: gap, handover_witness: witness, ..Default::default() }
+}
+
+fn holds(log: &Ocel, ocdg: &Ocdg, src: &str, tar: &str, rel: Relations) -> bool {
+    let src = log.object_map.get_by_left(src).unwrap();
+    let tar = log.object_map.get_by_left(tar).unwrap();
+    ocdg.irels.get(src).and_then(|targets| targets.get(tar)).is_some_and(|rels| rels.contains_key(&rel.relation_index().into()))
+}
+
+#[test]
+fn test_default_requires_the_same_event() {
+    let log = import_ocel(LOG).unwrap();
+    let ocdg = generate_ocdg_config(&log, &handover_config(None, HandoverWitness::SharedObject)).unwrap();
+
+    assert!(!holds(&log, &ocdg, "p1", "p2", Relations::INHERITANCE));
+    assert!(!holds(&log, &ocdg, "p1", "s1", Relations::CONSUMES));
+    // the courier is born in the shipping event itself
+    assert!(holds(&log, &ocdg, "p1", "c1", Relations::CONSUMES));
+}
+
+#[test]
+fn test_gap_captures_the_handover() {
+    let log = import_ocel(LOG).unwrap();
+    let ocdg = generate_ocdg_config(&log, &handover_config(Some(Duration::seconds(5)), HandoverWitness::SharedObject)).unwrap();
+
+    assert!(holds(&log, &ocdg, "p1", "p2", Relations::INHERITANCE));
+    assert!(holds(&log, &ocdg, "p1", "s1", Relations::CONSUMES));
+    assert!(holds(&log, &ocdg, "p1", "c1", Relations::CONSUMES));
+    assert!(!holds(&log, &ocdg, "p2", "p1", Relations::INHERITANCE));
+
+    // both sides of the handover are evidence
+    let (p1, p2) = (*log.object_map.get_by_left("p1").unwrap(), *log.object_map.get_by_left("p2").unwrap());
+    let mut events: Vec<&str> = ocdg.relation_events(p1, p2, Relations::INHERITANCE).unwrap().iter().map(|eid| log.event_map.get_by_right(eid).unwrap().as_str()).collect();
+    events.sort();
+    assert_eq!(events, vec!["e1", "e2"]);
+    assert_eq!(ocdg.metadata().unwrap().config["inheritance_max_gap_ms"], 5000);
+}
+
+#[test]
+fn test_gap_is_an_upper_bound() {
+    let log = import_ocel(LOG).unwrap();
+    let ocdg = generate_ocdg_config(&log, &handover_config(Some(Duration::seconds(4)), HandoverWitness::SharedObject)).unwrap();
+    assert!(!holds(&log, &ocdg, "p1", "p2", Relations::INHERITANCE));
+    assert!(!holds(&log, &ocdg, "p1", "s1", Relations::CONSUMES));
+
+    // only the configured relation is relaxed
+    let config = GenerationConfig { consumes_max_gap: None, ..handover_config(Some(Duration::seconds(5)), HandoverWitness::SharedObject) };
+    let ocdg = generate_ocdg_config(&log, &config).unwrap();
+    assert!(holds(&log, &ocdg, "p1", "p2", Relations::INHERITANCE));
+    assert!(!holds(&log, &ocdg, "p1", "s1", Relations::CONSUMES));
+}
+
+#[test]
+fn test_attribute_witness() {
+    let log = import_ocel(LOG).unwrap();
+    let gap = Some(Duration::seconds(5));
+    let ocdg = generate_ocdg_config(&log, &handover_config(gap, HandoverWitness::Attribute("handover".to_string()))).unwrap();
+    assert!(holds(&log, &ocdg, "p1", "p2", Relations::INHERITANCE));
+
+    let ocdg = generate_ocdg_config(&log, &handover_config(gap, HandoverWitness::Attribute("carrier".to_string()))).unwrap();
+    assert!(!holds(&log, &ocdg, "p1", "p2", Relations::INHERITANCE));
+}
+
+#[test]
+fn test_trace_explains_the_gap() {
+    let log = import_ocel(LOG).unwrap();
+    let (p1, p2) = (*log.object_map.get_by_left("p1").unwrap(), *log.object_map.get_by_left("p2").unwrap());
+
+    let strict = trace_pair_config(&log, &handover_config(None, HandoverWitness::SharedObject), p1, p2, Relations::INHERITANCE);
+    assert!(!strict.holds());
+    assert!(strict.to_string().contains("source and target share 0 events"));
+
+    let relaxed = trace_pair_config(&log, &handover_config(Some(Duration::seconds(4)), HandoverWitness::SharedObject), p1, p2, Relations::INHERITANCE);
+    assert!(!relaxed.holds());
+    assert!(relaxed.to_string().contains("target first event e2 is not within 4s after the source's last event (e1)"));
+}
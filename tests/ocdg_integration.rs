@@ -5,7 +5,7 @@ use pmrs::objects::ocel::Ocel;
 use pmrs::objects::ocel::importer::import_ocel;
 use pmrs::objects::ocel::exporter::export_ocel_pretty;
 use pmrs::objects::ocdg::importer::import_ocdg;
-use pmrs::algo::transformation::ocel::features::object_point::{ObjectPoint, ObjectPointConfig, object_point_features};
+use pmrs::algo::transformation::ocel::features::object_point::{LifecycleView, ObjectPoint, ObjectPointConfig, object_point_features};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::time::Instant;
@@ -46,10 +46,10 @@ fn test_ocdg_generation(){
                                          Relations::SPLIT,
                                          Relations::MERGE];
 
-    let ocdg: Ocdg = generate_ocdg(&log, &relations);
+    let ocdg: Ocdg = generate_ocdg(&log, &relations).unwrap();
     let params: HashMap<ObjectPoint, Option<Value>> = HashMap::from_iter([(ObjectPoint::ObjectLifetime, None), (ObjectPoint::ObjectEventInteractionOperator, None), (ObjectPoint::ObjectUnitSetRatio, None)]);
 
-    let feature_config = ObjectPointConfig { ocel: &log, ocdg: &ocdg, params: &params};
+    let feature_config = ObjectPointConfig { ocel: &log, ocdg: &ocdg, params: &params, lifecycle_view: LifecycleView::Full };
     let feature_extraction = object_point_features(feature_config);
     println!("{:?}", feature_extraction);
 
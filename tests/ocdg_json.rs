@@ -0,0 +1,69 @@
+use pmrs::objects::ocdg::exporter::canonical_edges;
+use pmrs::objects::ocdg::{generate_ocdg_config, Evidence, GenerationConfig, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use serde_json::json;
+use strum::IntoEnumIterator;
+
+fn edges(ocdg: &Ocdg) -> Vec<(String, String)> {
+    let label = |node| ocdg.object_map.get_by_right(&ocdg.net[node]).unwrap().to_owned();
+    let mut edges: Vec<(String, String)> = ocdg.net.edge_indices().map(|edge| ocdg.net.edge_endpoints(edge).unwrap()).map(|(a, b)| (label(a), label(b))).collect();
+    edges.sort();
+    edges
+}
+
+#[test]
+fn test_json_round_trip() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let mut ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: Relations::iter().collect(), ..Default::default() }).unwrap();
+    ocdg.build_event_index();
+
+    let restored = Ocdg::from_json(&ocdg.to_json()).unwrap();
+    assert_eq!(canonical_edges(&restored), canonical_edges(&ocdg));
+    assert_eq!(edges(&restored), edges(&ocdg));
+    assert_eq!(restored.object_map, ocdg.object_map);
+    assert_eq!(restored.event_map, ocdg.event_map);
+    assert_eq!(restored.metadata, ocdg.metadata);
+    // the lookups point at the same objects again
+    for (oid, node) in &restored.inodes {
+        assert_eq!(restored.net[*node], *oid);
+        assert_eq!(restored.node_attributes[oid].node_type, ocdg.node_attributes[oid].node_type);
+    }
+    for (src, targets) in &restored.iedges {
+        for (tar, edge) in targets {
+            assert_eq!(restored.net.edge_endpoints(*edge).unwrap(), (restored.inodes[src], restored.inodes[tar]));
+        }
+    }
+    let eid = *ocdg.ievents.as_ref().unwrap().keys().next().unwrap();
+    assert_eq!(restored.relations_of_event(eid), ocdg.relations_of_event(eid));
+
+    // the serde impls directly
+    let text = serde_json::to_string(&ocdg).unwrap();
+    let parsed: Ocdg = serde_json::from_str(&text).unwrap();
+    assert_eq!(canonical_edges(&parsed), canonical_edges(&ocdg));
+}
+
+#[test]
+fn test_json_counts_only() {
+    let log = import_ocel("logs/logistics.jsonocel").unwrap();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: Relations::iter().collect(), evidence: Evidence::CountsOnly, ..Default::default() }).unwrap();
+    let restored = Ocdg::from_json(&ocdg.to_json()).unwrap();
+    assert_eq!(restored.evidence, Evidence::CountsOnly);
+    for (src, targets) in &ocdg.irels {
+        for tar in targets.keys() {
+            for rel in Relations::iter() {
+                assert_eq!(restored.relation_support(*src, *tar, rel), ocdg.relation_support(*src, *tar, rel));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_json_invalid() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::INTERACTS], ..Default::default() }).unwrap();
+    let mut value = ocdg.to_json();
+    value["edges"] = json!([[0, 99, 0]]);
+    let error = Ocdg::from_json(&value).err().unwrap();
+    assert!(error.to_string().contains("points outside"), "{}", error);
+    assert!(Ocdg::from_json(&json!({"nodes": []})).is_err());
+}
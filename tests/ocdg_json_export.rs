@@ -0,0 +1,88 @@
+use pmrs::objects::ocdg::exporter::{canonical_edges, export_ocdg_json, export_ocdg_json_graph, export_ocdg_json_graph_with, export_ocdg_json_with, JsonExportOptions};
+use pmrs::objects::ocdg::importer::import_ocdg_json;
+use pmrs::objects::ocdg::{generate_ocdg_config, Evidence, GenerationConfig, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use serde_json::json;
+use strum::IntoEnumIterator;
+
+#[test]
+fn test_schema_and_round_trip() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: Relations::iter().collect(), ..Default::default() }).unwrap();
+    let exported = export_ocdg_json(&log, &ocdg);
+
+    assert_eq!(exported["schema"], json!("pmrs-ocdg"));
+    assert_eq!(exported["evidence"], json!("events"));
+    let i1 = exported["nodes"].as_array().unwrap().iter().find(|node| node["object_id"] == json!("i1")).unwrap();
+    assert_eq!(i1["node_type"], json!("item"));
+    assert_eq!(i1["event_list"], json!(["e1", "e2"]));
+    let edge = exported["edges"].as_array().unwrap().iter().find(|edge| edge["source_id"] == json!("i1") && edge["target_id"] == json!("o1")).unwrap();
+    assert_eq!(edge["relations"]["INTERACTS"], json!(["e1"]));
+
+    // the graph read back without the log holds the same relations and exports the same document
+    let imported = import_ocdg_json(&exported).unwrap();
+    assert_eq!(canonical_edges(&imported), canonical_edges(&ocdg));
+    assert_eq!(imported.net.edge_count(), ocdg.net.edge_count());
+    assert_eq!(export_ocdg_json(&log, &imported), exported);
+}
+
+#[test]
+fn test_round_trip_without_the_log() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: Relations::iter().collect(), ..Default::default() }).unwrap();
+    let exported = export_ocdg_json(&log, &ocdg);
+
+    // the lifecycles travel with the imported graph, the second export needs no log
+    let imported = import_ocdg_json(&exported).unwrap();
+    let i1 = *imported.object_map.get_by_left("i1").unwrap();
+    assert_eq!(imported.lifecycles[&i1], vec!["e1", "e2"]);
+    assert_eq!(export_ocdg_json_graph(&imported), exported);
+    assert_eq!(export_ocdg_json_graph(&import_ocdg_json(&export_ocdg_json_graph(&imported)).unwrap()), exported);
+
+    let options = JsonExportOptions { max_edges_with_events: 1 };
+    let counts = export_ocdg_json_with(&log, &ocdg, &options);
+    assert_eq!(export_ocdg_json_graph_with(&import_ocdg_json(&counts).unwrap(), &options), counts);
+
+    // a generated graph has no lifecycles of its own
+    assert!(ocdg.lifecycles.is_empty());
+    assert!(export_ocdg_json_graph(&ocdg)["nodes"].as_array().unwrap().iter().all(|node| node["event_list"] == json!([])));
+}
+
+#[test]
+fn test_counts_above_the_threshold() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::INTERACTS, Relations::DESCENDANTS], ..Default::default() }).unwrap();
+    let (src, targets) = ocdg.irels.iter().next().unwrap();
+    let (tar, _) = targets.iter().next().unwrap();
+
+    let exported = export_ocdg_json_with(&log, &ocdg, &JsonExportOptions { max_edges_with_events: 1 });
+    assert_eq!(exported["evidence"], json!("counts"));
+    let imported = import_ocdg_json(&exported).unwrap();
+    assert_eq!(imported.evidence, Evidence::CountsOnly);
+    let (src_label, tar_label) = (ocdg.object_map.get_by_right(src).unwrap(), ocdg.object_map.get_by_right(tar).unwrap());
+    let (isrc, itar) = (*imported.object_map.get_by_left(src_label).unwrap(), *imported.object_map.get_by_left(tar_label).unwrap());
+    for rel in [Relations::INTERACTS, Relations::DESCENDANTS] {
+        assert_eq!(imported.relation_support(isrc, itar, rel), ocdg.relation_support(*src, *tar, rel));
+    }
+    assert_eq!(export_ocdg_json_with(&log, &imported, &JsonExportOptions { max_edges_with_events: 1 }), exported);
+}
+
+#[test]
+fn test_without_evidence_and_invalid() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::INTERACTS], evidence: Evidence::None, ..Default::default() }).unwrap();
+    let exported = export_ocdg_json(&log, &ocdg);
+    assert_eq!(exported["evidence"], json!("none"));
+    assert_eq!(exported["edges"][0]["relations"]["INTERACTS"], json!(null));
+    assert_eq!(export_ocdg_json(&log, &import_ocdg_json(&exported).unwrap()), exported);
+
+    let mut unknown = exported.clone();
+    unknown["edges"][0]["relations"] = json!({"FRIENDS": null});
+    assert!(import_ocdg_json(&unknown).unwrap_err().to_string().contains("FRIENDS"));
+    let mut missing = exported.clone();
+    missing["edges"][0]["source_id"] = json!("nobody");
+    assert!(import_ocdg_json(&missing).is_err());
+    let mut mismatched = exported;
+    mismatched["edges"][0]["relations"]["INTERACTS"] = json!(["e1"]);
+    assert!(import_ocdg_json(&mismatched).is_err());
+}
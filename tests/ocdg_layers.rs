@@ -0,0 +1,56 @@
+use pmrs::objects::ocdg::layers::EdgeSet;
+use pmrs::objects::ocdg::{generate_ocdg, Ocdg, Relations};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+fn fixture() -> (Ocel, Ocdg) {
+    let log: Ocel = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    (log, ocdg)
+}
+
+#[test]
+fn test_edge_set_operations() {
+    let a: EdgeSet = vec![(3, 1), (1, 2), (1, 2), (2, 5)].into_iter().collect();
+    let b: EdgeSet = vec![(2, 5), (4, 4)].into_iter().collect();
+
+    assert_eq!(a.edges(), &[(1, 2), (2, 5), (3, 1)]);
+    assert!(a.contains(3, 1) && !a.contains(1, 3));
+    assert_eq!(a.union(&b).edges(), &[(1, 2), (2, 5), (3, 1), (4, 4)]);
+    assert_eq!(a.intersect(&b).edges(), &[(2, 5)]);
+    assert_eq!(a.difference(&b).edges(), &[(1, 2), (3, 1)]);
+    assert!(EdgeSet::default().union(&EdgeSet::default()).is_empty());
+}
+
+#[test]
+fn test_layers_de_morgan() {
+    let (_, ocdg) = fixture();
+    let all = ocdg.edge_set();
+    let (a, b) = (ocdg.layer(Relations::INTERACTS), ocdg.layer(Relations::COBIRTH));
+    assert!(!a.is_empty() && !b.is_empty());
+
+    assert_eq!(all.difference(&a.union(&b)), all.difference(&a).intersect(&all.difference(&b)));
+    assert_eq!(all.difference(&a.intersect(&b)), all.difference(&a).union(&all.difference(&b)));
+    assert_eq!(a.union(&b).len(), a.len() + b.len() - a.intersect(&b).len());
+    assert_eq!(Relations::iter().fold(EdgeSet::default(), |acc, rel| acc.union(&ocdg.layer(rel))), all);
+}
+
+#[test]
+fn test_layers_build_filtered_ocdg() {
+    let (log, mut ocdg) = fixture();
+    let colife = ocdg.layer(Relations::COLIFE);
+    // objects that live together are born and die together
+    let candidates = ocdg.layer(Relations::COBIRTH).intersect(&ocdg.layer(Relations::CODEATH));
+    assert!(colife.difference(&candidates).is_empty());
+
+    // every split child also ends differently from its parent
+    assert!(ocdg.layer(Relations::SPLIT).difference(&ocdg.layer(Relations::MERGE)).is_empty());
+    let merge_not_split = ocdg.layer(Relations::MERGE).difference(&ocdg.layer(Relations::SPLIT));
+    assert_eq!(merge_not_split.len(), 6);
+    ocdg.retain_edge_set(&merge_not_split);
+    assert_eq!(ocdg.edge_set(), merge_not_split);
+    assert_eq!(ocdg.net.edge_count(), merge_not_split.len());
+    let p1 = *log.object_map.get_by_left("p1").unwrap();
+    assert!(!merge_not_split.iter().any(|(src, _)| *src == p1));
+}
@@ -0,0 +1,57 @@
+use std::fs;
+
+use pmrs::objects::ocdg::exporter::export_ocdg;
+use pmrs::objects::ocdg::importer::import_ocdg;
+use pmrs::objects::ocdg::{generate_ocdg, generate_ocdg_with, log_fingerprint, Evidence, Relations};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+
+#[test]
+fn test_generation_records_metadata() {
+    let log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS, Relations::COBIRTH]).unwrap();
+    let metadata = ocdg.metadata().unwrap();
+
+    assert_eq!(metadata.crate_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(metadata.config["relations"], serde_json::json!(["INTERACTS", "COBIRTH"]));
+    assert_eq!(metadata.config["evidence"], "Full");
+    assert_eq!(metadata.log_fingerprint, log_fingerprint(&log));
+    let instances: usize = ocdg.irels.values().flat_map(|targets| targets.values()).map(|rels| rels.len()).sum();
+    assert_eq!(metadata.edge_counts.values().sum::<usize>(), instances);
+    assert_eq!(metadata.edge_counts.len(), 2);
+}
+
+#[test]
+fn test_log_fingerprint_ignores_internal_ids() {
+    let log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let reimported: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let other: Ocel = import_ocel("logs/supply.jsonocel").unwrap();
+
+    assert_eq!(log_fingerprint(&log), log_fingerprint(&reimported));
+    assert_ne!(log_fingerprint(&log), log_fingerprint(&other));
+}
+
+#[test]
+fn test_config_mismatch_warns() {
+    let log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let full = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+    let counts = generate_ocdg_with(&log, &[Relations::INTERACTS], Evidence::CountsOnly).unwrap();
+    let again = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+
+    assert!(full.metadata().unwrap().same_config(again.metadata().unwrap()));
+    assert!(full.metadata().unwrap().config_mismatch(again.metadata().unwrap()).is_none());
+    assert!(full.metadata().unwrap().config_mismatch(counts.metadata().unwrap()).unwrap().contains("different configs"));
+}
+
+#[test]
+fn test_metadata_round_trips_through_gexf() {
+    let log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS, Relations::DESCENDANTS]).unwrap();
+    let path = std::env::temp_dir().join("pmrs-metadata.gexf");
+    let path = path.to_str().unwrap();
+    export_ocdg(&ocdg, path).unwrap();
+    let imported = import_ocdg(path).unwrap();
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(imported.metadata(), ocdg.metadata());
+}
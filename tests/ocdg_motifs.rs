@@ -0,0 +1,93 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use pmrs::objects::ocdg::metrics::{motifs, MotifConfig, TriadCount};
+use pmrs::objects::ocdg::{generate_ocdg, Ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+// one event per linked pair of objects
+fn pair_log(objects: &[(&str, &str)], pairs: &[(usize, usize)]) -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec!["link".to_string()], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (label, obj_type)) in objects.iter().enumerate() {
+        log.object_map.insert(label.to_string(), oid);
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    for (eid, (a, b)) in pairs.iter().enumerate() {
+        log.objects.get_mut(a).unwrap().events.push(eid);
+        log.objects.get_mut(b).unwrap().events.push(eid);
+        log.events.insert(eid, OcelEvent { activity: "link".to_string(), timestamp: start + Duration::minutes(eid as i64), vmap: AHashMap::new(), omap: IntSet::from_iter([*a, *b]) });
+        log.event_map.insert(format!("e{}", eid), eid);
+    }
+    log
+}
+
+fn interacts(log: &Ocel) -> Ocdg {
+    generate_ocdg(log, &[Relations::INTERACTS]).unwrap()
+}
+
+#[test]
+fn test_complete_graph() {
+    let labels: Vec<String> = (0..8).map(|i| format!("p{}", i)).collect();
+    let objects: Vec<(&str, &str)> = labels.iter().map(|label| (label.as_str(), "part")).collect();
+    let pairs: Vec<(usize, usize)> = (0..8).flat_map(|a| (a + 1..8).map(move |b| (a, b))).collect();
+    let counts = motifs(&interacts(&pair_log(&objects, &pairs)), &MotifConfig::default());
+
+    assert_eq!(counts.triangles, 56);
+    assert_eq!(counts.open_triads, 0);
+    assert_eq!(counts.triad("part", "part", "part"), TriadCount { closed: 56, open: 0 });
+    assert_eq!(counts.transitivity(), 1.0);
+}
+
+#[test]
+fn test_typed_triads() {
+    let objects = [("o1", "order"), ("i1", "item"), ("v1", "invoice"), ("o2", "order"), ("i2", "item"), ("v2", "invoice")];
+    let pairs = [(0, 1), (1, 2), (0, 2), (3, 4), (4, 5)];
+    let counts = motifs(&interacts(&pair_log(&objects, &pairs)), &MotifConfig::default());
+
+    assert_eq!(counts.triangles, 1);
+    assert_eq!(counts.open_triads, 1);
+    assert_eq!(counts.triad("order", "item", "invoice"), TriadCount { closed: 1, open: 1 });
+    assert_eq!(counts.triad("item", "invoice", "order"), counts.triad("order", "item", "invoice"));
+    assert_eq!(counts.triads.len(), 1);
+    assert_eq!(counts.transitivity(), 0.75);
+}
+
+#[test]
+fn test_relation_restriction() {
+    let objects = [("a", "part"), ("b", "part"), ("c", "part")];
+    let ocdg = generate_ocdg(&pair_log(&objects, &[(0, 1), (1, 2), (0, 2)]), &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+
+    assert_eq!(motifs(&ocdg, &MotifConfig { relations: Some(vec![Relations::INTERACTS]) }).triangles, 1);
+    let none = motifs(&ocdg, &MotifConfig { relations: Some(vec![Relations::SPLIT]) });
+    assert_eq!((none.triangles, none.open_triads), (0, 0));
+    assert_eq!(none.transitivity(), 0.0);
+}
+
+#[test]
+fn test_triangles_match_brute_force() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let adjacent = |a: &usize, b: &usize| ocdg.irels.get(a).is_some_and(|targets| targets.contains_key(b)) || ocdg.irels.get(b).is_some_and(|targets| targets.contains_key(a));
+    let mut oids: Vec<usize> = ocdg.node_attributes.keys().copied().collect();
+    oids.sort_unstable();
+
+    let mut triangles = 0;
+    for (i, a) in oids.iter().enumerate() {
+        for (j, b) in oids.iter().enumerate().skip(i + 1) {
+            for c in oids.iter().skip(j + 1) {
+                if adjacent(a, b) && adjacent(b, c) && adjacent(a, c) {
+                    triangles += 1;
+                }
+            }
+        }
+    }
+    let counts = motifs(&ocdg, &MotifConfig::default());
+    assert_eq!(counts.triangles, triangles);
+    assert_eq!(counts.triads.values().map(|triad| triad.closed).sum::<usize>(), triangles);
+}
@@ -0,0 +1,42 @@
+use pmrs::objects::ocdg::exporter::{export_neighbor_documents, NeighborOptions};
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use serde_json::{json, Value};
+use strum::IntoEnumIterator;
+
+fn documents(options: &NeighborOptions) -> Vec<Value> {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let mut buffer: Vec<u8> = vec![];
+    let written = export_neighbor_documents(&log, &ocdg, &mut buffer, options).unwrap();
+    let lines: Vec<Value> = String::from_utf8(buffer).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(lines.len(), written);
+    lines
+}
+
+fn neighbor(id: &str, obj_type: &str, last_ts: &str) -> Value {
+    json!({"id": id, "type": obj_type, "event_count": 1, "last_ts": last_ts})
+}
+
+#[test]
+fn test_neighbor_document() {
+    let lines = documents(&NeighborOptions::default());
+    assert_eq!(lines.iter().map(|line| line["id"].as_str().unwrap()).collect::<Vec<&str>>(), vec!["i1", "i2", "o1", "p1", "r1"]);
+
+    let birth = "2020-07-09T07:20:01.527Z";
+    let items = json!([neighbor("i1", "item", birth), neighbor("i2", "item", birth)]);
+    assert_eq!(lines[2], json!({"id": "o1", "type": "order", "attributes": {"costs": 3500.0, "customer": "Apple"},
+                                "neighbors": {"COBIRTH": items, "CODEATH": [neighbor("i2", "item", birth)], "COLIFE": [neighbor("i2", "item", birth)],
+                                              "CONSUMES": items, "INTERACTS": items}}));
+    // i1 goes on to e2 alone
+    assert_eq!(lines[0]["neighbors"]["MERGE"], json!([neighbor("i2", "item", "2020-07-09T07:21:01.527Z")]));
+}
+
+#[test]
+fn test_neighbor_options() {
+    let lines = documents(&NeighborOptions { max_neighbors: Some(1), relations: Some(vec![Relations::INTERACTS, Relations::COLIFE]) });
+    let o1 = &lines[2];
+    assert_eq!(o1["neighbors"].as_object().unwrap().keys().collect::<Vec<&String>>(), vec!["COLIFE", "INTERACTS"]);
+    assert_eq!(o1["neighbors"]["INTERACTS"].as_array().unwrap().len(), 1);
+    assert_eq!(o1["neighbors"]["INTERACTS"][0]["id"], "i1");
+}
@@ -0,0 +1,60 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+use petgraph::Direction;
+use strum::IntoEnumIterator;
+
+// p1 spawns s1 and s2
+fn split_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (label, obj_type)) in [("p1", "pallet"), ("s1", "shipment"), ("s2", "shipment")].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, &[&str]); 3] = [("e1", &["p1"]), ("e2", &["p1", "s1", "s2"]), ("e3", &["s1"])];
+    for (minute, (id, objects)) in events.iter().enumerate() {
+        log.insert_event(id, "step", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+#[test]
+fn test_neighbors_by_direction() {
+    let log = split_log();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS, Relations::DESCENDANTS]).unwrap();
+    let oid = |label: &str| *log.object_map.get_by_left(label).unwrap();
+    let (p1, s1, s2) = (oid("p1"), oid("s1"), oid("s2"));
+
+    assert_eq!(ocdg.neighbors_with_relation(p1, Relations::DESCENDANTS, Direction::Outgoing), vec![s1, s2]);
+    assert!(ocdg.neighbors_with_relation(p1, Relations::DESCENDANTS, Direction::Incoming).is_empty());
+    assert_eq!(ocdg.neighbors_with_relation(s1, Relations::DESCENDANTS, Direction::Incoming), vec![p1]);
+    assert_eq!(ocdg.neighbors_with_relation(s1, Relations::INTERACTS, Direction::Incoming), vec![p1, s2]);
+    // not in the graph
+    assert!(ocdg.neighbors_with_relation(usize::MAX, Relations::INTERACTS, Direction::Outgoing).is_empty());
+    assert_eq!(ocdg.neighbors_with_relation_iter(usize::MAX, Relations::INTERACTS, Direction::Incoming).count(), 0);
+}
+
+#[test]
+fn test_neighbors_match_irels() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let holds = |src: &usize, tar: &usize, rel: Relations| ocdg.irels.get(src).and_then(|targets| targets.get(tar)).is_some_and(|rels| rels.contains_key(&rel.relation_index().into()));
+    for oid in ocdg.inodes.keys() {
+        for rel in Relations::iter() {
+            let mut outgoing: Vec<usize> = ocdg.inodes.keys().filter(|tar| holds(oid, tar, rel)).copied().collect();
+            outgoing.sort_unstable();
+            let mut incoming: Vec<usize> = ocdg.inodes.keys().filter(|src| holds(src, oid, rel)).copied().collect();
+            incoming.sort_unstable();
+            assert_eq!(ocdg.neighbors_with_relation(*oid, rel, Direction::Outgoing), outgoing);
+            assert_eq!(ocdg.neighbors_with_relation(*oid, rel, Direction::Incoming), incoming);
+            assert_eq!(ocdg.neighbors_with_relation_iter(*oid, rel, Direction::Incoming).count(), incoming.len());
+        }
+    }
+}
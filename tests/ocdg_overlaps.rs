@@ -0,0 +1,75 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use pmrs::objects::ocdg::{generate_ocdg, trace_pair, Ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+// a and b share three events in the middle of their lifecycles, c is born with a and d dies with b
+fn overlap_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, label) in ["a", "b", "c", "d"].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: "part".to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [&[&str]; 6] = [&["a", "c"], &["b"], &["a", "b"], &["a", "b", "c"], &["a", "b", "d"], &["b", "d"]];
+    for (minute, objects) in events.iter().enumerate() {
+        log.insert_event(&format!("e{}", minute), "step", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log.insert_event("e6", "step", start + Duration::minutes(6), &["a"], AHashMap::new()).unwrap();
+    log
+}
+
+fn events(ocdg: &Ocdg, log: &Ocel, src: &str, tar: &str) -> Option<Vec<String>> {
+    let src = *log.object_map.get_by_left(src).unwrap();
+    let tar = *log.object_map.get_by_left(tar).unwrap();
+    ocdg.relation_events(src, tar, Relations::OVERLAPS).map(|eids| {
+        let mut labels: Vec<String> = eids.iter().map(|eid| log.event_map.get_by_right(eid).unwrap().to_owned()).collect();
+        labels.sort();
+        labels
+    })
+}
+
+#[test]
+fn test_overlapping_lifecycles() {
+    let log = overlap_log();
+    let ocdg = generate_ocdg(&log, &[Relations::OVERLAPS]).unwrap();
+
+    let shared = Some(vec!["e2".to_owned(), "e3".to_owned(), "e4".to_owned()]);
+    assert_eq!(events(&ocdg, &log, "a", "b"), shared);
+    assert_eq!(events(&ocdg, &log, "b", "a"), shared);
+    // a and c are born together, b and d die together
+    assert_eq!(events(&ocdg, &log, "a", "c"), None);
+    assert_eq!(events(&ocdg, &log, "b", "d"), None);
+    // a and d share e4 and neither birth nor death
+    assert_eq!(events(&ocdg, &log, "a", "d"), Some(vec!["e4".to_owned()]));
+
+    let a = *log.object_map.get_by_left("a").unwrap();
+    let b = *log.object_map.get_by_left("b").unwrap();
+    let c = *log.object_map.get_by_left("c").unwrap();
+    assert!(trace_pair(&log, a, b, Relations::OVERLAPS).holds());
+    assert!(!trace_pair(&log, a, c, Relations::OVERLAPS).holds());
+}
+
+#[test]
+fn test_overlaps_is_disjoint_from_lifecycle_relations() {
+    let lifecycle = [Relations::COBIRTH, Relations::CODEATH, Relations::COLIFE];
+    for path in ["logs/order-to-cash.jsonocel", "logs/supply.jsonocel", "logs/logistics.jsonocel", "logs/three-days.jsonocel"] {
+        let log = import_ocel(path).unwrap();
+        let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+        for (src, targets) in &ocdg.irels {
+            for (tar, rels) in targets {
+                let present: IntSet<usize> = rels.keys().copied().collect();
+                if present.contains(&Relations::OVERLAPS.relation_index().into()) {
+                    assert!(lifecycle.iter().all(|rel| !present.contains(&rel.relation_index().into())), "{}: {} -> {}", path, src, tar);
+                    assert!(present.contains(&Relations::INTERACTS.relation_index().into()), "{}", path);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use pmrs::objects::ocdg::presets::{Preset, RelationSet};
+use pmrs::objects::ocdg::Relations;
+use strum::IntoEnumIterator;
+
+#[test]
+fn test_presets_hold_the_listed_relations() {
+    assert_eq!(RelationSet::preset(Preset::Lineage).relations(), [Relations::DESCENDANTS, Relations::INHERITANCE, Relations::CONSUMES, Relations::SPLIT, Relations::MERGE]);
+    assert_eq!(RelationSet::preset(Preset::Lifecycle).relations(), [Relations::COLIFE, Relations::COBIRTH, Relations::CODEATH]);
+    assert_eq!(RelationSet::preset(Preset::Interaction).relations(), [Relations::INTERACTS, Relations::MINION, Relations::PEELER, Relations::ENGAGES]);
+    assert_eq!(RelationSet::preset(Preset::All).into_vec(), Relations::iter().collect::<Vec<Relations>>());
+
+    // the three focused presets split the original relations, later ones like REACHABLE and OVERLAPS are only in All
+    let mut covered: Vec<Relations> = [Preset::Lineage, Preset::Lifecycle, Preset::Interaction].iter().flat_map(|preset| RelationSet::preset(*preset).into_vec()).collect();
+    covered.sort_unstable_by_key(|rel| rel.relation_index());
+    assert_eq!(covered, Relations::iter().filter(|rel| ![Relations::REACHABLE, Relations::OVERLAPS].contains(rel)).collect::<Vec<Relations>>());
+}
+
+#[test]
+fn test_names_and_matching() {
+    let set = RelationSet::from_names(&["Lifecycle", "INTERACTS", "COBIRTH"]).unwrap();
+    assert_eq!(set.relations(), [Relations::INTERACTS, Relations::COLIFE, Relations::COBIRTH, Relations::CODEATH]);
+    assert!(RelationSet::from_names(&["Lifecycle", "FRIENDS"]).unwrap_err().to_string().contains("unknown relation FRIENDS"));
+
+    assert_eq!(Preset::matching(&[Relations::CODEATH, Relations::COBIRTH, Relations::COLIFE]), Some(Preset::Lifecycle));
+    assert_eq!(Preset::matching(&Relations::iter().collect::<Vec<Relations>>()), Some(Preset::All));
+    assert_eq!(Preset::matching(&[Relations::COBIRTH]), None);
+    for preset in Preset::iter() {
+        assert_eq!(Preset::matching(RelationSet::preset(preset).relations()), Some(preset));
+    }
+}
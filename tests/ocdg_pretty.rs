@@ -0,0 +1,33 @@
+use pmrs::objects::ocdg::{Ocdg, generate_ocdg, Relations};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+
+fn fixture() -> (Ocel, Ocdg) {
+    let log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg: Ocdg = generate_ocdg(&log, &[Relations::INTERACTS, Relations::COBIRTH, Relations::DESCENDANTS]).unwrap();
+    (log, ocdg)
+}
+
+#[test]
+fn test_ocdg_debug_is_compact() {
+    let (_log, ocdg) = fixture();
+    assert_eq!(format!("{:?}", ocdg), "Ocdg { nodes: 5, edges: 8, objects: 5, events: 2, event_index: false }");
+}
+
+#[test]
+fn test_ocdg_pretty_snapshot() {
+    let (log, ocdg) = fixture();
+    let expected = "Ocdg { nodes: 5, edges: 8, objects: 5, events: 2, event_index: false }\n\
+                    i1 (item): 2 events, relations: [INTERACTS=2, COBIRTH=2]\n\
+                    i2 (item): 1 events, relations: [INTERACTS=2, COBIRTH=2]\n\
+                    o1 (order): 1 events, relations: [INTERACTS=2, COBIRTH=2]\n\
+                    ... 2 more\n";
+    assert_eq!(ocdg.pretty(&log, 3), expected);
+}
+
+#[test]
+fn test_node_info_pretty_resolves_events() {
+    let (log, ocdg) = fixture();
+    let oid = *log.object_map.get_by_left("i1").unwrap();
+    assert_eq!(ocdg.node_attributes[&oid].pretty(oid, &log), "i1 (item): [e1, e2]");
+}
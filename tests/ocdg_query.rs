@@ -0,0 +1,127 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use petgraph::Direction;
+use pmrs::objects::ocdg::query::{impact_set, neighbors_with, Page, PathPattern, PatternError, QueryLimits, QueryOrder};
+use pmrs::objects::ocdg::{generate_ocdg, generate_ocdg_config, GenerationConfig, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+use strum::IntoEnumIterator;
+
+type Query = fn(&Ocdg, &Ocel, usize, &[Relations], &QueryLimits) -> Page<usize>;
+
+// follows next_offset from the first page to the last
+fn collect_pages(query: Query, ocdg: &Ocdg, log: &Ocel, oid: usize, relations: &[Relations], order: QueryOrder) -> Vec<usize> {
+    let limits = QueryLimits { offset: 0, limit: 2, order };
+    let mut items = vec![];
+    let mut offset = Some(0);
+    while let Some(current) = offset {
+        let page = query(ocdg, log, oid, relations, &limits.page(current));
+        assert!(page.items.len() <= 2);
+        items.extend(page.items);
+        offset = page.next_offset;
+    }
+    items
+}
+
+#[test]
+fn test_pages_cover_the_result() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: Relations::iter().collect(), ..Default::default() }).unwrap();
+    let relation_sets = [vec![Relations::INTERACTS], vec![Relations::DESCENDANTS, Relations::CONSUMES]];
+    let queries: [Query; 2] = [neighbors_with, impact_set];
+    for oid in ocdg.inodes.keys() {
+        for relations in &relation_sets {
+            for query in queries {
+                for order in [QueryOrder::ByDegree, QueryOrder::ByOid, QueryOrder::ByFirstTimestamp] {
+                    let all = query(&ocdg, &log, *oid, relations, &QueryLimits::all(order));
+                    assert_eq!(all.total, all.items.len());
+                    assert_eq!(all.next_offset, None);
+                    // the same order on every call, so the pages line up with the whole result
+                    assert_eq!(collect_pages(query, &ocdg, &log, *oid, relations, order), all.items);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_orders_and_bounds() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: Relations::iter().collect(), ..Default::default() }).unwrap();
+    let (oid, _) = ocdg.irels.iter().max_by_key(|(oid, targets)| (targets.len(), usize::MAX - **oid)).unwrap();
+
+    let by_oid = neighbors_with(&ocdg, &log, *oid, &[Relations::INTERACTS], &QueryLimits::all(QueryOrder::ByOid)).items;
+    assert!(by_oid.windows(2).all(|pair| pair[0] < pair[1]));
+    let degree = |oid: &usize| ocdg.net.neighbors_undirected(ocdg.inodes[oid]).count();
+    let by_degree = neighbors_with(&ocdg, &log, *oid, &[Relations::INTERACTS], &QueryLimits::all(QueryOrder::ByDegree)).items;
+    assert!(by_degree.windows(2).all(|pair| degree(&pair[0]) >= degree(&pair[1])));
+    let birth = |oid: &usize| log.objects[oid].events.iter().map(|eid| log.events.event(eid).timestamp).min().unwrap();
+    let by_birth = neighbors_with(&ocdg, &log, *oid, &[Relations::INTERACTS], &QueryLimits::all(QueryOrder::ByFirstTimestamp)).items;
+    assert!(by_birth.windows(2).all(|pair| birth(&pair[0]) <= birth(&pair[1])));
+
+    let past_end = neighbors_with(&ocdg, &log, *oid, &[Relations::INTERACTS], &QueryLimits { offset: by_oid.len() + 5, limit: 10, order: QueryOrder::ByOid });
+    assert_eq!(past_end, Page { items: vec![], total: by_oid.len(), next_offset: None });
+    let empty_limit = neighbors_with(&ocdg, &log, *oid, &[Relations::INTERACTS], &QueryLimits { offset: 0, limit: 0, order: QueryOrder::ByOid });
+    assert_eq!(empty_limit.next_offset, None);
+    // unknown objects have no neighbours
+    assert_eq!(impact_set(&ocdg, &log, usize::MAX, &[Relations::INTERACTS], &QueryLimits::default()).total, 0);
+}
+
+// p1 spawns the shipments s1 and s2, s1 is then handed to the customer c1
+fn pallet_graph() -> (Ocel, Ocdg) {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (label, obj_type)) in [("p1", "pallet"), ("s1", "shipment"), ("s2", "shipment"), ("c1", "customer")].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, &[&str]); 3] = [("e1", &["p1"]), ("e2", &["p1", "s1", "s2"]), ("e3", &["s1", "c1"])];
+    for (minute, (id, objects)) in events.iter().enumerate() {
+        log.insert_event(id, "step", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS, Relations::DESCENDANTS]).unwrap();
+    (log, ocdg)
+}
+
+#[test]
+fn test_path_patterns() {
+    let (log, ocdg) = pallet_graph();
+    let all = QueryLimits::all(QueryOrder::ByOid);
+    let labels = |page: Page<Vec<usize>>| -> Vec<Vec<String>> {
+        page.items.into_iter().map(|path| path.iter().map(|oid| log.object_map.get_by_right(oid).unwrap().to_owned()).collect()).collect()
+    };
+
+    let spawned = PathPattern::parse("pallet -DESCENDANTS-> shipment").unwrap();
+    assert_eq!(labels(spawned.find(&ocdg, &log, &all)), vec![vec!["p1", "s1"], vec!["p1", "s2"]]);
+
+    let delivered = PathPattern::parse("pallet -DESCENDANTS-> shipment -INTERACTS-> customer").unwrap();
+    assert_eq!(labels(delivered.find(&ocdg, &log, &all)), vec![vec!["p1", "s1", "c1"]]);
+
+    // wildcards and reverse arrows, no object is visited twice
+    let siblings = PathPattern::parse("* <-DESCENDANTS- pallet -DESCENDANTS-> *").unwrap();
+    assert_eq!(labels(siblings.find(&ocdg, &log, &all)), vec![vec!["s1", "p1", "s2"], vec!["s2", "p1", "s1"]]);
+
+    // paged like the other queries, the total counts the paths beyond the page
+    let limits = QueryLimits { limit: 1, ..Default::default() };
+    let first = siblings.find(&ocdg, &log, &limits);
+    assert_eq!((first.items.len(), first.total, first.next_offset), (1, 2, Some(1)));
+    let second = siblings.find(&ocdg, &log, &limits.page(1));
+    assert_eq!(labels(second.clone()), vec![vec!["s2", "p1", "s1"]]);
+    assert_eq!((second.total, second.next_offset), (2, None));
+    assert_eq!(siblings.find(&ocdg, &log, &limits.page(5)).items.len(), 0);
+}
+
+#[test]
+fn test_path_pattern_errors() {
+    assert_eq!(PathPattern::parse(""), Err(PatternError::Empty));
+    assert_eq!(PathPattern::parse("order -SPLIT->"), Err(PatternError::MissingType));
+    assert_eq!(PathPattern::parse("order -FRIENDS-> item"), Err(PatternError::UnknownRelation("FRIENDS".to_string())));
+    assert_eq!(PathPattern::parse("order SPLIT item"), Err(PatternError::ExpectedArrow("SPLIT".to_string())));
+    assert_eq!(PathPattern::parse("order -SPLIT-> -SPLIT->"), Err(PatternError::ExpectedType("-SPLIT->".to_string())));
+    assert_eq!(PathPattern::parse("a -split-> b").unwrap().hops, vec![(Relations::SPLIT, Direction::Outgoing)]);
+    assert_eq!(PathPattern::parse("a -SPLIT-> b -SPLIT-> c -SPLIT-> d -SPLIT-> e -SPLIT-> f"), Err(PatternError::TooLong(5)));
+}
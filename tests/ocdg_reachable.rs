@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::objects::ocdg::{generate_ocdg_config, Evidence, GenerationConfig, Ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+// a spawns b, b spawns c, c spawns d. x spawns y and both spawn z in the same event
+fn chain_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, label) in ["a", "b", "c", "d", "x", "y", "z"].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: "batch".to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, &[&str]); 7] = [("e1", &["a"]), ("e2", &["a", "b"]), ("e3", &["b", "c"]), ("e4", &["c", "d"]),
+                                        ("e5", &["x"]), ("e6", &["x", "y"]), ("e7", &["x", "y", "z"])];
+    for (minute, (id, objects)) in events.iter().enumerate() {
+        log.insert_event(id, "spawn", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+// (source, target) -> sorted evidence event ids of the relation
+fn instances(ocdg: &Ocdg, rel: Relations) -> BTreeMap<(String, String), Vec<String>> {
+    let mut instances = BTreeMap::new();
+    for (src, targets) in &ocdg.irels {
+        for (tar, rels) in targets {
+            if let Some(eids) = rels.get(&rel.relation_index().into()) {
+                let mut eids: Vec<String> = eids.iter().map(|eid| ocdg.event_map.get_by_right(eid).unwrap().to_owned()).collect();
+                eids.sort();
+                instances.insert((ocdg.object_map.get_by_right(src).unwrap().to_owned(), ocdg.object_map.get_by_right(tar).unwrap().to_owned()), eids);
+            }
+        }
+    }
+    instances
+}
+
+fn pair(src: &str, tar: &str) -> (String, String) {
+    (src.to_owned(), tar.to_owned())
+}
+
+#[test]
+fn test_reachable_follows_descendant_chains() {
+    let log = chain_log();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::DESCENDANTS, Relations::REACHABLE], ..Default::default() }).unwrap();
+
+    let descendants = instances(&ocdg, Relations::DESCENDANTS);
+    assert_eq!(descendants.keys().cloned().collect::<Vec<_>>(), vec![pair("a", "b"), pair("b", "c"), pair("c", "d"), pair("x", "y"), pair("x", "z"), pair("y", "z")]);
+    // x -> z is a direct descendant and gets no REACHABLE edge although x -> y -> z is a chain
+    assert_eq!(instances(&ocdg, Relations::REACHABLE),
+               BTreeMap::from_iter([(pair("a", "c"), vec!["e2".to_owned(), "e3".to_owned()]),
+                                    (pair("a", "d"), vec!["e2".to_owned(), "e3".to_owned(), "e4".to_owned()]),
+                                    (pair("b", "d"), vec!["e3".to_owned(), "e4".to_owned()])]));
+    assert_eq!(ocdg.metadata().unwrap().edge_counts["REACHABLE"], 3);
+}
+
+// a spawns b and c in separate events, both merge into d
+fn diamond_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, label) in ["a", "b", "c", "d"].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: "batch".to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, &[&str]); 4] = [("e1", &["a"]), ("e2", &["a", "b"]), ("e3", &["a", "c"]), ("e4", &["b", "c", "d"])];
+    for (minute, (id, objects)) in events.iter().enumerate() {
+        log.insert_event(id, "spawn", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+#[test]
+fn test_reachable_unites_all_chains() {
+    let log = diamond_log();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::DESCENDANTS, Relations::REACHABLE], ..Default::default() }).unwrap();
+
+    let descendants = instances(&ocdg, Relations::DESCENDANTS);
+    assert_eq!(descendants.keys().cloned().collect::<Vec<_>>(), vec![pair("a", "b"), pair("a", "c"), pair("b", "d"), pair("c", "d")]);
+    // a -> b -> d and a -> c -> d both end in d, its edge holds the events of both chains
+    assert_eq!(instances(&ocdg, Relations::REACHABLE), BTreeMap::from_iter([(pair("a", "d"), vec!["e2".to_owned(), "e3".to_owned(), "e4".to_owned()])]));
+}
+
+#[test]
+fn test_reachable_without_descendants() {
+    let log = chain_log();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::REACHABLE], ..Default::default() }).unwrap();
+    // the chains are evaluated for the closure but only REACHABLE ends up in the graph
+    assert!(instances(&ocdg, Relations::DESCENDANTS).is_empty());
+    assert_eq!(instances(&ocdg, Relations::REACHABLE).len(), 3);
+    assert_eq!(ocdg.net.edge_count(), 3);
+
+    let counts = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::REACHABLE], evidence: Evidence::CountsOnly, ..Default::default() }).unwrap();
+    assert_eq!(counts.net.edge_count(), 3);
+    assert_eq!(counts.relation_support(0, 3, Relations::REACHABLE), Some(3));
+}
+
+#[test]
+fn test_reachable_extends_descendants() {
+    for path in ["logs/order-to-cash.jsonocel", "logs/supply.jsonocel", "logs/logistics.jsonocel", "logs/pallets.jsonocel"] {
+        let log = import_ocel(path).unwrap();
+        let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: Relations::iter().collect(), ..Default::default() }).unwrap();
+        let descendants = instances(&ocdg, Relations::DESCENDANTS);
+        for (src, tar) in instances(&ocdg, Relations::REACHABLE).keys() {
+            assert_ne!(src, tar, "{}", path);
+            assert!(!descendants.contains_key(&(src.to_owned(), tar.to_owned())), "{}", path);
+            assert!(descendants.keys().any(|(first, _)| first == src), "{}", path);
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+#[test]
+fn test_relation_path() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let oid = |label: &str| *log.object_map.get_by_left(label).unwrap();
+    let (o1, i1, i2, p1) = (oid("o1"), oid("i1"), oid("i2"), oid("p1"));
+
+    // the first relation of the edge by index
+    assert_eq!(ocdg.relation_path(o1, i2, None), Some(vec![(o1, i2, Relations::INTERACTS)]));
+    assert_eq!(ocdg.relation_path(o1, o1, None), Some(vec![]));
+    // o1 and p1 never meet
+    assert_eq!(ocdg.relation_path(o1, p1, None), None);
+    assert_eq!(ocdg.relation_path(o1, 1000, None), None);
+
+    // i2 shares its lifecycle with o1 and passes on to i1
+    let lineage = [Relations::COLIFE, Relations::INHERITANCE];
+    assert_eq!(ocdg.relation_path(o1, i1, Some(&lineage)), Some(vec![(o1, i2, Relations::COLIFE), (i2, i1, Relations::INHERITANCE)]));
+    assert_eq!(ocdg.relation_path(i1, o1, Some(&lineage)), None);
+    assert_eq!(ocdg.relation_path(o1, i1, Some(&[Relations::COLIFE])), None);
+}
@@ -0,0 +1,92 @@
+use pmrs::objects::ocdg::{generate_ocdg, generate_ocdg_with, Evidence, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::Ocel;
+use strum::IntoEnumIterator;
+
+fn oid(log: &Ocel, id: &str) -> usize {
+    *log.object_map.get_by_left(id).unwrap()
+}
+
+#[test]
+fn test_existing_and_absent_relations() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg: Ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let (i1, o1, r1) = (oid(&log, "i1"), oid(&log, "o1"), oid(&log, "r1"));
+
+    // i1 and o1 are both in e1
+    assert!(ocdg.has_relation(i1, o1, Relations::INTERACTS));
+    assert!(ocdg.relations_between(i1, o1).contains(&Relations::INTERACTS));
+    let e1 = *log.event_map.get_by_left("e1").unwrap();
+    assert!(ocdg.relation_events(i1, o1, Relations::INTERACTS).unwrap().contains(&e1));
+
+    // objects that never meet
+    assert!(!ocdg.has_relation(i1, r1, Relations::INTERACTS));
+    assert!(ocdg.relations_between(i1, r1).is_empty());
+    assert_eq!(ocdg.relation_events(i1, r1, Relations::INTERACTS), None);
+
+    // unknown objects are absent rather than a panic
+    assert!(!ocdg.has_relation(usize::MAX, o1, Relations::INTERACTS));
+    assert!(!ocdg.has_relation(i1, usize::MAX, Relations::INTERACTS));
+    assert!(ocdg.relations_between(usize::MAX, usize::MAX).is_empty());
+    assert_eq!(ocdg.relation_events(usize::MAX, o1, Relations::INTERACTS), None);
+
+    // the answers agree with irels for every pair
+    for (src, targets) in &ocdg.irels {
+        for (tar, rels) in targets {
+            let between = ocdg.relations_between(*src, *tar);
+            assert_eq!(between.len(), rels.len());
+            assert!(between.windows(2).all(|pair| pair[0].relation_index() < pair[1].relation_index()));
+            for rel in Relations::iter() {
+                assert_eq!(ocdg.has_relation(*src, *tar, rel), between.contains(&rel));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_presence_without_evidence() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg_with(&log, &[Relations::INTERACTS], Evidence::None).unwrap();
+    let (i1, o1) = (oid(&log, "i1"), oid(&log, "o1"));
+    assert!(ocdg.has_relation(i1, o1, Relations::INTERACTS));
+    assert_eq!(ocdg.relations_between(i1, o1), vec![Relations::INTERACTS]);
+    // the events are not kept
+    assert_eq!(ocdg.relation_events(i1, o1, Relations::INTERACTS), None);
+}
+
+#[test]
+fn test_relations_between_with_events() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS, Relations::COLIFE]).unwrap();
+    let (i2, o1, r1) = (oid(&log, "i2"), oid(&log, "o1"), oid(&log, "r1"));
+    let e1 = *log.event_map.get_by_left("e1").unwrap();
+
+    // i2 and o1 only ever meet in e1
+    let between = ocdg.get_relations_between(i2, o1);
+    assert_eq!(between.iter().map(|(rel, _)| *rel).collect::<Vec<Relations>>(), vec![Relations::INTERACTS, Relations::COLIFE]);
+    assert!(between.iter().all(|(_, events)| events.len() == 1 && events.contains(&e1)));
+    assert_eq!(ocdg.get_events_for_relation(i2, o1, Relations::COLIFE), Some(between[1].1));
+    assert_eq!(ocdg.get_events_for_relation(i2, o1, Relations::COBIRTH), None);
+
+    assert!(ocdg.get_relations_between(i2, r1).is_empty());
+    assert!(ocdg.get_relations_between(usize::MAX, o1).is_empty());
+    assert_eq!(ocdg.get_events_for_relation(usize::MAX, o1, Relations::INTERACTS), None);
+}
+
+#[test]
+fn test_any_relation_and_pairs() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let (i1, i2, o1, p1, r1) = (oid(&log, "i1"), oid(&log, "i2"), oid(&log, "o1"), oid(&log, "p1"), oid(&log, "r1"));
+
+    assert!(ocdg.has_any_relation(i1, o1));
+    assert!(!ocdg.has_any_relation(i1, r1));
+    assert!(!ocdg.has_relation(o1, p1, Relations::COLIFE));
+    assert!(!ocdg.has_any_relation(usize::MAX, o1));
+
+    // i2 and o1 only take part in e1, r1 and p1 only in e3
+    let mut expected = vec![(i2, o1), (o1, i2), (p1, r1), (r1, p1)];
+    expected.sort_unstable();
+    assert_eq!(ocdg.objects_with_relation(Relations::COLIFE), expected);
+    assert!(ocdg.objects_with_relation(Relations::ENGAGES).is_empty());
+}
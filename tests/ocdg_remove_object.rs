@@ -0,0 +1,49 @@
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+#[test]
+fn test_remove_object() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let mut ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    assert!(!ocdg.get_objects_by_type("item").is_empty());
+    ocdg.build_reverse_index();
+    let objects = ocdg.object_count();
+    let i1 = *log.object_map.get_by_left("i1").unwrap();
+    let others: Vec<usize> = ocdg.node_attributes.keys().copied().filter(|oid| *oid != i1).collect();
+    assert!(others.iter().any(|oid| ocdg.has_any_relation(i1, *oid) || ocdg.has_any_relation(*oid, i1)));
+
+    assert!(ocdg.remove_object(i1));
+    assert_eq!(ocdg.object_count(), objects - 1);
+    assert!(others.iter().all(|oid| !ocdg.has_any_relation(i1, *oid) && !ocdg.has_any_relation(*oid, i1)));
+    assert!(!ocdg.remove_object(i1));
+    assert_eq!(ocdg.object_count(), objects - 1);
+
+    // the lookups point into the shrunk net and the built indices follow
+    assert_eq!(ocdg.net.node_count(), objects - 1);
+    for (oid, node) in &ocdg.inodes {
+        assert_eq!(ocdg.net[*node], *oid);
+    }
+    for (src, targets) in &ocdg.iedges {
+        for (tar, edge) in targets {
+            assert_eq!(ocdg.net.edge_endpoints(*edge), Some((ocdg.inodes[src], ocdg.inodes[tar])));
+        }
+    }
+    assert!(!ocdg.get_objects_by_type("item").contains(&i1));
+    assert!(ocdg.irels_in.as_ref().unwrap().values().all(|sources| !sources.contains_key(&i1)));
+}
+
+#[test]
+fn test_remove_objects_of_type() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let mut ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let objects = ocdg.object_count();
+    let items = ocdg.get_objects_by_type("item").to_vec();
+    assert!(!items.is_empty());
+
+    assert_eq!(ocdg.remove_objects_of_type("item"), items.len());
+    assert_eq!(ocdg.object_count(), objects - items.len());
+    assert!(ocdg.get_objects_by_type("item").is_empty());
+    assert!(ocdg.irels.iter().all(|(src, targets)| !items.contains(src) && targets.keys().all(|tar| !items.contains(tar))));
+    assert_eq!(ocdg.remove_objects_of_type("item"), 0);
+}
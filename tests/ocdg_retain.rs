@@ -0,0 +1,73 @@
+use chrono::{TimeZone, Utc};
+use pmrs::objects::ocdg::{generate_ocdg, Ocdg, Relations};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+fn fixture() -> (Ocel, Ocdg) {
+    let log: Ocel = import_ocel("logs/supply.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    (log, ocdg)
+}
+
+fn assert_consistent(ocdg: &Ocdg) {
+    assert_eq!(ocdg.inodes.len(), ocdg.net.node_count());
+    assert_eq!(ocdg.node_attributes.len(), ocdg.net.node_count());
+    for (oid, node) in &ocdg.inodes {
+        assert_eq!(ocdg.net[*node], *oid);
+    }
+    let edge_count: usize = ocdg.iedges.values().map(|targets| targets.len()).sum();
+    assert_eq!(edge_count, ocdg.net.edge_count());
+    for (src, targets) in &ocdg.iedges {
+        for (tar, edge) in targets {
+            let (a, b) = ocdg.net.edge_endpoints(*edge).unwrap();
+            assert_eq!((ocdg.net[a], ocdg.net[b]), (*src, *tar));
+            assert!(ocdg.irels[src].contains_key(tar));
+        }
+    }
+}
+
+#[test]
+fn test_retain_edges_with_three_supporting_events() {
+    let (log, mut ocdg) = fixture();
+    let before = ocdg.net.edge_count();
+    ocdg.retain_edges(|edge| edge.event_count() >= 3);
+
+    let o1 = *log.object_map.get_by_left("o1").unwrap();
+    let i1 = *log.object_map.get_by_left("i1").unwrap();
+    assert!(ocdg.net.edge_count() < before);
+    assert_eq!(ocdg.net.edge_count(), 6);
+    assert!(ocdg.irels[&o1].contains_key(&i1) && ocdg.irels[&i1].contains_key(&o1));
+    // nodes are kept unless pruning is requested
+    assert_eq!(ocdg.net.node_count(), log.objects.len());
+    assert_consistent(&ocdg);
+}
+
+#[test]
+fn test_retain_edges_prunes_isolated_nodes() {
+    let (log, mut ocdg) = fixture();
+    ocdg.retain_edges_with(Some(&log), true, |edge| edge.event_count() >= 3);
+
+    // only the packages lose all of their edges
+    assert_eq!(ocdg.net.node_count(), 5);
+    assert_eq!(ocdg.object_map.len(), 5);
+    assert!(!ocdg.object_map.contains_left("p1") && ocdg.object_map.contains_left("i1"));
+    assert!(!ocdg.event_map.contains_left("e9") && ocdg.event_map.contains_left("e6"));
+    assert_consistent(&ocdg);
+}
+
+#[test]
+fn test_retain_edges_by_timestamp_and_relation() {
+    let (log, mut ocdg) = fixture();
+    let cutoff = Utc.ymd(2022, 2, 3).and_hms(0, 0, 0);
+    ocdg.retain_edges_with(Some(&log), false, |edge| {
+        assert!(edge.first_timestamp.is_some());
+        edge.last_timestamp.unwrap() >= cutoff && edge.has_relation(Relations::SPLIT)
+    });
+
+    let p1 = *log.object_map.get_by_left("p1").unwrap();
+    let mut targets: Vec<&str> = ocdg.irels[&p1].keys().map(|oid| log.object_map.get_by_right(oid).unwrap().as_str()).collect();
+    targets.sort();
+    assert_eq!(targets, vec!["p2", "p3"]);
+    assert_consistent(&ocdg);
+}
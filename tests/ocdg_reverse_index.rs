@@ -0,0 +1,49 @@
+use pmrs::objects::ocdg::{generate_ocdg, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+// every (target, relation) -> its sources, from the net and again from the reverse index
+fn predecessors(ocdg: &mut Ocdg) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
+    let mut oids: Vec<usize> = ocdg.node_attributes.keys().copied().collect();
+    oids.sort_unstable();
+    let scan = |ocdg: &Ocdg| oids.iter()
+                                 .flat_map(|oid| Relations::iter().map(move |rel| (*oid, rel)))
+                                 .map(|(oid, rel)| ocdg.predecessors_with_relation(oid, rel))
+                                 .collect::<Vec<Vec<usize>>>();
+    let built = ocdg.irels_in.take();
+    let scanned = scan(ocdg);
+    ocdg.irels_in = built;
+    (scanned, scan(ocdg))
+}
+
+#[test]
+fn test_reverse_index_matches_scan() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let mut ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    ocdg.build_reverse_index();
+    let (scanned, indexed) = predecessors(&mut ocdg);
+    assert_eq!(scanned, indexed);
+    assert!(indexed.iter().any(|sources| !sources.is_empty()));
+
+    // one entry per relation instance
+    let entries: usize = ocdg.irels_in.as_ref().unwrap().values().flat_map(|sources| sources.values()).map(|rels| rels.len()).sum();
+    let instances: usize = ocdg.irels.values().flat_map(|targets| targets.values()).map(|rels| rels.len()).sum();
+    assert_eq!(entries, instances);
+
+    let p1 = *log.object_map.get_by_left("p1").unwrap();
+    let p2 = *log.object_map.get_by_left("p2").unwrap();
+    assert!(ocdg.predecessors_with_relation(p2, Relations::SPLIT).contains(&p1));
+}
+
+#[test]
+fn test_reverse_index_follows_retain_edges() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let mut ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    ocdg.build_reverse_index();
+    ocdg.retain_edges(|edge| !edge.has_relation(Relations::SPLIT));
+
+    let p2 = *log.object_map.get_by_left("p2").unwrap();
+    assert!(ocdg.predecessors_with_relation(p2, Relations::SPLIT).is_empty());
+    let (scanned, indexed) = predecessors(&mut ocdg);
+    assert_eq!(scanned, indexed);
+}
@@ -0,0 +1,67 @@
+use std::str::FromStr;
+
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use pmrs::objects::ocdg::{generate_ocdg_config, trace_pair_config, GenerationConfig, Ocdg, RelationScope, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+use strum::IntoEnumIterator;
+
+// the truck lives from e1 to e5, the trailer from e2 to e4 and both only meet at e3
+fn yard_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec!["move".to_string()], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (label, oid, obj_type) in [("truck", 0, "truck"), ("trailer", 1, "trailer")] {
+        log.object_map.insert(label.to_string(), oid);
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    for (eid, omap) in [(1, vec![0]), (2, vec![1]), (3, vec![0, 1]), (4, vec![1]), (5, vec![0])] {
+        for oid in &omap {
+            log.objects.get_mut(oid).unwrap().events.push(eid);
+        }
+        log.events.insert(eid, OcelEvent { activity: "move".to_string(), timestamp: start + Duration::hours(eid as i64), vmap: AHashMap::new(), omap: IntSet::from_iter(omap) });
+        log.event_map.insert(format!("e{}", eid), eid);
+    }
+    log
+}
+
+fn generate(log: &Ocel, scope: RelationScope) -> Ocdg {
+    generate_ocdg_config(log, &GenerationConfig { relations: Relations::iter().collect(), scope, ..Default::default() }).unwrap()
+}
+
+#[test]
+fn test_engages_only_over_whole_lifecycle() {
+    let log = yard_log();
+    let whole = generate(&log, RelationScope::WholeLifecycle);
+    let overlap = generate(&log, RelationScope::OverlapOnly);
+
+    assert!(whole.relation_events(0, 1, Relations::ENGAGES).is_some());
+    assert!(overlap.relation_events(0, 1, Relations::ENGAGES).is_none());
+    // inside the window the truck only has the shared event
+    assert!(overlap.relation_events(1, 0, Relations::MINION).unwrap().contains(&3));
+    assert!(whole.relation_events(1, 0, Relations::MINION).is_none());
+    // shared events always lie inside the window
+    assert_eq!(whole.relation_events(0, 1, Relations::INTERACTS), overlap.relation_events(0, 1, Relations::INTERACTS));
+}
+
+#[test]
+fn test_trace_reports_overlap_window() {
+    let log = yard_log();
+    let config = GenerationConfig { scope: RelationScope::OverlapOnly, ..Default::default() };
+    let trace = trace_pair_config(&log, &config, 0, 1, Relations::ENGAGES);
+    assert!(!trace.holds());
+    assert!(trace.to_string().contains("[ok] source has 1 and target has 3 events in the overlap window"));
+    assert!(trace.to_string().contains("[fail] source first event e3 is part of the target lifecycle"));
+}
+
+#[test]
+fn test_scope_is_recorded() {
+    let log = yard_log();
+    let ocdg = generate(&log, RelationScope::OverlapOnly);
+    assert_eq!(ocdg.metadata().unwrap().config["scope"], "OverlapOnly");
+    assert_eq!(RelationScope::from_str("OverlapOnly").unwrap(), RelationScope::OverlapOnly);
+    assert_eq!(RelationScope::default(), RelationScope::WholeLifecycle);
+}
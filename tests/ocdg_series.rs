@@ -0,0 +1,80 @@
+use std::fs;
+
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use pmrs::objects::ocdg::series::{Interval, OcdgSeries};
+use pmrs::objects::ocdg::{GenerationConfig, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+
+// one event per hour, a and b meet in the first two hours, c joins later
+fn hourly_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec!["work".to_string()], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (label, oid) in [("a", 0), ("b", 1), ("c", 2)] {
+        log.object_map.insert(label.to_string(), oid);
+        log.objects.insert(oid, OcelObject { obj_type: "part".to_string(), ovmap: AHashMap::new(), events: vec![] });
+    }
+    let start = Utc.ymd(2022, 3, 1).and_hms(0, 0, 0);
+    for (eid, omap) in [vec![0, 1], vec![0, 1], vec![0, 2], vec![1, 2], vec![0, 1, 2], vec![2]].into_iter().enumerate() {
+        for oid in &omap {
+            log.objects.get_mut(oid).unwrap().events.push(eid);
+        }
+        log.events.insert(eid, OcelEvent { activity: "work".to_string(), timestamp: start + Duration::hours(eid as i64), vmap: AHashMap::new(), omap: IntSet::from_iter(omap) });
+        log.event_map.insert(format!("e{}", eid), eid);
+    }
+    log
+}
+
+fn config() -> GenerationConfig {
+    GenerationConfig { relations: vec![Relations::INTERACTS], ..Default::default() }
+}
+
+#[test]
+fn test_three_windows() {
+    let log = hourly_log();
+    let start = Utc.ymd(2022, 3, 1).and_hms(0, 0, 0);
+    let series = OcdgSeries::by_window(&log, Duration::hours(2), Duration::hours(2), &config()).unwrap();
+
+    assert_eq!(series.len(), 3);
+    assert_eq!(series.intervals(), (0..3).map(|i| Interval { start: start + Duration::hours(2 * i), end: start + Duration::hours(2 * i + 2) }).collect::<Vec<Interval>>());
+    let counts: Vec<usize> = series.statistics().iter().map(|stats| stats.edge_counts.get("INTERACTS").copied().unwrap_or(0)).collect();
+    assert_eq!(counts, vec![2, 4, 6]);
+    let stats = &series.statistics()[0];
+    assert_eq!((stats.nodes, stats.edges, stats.events), (2, 2, 2));
+
+    let third = series.at(&(start + Duration::minutes(299))).unwrap();
+    assert_eq!(third.net.node_count(), 3);
+    assert!(series.at(&(start + Duration::hours(6))).is_none());
+    assert!(series.at(&(start - Duration::hours(1))).is_none());
+}
+
+#[test]
+fn test_overlapping_windows() {
+    let log = hourly_log();
+    let start = Utc.ymd(2022, 3, 1).and_hms(0, 0, 0);
+    let series = OcdgSeries::by_window(&log, Duration::hours(3), Duration::hours(1), &config()).unwrap();
+
+    assert_eq!(series.len(), 6);
+    assert_eq!(series.all_at(&(start + Duration::hours(2))).len(), 3);
+    assert_eq!(series.entries()[5].0.start, start + Duration::hours(5));
+    assert!(OcdgSeries::by_window(&log, Duration::hours(3), Duration::zero(), &config()).unwrap().is_empty());
+}
+
+#[test]
+fn test_export_edge_counts() {
+    let log = hourly_log();
+    let series = OcdgSeries::by_window(&log, Duration::hours(2), Duration::hours(2), &config()).unwrap();
+    let path = std::env::temp_dir().join("pmrs-series.csv");
+    let path = path.to_str().unwrap();
+    series.export_edge_counts_csv(path).unwrap();
+    let content = fs::read_to_string(path).unwrap();
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(content, "start,end,relation,count\n\
+                         2022-03-01T00:00:00+00:00,2022-03-01T02:00:00+00:00,INTERACTS,2\n\
+                         2022-03-01T02:00:00+00:00,2022-03-01T04:00:00+00:00,INTERACTS,4\n\
+                         2022-03-01T04:00:00+00:00,2022-03-01T06:00:00+00:00,INTERACTS,6\n");
+}
@@ -0,0 +1,75 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use pmrs::objects::ocdg::similarity::{top_k, SimilarityIndex, SimilarityMetric};
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+
+// order o works on items o and o + 1 in its own event, "copy" works on the items of o3
+fn ring_log(orders: usize) -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec!["work".to_string()], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for i in 0..orders {
+        log.object_map.insert(format!("i{}", i), orders + i);
+        log.objects.insert(orders + i, OcelObject { obj_type: "item".to_string(), ovmap: AHashMap::new(), events: vec![] });
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    for o in 0..=orders {
+        let (oid, label, items) = match o == orders {
+            true => (2 * orders, "copy".to_string(), [orders + 3, orders + 4]),
+            false => (o, format!("o{}", o), [orders + o, orders + (o + 1) % orders])
+        };
+        log.object_map.insert(label, oid);
+        log.objects.insert(oid, OcelObject { obj_type: "order".to_string(), ovmap: AHashMap::new(), events: vec![o] });
+        for item in items {
+            log.objects.get_mut(&item).unwrap().events.push(o);
+        }
+        let omap: IntSet<usize> = IntSet::from_iter([oid, items[0], items[1]]);
+        log.events.insert(o, OcelEvent { activity: "work".to_string(), timestamp: start + Duration::minutes(o as i64), vmap: AHashMap::new(), omap });
+        log.event_map.insert(format!("e{}", o), o);
+    }
+    log
+}
+
+#[test]
+fn test_top_k_finds_planted_duplicate() {
+    let log = ring_log(10);
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+    let o3 = *log.object_map.get_by_left("o3").unwrap();
+    let copy = *log.object_map.get_by_left("copy").unwrap();
+
+    for metric in [SimilarityMetric::Jaccard, SimilarityMetric::Cosine] {
+        let top = top_k(&ocdg, o3, 3, metric);
+        assert_eq!(top[0].0, copy);
+        assert!((top[0].1 - 1.0).abs() < 1e-9);
+        assert_eq!(top.len(), 3);
+        assert!(top[1].1 < 0.9);
+    }
+    let top = top_k(&ocdg, o3, 3, SimilarityMetric::Jaccard);
+    assert!((top[1].1 - 1.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_top_k_restricts_to_same_type() {
+    let log = ring_log(10);
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+    let i4 = *log.object_map.get_by_left("i4").unwrap();
+    let mut index = SimilarityIndex::new(&ocdg);
+
+    assert!(index.top_k(i4, 5, SimilarityMetric::Jaccard).iter().all(|(oid, _)| ocdg.node_attributes[oid].node_type == "item"));
+    index.same_type = false;
+    assert!(index.top_k(i4, 20, SimilarityMetric::Jaccard).iter().any(|(oid, _)| ocdg.node_attributes[oid].node_type == "order"));
+}
+
+#[test]
+fn test_candidates_do_not_scan_all_nodes() {
+    let log = ring_log(200);
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+    let index = SimilarityIndex::new(&ocdg);
+    let o3 = *log.object_map.get_by_left("o3").unwrap();
+
+    // only the orders sharing i3 or i4 are scored
+    assert_eq!(index.candidates(o3).len(), 3);
+}
@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+use pmrs::objects::ocdg::skeleton::to_skeleton_log;
+use pmrs::objects::ocdg::{generate_ocdg_config, Evidence, GenerationConfig, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+const EVIDENCE_BASED: [Relations; 4] = [Relations::INTERACTS, Relations::COLIFE, Relations::COBIRTH, Relations::CODEATH];
+
+// (source, target, relation) -> sorted evidence events, restricted to the relations
+fn layers(ocdg: &Ocdg, relations: &[Relations]) -> BTreeMap<(String, String, String), Vec<usize>> {
+    let mut layers = BTreeMap::new();
+    for (src, targets) in &ocdg.irels {
+        for (tar, rels) in targets {
+            for (rel, eids) in rels {
+                let rel = Relations::from_index(*rel).unwrap();
+                if relations.contains(&rel) {
+                    let mut eids: Vec<usize> = eids.iter().copied().collect();
+                    eids.sort_unstable();
+                    layers.insert((ocdg.object_map.get_by_right(src).unwrap().to_owned(), ocdg.object_map.get_by_right(tar).unwrap().to_owned(), rel.to_string()), eids);
+                }
+            }
+        }
+    }
+    layers
+}
+
+fn assert_reproduces(path: &str, relations: Vec<Relations>) {
+    let log = import_ocel(path).unwrap();
+    let config = GenerationConfig { relations, ..Default::default() };
+    let original = generate_ocdg_config(&log, &config).unwrap();
+    let skeleton = to_skeleton_log(&original).unwrap();
+    let regenerated = generate_ocdg_config(&skeleton.log, &config).unwrap();
+
+    let guaranteed: Vec<Relations> = skeleton.guarantees.iter().filter(|(_, guaranteed)| *guaranteed).map(|(rel, _)| *rel).collect();
+    assert_eq!(layers(&regenerated, &guaranteed), layers(&original, &guaranteed), "{}", path);
+    assert_eq!(regenerated.net.node_count(), original.net.node_count());
+    // nothing confidential is left in the events
+    assert_eq!(skeleton.log.activities, vec!["event"]);
+    assert!(skeleton.log.events.values().all(|event| event.vmap.is_empty()));
+}
+
+#[test]
+fn test_skeleton_reproduces_evidence_based_relations() {
+    for path in ["logs/order-to-cash.jsonocel", "logs/supply.jsonocel", "logs/three-days.jsonocel", "logs/logistics.jsonocel", "logs/min.jsonocel", "logs/regions.jsonocel"] {
+        assert_reproduces(path, Relations::iter().collect());
+        assert_reproduces(path, EVIDENCE_BASED.to_vec());
+        // without INTERACTS the omaps only come from the lifecycle relations
+        assert_reproduces(path, vec![Relations::COBIRTH, Relations::CODEATH]);
+        assert_reproduces(path, vec![Relations::COLIFE]);
+    }
+}
+
+#[test]
+fn test_guarantees() {
+    let log = import_ocel("logs/order-to-cash.jsonocel").unwrap();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::INTERACTS, Relations::INHERITANCE], ..Default::default() }).unwrap();
+    let skeleton = to_skeleton_log(&ocdg).unwrap();
+    assert_eq!(skeleton.guarantees, vec![(Relations::INTERACTS, true), (Relations::INHERITANCE, false)]);
+
+    let presence = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::INTERACTS], evidence: Evidence::None, ..Default::default() }).unwrap();
+    assert!(to_skeleton_log(&presence).is_none());
+}
@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use pmrs::objects::ocdg::stream::{generate_ocdg_channel, generate_ocdg_channel_bounded};
+use pmrs::objects::ocdg::{generate_ocdg_config, GenerationConfig, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::Ocel;
+use strum::IntoEnumIterator;
+
+const LOG: &str = "logs/order-to-cash.jsonocel";
+
+fn all_relations() -> GenerationConfig {
+    GenerationConfig { relations: Relations::iter().collect(), ..Default::default() }
+}
+
+// (source, target, relation) -> supporting events
+fn final_edges(log: &Ocel, ocdg: &Ocdg) -> BTreeMap<(String, String, String), usize> {
+    let mut edges = BTreeMap::new();
+    for (src, targets) in &ocdg.irels {
+        for (tar, rels) in targets {
+            for (rel, events) in rels {
+                let rel = Relations::try_from(*rel as u8).unwrap();
+                edges.insert((log.object_map.get_by_right(src).unwrap().clone(), log.object_map.get_by_right(tar).unwrap().clone(), rel.to_string()), events.len());
+            }
+        }
+    }
+    edges
+}
+
+#[test]
+fn test_slow_consumer_bounds_in_flight_batches() {
+    let log = Arc::new(import_ocel(LOG).unwrap());
+    let capacity = 1;
+    let (handle, receiver) = generate_ocdg_channel_bounded(log.clone(), all_relations(), capacity, 1);
+
+    let mut streamed = BTreeMap::new();
+    let mut batches = 0;
+    for batch in receiver.iter() {
+        assert!(receiver.len() <= capacity);
+        assert!(!batch.is_empty());
+        if batches == 0 {
+            // the producer is blocked on the full channel while the first batch is processed
+            thread::sleep(Duration::from_millis(50));
+            assert!(!handle.is_finished());
+            assert_eq!(receiver.len(), capacity);
+        }
+        for edge in batch {
+            assert!(streamed.insert((edge.source, edge.target, edge.relation.to_string()), edge.events).is_none());
+        }
+        batches += 1;
+    }
+    assert!(batches > capacity + 1);
+
+    let ocdg = handle.join().unwrap().unwrap();
+    assert_eq!(streamed, final_edges(&log, &ocdg));
+    assert_eq!(final_edges(&log, &ocdg), final_edges(&log, &generate_ocdg_config(&log, &all_relations()).unwrap()));
+}
+
+#[test]
+fn test_default_channel_is_complete() {
+    let log = Arc::new(import_ocel(LOG).unwrap());
+    let (handle, receiver) = generate_ocdg_channel(log.clone(), all_relations());
+    let streamed: usize = receiver.iter().map(|batch| batch.len()).sum();
+    let ocdg = handle.join().unwrap().unwrap();
+    assert_eq!(streamed, final_edges(&log, &ocdg).len());
+}
+
+#[test]
+fn test_dropped_receiver_stops_generation() {
+    let log = Arc::new(import_ocel(LOG).unwrap());
+    let (handle, receiver) = generate_ocdg_channel_bounded(log, all_relations(), 1, 1);
+    drop(receiver);
+    assert!(handle.join().unwrap().is_err());
+}
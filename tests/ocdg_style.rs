@@ -0,0 +1,68 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::objects::ocdg::exporter::{export_ocdg_graphml, export_ocdg_graphml_styled, ocdg_to_dot, DotOptions, ExportOptions, VisualStyle};
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+use serde_json::json;
+
+// three tickets of different priority and an agent without one, the agent works on t1 three times
+fn ticket_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    let objects = [("t1", "ticket", Some("high")), ("t2", "ticket", Some("low")), ("t3", "ticket", Some("medium")), ("a1", "agent", None)];
+    for (oid, (label, obj_type, priority)) in objects.iter().enumerate() {
+        let ovmap = priority.map(|priority| AHashMap::from_iter([("priority".to_string(), json!(priority))])).unwrap_or_default();
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap, events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, &[&str]); 6] = [("e1", &["t1", "a1"]), ("e2", &["t1", "a1"]), ("e3", &["t2", "a1"]), ("e4", &["t1", "a1"]),
+                                        ("e5", &["t3", "t2"]), ("e6", &["t3"])];
+    for (minute, (id, objects)) in events.iter().enumerate() {
+        log.insert_event(id, "work", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+fn style() -> VisualStyle {
+    VisualStyle { node_color_by: Some("priority".to_string()), palette: vec!["red".to_string(), "green".to_string()],
+                  edge_width_by: Some(Relations::INTERACTS), buckets: vec![2, 3] }
+}
+
+#[test]
+fn test_styled_dot() {
+    let log = ticket_log();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS, Relations::COBIRTH]).unwrap();
+    let dot = ocdg_to_dot(&ocdg, &log, &DotOptions { style: Some(style()), ..Default::default() });
+    assert_eq!(dot, include_str!("snapshots/ocdg-style.dot"));
+
+    // without a style nothing changes
+    let plain = ocdg_to_dot(&ocdg, &log, &DotOptions::default());
+    assert!(!plain.contains("//") && !plain.contains("fillcolor") && !plain.contains("penwidth"));
+}
+
+#[test]
+fn test_styled_graphml() {
+    let log = ticket_log();
+    let ocdg = generate_ocdg(&log, &[Relations::INTERACTS, Relations::COBIRTH]).unwrap();
+    let options = ExportOptions { style: Some(style()), ..Default::default() };
+    let (t1, a1) = (log.object_map.get_by_left("t1").unwrap(), log.object_map.get_by_left("a1").unwrap());
+
+    let path = std::env::temp_dir().join("pmrs-styled.graphml");
+    export_ocdg_graphml_styled(&ocdg, &log, path.to_str().unwrap(), &options).unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains(r#"<key id="legend" for="graph" attr.name="legend" attr.type="string"/>"#));
+    assert!(content.contains("node color priority=high: red\nnode color priority=low: green\nnode color priority=medium: red\nedge width 1: 1 INTERACTS events"));
+    assert!(content.contains(&format!(r#"<node id="n{}"><data key="label">t1</data><data key="type">ticket</data><data key="color">red</data></node>"#, t1)));
+    assert!(content.contains(&format!(r#"<node id="n{}"><data key="label">a1</data><data key="type">agent</data></node>"#, a1)));
+    assert!(content.contains(&format!(r#"<edge source="n{}" target="n{}"><data key="relations">INTERACTS,COBIRTH</data><data key="support">INTERACTS:3,COBIRTH:1</data><data key="width">3</data></edge>"#, t1, a1)));
+
+    // without the log the nodes keep their colors to themselves
+    export_ocdg_graphml(&ocdg, path.to_str().unwrap(), &options).unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(!content.contains(r#"<data key="color">"#));
+    assert!(content.contains(r#"<data key="width">3</data>"#));
+}
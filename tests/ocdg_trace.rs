@@ -0,0 +1,118 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::objects::ocdg::{generate_ocdg, trace_pair, Ocdg, Relations, TraceResult};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+fn trace(log: &Ocel, src: &str, tar: &str, rel: Relations) -> TraceResult {
+    trace_pair(log, *log.object_map.get_by_left(src).unwrap(), *log.object_map.get_by_left(tar).unwrap(), rel)
+}
+
+#[test]
+fn test_trace_lineage_relations() {
+    let log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    assert!(trace(&log, "o1", "i1", Relations::CONSUMES).holds());
+    assert!(trace(&log, "i2", "i1", Relations::INHERITANCE).holds());
+    assert!(trace(&log, "i2", "i1", Relations::SPLIT).holds());
+
+    let consumes = trace(&log, "i1", "o1", Relations::CONSUMES);
+    assert!(!consumes.holds());
+    assert_eq!(consumes.failed_step().unwrap().condition, "source last event e2 is not the target's first event (e1)");
+    assert_eq!(trace(&log, "o1", "i1", Relations::INHERITANCE).failed_step().unwrap().condition, "source type order is not the target type (item)");
+    assert!(!trace(&log, "o1", "i1", Relations::SPLIT).holds());
+}
+
+#[test]
+fn test_trace_lifecycle_relations() {
+    let log: Ocel = import_ocel("logs/three-days.jsonocel").unwrap();
+    assert!(trace(&log, "o1", "i1", Relations::COBIRTH).holds());
+    assert!(trace(&log, "o1", "i2", Relations::CODEATH).holds());
+    assert!(trace(&log, "o2", "i3", Relations::COLIFE).holds());
+    assert!(trace(&log, "o1", "i2", Relations::MINION).holds());
+
+    assert_eq!(trace(&log, "o1", "i1", Relations::CODEATH).failed_step().unwrap().condition, "source last event e7 is not the target's last event (e3)");
+    assert!(!trace(&log, "o1", "i1", Relations::COLIFE).holds());
+    assert!(!trace(&log, "i2", "o1", Relations::MINION).holds());
+}
+
+#[test]
+fn test_trace_interaction_relations() {
+    let log: Ocel = import_ocel("logs/three-days.jsonocel").unwrap();
+    assert!(trace(&log, "o1", "i1", Relations::INTERACTS).holds());
+
+    let unrelated = trace(&log, "o1", "o2", Relations::INTERACTS);
+    assert!(!unrelated.holds());
+    assert_eq!(unrelated.to_string(), "INTERACTS o1 -> o2: no edge\n  \
+                                       [ok] source o1 is an object of the log\n  \
+                                       [ok] target o2 is an object of the log\n  \
+                                       [ok] source and target are different objects\n  \
+                                       [ok] both objects have events\n  \
+                                       [fail] source and target share 0 events\n");
+}
+
+#[test]
+fn test_trace_agrees_with_generation() {
+    for file in ["logs/min.jsonocel", "logs/three-days.jsonocel"] {
+        let log: Ocel = import_ocel(file).unwrap();
+        let relations: Vec<Relations> = Relations::iter().collect();
+        let ocdg = generate_ocdg(&log, &relations).unwrap();
+        for oid1 in log.objects.keys() {
+            for oid2 in log.objects.keys().filter(|oid2| *oid2 != oid1) {
+                for rel in &relations {
+                    let generated = ocdg.irels.get(oid1)
+                                              .and_then(|targets| targets.get(oid2))
+                                              .is_some_and(|rels| rels.contains_key(&(rel.relation_index() as usize)));
+                    assert_eq!(trace_pair(&log, *oid1, *oid2, *rel).holds(), generated, "{} {} {}", rel, oid1, oid2);
+                }
+            }
+        }
+    }
+}
+
+// s is born and ends in e0, every other object is an item that starts there too and lives on
+fn single_event_split_log(items: &[&str]) -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, label) in ["s"].iter().chain(items).enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: "item".to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let split: Vec<&str> = ["s"].iter().chain(items).copied().collect();
+    log.insert_event("e0", "split", start, &split, AHashMap::new()).unwrap();
+    log.insert_event("e1", "move", start + Duration::minutes(1), items, AHashMap::new()).unwrap();
+    log
+}
+
+fn split_edge(ocdg: &Ocdg, oid1: usize, oid2: usize) -> bool {
+    ocdg.irels.get(&oid1).and_then(|targets| targets.get(&oid2)).is_some_and(|rels| rels.contains_key(&(Relations::SPLIT.relation_index() as usize)))
+}
+
+#[test]
+fn test_trace_agrees_on_split_of_a_single_event_source() {
+    // the source is born in its last event and counts as one of the objects starting there, it never splits into itself
+    for items in [vec!["t1"], vec!["t1", "t2"]] {
+        let log = single_event_split_log(&items);
+        let ocdg = generate_ocdg(&log, &[Relations::SPLIT]).unwrap();
+        let s = *log.object_map.get_by_left("s").unwrap();
+        assert!(!split_edge(&ocdg, s, s));
+        assert!(!trace_pair(&log, s, s, Relations::SPLIT).holds());
+        for item in &items {
+            let tar = *log.object_map.get_by_left(*item).unwrap();
+            assert!(split_edge(&ocdg, s, tar), "{}", item);
+            assert!(trace_pair(&log, s, tar, Relations::SPLIT).holds(), "{}", item);
+        }
+    }
+
+    // two events of the source, a single item starting at its last one is no split
+    let mut log = single_event_split_log(&["t1"]);
+    log.insert_event("e-1", "create", Utc.ymd(2021, 12, 31).and_hms(0, 0, 0), &["s"], AHashMap::new()).unwrap();
+    let ocdg = generate_ocdg(&log, &[Relations::SPLIT]).unwrap();
+    let (s, t1) = (*log.object_map.get_by_left("s").unwrap(), *log.object_map.get_by_left("t1").unwrap());
+    assert!(!split_edge(&ocdg, s, t1));
+    assert!(!trace_pair(&log, s, t1, Relations::SPLIT).holds());
+}
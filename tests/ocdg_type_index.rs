@@ -0,0 +1,42 @@
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::hierarchy::TypeHierarchy;
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::Ocel;
+use strum::IntoEnumIterator;
+
+fn oids(log: &Ocel, labels: &[&str]) -> Vec<usize> {
+    let mut oids: Vec<usize> = labels.iter().map(|label| *log.object_map.get_by_left(*label).unwrap()).collect();
+    oids.sort_unstable();
+    oids
+}
+
+#[test]
+fn test_objects_by_type() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    assert!(ocdg.itypes.get().is_none());
+    let types = ["item", "order", "package", "product", "truck"];
+    let found: Vec<Vec<usize>> = types.iter().map(|obj_type| ocdg.get_objects_by_type(obj_type).to_vec()).collect();
+    assert_eq!(found, vec![oids(&log, &["i1", "i2"]), oids(&log, &["o1"]), oids(&log, &["p1"]), oids(&log, &["r1"]), vec![]]);
+
+    // the first lookup builds the index, later ones borrow from it
+    assert_eq!(ocdg.itypes.get().unwrap().len(), 4);
+    assert!(std::ptr::eq(ocdg.get_objects_by_type("item"), ocdg.get_objects_by_type("item")));
+    assert_eq!(ocdg.iter_objects_by_type("item").collect::<Vec<usize>>(), oids(&log, &["i1", "i2"]));
+}
+
+#[test]
+fn test_type_index_rebuilt() {
+    let log = import_ocel("logs/min.jsonocel").unwrap();
+    let mut ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    assert_eq!(ocdg.get_objects_by_type("package"), oids(&log, &["p1"]));
+    ocdg.retain_types(&["item", "order"], &TypeHierarchy::new());
+    assert!(ocdg.itypes.get().is_none());
+    assert_eq!(ocdg.get_objects_by_type("item"), oids(&log, &["i1", "i2"]));
+    assert!(ocdg.get_objects_by_type("package").is_empty());
+    assert_eq!(ocdg.type_index().len(), 2);
+
+    let filtered = ocdg.filter_relations(&[Relations::INTERACTS]);
+    assert!(filtered.itypes.get().is_none());
+    assert_eq!(filtered.get_objects_by_type("order"), oids(&log, &["o1"]));
+}
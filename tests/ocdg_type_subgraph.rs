@@ -0,0 +1,58 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use pmrs::objects::ocdg::{generate_ocdg, Ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+use strum::IntoEnumIterator;
+
+// the package p1 ends in e2 where p2 starts, the truck t1 carries both
+fn repack_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (label, obj_type)) in [("o1", "order"), ("p1", "package"), ("p2", "package"), ("t1", "truck")].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, &[&str]); 3] = [("e1", &["o1", "p1"]), ("e2", &["p1", "p2", "t1"]), ("e3", &["p2", "t1"])];
+    for (minute, (id, objects)) in events.iter().enumerate() {
+        log.insert_event(id, "step", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+fn oids(log: &Ocel, labels: &[&str]) -> IntSet<usize> {
+    labels.iter().map(|label| *log.object_map.get_by_left(*label).unwrap()).collect()
+}
+
+fn nodes(ocdg: &Ocdg) -> IntSet<usize> {
+    ocdg.node_attributes.keys().copied().collect()
+}
+
+#[test]
+fn test_type_induced_subgraph() {
+    let log = repack_log();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let (p1, p2, t1) = (*log.object_map.get_by_left("p1").unwrap(), *log.object_map.get_by_left("p2").unwrap(), *log.object_map.get_by_left("t1").unwrap());
+    assert!(ocdg.has_relation(p1, p2, Relations::INHERITANCE));
+
+    let packages = ocdg.type_induced_subgraph("package", "package");
+    assert_eq!(nodes(&packages), oids(&log, &["p1", "p2"]));
+    assert!(packages.has_relation(p1, p2, Relations::INHERITANCE));
+    assert_eq!(packages.relations_between(p1, p2), ocdg.relations_between(p1, p2));
+
+    let carried = ocdg.type_induced_subgraph("package", "truck");
+    assert_eq!(nodes(&carried), oids(&log, &["p1", "p2", "t1"]));
+    assert!(!carried.has_any_relation(p1, p2) && !carried.has_any_relation(p2, p1));
+    assert!(carried.has_relation(p2, t1, Relations::INTERACTS));
+    let pairs: usize = carried.irels.values().map(|targets| targets.len()).sum();
+    assert_eq!(carried.net.edge_count(), pairs);
+    assert!(carried.irels.iter().all(|(src, targets)| targets.keys().all(|tar| (*src == t1) != (*tar == t1))));
+
+    let ordered = ocdg.type_induced_subgraph("order", "package");
+    assert_eq!(nodes(&ordered), oids(&log, &["o1", "p1", "p2"]));
+    assert!(!ordered.has_any_relation(p1, p2));
+    assert_eq!(ordered.type_induced_subgraph("truck", "truck").object_count(), 0);
+}
@@ -0,0 +1,142 @@
+use chrono::{TimeZone, Utc};
+use nohash_hasher::IntSet;
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::importer::timestamp::TimestampParser;
+use pmrs::objects::ocel::importer::{import_ocel2_json, import_ocel2_json_str, ImportConfig, OcelError};
+use pmrs::objects::ocel::view::OcelView;
+use pmrs::objects::ocel::{Ocel, OcelAttributeValue};
+use serde_json::json;
+use strum::IntoEnumIterator;
+
+const EXAMPLE: &str = include_str!("../logs/ocel2-example.json");
+
+fn example() -> Ocel {
+    import_ocel2_json_str(EXAMPLE, &ImportConfig::default()).unwrap()
+}
+
+fn oid(log: &Ocel, label: &str) -> usize {
+    *log.object_map.get_by_left(label).unwrap()
+}
+
+fn activities(log: &Ocel, object: &str) -> Vec<String> {
+    log.objects[&oid(log, object)].events.iter().map(|eid| log.events.event(eid).activity.to_owned()).collect()
+}
+
+fn related(log: &Ocel, object: &str) -> Vec<String> {
+    let mut targets: Vec<String> = log.object_relations.get(&oid(log, object)).into_iter().flatten().map(|tar| log.object_map.get_by_right(tar).unwrap().to_owned()).collect();
+    targets.sort();
+    targets
+}
+
+#[test]
+fn test_import_example() {
+    let log = import_ocel2_json("logs/ocel2-example.json", &ImportConfig::default()).unwrap();
+    assert_eq!(log.events.len(), 6);
+    assert_eq!(log.objects.len(), 5);
+    assert_eq!(log.global_log.get("ocel:version"), Some(&json!("2.0")));
+    let mut activities_seen = log.activities.clone();
+    activities_seen.sort();
+    assert_eq!(activities_seen, vec!["create package", "pay order", "pick item", "place order", "send package"]);
+    assert!(log.is_time_ordered());
+    assert_eq!(activities(&log, "o1"), vec!["place order", "pay order"]);
+    assert_eq!(activities(&log, "i1"), vec!["place order", "pick item", "create package"]);
+    // an object related twice by one event is in its lifecycle once
+    assert_eq!(activities(&log, "p1"), vec!["create package", "send package"]);
+}
+
+#[test]
+fn test_object_relations() {
+    let log = example();
+    assert_eq!(related(&log, "o1"), vec!["c1", "i1", "i2"]);
+    assert_eq!(related(&log, "p1"), vec!["i1", "i2"]);
+    assert!(related(&log, "c1").is_empty());
+    // a view keeps the relations among its objects only
+    let seeds: IntSet<usize> = [oid(&log, "p1")].into_iter().collect();
+    let view = OcelView::around(&log, &seeds).to_ocel();
+    assert_eq!(related(&view, "p1"), vec!["i1", "i2"]);
+    assert!(!view.object_relations.contains_key(&oid(&log, "o1")));
+}
+
+#[test]
+fn test_typed_attributes() {
+    let log = example();
+    let o1 = &log.objects[&oid(&log, "o1")];
+    // the latest value of a changing attribute wins
+    assert_eq!(o1.ovmap.get("status"), Some(&json!("paid")));
+    assert_eq!(o1.ovmap.get("price"), Some(&json!(1399.5)));
+    assert_eq!(log.objects[&oid(&log, "c1")].ovmap.get("premium"), Some(&json!(true)));
+    assert_eq!(log.objects[&oid(&log, "i1")].ovmap.get("quantity"), Some(&json!(2)));
+    assert_eq!(log.objects[&oid(&log, "i2")].ovmap.get("quantity"), Some(&json!(1)));
+    assert_eq!(log.objects[&oid(&log, "p1")].ovmap.get("due"), Some(&json!("2023-04-05T10:00:00+00:00")));
+    let e5 = &log.events.event(log.event_map.get_by_left("e5").unwrap());
+    assert_eq!(e5.vmap.get("boxes"), Some(&json!(1)));
+    assert_eq!(e5.timestamp, Utc.ymd(2023, 4, 2).and_hms(8, 0, 0));
+}
+
+#[test]
+fn test_typed_attribute_table() {
+    let log = example();
+    let p1 = oid(&log, "p1");
+    // a declared time attribute is kept as a timestamp, only its json form is text
+    assert_eq!(log.typed_attributes.object(&p1, "due"), Some(&OcelAttributeValue::Timestamp(Utc.ymd(2023, 4, 5).and_hms(10, 0, 0))));
+    assert_eq!(log.typed_attributes.object(&oid(&log, "c1"), "premium"), Some(&OcelAttributeValue::Boolean(true)));
+    assert_eq!(log.typed_attributes.object(&oid(&log, "o1"), "status"), Some(&OcelAttributeValue::String("paid".to_owned())));
+    let e5 = log.event_map.get_by_left("e5").unwrap();
+    assert_eq!(log.typed_attributes.event(e5, "boxes"), Some(&OcelAttributeValue::Number(1.into())));
+    assert_eq!(log.typed_attributes.event(e5, "missing"), None);
+
+    // views keep the values of their events and objects only
+    let seeds: IntSet<usize> = [p1].into_iter().collect();
+    let view = OcelView::around(&log, &seeds).to_ocel();
+    assert!(view.typed_attributes.object(&p1, "due").is_some());
+    assert!(view.typed_attributes.object(&oid(&log, "c1"), "premium").is_none());
+}
+
+#[test]
+fn test_attribute_value_parse() {
+    let timestamps = TimestampParser::default();
+    assert_eq!(OcelAttributeValue::parse(&json!("12"), Some("integer"), &timestamps), Ok(OcelAttributeValue::Number(12.into())));
+    assert_eq!(OcelAttributeValue::parse(&json!("false"), Some("boolean"), &timestamps), Ok(OcelAttributeValue::Boolean(false)));
+    assert_eq!(OcelAttributeValue::parse(&json!(3), Some("string"), &timestamps), Ok(OcelAttributeValue::String("3".to_owned())));
+    assert_eq!(OcelAttributeValue::parse(&json!("2023-01-01T00:00:00Z"), Some("time"), &timestamps), Ok(OcelAttributeValue::Timestamp(Utc.ymd(2023, 1, 1).and_hms(0, 0, 0))));
+    assert_eq!(OcelAttributeValue::parse(&json!(true), None, &timestamps), Ok(OcelAttributeValue::Boolean(true)));
+    assert!(OcelAttributeValue::parse(&json!("1.5"), Some("integer"), &timestamps).is_err());
+    assert!(OcelAttributeValue::parse(&json!("x"), Some("colour"), &timestamps).is_err());
+}
+
+#[test]
+fn test_generation() {
+    let log = example();
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    assert_eq!(ocdg.net.node_count(), 5);
+    assert!(ocdg.net.edge_count() > 0);
+}
+
+#[test]
+fn test_inconsistent_logs() {
+    let config = ImportConfig::default();
+    let duplicate = EXAMPLE.replacen("\"id\": \"e2\"", "\"id\": \"e1\"", 1);
+    let error = import_ocel2_json_str(&duplicate, &config).err().unwrap();
+    assert!(matches!(&error, OcelError::DuplicateId(id) if id == "e1"));
+    assert!(error.to_string().contains("the id e1 is used more than once"), "{}", error);
+
+    let unknown = EXAMPLE.replacen("{ \"objectId\": \"o1\", \"qualifier\": \"order\" } ]", "{ \"objectId\": \"o9\", \"qualifier\": \"order\" } ]", 1);
+    let error = import_ocel2_json_str(&unknown, &config).err().unwrap();
+    assert!(matches!(&error, OcelError::UnknownObject { from, object } if from == "Event e2" && object == "o9"));
+    assert!(error.to_string().contains("Event e2 references the unknown object o9"), "{}", error);
+
+    let mismatch = EXAMPLE.replacen("\"value\": \"2\"", "\"value\": \"two\"", 1);
+    let error = import_ocel2_json_str(&mismatch, &config).err().unwrap();
+    assert!(matches!(&error, OcelError::InvalidValue { at, .. } if at == "Object i1 attribute quantity"));
+    assert!(error.to_string().contains("Object i1 attribute quantity"), "{}", error);
+    assert!(matches!(import_ocel2_json_str("{", &config), Err(OcelError::Parse(_))));
+}
+
+#[test]
+fn test_file_import_uses_the_config() {
+    // without any format no timestamp of the file can be read
+    let config = ImportConfig { timestamps: TimestampParser::new(vec![]), ..Default::default() };
+    let error = import_ocel2_json("logs/ocel2-example.json", &config).err().unwrap();
+    assert!(matches!(&error, OcelError::Timestamp { .. }), "{}", error);
+    assert!(matches!(import_ocel2_json("logs/missing.json", &ImportConfig::default()), Err(OcelError::Io(_))));
+}
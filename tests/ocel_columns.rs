@@ -0,0 +1,86 @@
+use chrono::{TimeZone, Utc};
+use nohash_hasher::IntSet;
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::{EventColumns, OcelEvent};
+use serde_json::json;
+
+fn event(activity: &str, hour: u32, omap: &[usize]) -> OcelEvent {
+    OcelEvent { activity: activity.to_string(),
+                timestamp: Utc.ymd(2022, 5, 1).and_hms(hour, 0, 0),
+                vmap: Default::default(),
+                omap: omap.iter().copied().collect() }
+}
+
+#[test]
+fn test_columns_round_trip() {
+    let log = import_ocel("logs/order-to-cash.jsonocel").unwrap();
+    let copy: EventColumns = log.events.iter().map(|(eid, event)| (*eid, event.to_event())).collect();
+    assert_eq!(copy, log.events);
+    assert_eq!(copy.iter().len(), log.events.len());
+
+    for (eid, event) in &log.events {
+        let owned = event.to_event();
+        assert_eq!(copy.event(eid).activity, owned.activity);
+        assert_eq!(copy.event(eid).omap.to_set(), owned.omap);
+        assert_eq!(copy.event(eid).vmap.to_map(), owned.vmap);
+        // the omaps are stored sorted
+        assert!(event.omap.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+    assert!(copy.get(&(log.events.len() + 1)).is_none());
+}
+
+#[test]
+fn test_replacing_keeps_the_row() {
+    let mut columns: EventColumns = [(7, event("pack", 8, &[3, 1])), (2, event("ship", 9, &[1]))].into_iter().collect();
+    columns.set_attribute(&7, "weight", json!(12));
+
+    let replaced = columns.insert(7, event("repack", 10, &[4]));
+    assert_eq!(replaced.map(|old| (old.activity, old.omap)), Some(("pack".to_string(), IntSet::from_iter([1, 3]))));
+    assert_eq!(columns.keys().copied().collect::<Vec<usize>>(), vec![7, 2]);
+    assert_eq!(&*columns.event(&7).omap, &[4]);
+    assert!(columns.event(&7).vmap.is_empty());
+
+    assert!(columns.update(&2, |event| event.omap.extend([5, 0])));
+    assert!(!columns.update(&9, |event| event.activity.clear()));
+    assert_eq!(&*columns.event(&2).omap, &[0, 1, 5]);
+}
+
+#[test]
+fn test_object_edits() {
+    let mut columns: EventColumns = [(0, event("pack", 8, &[1, 3])), (1, event("ship", 9, &[3]))].into_iter().collect();
+    assert!(columns.insert_object(&0, 2));
+    assert!(!columns.insert_object(&0, 3));
+    assert!(!columns.insert_object(&5, 3));
+    assert_eq!(&*columns.event(&0).omap, &[1, 2, 3]);
+
+    columns.retain_objects(|oid| *oid != 3);
+    assert_eq!(&*columns.event(&0).omap, &[1, 2]);
+    assert!(columns.event(&1).omap.is_empty());
+
+    columns.set_attribute(&1, "carrier", json!("dhl"));
+    assert_eq!(columns.set_attribute(&1, "carrier", json!("ups")), Some(json!("dhl")));
+    assert_eq!(columns.event(&1).vmap["carrier"], "ups");
+    assert!(columns.set_attribute(&5, "carrier", json!("ups")).is_none());
+}
+
+#[test]
+fn test_removal_and_compaction() {
+    let mut columns: EventColumns = (0..200).map(|eid| (eid, event(if eid % 2 == 0 {"pack"} else {"ship"}, 8, &[eid, eid + 1]))).collect();
+    for eid in 0..200 {
+        columns.set_attribute(&eid, "index", json!(eid));
+    }
+    columns.retain(|eid, _| eid % 3 == 0);
+
+    // removing two thirds of the rows compacted the store, the kept events are unchanged and keep their order
+    assert_eq!(columns.len(), 67);
+    assert_eq!(columns.keys().copied().collect::<Vec<usize>>(), (0..200).filter(|eid| eid % 3 == 0).collect::<Vec<usize>>());
+    for (eid, event) in &columns {
+        assert_eq!(&*event.omap, &[*eid, eid + 1]);
+        assert_eq!(event.vmap["index"], json!(eid));
+        assert_eq!(event.activity, if eid % 2 == 0 {"pack"} else {"ship"});
+    }
+    let removed = columns.remove(&3).unwrap();
+    assert_eq!(removed.vmap["index"], json!(3));
+    assert!(!columns.contains_key(&3));
+    assert!(columns.remove(&3).is_none());
+}
@@ -0,0 +1,78 @@
+use chrono::Duration;
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::dedupe::{DedupKeys, TimestampKey};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::merge::AttributeConflict;
+use serde_json::Value;
+
+// copies an event under a new id, the closure can change the copy before it is added
+fn duplicate<F>(log: &mut Ocel, id: &str, copy_id: &str, change: F) -> usize where F: FnOnce(&mut pmrs::objects::ocel::OcelEvent) {
+    let eid = *log.event_map.get_by_left(id).unwrap();
+    let mut copy = log.events.event(&eid).to_event();
+    change(&mut copy);
+    let copy_eid = log.events.keys().max().unwrap() + 1;
+    for oid in &copy.omap {
+        log.objects.get_mut(oid).unwrap().events.push(copy_eid);
+    }
+    log.events.insert(copy_eid, copy);
+    log.event_map.insert(copy_id.to_string(), copy_eid);
+    copy_eid
+}
+
+#[test]
+fn test_dedupe_exact_duplicate() {
+    let mut log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let copy = duplicate(&mut log, "e1", "e1-reimport", |_| {});
+    let report = log.dedupe_events(&DedupKeys::default());
+
+    assert_eq!(report.merged, vec![("e1".to_string(), vec!["e1-reimport".to_string()])]);
+    assert_eq!(report.removed_count(), 1);
+    assert_eq!(log.events.len(), 3);
+    assert!(!log.event_map.contains_left("e1-reimport"));
+    assert!(log.objects.values().all(|obj| !obj.events.contains(&copy)));
+    let o1 = log.object_map.get_by_left("o1").unwrap();
+    assert_eq!(log.objects[o1].events, vec![*log.event_map.get_by_left("e1").unwrap()]);
+}
+
+#[test]
+fn test_dedupe_near_duplicate_by_attribute() {
+    let mut log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    duplicate(&mut log, "e1", "e1-wms", |event| {
+        event.vmap.insert("resource".to_string(), Value::from("Anna"));
+        event.vmap.insert("channel".to_string(), Value::from("web"));
+    });
+
+    // the differing attribute is part of the identity, nothing is merged
+    let keys = DedupKeys { attributes: vec!["resource".to_string()], ..Default::default() };
+    assert!(log.clone().dedupe_events(&keys).merged.is_empty());
+
+    let mut kept_second = log.clone();
+    kept_second.dedupe_events(&DedupKeys { conflict: AttributeConflict::KeepSecond, ..Default::default() });
+    let e1 = *log.event_map.get_by_left("e1").unwrap();
+    assert_eq!(kept_second.events.event(&e1).vmap["resource"], "Anna");
+
+    let report = log.dedupe_events(&DedupKeys::default());
+    assert_eq!(report.merged, vec![("e1".to_string(), vec!["e1-wms".to_string()])]);
+    assert_eq!(log.events.event(&e1).vmap["resource"], "Alessandro");
+    assert_eq!(log.events.event(&e1).vmap["channel"], "web");
+}
+
+#[test]
+fn test_dedupe_bucketed_timestamp_unions_omap() {
+    let mut log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let r1 = *log.object_map.get_by_left("r1").unwrap();
+    duplicate(&mut log, "e1", "e1-late", |event| {
+        event.timestamp = event.timestamp + Duration::seconds(20);
+        event.omap.insert(r1);
+    });
+
+    assert!(log.clone().dedupe_events(&DedupKeys::default()).merged.is_empty());
+    let keys = DedupKeys { timestamp: TimestampKey::Bucket(Duration::minutes(5)), omap: false, ..Default::default() };
+    let report = log.dedupe_events(&keys);
+
+    let e1 = *log.event_map.get_by_left("e1").unwrap();
+    assert_eq!(report.removed_count(), 1);
+    assert!(log.events.event(&e1).omap.contains(&r1));
+    // r1 now starts with the merged event
+    assert_eq!(log.objects[&r1].events[0], e1);
+}
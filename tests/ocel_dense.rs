@@ -0,0 +1,88 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::{IntMap, IntSet};
+use pmrs::objects::ocdg::{generate_ocdg, trace_pair, Relations};
+use pmrs::objects::ocel::{EventBitSet, EventColumns, Ocel, OcelEvent, OcelObject, TypedAttributes};
+use strum::IntoEnumIterator;
+
+// sparse event ids that are not in timestamp order, the hub object takes part in every event
+fn sparse_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec!["work".to_string()], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (label, oid, obj_type) in [("hub", 0, "station"), ("o1", 10, "order"), ("o2", 20, "order"), ("i1", 30, "item"), ("i2", 40, "item")] {
+        log.object_map.insert(label.to_string(), oid);
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(usize, i64, Vec<usize>); 6] = [(9000, 0, vec![0, 10, 30]),
+                                                  (17, 1, vec![0, 10, 30, 40]),
+                                                  (4242, 2, vec![0, 20, 40]),
+                                                  (3, 3, vec![0, 10, 40]),
+                                                  (777, 4, vec![0, 20]),
+                                                  (88, 5, vec![0, 30])];
+    for (eid, minute, omap) in events {
+        for oid in &omap {
+            log.objects.get_mut(oid).unwrap().events.push(eid);
+        }
+        log.events.insert(eid, OcelEvent { activity: "work".to_string(), timestamp: start + Duration::minutes(minute), vmap: AHashMap::new(), omap: IntSet::from_iter(omap) });
+        log.event_map.insert(format!("e{}", eid), eid);
+    }
+    log
+}
+
+#[test]
+fn test_dense_ids_follow_timestamps() {
+    let log = sparse_log();
+    let dense = log.dense_events();
+    assert_eq!(dense.len(), 6);
+    assert_eq!((0..6).map(|index| dense.to_original(index).unwrap()).collect::<Vec<usize>>(), vec![9000, 17, 4242, 3, 777, 88]);
+    for eid in log.events.keys() {
+        assert_eq!(dense.to_original(dense.to_dense(*eid).unwrap()), Some(*eid));
+    }
+    assert_eq!(dense.to_dense(5), None);
+    assert_eq!(dense.to_original(6), None);
+    assert_eq!(dense.lifecycle(&log, 40), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_event_bitset() {
+    let mut a = EventBitSet::new(100);
+    let b = sparse_log().dense_events().bitset(&[1, 3, 5]);
+    for dense in [1, 5, 70] {
+        a.insert(dense);
+    }
+    assert!(a.contains(70) && !a.contains(71) && !a.contains(500));
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.intersection_len(&b), 2);
+    assert_eq!(a.iter().collect::<Vec<usize>>(), vec![1, 5, 70]);
+    // inserts past the capacity grow the set
+    a.insert(130);
+    assert!(a.contains(130));
+    assert!(EventBitSet::new(10).is_empty());
+}
+
+#[test]
+fn test_generation_keeps_original_ids() {
+    let log = sparse_log();
+    let relations: Vec<Relations> = Relations::iter().collect();
+    let ocdg = generate_ocdg(&log, &relations).unwrap();
+
+    let shared = ocdg.relation_events(10, 40, Relations::INTERACTS).unwrap();
+    assert_eq!(shared, &IntSet::from_iter([17, 3]));
+    let minion = ocdg.relation_events(0, 20, Relations::MINION).unwrap();
+    assert_eq!(minion, &IntSet::from_iter([4242, 777]));
+    assert!(ocdg.event_map.contains_right(&9000));
+
+    for oid1 in log.objects.keys() {
+        for oid2 in log.objects.keys().filter(|oid2| *oid2 != oid1) {
+            for rel in &relations {
+                let generated = ocdg.irels.get(oid1)
+                                          .and_then(|targets| targets.get(oid2))
+                                          .is_some_and(|rels| rels.contains_key(&(rel.relation_index() as usize)));
+                assert_eq!(trace_pair(&log, *oid1, *oid2, *rel).holds(), generated, "{} {} {}", rel, oid1, oid2);
+            }
+        }
+    }
+}
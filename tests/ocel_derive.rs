@@ -0,0 +1,62 @@
+use std::collections::{BTreeMap, HashMap};
+use pmrs::algo::transformation::ocel::features::object_point::{object_point_features, LifecycleView, ObjectPoint, ObjectPointConfig};
+use pmrs::objects::ocdg::{generate_ocdg_config, GenerationConfig, Relations};
+use pmrs::objects::ocel::derive::{add_flags, AttributePredicate, DeriveMode, DerivedActivity};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::Ocel;
+use serde_json::Value;
+
+// e1 (o1, i1, i2) places an order for 120.0, e2 (o2, i3) one for 45.5
+const LOG: &str = "logs/order-to-cash.jsonocel";
+
+fn rules() -> Vec<DerivedActivity> {
+    vec![DerivedActivity { name: "expensive_order".to_owned(),
+                           predicate: AttributePredicate::And(vec![AttributePredicate::Activity("place_order".to_owned()),
+                                                                   AttributePredicate::GreaterThan("price".to_owned(), 100.0)]) },
+         DerivedActivity { name: "unpriced".to_owned(),
+                           predicate: AttributePredicate::Not(Box::new(AttributePredicate::Exists("price".to_owned()))) }]
+}
+
+fn activities(log: &Ocel, object: &str) -> Vec<String> {
+    log.objects[log.object_map.get_by_left(object).unwrap()].events.iter().map(|eid| log.events.event(eid).activity.to_owned()).collect()
+}
+
+#[test]
+fn test_flag_mode() {
+    let original = import_ocel(LOG).unwrap();
+    let mut log = original.clone();
+    let report = add_flags(&mut log, &rules(), DeriveMode::Flag);
+
+    assert_eq!(report.matched, BTreeMap::from_iter([("expensive_order".to_owned(), 1), ("unpriced".to_owned(), original.events.len() - 2)]));
+    assert!(report.added.is_empty());
+    assert_eq!(log.events.len(), original.events.len());
+    assert_eq!(log.activities, original.activities);
+    let e1 = &log.events.event(log.event_map.get_by_left("e1").unwrap());
+    assert_eq!(e1.vmap.get("expensive_order"), Some(&Value::Bool(true)));
+    assert!(!log.events.event(log.event_map.get_by_left("e2").unwrap()).vmap.contains_key("expensive_order"));
+}
+
+#[test]
+fn test_clone_mode() {
+    let original = import_ocel(LOG).unwrap();
+    let mut log = original.clone();
+    let report = add_flags(&mut log, &rules()[..1], DeriveMode::Clone);
+
+    assert_eq!(report.added, vec!["e1:expensive_order"]);
+    assert_eq!(log.events.len(), original.events.len() + 1);
+    assert!(log.activities.contains(&"expensive_order".to_owned()));
+    // the clone follows the original in every lifecycle of its objects
+    for object in ["o1", "i1", "i2"] {
+        let mut expected = activities(&original, object);
+        expected.insert(1, "expensive_order".to_owned());
+        assert_eq!(activities(&log, object), expected, "{}", object);
+    }
+    assert_eq!(activities(&log, "o2"), activities(&original, "o2"));
+
+    // features computed afterwards see the derived activity
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::INTERACTS], ..Default::default() }).unwrap();
+    let params = HashMap::from_iter([(ObjectPoint::ActivityExistenceCount, None)]);
+    let df = object_point_features(ObjectPointConfig { ocel: &log, ocdg: &ocdg, params: &params, lifecycle_view: LifecycleView::Full });
+    let counts: u32 = df.column("ActivityExistenceCount:expensive_order").unwrap().u32().unwrap().into_no_null_iter().sum();
+    assert_eq!(counts, 3);
+}
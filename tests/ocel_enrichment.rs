@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::enrichment::EnrichKey;
+use pmrs::objects::ocel::importer::{import_ocel, OcelError};
+use pmrs::objects::ocel::exporter::export_ocel;
+use serde_json::Value;
+
+fn object<'a>(log: &'a Ocel, id: &str) -> &'a pmrs::objects::ocel::OcelObject {
+    &log.objects[log.object_map.get_by_left(id).unwrap()]
+}
+
+#[test]
+fn test_enrich_objects_from_csv_by_attribute() {
+    let mut log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let unmatched = log.enrich_objects_from_csv("order", &EnrichKey::Attribute("customer".to_string()), "logs/customer-segments.csv", "crm:").unwrap();
+
+    assert_eq!(unmatched, vec!["Banana".to_string()]);
+    assert_eq!(object(&log, "o1").ovmap["crm:segment"], Value::from("enterprise"));
+    assert!(!object(&log, "i1").ovmap.contains_key("crm:segment"));
+
+    // enriched attributes are part of the exported log
+    let export_path = std::env::temp_dir().join("pmrs-enriched.jsonocel");
+    export_ocel(&log, export_path.to_str().unwrap()).unwrap();
+    let reimported: Ocel = import_ocel(export_path.to_str().unwrap()).unwrap();
+    assert_eq!(object(&reimported, "o1").ovmap["crm:segment"], Value::from("enterprise"));
+}
+
+#[test]
+fn test_enrich_objects_by_id() {
+    let mut log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let data: HashMap<String, HashMap<String, Value>> = HashMap::from_iter([
+        ("i1".to_string(), HashMap::from_iter([("weight".to_string(), Value::from(2.5))])),
+        ("i9".to_string(), HashMap::from_iter([("weight".to_string(), Value::from(1.0))])),
+    ]);
+    let unmatched = log.enrich_objects("item", &EnrichKey::ObjectId, &data, "");
+
+    assert_eq!(unmatched, vec!["i9".to_string()]);
+    assert_eq!(object(&log, "i1").ovmap["weight"], Value::from(2.5));
+    assert_eq!(object(&log, "i1").ovmap["color"], Value::from("NaN"));
+    assert!(!object(&log, "i2").ovmap.contains_key("weight"));
+}
+
+#[test]
+fn test_enrich_objects_from_csv_quoted_fields() {
+    let mut log: Ocel = import_ocel("logs/min.jsonocel").unwrap();
+    let path = std::env::temp_dir().join("pmrs-enrichment-quoted.csv");
+    std::fs::write(&path, "id,note,weight\ni1,\"fragile, keep dry\",2.5\n\ni2,\"first line\nsecond line\", 1\n").unwrap();
+    let unmatched = log.enrich_objects_from_csv("item", &EnrichKey::ObjectId, path.to_str().unwrap(), "").unwrap();
+
+    assert!(unmatched.is_empty());
+    assert_eq!(object(&log, "i1").ovmap["note"], Value::from("fragile, keep dry"));
+    assert_eq!(object(&log, "i2").ovmap["note"], Value::from("first line\nsecond line"));
+    assert_eq!(object(&log, "i2").ovmap["weight"], Value::from(1));
+
+    std::fs::write(&path, "id,note\ni1,a,b\n").unwrap();
+    let error = log.enrich_objects_from_csv("item", &EnrichKey::ObjectId, path.to_str().unwrap(), "").err().unwrap();
+    assert!(matches!(error, OcelError::RowLength { row: 2, columns: 3, header: 2 }));
+    assert_eq!(error.to_string(), "Row 2 has 3 columns but the header has 2.");
+}
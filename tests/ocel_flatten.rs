@@ -0,0 +1,42 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+
+// the items i1 and i2 share the create event of o1 and are picked on their own
+fn shared_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (label, obj_type)) in [("o1", "order"), ("i1", "item"), ("i2", "item")].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let events: [(&str, &str, &[&str]); 3] = [("e1", "create", &["o1", "i1", "i2"]), ("e2", "pick", &["i1"]), ("e3", "pick", &["i2"])];
+    for (minute, (id, activity, objects)) in events.iter().enumerate() {
+        log.insert_event(id, activity, start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+#[test]
+fn test_flatten_duplication() {
+    let log = shared_log();
+    let flat = log.flatten_all(&["item", "order"]);
+    assert_eq!(flat.len(), 2);
+
+    let items = &flat["item"];
+    assert_eq!(items.traces.iter().map(|trace| trace.case.as_str()).collect::<Vec<&str>>(), vec!["i1", "i2"]);
+    assert_eq!(items.traces[0].events.iter().map(|event| event.activity.as_str()).collect::<Vec<&str>>(), vec!["create", "pick"]);
+    assert_eq!((items.duplication.events, items.duplication.flat_events, items.duplication.duplicated_events), (3, 4, 1));
+    assert_eq!(items.duplication.activity_factors["create"], 2.0);
+    assert_eq!(items.duplication.activity_factors["pick"], 1.0);
+    assert!((items.duplication.factor() - 4.0 / 3.0).abs() < 1e-9);
+
+    let orders = &flat["order"];
+    assert_eq!(orders.traces.len(), 1);
+    assert_eq!((orders.duplication.duplicated_events, orders.duplication.factor()), (0, 1.0));
+    assert_eq!(flat["item"], log.flatten("item"));
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use pmrs::objects::ocel::importer::{import_ocel_csv, import_ocel_csv_str, CsvImportConfig, CsvObjectType, OcelError};
+use pmrs::objects::ocel::Ocel;
+use serde_json::json;
+
+const ORDERS: &str = "order line;activity;time;object_1;object_2;object_2_type;amount
+x1;place;2022-01-01T10:00:00Z;o1;c1;customer;\"1,5\"
+x2;pay;2022-01-01T12:00:00Z;o1;;;20
+x3;ship;not a time;o1;;;
+x4;pick;2022-01-01T11:00:00Z;o1;i1;item;
+";
+
+fn activities(log: &Ocel, object: &str) -> Vec<String> {
+    log.objects[log.object_map.get_by_left(object).unwrap()].events.iter().map(|eid| log.events.event(eid).activity.to_owned()).collect()
+}
+
+fn obj_type<'a>(log: &'a Ocel, object: &str) -> &'a str {
+    &log.objects[log.object_map.get_by_left(object).unwrap()].obj_type
+}
+
+#[test]
+fn test_single_object_type() {
+    let config = CsvImportConfig { timestamp: None, ..Default::default() }.with_objects("object_id", CsvObjectType::Fixed("parcel".to_string()));
+    let (log, report) = import_ocel_csv("logs/parcels.csv", &config).unwrap();
+    assert_eq!(report.rows, 8);
+    assert_eq!(report.events, 5);
+    assert_eq!(report.objects, 2);
+    assert!(log.objects.values().all(|object| object.obj_type == "parcel"));
+    // rows sharing an event id are one event, quoted fields keep their delimiter
+    assert_eq!(activities(&log, "p1"), vec!["register", "load, truck", "deliver"]);
+    assert_eq!(activities(&log, "p2"), vec!["register", "load, truck"]);
+    assert!(log.is_time_ordered());
+    // a row without objects is an event without objects
+    assert!(log.events.event(log.event_map.get_by_left("e4").unwrap()).omap.is_empty());
+    assert_eq!(report.skipped, vec![(6, "missing event_id".to_string()), (8, "missing activity".to_string())]);
+    assert_eq!(report.warnings.len(), 1);
+}
+
+#[test]
+fn test_column_mapping() {
+    let config = CsvImportConfig { event_id: "order line".to_string(), timestamp: Some("time".to_string()), delimiter: b';', ..Default::default() }
+                     .with_objects("object_1", CsvObjectType::Map(HashMap::from([("o1".to_string(), "order".to_string())])))
+                     .with_objects("object_2*", CsvObjectType::Column("object_2_type".to_string()));
+    let (log, report) = import_ocel_csv_str(ORDERS, &config).unwrap();
+    assert_eq!(obj_type(&log, "o1"), "order");
+    assert_eq!(obj_type(&log, "c1"), "customer");
+    assert_eq!(obj_type(&log, "i1"), "item");
+    // events are ordered by their timestamps
+    assert_eq!(activities(&log, "o1"), vec!["place", "pick", "pay"]);
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].0, 4);
+    assert!(report.skipped[0].1.contains("not a time"));
+    // unmapped columns are event attributes
+    let x2 = &log.events.event(log.event_map.get_by_left("x2").unwrap());
+    assert_eq!(x2.vmap.get("amount"), Some(&json!(20)));
+    assert_eq!(log.events.event(log.event_map.get_by_left("x1").unwrap()).vmap.get("amount"), Some(&json!("1,5")));
+    assert!(log.events.event(log.event_map.get_by_left("x4").unwrap()).vmap.is_empty());
+}
+
+#[test]
+fn test_unknown_columns() {
+    let config = CsvImportConfig::default().with_objects("object_*", CsvObjectType::Fixed("order".to_string()));
+    let error = import_ocel_csv_str("event_id,activity,object_1\ne1,a,o1\n", &config).err().unwrap();
+    assert!(matches!(&error, OcelError::UnknownColumn(column) if column == "timestamp"));
+    assert_eq!(error.to_string(), "The csv has no column timestamp");
+    let error = import_ocel_csv_str("event_id,activity,timestamp\ne1,a,2022-01-01T10:00:00Z\n", &config).err().unwrap();
+    assert_eq!(error.to_string(), "No column of the csv matches object_*");
+}
@@ -0,0 +1,80 @@
+use std::sync::Mutex;
+
+use log::{Level, Log, Metadata, Record};
+use pmrs::objects::ocel::importer::{import_ocel_with_report, ImportConfig};
+
+// collects the warnings of the crate, installed once per test binary
+struct Collector(Mutex<Vec<String>>);
+
+impl Log for Collector {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Warn && metadata.target().starts_with("pmrs")
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static COLLECTOR: Collector = Collector(Mutex::new(vec![]));
+
+#[test]
+fn test_import_report_omap_statistics() {
+    let (log, report) = import_ocel_with_report("logs/wide-omap.jsonocel", &ImportConfig::default()).unwrap();
+
+    assert_eq!(report.events, log.events.len());
+    assert_eq!(report.objects, 500);
+    assert_eq!(report.omap_max, 500);
+    // one wide event among 201 stays above the 99th percentile
+    assert_eq!(report.omap_p99, 2);
+    assert_eq!(report.largest_omaps.len(), 10);
+    assert_eq!(report.largest_omaps[0], ("e1".to_string(), "load_truck".to_string(), 500));
+    assert!(report.largest_omaps[1..].iter().all(|(_, activity, size)| activity == "scan_item" && *size == 2));
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("event e1 (load_truck) references 500 objects"));
+}
+
+#[test]
+fn test_import_report_threshold() {
+    let (_, report) = import_ocel_with_report("logs/wide-omap.jsonocel", &ImportConfig { omap_warning_threshold: 500, ..Default::default() }).unwrap();
+    assert!(report.warnings.is_empty());
+
+    let (_, report) = import_ocel_with_report("logs/min.jsonocel", &ImportConfig { omap_warning_threshold: 2, ..Default::default() }).unwrap();
+    assert_eq!(report.omap_max, 3);
+    assert_eq!(report.largest_omaps.len(), 3);
+    assert_eq!(report.warnings.len(), 1);
+}
+
+#[test]
+fn test_omap_warning_is_logged() {
+    let _ = log::set_logger(&COLLECTOR);
+    log::set_max_level(log::LevelFilter::Warn);
+    let (_, report) = import_ocel_with_report("logs/wide-omap.jsonocel", &ImportConfig::default()).unwrap();
+    assert!(COLLECTOR.0.lock().unwrap().contains(&report.warnings[0]));
+}
+
+#[test]
+fn test_numeric_ids_keep_their_digits() {
+    let (log, report) = import_ocel_with_report("logs/numeric-ids.jsonocel", &ImportConfig::default()).unwrap();
+    let omap = |eid: &str| -> Vec<String> {
+        let mut labels: Vec<String> = log.events.event(log.event_map.get_by_left(eid).unwrap()).omap.iter().map(|oid| log.object_map.get_by_right(oid).unwrap().to_owned()).collect();
+        labels.sort();
+        labels
+    };
+
+    // both pairs collide once read as doubles
+    assert_eq!(omap("e1"), vec!["9007199254740992", "9007199254740993"]);
+    assert_eq!(omap("e2"), vec!["123456789012345678901", "123456789012345678902"]);
+    assert_eq!(omap("e3"), vec!["7", "9007199254740993"]);
+    let oid = log.object_map.get_by_left("9007199254740993").unwrap();
+    assert_eq!(log.objects[oid].events.len(), 2);
+
+    assert_eq!(report.numeric_ids, 5);
+    assert!(report.warnings.iter().any(|warning| warning.starts_with("5 object references are numbers")));
+    let (_, report) = import_ocel_with_report("logs/min.jsonocel", &ImportConfig::default()).unwrap();
+    assert_eq!(report.numeric_ids, 0);
+}
@@ -0,0 +1,47 @@
+use pmrs::objects::ocel::importer::{import_ocel_str, import_ocel_str_with_report, ImportConfig};
+use pmrs::objects::ocel::Ocel;
+use serde_json::json;
+
+const SPARSE: &str = include_str!("../logs/sparse.jsonocel");
+const DUPLICATE_EVENTS: &str = include_str!("../logs/duplicate-events.jsonocel");
+
+fn activities(log: &Ocel, object: &str) -> Vec<String> {
+    log.objects[log.object_map.get_by_left(object).unwrap()].events.iter().map(|eid| log.events.event(eid).activity.to_owned()).collect()
+}
+
+#[test]
+fn test_optional_attributes() {
+    let log = import_ocel_str(SPARSE, &ImportConfig::default()).unwrap();
+    assert_eq!(log.events.len(), 2);
+    assert!(log.global_event.is_empty());
+    let e1 = &log.events.event(log.event_map.get_by_left("e1").unwrap());
+    assert_eq!(e1.vmap.get("price"), Some(&json!(20.5)));
+    assert!(log.events.event(log.event_map.get_by_left("e2").unwrap()).vmap.is_empty());
+    let c1 = &log.objects[log.object_map.get_by_left("c1").unwrap()];
+    assert_eq!(c1.obj_type, "customer");
+    assert!(c1.ovmap.is_empty());
+    assert_eq!(activities(&log, "o1"), vec!["place_order", "pay_order"]);
+}
+
+#[test]
+fn test_unknown_objects_are_inserted() {
+    let (log, report) = import_ocel_str_with_report(SPARSE, &ImportConfig::default()).unwrap();
+    assert_eq!(log.objects.len(), 3);
+    // the type comes from the default of ocel:global-object
+    let c9 = &log.objects[log.object_map.get_by_left("c9").unwrap()];
+    assert_eq!(c9.obj_type, "customer");
+    assert!(c9.ovmap.is_empty());
+    assert_eq!(activities(&log, "c9"), vec!["pay_order"]);
+    assert_eq!(report.objects, 3);
+    assert!(report.warnings.iter().any(|warning| warning.starts_with("1 objects are referenced by events but missing from ocel:objects: c9.")), "{:?}", report.warnings);
+}
+
+#[test]
+fn test_duplicate_ids() {
+    let error = import_ocel_str(DUPLICATE_EVENTS, &ImportConfig::default()).err().unwrap();
+    assert!(error.to_string().contains("the id e1 is used more than once"), "{}", error);
+
+    let duplicate_objects = SPARSE.replacen("\"c1\": {", "\"o1\": {", 1);
+    let error = import_ocel_str(&duplicate_objects, &ImportConfig::default()).err().unwrap();
+    assert!(error.to_string().contains("the id o1 is used more than once"), "{}", error);
+}
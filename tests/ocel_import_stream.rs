@@ -0,0 +1,92 @@
+use std::collections::{BTreeMap, HashSet};
+
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::importer::stream::{import_ocel_streaming, import_ocel_streaming_from, HyperLogLog, StreamConfig, TopK};
+use pmrs::objects::ocel::Ocel;
+
+fn exact_counts<'a, I: Iterator<Item = &'a str>>(items: I) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for item in items {
+        *counts.entry(item.to_owned()).or_default() += 1;
+    }
+    counts
+}
+
+fn top_counts(top_k: &TopK) -> BTreeMap<String, usize> {
+    top_k.top(usize::MAX).into_iter().map(|(item, count, _)| (item, count)).collect()
+}
+
+#[test]
+fn test_stats_match_the_log() {
+    for fixture in ["order-to-cash", "erp", "fleet"] {
+        let path = format!("logs/{}.jsonocel", fixture);
+        let log: Ocel = import_ocel(&path).unwrap();
+        let (streamed, stats) = import_ocel_streaming(&path, &StreamConfig::default()).unwrap();
+        assert!(streamed.is_none());
+
+        assert_eq!(stats.events, log.events.len(), "{}", fixture);
+        assert!(stats.activities.is_exact() && stats.object_types.is_exact());
+        assert_eq!(top_counts(&stats.activities), exact_counts(log.events.values().map(|event| event.activity)), "{}", fixture);
+        let mut omap_sizes: BTreeMap<usize, usize> = BTreeMap::new();
+        for event in log.events.values() {
+            *omap_sizes.entry(event.omap.len()).or_default() += 1;
+        }
+        assert_eq!(stats.omap_sizes, omap_sizes, "{}", fixture);
+
+        // the fixtures are far below the range where the estimates leave linear counting
+        let referenced: HashSet<&usize> = log.events.values().flat_map(|event| event.omap).collect();
+        assert!((stats.referenced_objects.estimate() - referenced.len() as f64).abs() < 0.5, "{}", fixture);
+        for (obj_type, count) in top_counts(&stats.object_types) {
+            assert!((stats.distinct_objects[&obj_type].estimate() - count as f64).abs() < 0.5, "{}", fixture);
+        }
+    }
+}
+
+#[test]
+fn test_log_is_built_on_request() {
+    let config = StreamConfig { stats_only: false, ..Default::default() };
+    let (streamed, stats) = import_ocel_streaming("logs/order-to-cash.jsonocel", &config).unwrap();
+    let streamed = streamed.unwrap();
+    let log = import_ocel("logs/order-to-cash.jsonocel").unwrap();
+    assert_eq!(streamed.event_map, log.event_map);
+    assert_eq!(streamed.object_map.len(), log.object_map.len());
+    assert_eq!(stats.objects, log.objects.len());
+
+    let duplicate = r#"{"ocel:global-log": {}, "ocel:events": {"e1": {"ocel:activity": "a", "ocel:timestamp": "2022-01-01T00:00:00Z", "ocel:omap": []},
+                                                               "e1": {"ocel:activity": "b", "ocel:timestamp": "2022-01-01T00:00:00Z", "ocel:omap": []}}}"#;
+    let error = import_ocel_streaming_from(duplicate.as_bytes(), &config).err().unwrap();
+    assert!(error.to_string().contains("the id e1 is used more than once"), "{}", error);
+    assert_eq!(import_ocel_streaming_from(duplicate.as_bytes(), &StreamConfig::default()).unwrap().1.events, 2);
+    assert!(import_ocel_streaming_from(r#"{"ocel:global-log": {}}"#.as_bytes(), &StreamConfig::default()).is_err());
+}
+
+#[test]
+fn test_hyperloglog_error_bound() {
+    for precision in [10, 12, 14] {
+        let mut hll = HyperLogLog::new(precision);
+        let n = 200_000;
+        for i in 0..n {
+            hll.insert(&format!("object-{}", i));
+            // repeats do not count
+            hll.insert(&format!("object-{}", i / 2));
+        }
+        let relative = (hll.estimate() - n as f64).abs() / n as f64;
+        assert!(relative < 3.0 * hll.standard_error(), "precision {}: {}", precision, relative);
+    }
+}
+
+#[test]
+fn test_top_k_overestimation_is_bounded() {
+    let mut top_k = TopK::new(3);
+    let stream = ["a", "a", "a", "a", "b", "b", "b", "c", "c", "d", "e", "a"];
+    for item in stream {
+        top_k.add(item);
+    }
+    assert!(!top_k.is_exact());
+    for (item, count, error) in top_k.top(3) {
+        let exact = stream.iter().filter(|other| **other == item).count();
+        assert!(count >= exact && count - error <= exact, "{}", item);
+    }
+    assert_eq!(top_k.top(1)[0].0, "a");
+    assert_eq!(top_k.count("a"), Some(5));
+}
@@ -0,0 +1,51 @@
+use pmrs::objects::ocel::importer::{import_ocel_xes, import_ocel_xes_str, ImportConfig, OcelError};
+use pmrs::objects::ocel::Ocel;
+use serde_json::json;
+
+fn activities(log: &Ocel, case: &str) -> Vec<String> {
+    log.objects[log.object_map.get_by_left(case).unwrap()].events.iter().map(|eid| log.events.event(eid).activity.to_owned()).collect()
+}
+
+#[test]
+fn test_running_example() {
+    let log = import_ocel_xes("logs/running-example.xes", &ImportConfig::default()).unwrap();
+    assert_eq!(log.objects.len(), 3);
+    assert!(log.objects.values().all(|object| object.obj_type == "case"));
+    // the register request of case 2 and 3 is one event
+    assert_eq!(log.events.len(), 13);
+    assert_eq!(activities(&log, "1"), vec!["register request", "examine thoroughly", "check ticket", "decide", "reject request"]);
+    assert_eq!(activities(&log, "2"), vec!["register request", "check ticket", "examine casually", "decide", "pay compensation"]);
+    assert_eq!(activities(&log, "3"), vec!["register request", "examine casually", "check ticket", "decide"]);
+    assert!(log.is_time_ordered());
+
+    let shared = &log.events.event(&log.objects[log.object_map.get_by_left("2").unwrap()].events[0]);
+    assert_eq!(shared.omap.len(), 2);
+    assert_eq!(shared.vmap["org:resource"], json!("Mike"));
+    assert_eq!(shared.vmap["Costs"], json!(50));
+    assert!(!shared.vmap.contains_key("concept:name") && !shared.vmap.contains_key("time:timestamp"));
+
+    let paid = log.events.values().find(|event| event.activity == "pay compensation").unwrap();
+    assert_eq!(paid.vmap["paid"], json!(true));
+    assert_eq!(log.events.values().find(|event| event.vmap.get("Costs") == Some(&json!(400.5))).unwrap().activity, "examine casually");
+
+    // nested lists are flattened, the globals and extensions are not read
+    let case = &log.objects[log.object_map.get_by_left("3").unwrap()];
+    assert_eq!(case.ovmap["documents"], json!("[document=ticket;receipts=[receipt=hotel;receipt=train]]"));
+    assert_eq!(log.global_log["concept:name"], json!("running-example"));
+    assert_eq!(log.global_log["ocel:object-types"], json!(["case"]));
+}
+
+#[test]
+fn test_unnamed_traces_and_errors() {
+    let content = "<log><trace><event><string key=\"concept:name\" value=\"a\"/><date key=\"time:timestamp\" value=\"2022-01-01T10:00:00Z\"/></event></trace>
+                   <trace/></log>";
+    let log = import_ocel_xes_str(content, &ImportConfig::default()).unwrap();
+    assert_eq!(activities(&log, "trace 1"), vec!["a"]);
+    assert!(log.objects[log.object_map.get_by_left("trace 2").unwrap()].events.is_empty());
+
+    let missing = "<log><trace><event><string key=\"concept:name\" value=\"a\"/></event></trace></log>";
+    let error = import_ocel_xes_str(missing, &ImportConfig::default()).unwrap_err();
+    assert!(matches!(&error, OcelError::Missing(what) if what.contains("time:timestamp")), "{}", error);
+    assert!(matches!(import_ocel_xes_str("<trace></trace>", &ImportConfig::default()), Err(OcelError::Missing(_))));
+    assert!(matches!(import_ocel_xes("logs/missing.xes", &ImportConfig::default()), Err(OcelError::Io(_))));
+}
@@ -0,0 +1,61 @@
+use ahash::AHashMap;
+use chrono::{TimeZone, Utc};
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+fn oid(log: &Ocel, label: &str) -> usize {
+    *log.object_map.get_by_left(label).unwrap()
+}
+
+#[test]
+fn test_inserted_event_moves_births() {
+    let mut log: Ocel = import_ocel("logs/fleet.jsonocel").unwrap();
+    let (t1, v1, d1) = (oid(&log, "t1"), oid(&log, "v1"), oid(&log, "d1"));
+    let e0 = log.insert_event("e0", "inspect", Utc.ymd(2022, 5, 1).and_hms(7, 0, 0), &["t1", "v1"], AHashMap::new()).unwrap();
+
+    assert!(log.is_time_ordered());
+    assert_eq!(log.objects[&t1].events[0], e0);
+    assert!(log.activities.contains(&"inspect".to_string()));
+    let ocdg = generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    assert_eq!(ocdg.relation_events(t1, v1, Relations::COBIRTH).unwrap().iter().copied().collect::<Vec<usize>>(), vec![e0]);
+    assert!(ocdg.relation_events(t1, d1, Relations::COBIRTH).is_none());
+}
+
+#[test]
+fn test_insert_event_rejects_bad_input() {
+    let mut log: Ocel = import_ocel("logs/fleet.jsonocel").unwrap();
+    let timestamp = Utc.ymd(2022, 5, 1).and_hms(7, 0, 0);
+    assert!(log.insert_event("e1", "inspect", timestamp, &["t1"], AHashMap::new()).is_err());
+    assert!(log.insert_event("e9", "inspect", timestamp, &["x1"], AHashMap::new()).is_err());
+    assert_eq!(log.events.len(), 3);
+}
+
+#[test]
+fn test_rebuild_repairs_direct_edits() {
+    let mut log: Ocel = import_ocel("logs/fleet.jsonocel").unwrap();
+    let t1 = oid(&log, "t1");
+    let e3 = *log.event_map.get_by_left("e3").unwrap();
+    log.events.update(&e3, |event| {
+        event.timestamp = Utc.ymd(2022, 5, 1).and_hms(6, 0, 0);
+        event.activity = "prepare".to_string();
+    });
+    assert!(!log.is_time_ordered());
+
+    log.rebuild_derived_indices();
+    assert!(log.is_time_ordered());
+    assert_eq!(log.objects[&t1].events[0], e3);
+    assert!(!log.activities.contains(&"arrive".to_string()));
+    assert!(log.activities.contains(&"prepare".to_string()));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "rebuild_derived_indices")]
+fn test_generation_rejects_unordered_lifecycles() {
+    let mut log: Ocel = import_ocel("logs/fleet.jsonocel").unwrap();
+    let t1 = oid(&log, "t1");
+    log.objects.get_mut(&t1).unwrap().events.reverse();
+    generate_ocdg(&log, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+}
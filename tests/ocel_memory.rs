@@ -0,0 +1,44 @@
+use std::mem::size_of;
+
+use pmrs::objects::ocel::importer::{import_ocel, import_ocel_with_report, ImportConfig};
+use pmrs::objects::ocel::{EventColumns, OcelObject};
+
+#[test]
+fn test_breakdown_matches_element_counts() {
+    let log = import_ocel("logs/fleet.jsonocel").unwrap();
+    let memory = log.memory_breakdown();
+
+    // load references three objects, depart and arrive two each
+    assert_eq!(memory.omaps, 7 * size_of::<usize>());
+    assert_eq!(memory.lifecycles, memory.omaps);
+    // every activity is stored once in the interned list and once as a lookup key
+    assert_eq!(memory.events, 3 * EventColumns::row_size() + 3 * (2 * size_of::<String>() + size_of::<u32>()) + 2 * ("load".len() + "depart".len() + "arrive".len()));
+    assert_eq!(memory.objects, 3 * (size_of::<usize>() + size_of::<OcelObject>()) + "truck".len() + "van".len() + "driver".len());
+    assert_eq!(memory.id_maps, 6 * 2 * (size_of::<String>() + size_of::<usize>()) + 6 * 2);
+    assert_eq!(memory.activities, 3 * size_of::<String>() + 16);
+    assert_eq!(memory.total(), memory.events + memory.objects + memory.omaps + memory.lifecycles + memory.attributes + memory.id_maps + memory.activities);
+}
+
+#[test]
+fn test_attributes_grow_with_values() {
+    let mut log = import_ocel("logs/fleet.jsonocel").unwrap();
+    let before = log.memory_breakdown();
+    log.events.set_attribute(&0, "note", serde_json::json!(["abc", "de"]));
+    let after = log.memory_breakdown();
+
+    // a new attribute adds its column with the name and one (row, value) entry
+    let column = 2 * (size_of::<String>() + "note".len()) + size_of::<usize>() + size_of::<Vec<(usize, serde_json::Value)>>();
+    assert_eq!(after.attributes - before.attributes, column + size_of::<(usize, serde_json::Value)>() + 2 * size_of::<serde_json::Value>() + 5);
+    assert_eq!(after.total() - before.total(), after.attributes - before.attributes);
+}
+
+#[test]
+fn test_import_report_profile() {
+    let (log, report) = import_ocel_with_report("logs/fleet.jsonocel", &ImportConfig::default()).unwrap();
+    assert_eq!(report.memory, log.memory_breakdown());
+    assert_eq!(report.timings.total(), report.timings.read + report.timings.parse + report.timings.index);
+
+    // the raw file and the parsed document are held together with the log
+    let file_size = std::fs::metadata("logs/fleet.jsonocel").unwrap().len() as usize;
+    assert!(report.peak_bytes > file_size + report.memory.total());
+}
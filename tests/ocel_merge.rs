@@ -0,0 +1,69 @@
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::merge::{link_objects, merge_with_links, AttributeConflict, MergeConfig, ObjectMatcher};
+use serde_json::Value;
+
+fn ids(log_a: &Ocel, log_b: &Ocel, links: &[(usize, usize, f64)]) -> Vec<(String, String, f64)> {
+    let mut ids: Vec<(String, String, f64)> = links.iter()
+                                                   .map(|(a, b, score)| (log_a.object_map.get_by_right(a).unwrap().to_owned(), log_b.object_map.get_by_right(b).unwrap().to_owned(), *score))
+                                                   .collect();
+    ids.sort_by(|a, b| a.0.cmp(&b.0));
+    ids
+}
+
+#[test]
+fn test_link_objects_normalized_id() {
+    let erp: Ocel = import_ocel("logs/erp.jsonocel").unwrap();
+    let wms: Ocel = import_ocel("logs/wms.jsonocel").unwrap();
+    let links = link_objects(&erp, &wms, &ObjectMatcher::NormalizedId);
+
+    assert_eq!(ids(&erp, &wms, &links), vec![("MAT-7".to_string(), "mat_7".to_string(), 1.0),
+                                             ("SO-1001".to_string(), "1001/SO".to_string(), 1.0),
+                                             ("SO-1002".to_string(), "1002".to_string(), 0.5)]);
+}
+
+#[test]
+fn test_link_objects_exact_attribute() {
+    let erp: Ocel = import_ocel("logs/erp.jsonocel").unwrap();
+    let wms: Ocel = import_ocel("logs/wms.jsonocel").unwrap();
+    let links = link_objects(&erp, &wms, &ObjectMatcher::ExactAttribute("reference".to_string()));
+
+    assert_eq!(ids(&erp, &wms, &links), vec![("SO-1001".to_string(), "1001/SO".to_string(), 1.0)]);
+}
+
+#[test]
+fn test_merge_with_links_unifies_renamed_objects() {
+    let erp: Ocel = import_ocel("logs/erp.jsonocel").unwrap();
+    let wms: Ocel = import_ocel("logs/wms.jsonocel").unwrap();
+    let links = link_objects(&erp, &wms, &ObjectMatcher::NormalizedId);
+    let (merged, report) = merge_with_links(&erp, &wms, &links, &MergeConfig::default());
+
+    assert_eq!(report.applied.len(), 2);
+    assert_eq!(report.rejected, vec![("SO-1002".to_string(), "1002".to_string(), 0.5)]);
+    // SO-1001 and MAT-7 are unified, SO-1002 and 1002 stay separate
+    assert_eq!(merged.objects.len(), 4);
+    assert_eq!(merged.events.len(), 6);
+    assert!(!merged.object_map.contains_left("1001/SO"));
+
+    let order = &merged.objects[merged.object_map.get_by_left("SO-1001").unwrap()];
+    let activities: Vec<&str> = order.events.iter().map(|eid| merged.events.event(eid).activity).collect();
+    assert_eq!(activities, vec!["create_order", "approve_order", "pick", "ship"]);
+    assert_eq!(order.ovmap["customer"], Value::from("Apple"));
+
+    let material = &merged.objects[merged.object_map.get_by_left("MAT-7").unwrap()];
+    assert_eq!(material.ovmap["bin"], Value::from("A3"));
+}
+
+#[test]
+fn test_merge_with_links_conflict_policy() {
+    let erp: Ocel = import_ocel("logs/erp.jsonocel").unwrap();
+    let wms: Ocel = import_ocel("logs/wms.jsonocel").unwrap();
+    let links = link_objects(&erp, &wms, &ObjectMatcher::NormalizedId);
+    let config = MergeConfig { threshold: 0.4, conflict: AttributeConflict::KeepSecond };
+    let (merged, report) = merge_with_links(&erp, &wms, &links, &config);
+
+    assert!(report.rejected.is_empty());
+    assert_eq!(merged.objects.len(), 3);
+    let order = &merged.objects[merged.object_map.get_by_left("SO-1001").unwrap()];
+    assert_eq!(order.ovmap["customer"], Value::from("Apple Inc."));
+}
@@ -0,0 +1,96 @@
+use pmrs::objects::ocdg::{generate_ocdg_config, GenerationConfig, Ocdg, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::removal::{RemovalMode, REMOVED_ATTRIBUTE};
+use pmrs::objects::ocel::Ocel;
+use serde_json::Value;
+
+// order o2 owns item i3 (e2, e5) and invoice r2 (e7)
+const LOG: &str = "logs/order-to-cash.jsonocel";
+
+fn setup() -> (Ocel, Ocdg) {
+    let log = import_ocel(LOG).unwrap();
+    let ocdg = generate_ocdg_config(&log, &GenerationConfig { relations: vec![Relations::INTERACTS], ..Default::default() }).unwrap();
+    (log, ocdg)
+}
+
+fn oids(log: &Ocel, labels: &[&str]) -> Vec<usize> {
+    labels.iter().map(|label| *log.object_map.get_by_left(*label).unwrap()).collect()
+}
+
+fn labels(log: &Ocel, eids: &[usize]) -> Vec<String> {
+    let mut labels: Vec<String> = eids.iter().map(|eid| log.event_map.get_by_right(eid).unwrap().to_owned()).collect();
+    labels.sort();
+    labels
+}
+
+#[test]
+fn test_plan_reports_the_blast_radius() {
+    let (log, ocdg) = setup();
+    let plan = log.plan_object_removal(&oids(&log, &["o2"]), &ocdg);
+    assert!(plan.emptied_events.is_empty());
+    assert_eq!(labels(&log, &plan.shrunk_events), vec!["e2", "e7"]);
+    // i3 only interacts with o2, r2 still interacts with r1 and p1
+    assert_eq!(plan.dependents, oids(&log, &["i3"]));
+
+    // unknown ids are ignored
+    let mut removed = oids(&log, &["o2", "i3"]);
+    removed.push(usize::MAX);
+    let plan = log.plan_object_removal(&removed, &ocdg);
+    assert_eq!(plan.objects.len(), 2);
+    assert_eq!(labels(&log, &plan.emptied_events), vec!["e2", "e5"]);
+    assert_eq!(labels(&log, &plan.shrunk_events), vec!["e7"]);
+    assert!(plan.dependents.is_empty());
+    assert!(!plan.is_strict_safe());
+}
+
+#[test]
+fn test_cascade() {
+    let (mut log, ocdg) = setup();
+    let plan = log.plan_object_removal(&oids(&log, &["o2", "i3"]), &ocdg);
+    let mut dropped = log.apply_removal(&plan, RemovalMode::Cascade).unwrap();
+    dropped.sort();
+
+    assert_eq!(dropped, vec!["e2", "e5"]);
+    assert_eq!(log.events.len(), 7);
+    assert_eq!(log.objects.len(), 6);
+    assert!(!log.object_map.contains_left("o2"));
+    let r2 = log.object_map.get_by_left("r2").unwrap();
+    assert_eq!(labels(&log, &log.objects[r2].events), vec!["e7", "e8"]);
+    let e7 = log.event_map.get_by_left("e7").unwrap();
+    assert_eq!(log.events.event(e7).omap.len(), 1);
+}
+
+#[test]
+fn test_tombstone() {
+    let (mut log, ocdg) = setup();
+    let removed = oids(&log, &["o2", "i3"]);
+    let plan = log.plan_object_removal(&removed, &ocdg);
+    assert!(log.apply_removal(&plan, RemovalMode::Tombstone).unwrap().is_empty());
+
+    assert_eq!(log.events.len(), 9);
+    assert_eq!(log.objects.len(), 8);
+    for oid in removed {
+        assert_eq!(log.objects[&oid].ovmap.get(REMOVED_ATTRIBUTE), Some(&Value::Bool(true)));
+    }
+    let flagged: Vec<usize> = log.events.iter().filter(|(_, event)| event.vmap.contains_key(REMOVED_ATTRIBUTE)).map(|(eid, _)| *eid).collect();
+    assert_eq!(labels(&log, &flagged), vec!["e2", "e5"]);
+    let e7 = log.event_map.get_by_left("e7").unwrap();
+    assert_eq!(log.events.event(e7).omap.len(), 2);
+}
+
+#[test]
+fn test_strict() {
+    let (mut log, ocdg) = setup();
+    let plan = log.plan_object_removal(&oids(&log, &["o2", "i3"]), &ocdg);
+    let err = log.apply_removal(&plan, RemovalMode::Strict).unwrap_err();
+    assert!(err.to_string().contains("e2, e5"));
+    assert_eq!(log.objects.len(), 8);
+    assert_eq!(log.events.len(), 9);
+
+    let plan = log.plan_object_removal(&oids(&log, &["o2"]), &ocdg);
+    assert!(log.apply_removal(&plan, RemovalMode::Strict).unwrap().is_empty());
+    assert_eq!(log.events.len(), 9);
+    assert_eq!(log.objects.len(), 7);
+    let e2 = log.event_map.get_by_left("e2").unwrap();
+    assert_eq!(log.events.event(e2).omap.len(), 1);
+}
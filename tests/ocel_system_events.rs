@@ -0,0 +1,82 @@
+use pmrs::algo::transformation::ocel::features::object_point::{object_average_event_interaction, object_unit_set_ratio};
+use pmrs::objects::ocdg::{generate_ocdg, Relations};
+use pmrs::objects::ocel::importer::{import_ocel, import_ocel_with_report, ImportConfig};
+use strum::IntoEnumIterator;
+
+// fleet.jsonocel with three system events (two backups and a sync) that reference no objects
+const LOG: &str = "logs/fleet-system.jsonocel";
+
+#[test]
+fn test_import_report_counts_empty_omaps() {
+    let (log, report) = import_ocel_with_report(LOG, &ImportConfig::default()).unwrap();
+    assert_eq!(report.events, 6);
+    assert_eq!(report.empty_omaps, 3);
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].starts_with("3 events reference no objects"));
+    assert!(log.objects.values().all(|obj| obj.events.len() == 3 || obj.events.len() == 1));
+
+    let (_, report) = import_ocel_with_report("logs/fleet.jsonocel", &ImportConfig::default()).unwrap();
+    assert_eq!(report.empty_omaps, 0);
+    assert!(report.warnings.is_empty());
+}
+
+#[test]
+fn test_events_without_objects() {
+    let log = import_ocel(LOG).unwrap();
+    let labels: Vec<&str> = log.events_without_objects().iter().map(|eid| log.event_map.get_by_right(eid).unwrap().as_str()).collect();
+    assert_eq!(labels, vec!["s1", "s2", "s3"]);
+}
+
+#[test]
+fn test_drop_events_without_objects() {
+    let mut log = import_ocel(LOG).unwrap();
+    let mut dropped = log.drop_events_without_objects();
+    dropped.sort();
+    assert_eq!(dropped, vec!["s1", "s2", "s3"]);
+    assert_eq!(log.events.len(), 3);
+    assert_eq!(log.event_map.len(), 3);
+    assert!(log.events_without_objects().is_empty());
+    assert!(!log.activities.contains(&"backup".to_string()));
+    assert!(!log.activities.contains(&"sync".to_string()));
+    assert!(log.drop_events_without_objects().is_empty());
+}
+
+#[test]
+fn test_generation_ignores_system_events() {
+    let with_system = import_ocel(LOG).unwrap();
+    let without = import_ocel("logs/fleet.jsonocel").unwrap();
+    let ocdg = generate_ocdg(&with_system, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+    let reference = generate_ocdg(&without, &Relations::iter().collect::<Vec<Relations>>()).unwrap();
+
+    assert_eq!(ocdg.net.node_count(), reference.net.node_count());
+    assert_eq!(ocdg.net.edge_count(), reference.net.edge_count());
+    assert_eq!(ocdg.metadata().unwrap().edge_counts, reference.metadata().unwrap().edge_counts);
+    for eid in with_system.events_without_objects() {
+        assert_eq!(ocdg.relations_of_event(eid), Some(vec![]));
+    }
+
+    // the coverage report lists them as uncovered events of their activity
+    let report = ocdg.uncovered_events(&with_system).unwrap();
+    let backup = report.activity("backup").unwrap();
+    assert_eq!((backup.events, backup.uncovered, backup.single_object), (2, 2, 2));
+}
+
+#[test]
+fn test_features_tolerate_empty_omaps() {
+    let mut log = import_ocel(LOG).unwrap();
+    let t1 = *log.object_map.get_by_left("t1").unwrap();
+    let s1 = *log.event_map.get_by_left("s1").unwrap();
+    let interaction = object_average_event_interaction(&log, &t1);
+    assert_eq!(interaction, 4.0 / 3.0);
+
+    // a lifecycle that still points to a system event must not underflow
+    log.objects.get_mut(&t1).unwrap().events.insert(0, s1);
+    assert_eq!(object_average_event_interaction(&log, &t1), 1.0);
+
+    // objects left without events yield zero instead of nan
+    log.drop_events_without_objects();
+    log.events.clear();
+    log.rebuild_derived_indices();
+    assert_eq!(object_average_event_interaction(&log, &t1), 0.0);
+    assert_eq!(object_unit_set_ratio(&log, &t1), 0.0);
+}
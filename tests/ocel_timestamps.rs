@@ -0,0 +1,62 @@
+use std::fs;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use pmrs::objects::ocel::importer::timestamp::{TimestampFormat, TimestampParser};
+use pmrs::objects::ocel::importer::{import_ocel, import_ocel_with, ImportConfig};
+
+fn at(hour: u32, millis: i64) -> DateTime<Utc> {
+    Utc.ymd(2022, 5, 1).and_hms(hour, 0, 0) + Duration::milliseconds(millis)
+}
+
+#[test]
+fn test_formats() {
+    let parser = TimestampParser::default()
+                 .with_format(TimestampFormat::Pattern("%d.%m.%Y %H:%M:%S,%3f".to_string()))
+                 .with_format(TimestampFormat::Pattern("%Y/%m/%d %H:%M".to_string()))
+                 .with_format(TimestampFormat::EpochSeconds)
+                 .with_custom("hours into may", |value| value.strip_suffix("h into may").and_then(|hours| hours.parse().ok()).map(|hours| at(0, 0) + Duration::hours(hours)));
+
+    assert_eq!(parser.parse("2022-05-01T10:00:00+02:00").unwrap(), at(8, 0));
+    assert_eq!(parser.parse("01.05.2022 08:00:00,250").unwrap(), at(8, 250));
+    assert_eq!(parser.parse("2022/05/01 08:00").unwrap(), at(8, 0));
+    assert_eq!(parser.parse("1651392000.5").unwrap(), at(8, 500));
+    assert_eq!(parser.parse("8h into may").unwrap(), at(8, 0));
+    assert_eq!(TimestampParser::new(vec![TimestampFormat::EpochMillis]).parse("1651392000500").unwrap(), at(8, 500));
+}
+
+#[test]
+fn test_unknown_format_names_the_value_and_formats() {
+    let parser = TimestampParser::default().with_format(TimestampFormat::EpochSeconds);
+    let error = parser.parse("yesterday noon").unwrap_err();
+    assert_eq!(error.tried, vec!["rfc3339", "epoch seconds"]);
+    assert_eq!(error.to_string(), "Timestamp \"yesterday noon\" matches none of the formats tried: rfc3339, epoch seconds");
+}
+
+#[test]
+fn test_import_with_formats() {
+    let config = ImportConfig { timestamps: TimestampParser::new(vec![TimestampFormat::Pattern("%d.%m.%Y %H:%M:%S,%3f".to_string()), TimestampFormat::EpochSeconds]),
+                                ..Default::default() };
+    let log = import_ocel_with("logs/fleet-exotic.jsonocel", &config).unwrap();
+    let timestamp = |label: &str| log.events.event(log.event_map.get_by_left(label).unwrap()).timestamp;
+    assert_eq!(timestamp("e1"), at(8, 250));
+    assert_eq!(timestamp("e2"), at(9, 500));
+    assert_eq!(timestamp("e3"), at(12, 0));
+
+    let error = import_ocel("logs/fleet-exotic.jsonocel").unwrap_err().to_string();
+    assert!(error.contains("matches none of the formats tried: rfc3339"), "{}", error);
+}
+
+#[test]
+fn test_import_detects_format() {
+    let content = fs::read_to_string("logs/fleet-exotic.jsonocel").unwrap().replace("1651395600.5", "\"01.05.2022 09:00:00,500\"");
+    let path = std::env::temp_dir().join("pmrs-detect.jsonocel");
+    let path = path.to_str().unwrap();
+    fs::write(path, content).unwrap();
+    let log = import_ocel_with(path, &ImportConfig { timestamps: TimestampParser::auto(10), ..Default::default() });
+    fs::remove_file(path).unwrap();
+
+    let log = log.unwrap();
+    assert_eq!(log.events.event(log.event_map.get_by_left("e2").unwrap()).timestamp, at(9, 500));
+    let detected = TimestampParser::auto(2).detected(["1651392000.5", "1651395600"]);
+    assert_eq!(detected.parse("1651392000.5").unwrap(), at(8, 500));
+}
@@ -0,0 +1,61 @@
+use std::collections::BTreeSet;
+use rayon::prelude::*;
+use pmrs::objects::ocdg::{generate_ocdg_config, GenerationConfig, Relations};
+use pmrs::objects::ocel::importer::import_ocel;
+use pmrs::objects::ocel::view::OcelView;
+use pmrs::objects::ocel::Ocel;
+
+// orders o1 and o2 are in the EU, o3 in the US and o4 has no region. the truck t1 loads items of o1 and o3,
+// and e7 merges o1 with o3
+const LOG: &str = "logs/regions.jsonocel";
+
+fn labels(log: &Ocel, view: &OcelView) -> (BTreeSet<String>, BTreeSet<String>) {
+    (view.objects.iter().map(|oid| log.object_map.get_by_right(oid).unwrap().to_owned()).collect(),
+     view.events.iter().map(|eid| log.event_map.get_by_right(eid).unwrap().to_owned()).collect())
+}
+
+fn set(items: &[&str]) -> BTreeSet<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+#[test]
+fn test_partition_by_region() {
+    let log = import_ocel(LOG).unwrap();
+    let views = log.partition_by_attribute("order", "region");
+    assert_eq!(views.keys().cloned().collect::<BTreeSet<String>>(), set(&["EU", "US"]));
+
+    let (eu_objects, eu_events) = labels(&log, &views["EU"]);
+    let (us_objects, us_events) = labels(&log, &views["US"]);
+    // the truck only shares an event with the items, o3 is kept out of the EU although it shares e7 with o1
+    assert_eq!(eu_objects, set(&["o1", "o2", "i1", "i2", "i3"]));
+    assert_eq!(us_objects, set(&["o3", "i4"]));
+    assert_eq!(eu_events, set(&["e1", "e2", "e4", "e5", "e7"]));
+    assert_eq!(us_events, set(&["e3", "e4", "e7"]));
+
+    let orders = |objects: &BTreeSet<String>| -> BTreeSet<String> { objects.iter().filter(|label| label.starts_with('o')).cloned().collect() };
+    assert!(orders(&eu_objects).is_disjoint(&orders(&us_objects)));
+    assert!(!views.values().any(|view| view.contains_object(log.object_map.get_by_left("o4").unwrap())));
+}
+
+#[test]
+fn test_views_become_logs() {
+    let log = import_ocel(LOG).unwrap();
+    let views = log.partition_by_attribute("order", "region");
+    let eu = views["EU"].to_ocel();
+
+    assert_eq!(eu.objects.len(), 5);
+    assert_eq!(eu.events.len(), 5);
+    let load = eu.event_map.get_by_left("e4").unwrap();
+    assert_eq!(eu.events.event(load).omap.len(), 1);
+    let merge = eu.event_map.get_by_left("e7").unwrap();
+    assert_eq!(eu.events.event(merge).omap.len(), 1);
+    let i1 = eu.object_map.get_by_left("i1").unwrap();
+    assert_eq!(eu.objects[i1].events.len(), 2);
+    assert!(eu.is_time_ordered());
+
+    // generation fans out over the partitions
+    let config = GenerationConfig { relations: vec![Relations::INTERACTS], ..Default::default() };
+    let mut nodes: Vec<(String, usize)> = views.par_iter().map(|(region, view)| (region.to_owned(), generate_ocdg_config(&view.to_ocel(), &config).unwrap().net.node_count())).collect();
+    nodes.sort();
+    assert_eq!(nodes, vec![("EU".to_string(), 5), ("US".to_string(), 2)]);
+}
@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::PathBuf;
+
+use pmrs::objects::ocdg::Relations;
+use pmrs::pipeline::{estimate, load_with_preset, run, GenerationStage, PipelineConfig};
+
+fn fixture() -> String {
+    format!("{}/logs/order-to-cash.jsonocel", env!("CARGO_MANIFEST_DIR"))
+}
+
+// each test writes its config and artifacts into its own directory
+fn workspace(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pmrs-pipeline-{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_run_all_stages() {
+    let dir = workspace("stages");
+    let config = serde_json::json!({
+        "input": fixture(),
+        "filters": [{"stage": "drop_events_without_objects"},
+                    {"stage": "between", "start": "2022-06-01T08:00:00Z", "end": "2022-06-01T11:00:00Z"}],
+        "generation": {"relations": ["INTERACTS", "COBIRTH"]},
+        "analyses": [{"stage": "relation_counts"}, {"stage": "motifs"}, {"stage": "uncovered_events", "csv": "uncovered.csv"}],
+        "exports": [{"stage": "gexf", "path": "graph.gexf"}, {"stage": "metrics", "path": "metrics.txt"}, {"stage": "analyses", "path": "analyses.json"}]
+    });
+    let config_path = dir.join("pipeline.json");
+    fs::write(&config_path, config.to_string()).unwrap();
+
+    let report = run(config_path.to_str().unwrap()).unwrap();
+    let stages: Vec<&str> = report.stages.iter().map(|stage| stage.stage.as_str()).collect();
+    assert_eq!(stages, vec!["import", "filter drop_events_without_objects", "filter between", "generation",
+                            "analysis relation_counts", "analysis motifs", "analysis uncovered_events",
+                            "export gexf", "export metrics", "export analyses"]);
+    assert_eq!(report.stages[1].summary, "8 of 9 events kept");
+    // the payment at 12:00 falls outside of the window
+    assert_eq!(report.stages[2].summary, "7 of 8 events kept");
+    assert_eq!(report.stages[3].summary, "7 nodes, 12 edges");
+
+    assert_eq!(report.analyses["relation_counts"], serde_json::json!({"COBIRTH": 8, "INTERACTS": 12}));
+    assert_eq!(report.analyses["motifs"]["triangles"], 1);
+    // the three picks involve a single item each
+    assert_eq!(report.analyses["uncovered_events"]["events"], 3);
+
+    let artifacts: Vec<PathBuf> = ["uncovered.csv", "graph.gexf", "metrics.txt", "analyses.json"].iter().map(|name| dir.join(name)).collect();
+    assert_eq!(report.artifacts, artifacts);
+    assert!(artifacts.iter().all(|artifact| artifact.exists()));
+    let written: serde_json::Value = serde_json::from_str(&fs::read_to_string(dir.join("analyses.json")).unwrap()).unwrap();
+    assert_eq!(written["relation_counts"], report.analyses["relation_counts"]);
+    assert!(report.to_string().contains("wrote"));
+}
+
+#[test]
+fn test_errors_name_the_stage() {
+    let dir = workspace("errors");
+    let config_path = dir.join("pipeline.json");
+    fs::write(&config_path, serde_json::json!({"input": fixture(), "generation": {"relations": ["INTERACTS", "FRIENDS"]}}).to_string()).unwrap();
+    let error = run(config_path.to_str().unwrap()).unwrap_err();
+    assert_eq!(error.stage, "generation");
+    assert!(error.to_string().contains("unknown relation FRIENDS"));
+
+    fs::write(&config_path, serde_json::json!({"input": fixture(), "analyses": [{"stage": "cohorts"}]}).to_string()).unwrap();
+    let error = run(config_path.to_str().unwrap()).unwrap_err();
+    assert_eq!(error.stage, "analysis cohorts");
+
+    fs::write(&config_path, serde_json::json!({"input": "missing.jsonocel"}).to_string()).unwrap();
+    assert_eq!(run(config_path.to_str().unwrap()).unwrap_err().stage, "import");
+    assert_eq!(run(dir.join("missing.json").to_str().unwrap()).unwrap_err().stage, "config");
+}
+
+#[test]
+fn test_ron_config() {
+    let ron = format!("(input: \"{}\", filters: [(stage: \"drop_events_without_objects\")], generation: Some((relations: [\"COBIRTH\"])), analyses: [(stage: \"cohorts\")])", fixture());
+    let dir = workspace("ron");
+    let config_path = dir.join("pipeline.ron");
+    fs::write(&config_path, &ron).unwrap();
+
+    assert_eq!(PipelineConfig::from_file(config_path.to_str().unwrap()).unwrap().filters.len(), 1);
+    let report = run(config_path.to_str().unwrap()).unwrap();
+    assert_eq!(report.analyses["cohorts"]["cohorts"], 5);
+}
+
+#[test]
+fn test_estimate_skips_generation() {
+    let dir = workspace("estimate");
+    let config = serde_json::json!({
+        "input": fixture(),
+        "filters": [{"stage": "drop_events_without_objects"}],
+        "generation": {"relations": ["INTERACTS", "COBIRTH"]},
+        "exports": [{"stage": "gexf", "path": "graph.gexf"}]
+    });
+    let config_path = dir.join("pipeline.json");
+    fs::write(&config_path, config.to_string()).unwrap();
+
+    let (report, estimate) = estimate(config_path.to_str().unwrap()).unwrap();
+    let stages: Vec<&str> = report.stages.iter().map(|stage| stage.stage.as_str()).collect();
+    assert_eq!(stages, vec!["import", "filter drop_events_without_objects", "estimate generation"]);
+    // every object is sampled, so the counts are exact
+    assert_eq!(estimate.nodes, 8);
+    assert_eq!(estimate.edges["INTERACTS"].expected, 18.0);
+    assert!(!dir.join("graph.gexf").exists());
+}
+
+#[test]
+fn test_relation_presets() {
+    let stage = GenerationStage { relations: vec!["Lineage".to_owned(), "INTERACTS".to_owned()], ..Default::default() };
+    assert_eq!(stage.config().unwrap().relations, vec![Relations::INTERACTS, Relations::DESCENDANTS, Relations::INHERITANCE, Relations::CONSUMES, Relations::SPLIT, Relations::MERGE]);
+
+    // the preset of the cli replaces the relations of the config and adds a missing generation stage
+    let dir = workspace("presets");
+    let config_path = dir.join("pipeline.json");
+    fs::write(&config_path, serde_json::json!({"input": fixture(), "generation": {"relations": ["INTERACTS"]}}).to_string()).unwrap();
+    let (config, _) = load_with_preset(config_path.to_str().unwrap(), Some("Lifecycle")).unwrap();
+    assert_eq!(config.generation.unwrap().config().unwrap().relations, vec![Relations::COLIFE, Relations::COBIRTH, Relations::CODEATH]);
+    fs::write(&config_path, serde_json::json!({"input": fixture()}).to_string()).unwrap();
+    let (config, _) = load_with_preset(config_path.to_str().unwrap(), Some("Interaction")).unwrap();
+    assert_eq!(config.generation.unwrap().relations, vec!["Interaction"]);
+    assert_eq!(load_with_preset(config_path.to_str().unwrap(), Some("Everything")).unwrap_err().stage, "config");
+}
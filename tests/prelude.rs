@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+use pmrs::prelude::*;
+
+// the whole flow from import to features only through the prelude
+#[test]
+fn test_prelude_covers_the_main_flow() {
+    let log: Ocel = import_ocel("logs/order-to-cash.jsonocel").unwrap();
+    let config = GenerationConfig { relations: RelationSet::preset(Preset::Interaction).into_vec(), evidence: Evidence::Full, ..Default::default() };
+    let ocdg: Ocdg = generate_ocdg_config(&log, &config).unwrap();
+    assert!(ocdg.net.edge_count() > 0);
+
+    let mut graphml: Vec<u8> = vec![];
+    export_ocdg_graphml_to(&ocdg, &mut graphml, &ExportOptions::default()).unwrap();
+    assert!(String::from_utf8(graphml).unwrap().contains("<graphml"));
+
+    let params = HashMap::from_iter([(ObjectPoint::UniqueNeighborCount, None)]);
+    let df = object_point_features(ObjectPointConfig { ocel: &log, ocdg: &ocdg, params: &params, lifecycle_view: LifecycleView::Full });
+    assert_eq!(df.height(), ocdg.net.node_count());
+    assert!(!cohorts(&log, &ocdg).cohorts.is_empty());
+    assert_eq!(type_lift(&log), pmrs::analysis::type_lift(&log));
+
+    let o1 = *log.object_map.get_by_left("o1").unwrap();
+    assert!(object_journey(&log, &ocdg, o1).is_some());
+}
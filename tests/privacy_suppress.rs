@@ -0,0 +1,69 @@
+use chrono::{TimeZone, Utc};
+use pmrs::algo::analysis::timeseries_to_json;
+use pmrs::algo::privacy::{suppress, suppress_with, PrivacyConfig};
+use serde_json::{json, Value};
+
+fn report() -> Value {
+    json!({"activities": {"create": 12, "approve": 3, "cancel": 1},
+           "type_pairs": [{"src": "order", "tar": "item", "count": 40}, {"src": "order", "tar": "invoice", "count": 2}],
+           "variants": [["create", "approve", 7], ["create", "cancel", 4]],
+           "attributes": {"region": {"top": [{"value": "north", "count": 9}, {"value": "south", "count": 0}]}},
+           "rate": 0.25,
+           "year": 2022})
+}
+
+// every integer leaf outside of the exempt keys
+fn counts(value: &Value, exempt: &[&str], out: &mut Vec<u64>) {
+    match value {
+        Value::Object(fields) => fields.iter().filter(|(key, _)| !exempt.contains(&key.as_str())).for_each(|(_, field)| counts(field, exempt, out)),
+        Value::Array(items) => items.iter().for_each(|item| counts(item, exempt, out)),
+        Value::Number(number) => out.extend(number.as_u64()),
+        _ => {}
+    }
+}
+
+#[test]
+fn test_no_count_below_k_survives() {
+    let suppressed = suppress(&report(), 5);
+    let mut remaining: Vec<u64> = vec![];
+    counts(&suppressed, &[], &mut remaining);
+
+    assert!(remaining.iter().all(|count| *count >= 5));
+    assert_eq!(suppressed["activities"]["approve"], "<5");
+    assert_eq!(suppressed["activities"]["create"], 12);
+    assert_eq!(suppressed["type_pairs"][1]["count"], "<5");
+    assert_eq!(suppressed["variants"][1][2], "<5");
+    assert_eq!(suppressed["attributes"]["region"]["top"][1]["count"], "<5");
+    assert_eq!(suppressed["rate"], 0.25);
+}
+
+#[test]
+fn test_report_marks_fields() {
+    let config = PrivacyConfig { k: 5, exempt: vec!["year".to_string()], ..Default::default() };
+    let (suppressed, marks) = suppress_with(&report(), &config);
+
+    assert_eq!(suppressed["year"], 2022);
+    assert_eq!(marks.redacted, vec!["/activities/approve", "/activities/cancel", "/attributes/region/top/1/count", "/type_pairs/1/count", "/variants/1/2"]);
+    assert!(marks.noised.is_empty());
+}
+
+#[test]
+fn test_noise_keeps_counts_above_k() {
+    let config = PrivacyConfig { k: 5, epsilon: Some(0.5), exempt: vec!["year".to_string()], seed: Some(7) };
+    let (suppressed, marks) = suppress_with(&report(), &config);
+    let mut remaining: Vec<u64> = vec![];
+    counts(&suppressed, &["year"], &mut remaining);
+
+    assert!(remaining.iter().all(|count| *count >= 5));
+    assert_eq!(marks.noised.len(), 4);
+    // the same seed gives the same noise
+    assert_eq!(suppress_with(&report(), &config).0, suppressed);
+}
+
+#[test]
+fn test_timeseries_export() {
+    let series = vec![(Utc.ymd(2022, 1, 1).and_hms(0, 0, 0), 8), (Utc.ymd(2022, 1, 2).and_hms(0, 0, 0), 2)];
+    let suppressed = suppress(&timeseries_to_json(&series), 3);
+    assert_eq!(suppressed[0]["count"], 8);
+    assert_eq!(suppressed[1]["count"], "<3");
+}
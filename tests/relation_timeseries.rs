@@ -0,0 +1,72 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::algo::analysis::{relation_timeseries, relation_timeseries_config, timeseries_to_csv, Granularity, RelationSeriesConfig, SeriesCount};
+use pmrs::objects::ocdg::{Ocdg, generate_ocdg, Relations};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+use pmrs::objects::ocel::importer::import_ocel;
+
+fn fixture() -> (Ocel, Ocdg) {
+    let log: Ocel = import_ocel("logs/three-days.jsonocel").unwrap();
+    let ocdg: Ocdg = generate_ocdg(&log, &[Relations::INTERACTS]).unwrap();
+    (log, ocdg)
+}
+
+fn counts(series: &[(chrono::DateTime<Utc>, usize)]) -> Vec<usize> {
+    series.iter().map(|(_, count)| *count).collect()
+}
+
+#[test]
+fn test_relation_timeseries_events_per_day() {
+    let (log, ocdg) = fixture();
+    let series = relation_timeseries(&log, &ocdg, Relations::INTERACTS, Granularity::Day);
+    assert_eq!(series[0].0, Utc.ymd(2022, 1, 1).and_hms(0, 0, 0));
+    assert_eq!(counts(&series), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_relation_timeseries_edges_first_seen() {
+    let (log, ocdg) = fixture();
+    let all = relation_timeseries_config(RelationSeriesConfig { ocel: &log, ocdg: &ocdg, relation: Relations::INTERACTS, bucket: Granularity::Day, count: SeriesCount::EdgesFirstSeen, object_type: None });
+    // INTERACTS is symmetric, each pair counts once although it is stored in both directions
+    assert_eq!(counts(&all), vec![3, 1, 1]);
+
+    let orders = relation_timeseries_config(RelationSeriesConfig { ocel: &log, ocdg: &ocdg, relation: Relations::INTERACTS, bucket: Granularity::Day, count: SeriesCount::EdgesFirstSeen, object_type: Some("order") });
+    assert_eq!(counts(&orders), vec![2, 1, 1]);
+}
+
+// a and b are born together on the first day, c, d and e on the second
+#[test]
+fn test_relation_timeseries_symmetric_pairs_first_seen() {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, (label, obj_type)) in [("a", "box"), ("b", "box"), ("c", "box"), ("d", "box"), ("e", "pallet")].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(label.to_string(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    log.insert_event("e1", "pack", start, &["a", "b"], AHashMap::new()).unwrap();
+    log.insert_event("e2", "pack", start + Duration::days(1), &["c", "d", "e"], AHashMap::new()).unwrap();
+    let ocdg = generate_ocdg(&log, &[Relations::COBIRTH]).unwrap();
+
+    let config = |object_type| RelationSeriesConfig { ocel: &log, ocdg: &ocdg, relation: Relations::COBIRTH, bucket: Granularity::Day, count: SeriesCount::EdgesFirstSeen, object_type };
+    assert_eq!(counts(&relation_timeseries_config(config(None))), vec![1, 3]);
+    // the pairs with a pallet count from the box side, the pairs among boxes once
+    assert_eq!(counts(&relation_timeseries_config(config(Some("box")))), vec![1, 3]);
+    assert_eq!(counts(&relation_timeseries_config(config(Some("pallet")))), vec![2]);
+}
+
+#[test]
+fn test_relation_timeseries_missing_relation_is_empty() {
+    let (log, ocdg) = fixture();
+    assert!(relation_timeseries(&log, &ocdg, Relations::SPLIT, Granularity::Day).is_empty());
+}
+
+#[test]
+fn test_relation_timeseries_csv() {
+    let (log, ocdg) = fixture();
+    let series = relation_timeseries(&log, &ocdg, Relations::INTERACTS, Granularity::Week);
+    assert_eq!(timeseries_to_csv(&series), "bucket,count\n2021-12-27T00:00:00+00:00,3\n2022-01-03T00:00:00+00:00,3\n");
+}
@@ -0,0 +1,42 @@
+use pmrs::algo::analysis::{resource_network, resource_workload, ResourceNetworkDot, ResourceWorkload};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::importer::import_ocel;
+
+fn handover(net: &petgraph::graph::DiGraph<String, usize>, from: &str, to: &str) -> Option<usize> {
+    let a = net.node_indices().find(|node| net[*node] == from)?;
+    let b = net.node_indices().find(|node| net[*node] == to)?;
+    net.find_edge(a, b).map(|edge| net[edge])
+}
+
+#[test]
+fn test_resource_network_counts_cases() {
+    let log: Ocel = import_ocel("logs/handover.jsonocel").unwrap();
+    let net = resource_network(&log, "user", "order");
+
+    assert_eq!(net.node_count(), 2);
+    assert_eq!(net.edge_count(), 2);
+    // both orders go from u1 to u2, only c1 goes back
+    assert_eq!(handover(&net, "u1", "u2"), Some(2));
+    assert_eq!(handover(&net, "u2", "u1"), Some(1));
+}
+
+#[test]
+fn test_resource_workload() {
+    let log: Ocel = import_ocel("logs/handover.jsonocel").unwrap();
+    let workload = resource_workload(&log, "user", "order");
+
+    assert_eq!(workload, vec![ResourceWorkload { resource: "u1".to_string(), events: 3, cases: 2 },
+                              ResourceWorkload { resource: "u2".to_string(), events: 2, cases: 2 }]);
+}
+
+#[test]
+fn test_resource_network_dot() {
+    let log: Ocel = import_ocel("logs/handover.jsonocel").unwrap();
+    let net = resource_network(&log, "user", "order");
+    let dot = ResourceNetworkDot(&net).to_dot();
+
+    assert!(dot.starts_with("digraph handover {"));
+    assert!(dot.contains("n0 [label=\"u1\"];"));
+    assert!(dot.contains("n0 -> n1 [label=\"2\"];"));
+    assert!(dot.contains("n1 -> n0 [label=\"1\"];"));
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "service")]
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use pmrs::objects::ocdg::{generate_ocdg_config, GenerationConfig, Ocdg, OcdgError};
+use pmrs::objects::ocel::Ocel;
+use pmrs::service::{watch, OcdgHandle, ServiceConfig};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pmrs-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn config() -> ServiceConfig {
+    ServiceConfig { poll_interval: Duration::from_millis(10), ..Default::default() }
+}
+
+fn wait_for_version(handle: &OcdgHandle, version: usize) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+        if handle.snapshot().version >= version {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    false
+}
+
+#[test]
+fn test_snapshot_grows_with_delta_files() {
+    let dir = temp_dir("watch");
+    let handle = watch(&dir, config()).unwrap();
+    assert_eq!(handle.snapshot().version, 0);
+    assert_eq!(handle.snapshot().net.node_count(), 0);
+
+    fs::copy("logs/deltas/delta-01.jsonocel", dir.join("delta-01.jsonocel")).unwrap();
+    assert!(wait_for_version(&handle, 1));
+    let first = handle.snapshot();
+    assert_eq!(first.net.node_count(), 2);
+    assert_eq!(first.log.as_ref().unwrap().events.len(), 2);
+
+    fs::copy("logs/deltas/delta-02.jsonocel", dir.join("delta-02.jsonocel")).unwrap();
+    assert!(wait_for_version(&handle, 2));
+    let second = handle.snapshot();
+    assert_eq!(second.net.node_count(), 3);
+    assert!(second.net.edge_count() > first.net.edge_count());
+    // the shared objects of both deltas are folded together
+    let log = second.log.as_ref().unwrap();
+    assert_eq!(log.events.len(), 4);
+    assert_eq!(log.objects[log.object_map.get_by_left("i1").unwrap()].events.len(), 3);
+    // an older snapshot is left untouched
+    assert_eq!(first.net.node_count(), 2);
+
+    handle.stop();
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+static FAILED_ONCE: AtomicBool = AtomicBool::new(false);
+
+// fails the first time the second delta is merged in
+fn flaky_generate(log: &Ocel, config: &GenerationConfig) -> Result<Ocdg, OcdgError> {
+    if log.events.len() == 4 && !FAILED_ONCE.swap(true, Ordering::SeqCst) {
+        return Err(OcdgError::Invalid("flaky".to_owned()));
+    }
+    generate_ocdg_config(log, config)
+}
+
+#[test]
+fn test_failed_generation_is_retried() {
+    let dir = temp_dir("retry");
+    let handle = watch(&dir, ServiceConfig { generate: flaky_generate, ..config() }).unwrap();
+
+    fs::copy("logs/deltas/delta-01.jsonocel", dir.join("delta-01.jsonocel")).unwrap();
+    assert!(wait_for_version(&handle, 1));
+    fs::copy("logs/deltas/delta-02.jsonocel", dir.join("delta-02.jsonocel")).unwrap();
+    assert!(wait_for_version(&handle, 2));
+
+    // the failed attempt kept the first snapshot, the delta was merged again on a later poll
+    assert!(FAILED_ONCE.load(Ordering::SeqCst));
+    let snapshot = handle.snapshot();
+    assert_eq!(snapshot.version, 2);
+    assert_eq!(snapshot.files.len(), 2);
+    assert_eq!(snapshot.log.as_ref().unwrap().events.len(), 4);
+    assert_eq!(snapshot.net.node_count(), 3);
+
+    handle.stop();
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_hooks_run_on_update() {
+    let dir = temp_dir("hooks");
+    let export = dir.join("export").with_extension("gexf");
+    let handle = watch(&dir, config()).unwrap();
+    let updates = Arc::new(AtomicUsize::new(0));
+    let counter = updates.clone();
+    handle.on_update(Box::new(move |_| {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }));
+    handle.export_on_update(export.to_str().unwrap());
+
+    fs::copy("logs/deltas/delta-01.jsonocel", dir.join("delta-01.jsonocel")).unwrap();
+    fs::write(dir.join("notes.txt"), "not a log").unwrap();
+    assert!(wait_for_version(&handle, 1));
+    handle.stop();
+
+    assert_eq!(updates.load(Ordering::Relaxed), 1);
+    assert!(fs::read_to_string(&export).unwrap().contains("o1"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_watch_rejects_missing_directory() {
+    assert!(watch(&std::env::temp_dir().join("pmrs-missing-watch-dir"), config()).is_err());
+}
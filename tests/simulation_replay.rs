@@ -0,0 +1,39 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use pmrs::algo::simulation::{replay, replay_cancellable, replay_iter};
+use pmrs::objects::ocel::importer::import_ocel;
+
+#[test]
+fn test_replay_as_fast_as_possible_in_order() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let mut received: Vec<String> = vec![];
+    let emitted = replay(&log, 0.0, |ev| received.push(ev.event_id.to_owned()));
+
+    assert_eq!(emitted, 11);
+    assert_eq!(received, (1..=11).map(|i| format!("e{}", i)).collect::<Vec<String>>());
+}
+
+#[test]
+fn test_replay_iter_scales_delays() {
+    let log = import_ocel("logs/three-days.jsonocel").unwrap();
+    let delays: Vec<Duration> = replay_iter(&log, 3600.0).map(|(delay, _)| delay).collect();
+
+    assert_eq!(delays[0], Duration::ZERO);
+    // e1 -> e2 is six and a half hours of log time
+    assert_eq!(delays[1], Duration::from_millis(6500));
+    assert!(replay_iter(&log, 0.0).all(|(delay, _)| delay == Duration::ZERO));
+}
+
+#[test]
+fn test_replay_cancellable() {
+    let log = import_ocel("logs/supply.jsonocel").unwrap();
+    let cancel = AtomicBool::new(false);
+    let mut received = 0;
+    let emitted = replay_cancellable(&log, 0.0, &cancel, |_| {
+        received += 1;
+        if received == 4 {
+            cancel.store(true, Ordering::Relaxed);
+        }
+    });
+    assert_eq!(emitted, 4);
+}
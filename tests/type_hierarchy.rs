@@ -0,0 +1,97 @@
+use std::str::FromStr;
+
+use pmrs::objects::ocdg::{generate_ocdg_config, generate_ocdg_with, trace_pair_config, Evidence, GenerationConfig, Ocdg, Relations};
+use pmrs::objects::ocel::Ocel;
+use pmrs::objects::ocel::hierarchy::{TypeHierarchy, TypeMatching};
+use pmrs::objects::ocel::importer::import_ocel;
+use strum::IntoEnumIterator;
+
+fn fleet() -> TypeHierarchy {
+    TypeHierarchy::new().with_parent("truck", "vehicle")
+                        .with_parent("van", "vehicle")
+                        .with_parent("vehicle", "asset")
+}
+
+fn config(type_matching: TypeMatching) -> GenerationConfig {
+    GenerationConfig { relations: Relations::iter().collect(),
+                       evidence: Evidence::Full,
+                       type_matching,
+                       hierarchy: fleet(),
+                       ..Default::default() }
+}
+
+fn oid(log: &Ocel, label: &str) -> usize {
+    *log.object_map.get_by_left(label).unwrap()
+}
+
+#[test]
+fn test_hierarchy_ancestors() {
+    let hierarchy = fleet();
+    assert_eq!(hierarchy.parent("truck"), Some("vehicle"));
+    assert_eq!(hierarchy.ancestor("truck", 1), "vehicle");
+    assert_eq!(hierarchy.ancestor("truck", 5), "asset");
+    assert_eq!(hierarchy.ancestor("driver", 1), "driver");
+    assert!(hierarchy.is_a("van", "asset"));
+    assert!(!hierarchy.is_a("vehicle", "van"));
+    assert!(hierarchy.is_same_type_at("truck", "van", TypeMatching::Ancestor(1)));
+    assert!(!hierarchy.is_same_type_at("truck", "van", TypeMatching::Exact));
+    assert!(!hierarchy.is_same_type_at("truck", "driver", TypeMatching::Ancestor(2)));
+}
+
+#[test]
+fn test_hierarchy_rejects_cycles() {
+    let mut hierarchy = fleet();
+    assert!(!hierarchy.set_parent("asset", "truck"));
+    assert!(!hierarchy.set_parent("van", "van"));
+    assert_eq!(hierarchy.parent("asset"), None);
+    assert!(hierarchy.set_parent("asset", "resource"));
+}
+
+#[test]
+fn test_type_matching_from_str() {
+    assert_eq!(TypeMatching::from_str("Exact").unwrap(), TypeMatching::Exact);
+    assert_eq!(TypeMatching::default(), TypeMatching::Exact);
+}
+
+#[test]
+fn test_merge_across_subtypes() {
+    let log: Ocel = import_ocel("logs/fleet.jsonocel").unwrap();
+    let (t1, v1) = (oid(&log, "t1"), oid(&log, "v1"));
+
+    let exact: Ocdg = generate_ocdg_config(&log, &config(TypeMatching::Exact)).unwrap();
+    assert!(exact.relation_events(v1, t1, Relations::MERGE).is_none());
+    assert!(exact.relation_events(v1, t1, Relations::CONSUMES).is_some());
+
+    let parent: Ocdg = generate_ocdg_config(&log, &config(TypeMatching::Ancestor(1))).unwrap();
+    let e1 = *log.event_map.get_by_left("e1").unwrap();
+    assert!(parent.relation_events(v1, t1, Relations::MERGE).unwrap().contains(&e1));
+    assert!(parent.relation_events(v1, t1, Relations::CONSUMES).is_none());
+    assert!(trace_pair_config(&log, &config(TypeMatching::Ancestor(1)), v1, t1, Relations::MERGE).holds());
+}
+
+#[test]
+fn test_default_config_matches_exact_generation() {
+    let log: Ocel = import_ocel("logs/fleet.jsonocel").unwrap();
+    let relations: Vec<Relations> = Relations::iter().collect();
+    let expected = generate_ocdg_with(&log, &relations, Evidence::Full).unwrap();
+    let generated = generate_ocdg_config(&log, &GenerationConfig { relations, ..Default::default() }).unwrap();
+    assert_eq!(generated.irels, expected.irels);
+}
+
+#[test]
+fn test_retain_types_keeps_descendants() {
+    let log: Ocel = import_ocel("logs/fleet.jsonocel").unwrap();
+    let mut ocdg: Ocdg = generate_ocdg_config(&log, &config(TypeMatching::Exact)).unwrap();
+    ocdg.retain_types(&["vehicle"], &fleet());
+
+    let mut labels: Vec<&str> = ocdg.object_map.left_values().map(|label| label.as_str()).collect();
+    labels.sort();
+    assert_eq!(labels, vec!["t1", "v1"]);
+    assert_eq!(ocdg.net.node_count(), 2);
+    assert_eq!(ocdg.inodes.len(), 2);
+    let d1 = oid(&log, "d1");
+    assert!(!ocdg.irels.contains_key(&d1));
+    assert!(ocdg.irels.values().all(|targets| !targets.contains_key(&d1)));
+    let edge_count: usize = ocdg.iedges.values().map(|targets| targets.len()).sum();
+    assert_eq!(edge_count, ocdg.net.edge_count());
+}
@@ -0,0 +1,48 @@
+use ahash::AHashMap;
+use bimap::BiMap;
+use chrono::{Duration, TimeZone, Utc};
+use nohash_hasher::IntMap;
+use pmrs::algo::analysis::{export_type_lift_csv, type_lift, type_lift_with};
+use pmrs::objects::ocel::{EventColumns, Ocel, OcelObject, TypedAttributes};
+
+// ten events: four with A and B, one with A and C, four with only C and one without objects.
+// A is in 5, B in 4 and C in 5 events
+fn contrived_log() -> Ocel {
+    let mut log = Ocel { global_log: AHashMap::new(), global_event: AHashMap::new(), global_object: AHashMap::new(),
+                         events: EventColumns::default(), objects: IntMap::default(), object_map: BiMap::new(), event_map: BiMap::new(),
+                         activities: vec![], object_relations: IntMap::default(), typed_attributes: TypedAttributes::default() };
+    for (oid, obj_type) in ["A", "B", "C"].iter().enumerate() {
+        log.objects.insert(oid, OcelObject { obj_type: obj_type.to_string(), ovmap: AHashMap::new(), events: vec![] });
+        log.object_map.insert(obj_type.to_lowercase(), oid);
+    }
+    let start = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let omaps: [&[&str]; 10] = [&["a", "b"], &["a", "b"], &["a", "b"], &["a", "b"], &["a", "c"], &["c"], &["c"], &["c"], &["c"], &[]];
+    for (minute, objects) in omaps.iter().enumerate() {
+        log.insert_event(&format!("e{}", minute), "step", start + Duration::minutes(minute as i64), objects, AHashMap::new()).unwrap();
+    }
+    log
+}
+
+#[test]
+fn test_exact_lift() {
+    let log = contrived_log();
+    // A-B: 0.4 / (0.5 * 0.4) = 2, A-C: 0.1 / (0.5 * 0.5) = 0.4, B and C never meet
+    let lifts = type_lift_with(&log, 1);
+    assert_eq!(lifts.len(), 2);
+    assert_eq!((lifts[0].0.as_str(), lifts[0].1.as_str()), ("A", "B"));
+    assert!((lifts[0].2 - 2.0).abs() < 1e-12);
+    assert_eq!((lifts[1].0.as_str(), lifts[1].1.as_str()), ("A", "C"));
+    assert!((lifts[1].2 - 0.4).abs() < 1e-12);
+
+    // the single A-C event is not enough to trust its lift
+    assert_eq!(type_lift_with(&log, 2).len(), 1);
+    assert!(type_lift(&log).is_empty());
+}
+
+#[test]
+fn test_lift_csv() {
+    let log = contrived_log();
+    let path = std::env::temp_dir().join("pmrs-type-lift.csv");
+    export_type_lift_csv(&type_lift_with(&log, 1), path.to_str().unwrap()).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "first_type,second_type,lift\nA,B,2\nA,C,0.4\n");
+}